@@ -0,0 +1,35 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+#[cfg_attr(not(unix), allow(dead_code))]
+mod rpc;
+#[cfg(unix)]
+mod server;
+
+/// Keeps one connection to the x0x daemon open and answers JSON-RPC requests for boreds on a
+/// unix socket, so frontends and scripts don't each have to connect
+#[cfg(unix)]
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    server::run().await
+}
+
+#[cfg(not(unix))]
+fn main() {
+    eprintln!("bored-daemon listens on a unix socket, which this system doesn't have");
+    std::process::exit(1);
+}
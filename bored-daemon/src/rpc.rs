@@ -0,0 +1,225 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! JSON-RPC 2.0 requests and responses, one json object per line
+
+use bored::BoredError;
+use bored::url::BoredAddress;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::path::PathBuf;
+
+/// The line isn't json
+pub const PARSE_ERROR: i64 = -32700;
+/// The json isn't a request
+pub const INVALID_REQUEST: i64 = -32600;
+pub const METHOD_NOT_FOUND: i64 = -32601;
+pub const INVALID_PARAMS: i64 = -32602;
+/// Getting the bored failed, the error's code is in the data
+pub const BORED_ERROR: i64 = -32000;
+/// Writing the feed file failed
+pub const FEED_WRITE_ERROR: i64 = -32001;
+
+/// What a request asks the daemon to do
+#[derive(Debug, PartialEq)]
+pub enum Method {
+    /// The bored as it is in the cache, asking peers for it if it isn't there
+    Get(BoredAddress),
+    /// Ask peers for the latest of the bored and return it
+    Update(BoredAddress),
+    /// Send a "changed" notification with the bored each time it changes, until the connection
+    /// is closed
+    Watch(BoredAddress),
+    /// Write an atom feed of the bored to the path, then write it again each time the bored
+    /// changes until the connection is closed
+    Feed(BoredAddress, PathBuf),
+}
+
+#[derive(Debug, PartialEq)]
+pub struct RpcError {
+    pub code: i64,
+    pub message: String,
+    /// BoredError::code of errors getting boreds
+    pub data: Option<&'static str>,
+}
+
+impl RpcError {
+    pub fn new(code: i64, message: &str) -> RpcError {
+        RpcError {
+            code,
+            message: message.to_string(),
+            data: None,
+        }
+    }
+}
+
+impl From<BoredError> for RpcError {
+    fn from(e: BoredError) -> Self {
+        RpcError {
+            code: BORED_ERROR,
+            message: e.to_string(),
+            data: Some(e.code()),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct Request {
+    jsonrpc: String,
+    /// None for notifications, which get no response
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Deserialize)]
+struct AddressParams {
+    address: String,
+}
+
+#[derive(Deserialize)]
+struct FeedParams {
+    path: PathBuf,
+}
+
+/// The id of the request, none if it had none or couldn't be read, and what it asks for
+pub fn parse_request(line: &str) -> (Option<Value>, Result<Method, RpcError>) {
+    let value: Value = match serde_json::from_str(line) {
+        Ok(value) => value,
+        Err(e) => return (None, Err(RpcError::new(PARSE_ERROR, &e.to_string()))),
+    };
+    let request: Request = match serde_json::from_value(value) {
+        Ok(request) => request,
+        Err(e) => return (None, Err(RpcError::new(INVALID_REQUEST, &e.to_string()))),
+    };
+    if request.jsonrpc != "2.0" {
+        return (
+            request.id,
+            Err(RpcError::new(
+                INVALID_REQUEST,
+                "Only JSON-RPC 2.0 is spoken",
+            )),
+        );
+    }
+    let method = get_address(&request.params).and_then(|address| match request.method.as_str() {
+        "get" => Ok(Method::Get(address)),
+        "update" => Ok(Method::Update(address)),
+        "watch" => Ok(Method::Watch(address)),
+        "feed" => get_params::<FeedParams>(&request.params)
+            .map(|params| Method::Feed(address, params.path)),
+        method => Err(RpcError::new(
+            METHOD_NOT_FOUND,
+            &format!("No method called {method}"),
+        )),
+    });
+    (request.id, method)
+}
+
+fn get_params<T: for<'a> Deserialize<'a>>(params: &Value) -> Result<T, RpcError> {
+    T::deserialize(params).map_err(|e| RpcError::new(INVALID_PARAMS, &e.to_string()))
+}
+
+fn get_address(params: &Value) -> Result<BoredAddress, RpcError> {
+    let params: AddressParams = get_params(params)?;
+    BoredAddress::from_string(&params.address)
+        .map_err(|e| RpcError::new(INVALID_PARAMS, &e.to_string()))
+}
+
+/// The response line for a request, ending in a newline
+pub fn response(id: Option<Value>, result: Result<Value, RpcError>) -> String {
+    let response = match result {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Err(error) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": {"code": error.code, "message": error.message, "data": error.data},
+        }),
+    };
+    format!("{response}\n")
+}
+
+/// A notification line sent without being asked for, ending in a newline
+pub fn notification(method: &str, params: Value) -> String {
+    format!(
+        "{}\n",
+        json!({"jsonrpc": "2.0", "method": method, "params": params})
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_request() {
+        let welcome = BoredAddress::from_string("bored://welcome").unwrap();
+        assert_eq!(
+            parse_request(
+                r#"{"jsonrpc": "2.0", "id": 1, "method": "get", "params": {"address": "bored://welcome"}}"#
+            ),
+            (Some(json!(1)), Ok(Method::Get(welcome.clone())))
+        );
+        assert_eq!(
+            parse_request(
+                r#"{"jsonrpc": "2.0", "method": "watch", "params": {"address": "welcome"}}"#
+            ),
+            (None, Ok(Method::Watch(welcome.clone())))
+        );
+        assert_eq!(
+            parse_request(
+                r#"{"jsonrpc": "2.0", "id": 4, "method": "feed", "params": {"address": "welcome", "path": "welcome.xml"}}"#
+            ),
+            (
+                Some(json!(4)),
+                Ok(Method::Feed(welcome, PathBuf::from("welcome.xml")))
+            )
+        );
+        let (_, method) = parse_request(
+            r#"{"jsonrpc": "2.0", "id": 5, "method": "feed", "params": {"address": "welcome"}}"#,
+        );
+        assert_eq!(method.unwrap_err().code, INVALID_PARAMS);
+        let (id, method) = parse_request(r#"{"jsonrpc": "2.0", "id": "a", "method": "post"}"#);
+        assert_eq!(id, Some(json!("a")));
+        assert_eq!(method.unwrap_err().code, INVALID_PARAMS);
+        let (_, method) = parse_request(
+            r#"{"jsonrpc": "2.0", "id": 2, "method": "post", "params": {"address": "welcome"}}"#,
+        );
+        assert_eq!(method.unwrap_err().code, METHOD_NOT_FOUND);
+        assert_eq!(parse_request("{").1.unwrap_err().code, PARSE_ERROR);
+        assert_eq!(
+            parse_request(r#"{"jsonrpc": "1.0", "id": 3, "method": "get"}"#)
+                .1
+                .unwrap_err()
+                .code,
+            INVALID_REQUEST
+        );
+    }
+
+    #[test]
+    fn test_response() {
+        assert_eq!(
+            response(Some(json!(1)), Ok(json!(true))),
+            "{\"id\":1,\"jsonrpc\":\"2.0\",\"result\":true}\n"
+        );
+        let error = RpcError::from(BoredError::NoBored);
+        let response: Value = serde_json::from_str(&response(None, Err(error))).unwrap();
+        assert_eq!(response["id"], Value::Null);
+        assert_eq!(response["error"]["code"], json!(BORED_ERROR));
+        assert_eq!(response["error"]["data"], json!(BoredError::NoBored.code()));
+    }
+}
@@ -0,0 +1,190 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::rpc::{self, Method, RpcError};
+use bored::export;
+use bored::url::BoredAddress;
+use bored::x0x_client::{self, X0xBoredClient};
+use bored::{Bored, BoredError};
+use serde_json::{Value, json};
+use std::error::Error;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::mpsc::unbounded_channel;
+
+/// Environment variable holding the path of the socket to listen on
+const SOCKET_VAR: &str = "BORED_DAEMON_SOCKET";
+/// Time between asking peers for changes to each watched bored
+const WATCH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Answer requests on the socket until the daemon is stopped
+pub async fn run() -> Result<(), Box<dyn Error>> {
+    let client = Arc::new(X0xBoredClient::init().await?);
+    let socket_path = get_socket_path();
+    // a socket left behind by a daemon that didn't shut down cleanly stops binding
+    let _ = std::fs::remove_file(&socket_path);
+    let listener = UnixListener::bind(&socket_path)?;
+    println!("Serving boreds on {}", socket_path.display());
+    loop {
+        let (stream, _) = listener.accept().await?;
+        tokio::spawn(serve_connection(stream, client.clone()));
+    }
+}
+
+/// The socket from BORED_DAEMON_SOCKET, otherwise daemon.sock in the we are bored data directory
+fn get_socket_path() -> PathBuf {
+    if let Some(socket_path) = std::env::var_os(SOCKET_VAR) {
+        return PathBuf::from(socket_path);
+    }
+    let data_dir = x0x_client::get_we_are_bored_data_dir().unwrap_or_else(|| PathBuf::from("."));
+    let _ = std::fs::create_dir_all(&data_dir);
+    data_dir.join("daemon.sock")
+}
+
+/// Answer requests one line at a time until the connection is closed, along with the
+/// notifications of any boreds being watched
+async fn serve_connection(stream: UnixStream, client: Arc<X0xBoredClient>) {
+    let (reader, mut writer) = stream.into_split();
+    let (sender, mut receiver) = unbounded_channel::<String>();
+    let writing = tokio::spawn(async move {
+        while let Some(line) = receiver.recv().await {
+            if writer.write_all(line.as_bytes()).await.is_err() {
+                break;
+            }
+        }
+    });
+    let mut watches = vec![];
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (id, method) = rpc::parse_request(&line);
+        let result = match method {
+            Ok(Method::Get(address)) => get(&client, &address).await,
+            Ok(Method::Update(address)) => update(&client, &address).await,
+            Ok(Method::Watch(address)) => {
+                let sender = sender.clone();
+                let last = client.get_cached_bored(&address).ok();
+                let notify = move |address: &BoredAddress, bored: &Bored| {
+                    let changed = json!({"address": address.to_string(), "bored": bored});
+                    sender.send(rpc::notification("changed", changed)).is_ok()
+                };
+                watches.push(tokio::spawn(watch(client.clone(), address, last, notify)));
+                Ok(Value::Bool(true))
+            }
+            Ok(Method::Feed(address, path)) => match write_feed(&client, &address, &path).await {
+                Ok(bored) => {
+                    let rewrite = move |address: &BoredAddress, bored: &Bored| {
+                        // a failed write is tried again the next time the bored changes
+                        let _ = save_feed(address, bored, &path);
+                        true
+                    };
+                    watches.push(tokio::spawn(watch(
+                        client.clone(),
+                        address,
+                        Some(bored),
+                        rewrite,
+                    )));
+                    Ok(Value::Bool(true))
+                }
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        };
+        // requests without an id are notifications, which are only answered if they are wrong
+        if id.is_some() || result.is_err() {
+            let _ = sender.send(rpc::response(id, result));
+        }
+    }
+    for watch in watches {
+        watch.abort();
+    }
+    drop(sender);
+    let _ = writing.await;
+}
+
+async fn get(client: &X0xBoredClient, address: &BoredAddress) -> Result<Value, RpcError> {
+    match client.get_cached_bored(address) {
+        Ok(bored) => to_value(&bored),
+        Err(_) => update(client, address).await,
+    }
+}
+
+async fn update(client: &X0xBoredClient, address: &BoredAddress) -> Result<Value, RpcError> {
+    to_value(&fetch(client, address).await?)
+}
+
+async fn fetch(client: &X0xBoredClient, address: &BoredAddress) -> Result<Bored, BoredError> {
+    client
+        .fetch_boreds(std::slice::from_ref(address))
+        .await
+        .into_iter()
+        .next()
+        .unwrap_or(Err(BoredError::NoBored))
+}
+
+fn to_value(bored: &Bored) -> Result<Value, RpcError> {
+    serde_json::to_value(bored).map_err(|e| BoredError::from(e).into())
+}
+
+/// Fetch the latest of the bored and write its atom feed to the path
+async fn write_feed(
+    client: &X0xBoredClient,
+    address: &BoredAddress,
+    path: &Path,
+) -> Result<Bored, RpcError> {
+    let bored = fetch(client, address).await?;
+    save_feed(address, &bored, path)?;
+    Ok(bored)
+}
+
+fn save_feed(address: &BoredAddress, bored: &Bored, path: &Path) -> Result<(), RpcError> {
+    let atom = export::to_atom(bored, address)?;
+    std::fs::write(path, atom).map_err(|e| {
+        RpcError::new(
+            rpc::FEED_WRITE_ERROR,
+            &format!("Couldn't write {}: {e}", path.display()),
+        )
+    })
+}
+
+/// Ask peers for changes to the bored every WATCH_INTERVAL and pass it on when it has changed
+/// from the last seen, until passing it on fails or the connection closes
+async fn watch(
+    client: Arc<X0xBoredClient>,
+    address: BoredAddress,
+    mut last: Option<Bored>,
+    mut changed: impl FnMut(&BoredAddress, &Bored) -> bool,
+) {
+    loop {
+        tokio::time::sleep(WATCH_INTERVAL).await;
+        let Ok(bored) = fetch(&client, &address).await else {
+            continue;
+        };
+        if last.as_ref() == Some(&bored) {
+            continue;
+        }
+        if !changed(&address, &bored) {
+            return;
+        }
+        last = Some(bored);
+    }
+}
@@ -0,0 +1,91 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+mod render;
+
+use axum::Router;
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::response::{Html, IntoResponse, Redirect, Response};
+use axum::routing::get;
+use bored::url::BoredAddress;
+use bored::x0x_client::X0xBoredClient;
+use std::collections::HashMap;
+use std::error::Error;
+use std::sync::Arc;
+
+/// Environment variable holding the address and port to serve on
+const LISTEN_VAR: &str = "BORED_GATEWAY_LISTEN";
+/// Only this machine can read boreds through the gateway unless BORED_GATEWAY_LISTEN says
+/// otherwise
+const DEFAULT_LISTEN: &str = "127.0.0.1:8080";
+
+/// Serves boreds as web pages so they can be read in a browser, through the x0x daemon on this
+/// machine
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn Error>> {
+    let mut client = X0xBoredClient::init().await?;
+    // readers of the gateway can't change what they read
+    client.set_read_only(true);
+    let listen = std::env::var(LISTEN_VAR).unwrap_or_else(|_| DEFAULT_LISTEN.to_string());
+    let app = Router::new()
+        .route("/", get(index))
+        .route("/go", get(go))
+        .route("/bored/{address}", get(show_bored))
+        .with_state(Arc::new(client));
+    let listener = tokio::net::TcpListener::bind(&listen).await?;
+    println!("Serving boreds on http://{listen}");
+    axum::serve(listener, app).await?;
+    Ok(())
+}
+
+async fn index() -> Html<String> {
+    Html(render::render_index())
+}
+
+/// Go to the page for the address typed in on the index
+async fn go(Query(query): Query<HashMap<String, String>>) -> Response {
+    let address = query.get("address").map_or("", String::as_str);
+    match BoredAddress::from_string(address) {
+        Ok(address) => Redirect::to(&render::get_bored_path(&address)).into_response(),
+        Err(e) => error_page(StatusCode::BAD_REQUEST, &e.to_string()),
+    }
+}
+
+/// The bored at the address as it is after asking peers for its latest notices
+async fn show_bored(
+    State(client): State<Arc<X0xBoredClient>>,
+    Path(address): Path<String>,
+) -> Response {
+    let address = match BoredAddress::from_string(&address) {
+        Ok(address) => address,
+        Err(e) => return error_page(StatusCode::BAD_REQUEST, &e.to_string()),
+    };
+    let fetched = client.fetch_boreds(std::slice::from_ref(&address)).await;
+    match fetched.into_iter().next() {
+        Some(Ok(bored)) => match render::render_bored(&bored) {
+            Ok(html) => Html(html).into_response(),
+            Err(e) => error_page(StatusCode::INTERNAL_SERVER_ERROR, &e.to_string()),
+        },
+        Some(Err(e)) => error_page(StatusCode::NOT_FOUND, &e.to_string()),
+        None => error_page(StatusCode::NOT_FOUND, "No bored"),
+    }
+}
+
+fn error_page(status: StatusCode, message: &str) -> Response {
+    (status, Html(render::render_error(message))).into_response()
+}
@@ -0,0 +1,140 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use bored::export::{escape_markup, to_html_with_links};
+use bored::url::{BoredAddress, URL};
+use bored::{Bored, BoredError};
+
+const STYLE: &str = "body { font-family: monospace; background: #111; color: #eee; }
+a { color: #6ce4af; }";
+
+/// A whole html page with the gateway's styling
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n\
+         <style>\n{STYLE}\n</style>\n</head>\n<body>\n{body}\n</body>\n</html>\n",
+        escape_markup(title)
+    )
+}
+
+/// The first page, asking for the address of a bored to read
+pub fn render_index() -> String {
+    page(
+        "Bored gateway",
+        "<h1>Bored gateway</h1>\n<p>Read a bored without Surf Bored.</p>\n\
+         <form action=\"/go\"><input name=\"address\" placeholder=\"bored://\" size=\"40\">\
+         <button>Go</button></form>",
+    )
+}
+
+/// A page saying why a bored couldn't be shown
+pub fn render_error(message: &str) -> String {
+    page(
+        "Bored gateway",
+        &format!(
+            "<h1>Can't show the bored</h1>\n<p>{}</p>\n<p><a href=\"/\">Back</a></p>",
+            escape_markup(message)
+        ),
+    )
+}
+
+/// The bored drawn as surf bored shows it, by the library's html export so notices hidden by
+/// moderation are drawn empty there too
+pub fn render_bored(bored: &Bored) -> Result<String, BoredError> {
+    to_html_with_links(bored, get_href)
+}
+
+/// Where a hyperlink on a bored goes from the gateway, bored:// links are to the gateway's page
+/// for the bored and links it can't follow go nowhere
+fn get_href(link: &str) -> Option<String> {
+    match URL::from_string(link.to_string()) {
+        Ok(URL::BoredNet(address)) => Some(get_bored_path(&address)),
+        Ok(URL::ClearNet(url)) => Some(url),
+        _ => None,
+    }
+}
+
+/// The gateway's path for a bored
+pub fn get_bored_path(address: &BoredAddress) -> String {
+    let address = address.to_string();
+    format!(
+        "/bored/{}",
+        encode_path_segment(address.trim_start_matches("bored://"))
+    )
+}
+
+/// Percent encode everything but the characters that can be in a path segment as they are
+fn encode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            }
+            _ => format!("%{byte:02X}"),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bored::Coordinate;
+    use bored::notice::Notice;
+
+    #[test]
+    fn test_get_href() {
+        assert_eq!(
+            get_href("bored://welcome").as_deref(),
+            Some("/bored/welcome")
+        );
+        assert_eq!(
+            get_href("https://example.com").as_deref(),
+            Some("https://example.com")
+        );
+        // app links only work in surf bored
+        assert_eq!(get_href("app://quit"), None);
+        // links are checked by character, not cut part way through one
+        assert_eq!(
+            get_href("aéééé").as_deref(),
+            Some("/bored/a%C3%A9%C3%A9%C3%A9%C3%A9")
+        );
+    }
+
+    #[test]
+    fn test_get_bored_path() {
+        let address = BoredAddress::from_string("bored://my bored/é").unwrap();
+        assert_eq!(get_bored_path(&address), "/bored/my%20bored%2F%C3%A9");
+    }
+
+    #[test]
+    fn test_render_bored() -> Result<(), BoredError> {
+        let mut bored = Bored::create("<Bored>", Coordinate { x: 40, y: 10 });
+        let mut notice = Notice::create(Coordinate { x: 20, y: 4 });
+        notice.write("Hello [there](bored://welcome)")?;
+        bored.add(notice, Coordinate { x: 2, y: 3 })?;
+        let mut hidden = Notice::create(Coordinate { x: 10, y: 4 });
+        hidden.write("Spam")?;
+        bored.add(hidden, Coordinate { x: 25, y: 3 })?;
+        bored.set_hidden_notices(vec![bored.notices()[1].get_hash()])?;
+        let html = render_bored(&bored)?;
+        assert!(html.contains("<title>&lt;Bored&gt;</title>"));
+        assert!(html.contains("Hello <a href=\"/bored/welcome\">there</a>"));
+        assert!(!html.contains("Spam"));
+        Ok(())
+    }
+}
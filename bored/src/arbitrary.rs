@@ -0,0 +1,192 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! proptest strategies for the bored types, enabled with the proptest feature. Generated
+//! notices and boreds are always valid, i.e. they pass Bored::check_invariants.
+
+use proptest::prelude::*;
+
+use crate::notice::Notice;
+use crate::{Bored, Coordinate};
+
+/// Largest bored generated, kept small so notices overlap and get pruned often
+const MAX_BORED_DIMENSIONS: Coordinate = Coordinate { x: 80, y: 30 };
+/// Largest notice generated
+const MAX_NOTICE_DIMENSIONS: Coordinate = Coordinate { x: 30, y: 10 };
+const MAX_NOTICES: usize = 12;
+
+impl Arbitrary for Coordinate {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Coordinate>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (any::<u16>(), any::<u16>())
+            .prop_map(|(x, y)| Coordinate { x, y })
+            .boxed()
+    }
+}
+
+/// Text for a notice of the given dimensions, shortened until it fits
+fn fitting_content(dimensions: Coordinate) -> impl Strategy<Value = String> {
+    "([a-z ]{0,12}|\n|\\[[a-z]{1,6}\\]\\(bored://[a-z]{1,6}\\)){0,6}".prop_map(move |content| {
+        let mut notice = Notice::create(dimensions);
+        // hidden hyperlink urls mean longer content can fit but checking is slow so trim first
+        let mut content: String = content
+            .chars()
+            .take(notice.get_max_chars() + notice.get_max_lines())
+            .collect();
+        // removing the last character may leave half a hyperlink which can then be wider
+        // than the link so keep going until it fits
+        while notice.write(&content).is_err() {
+            content.pop();
+        }
+        content
+    })
+}
+
+/// A notice that fits within dimensions with content that fits within the notice
+fn notice_within(bounds: Coordinate) -> impl Strategy<Value = Notice> {
+    (
+        3..=bounds.x.min(MAX_NOTICE_DIMENSIONS.x),
+        3..=bounds.y.min(MAX_NOTICE_DIMENSIONS.y),
+    )
+        .prop_flat_map(move |(width, height)| {
+            let dimensions = Coordinate {
+                x: width,
+                y: height,
+            };
+            (
+                0..=bounds.x - width,
+                0..=bounds.y - height,
+                fitting_content(dimensions),
+                any::<u32>(),
+            )
+                .prop_map(move |(x, y, content, id)| {
+                    let mut notice = Notice::create(dimensions);
+                    notice.write(&content).expect("content was made to fit");
+                    notice.set_notice_id(format!("notice:{id}:proptest"));
+                    let top_left = Coordinate { x, y };
+                    // relocate only needs a bored for its dimensions
+                    let bounds = Bored::create("", top_left.add(&dimensions));
+                    notice
+                        .relocate(&bounds, top_left)
+                        .expect("notice was made to fit");
+                    notice
+                })
+        })
+        .boxed()
+}
+
+impl Arbitrary for Notice {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Notice>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        notice_within(MAX_BORED_DIMENSIONS).boxed()
+    }
+}
+
+impl Arbitrary for Bored {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Bored>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (
+            3..=MAX_BORED_DIMENSIONS.x,
+            3..=MAX_BORED_DIMENSIONS.y,
+            "[A-Za-z ]{0,20}",
+        )
+            .prop_flat_map(|(width, height, name)| {
+                let dimensions = Coordinate {
+                    x: width,
+                    y: height,
+                };
+                proptest::collection::vec(notice_within(dimensions), 0..=MAX_NOTICES).prop_map(
+                    move |notices| {
+                        let mut bored = Bored::create(&name, dimensions);
+                        for notice in notices {
+                            let top_left = notice.get_top_left();
+                            bored.add(notice, top_left).expect("notice was made to fit");
+                        }
+                        bored
+                    },
+                )
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{BoredError, WhatsOnTheBored};
+
+    proptest! {
+        // building boreds is slow in debug builds so fewer cases than the default 256
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn generated_boreds_are_valid(bored in any::<Bored>()) {
+            prop_assert_eq!(bored.check_invariants(), Ok(()));
+        }
+
+        #[test]
+        fn serialization_round_trips(bored in any::<Bored>()) {
+            let json = serde_json::to_string(&bored).unwrap();
+            prop_assert_eq!(Bored::from_json(&json), Ok(bored));
+        }
+
+        #[test]
+        fn pruning_is_idempotent(bored in any::<Bored>()) {
+            let mut pruned = bored.clone();
+            pruned.prune_non_visible().unwrap();
+            prop_assert_eq!(pruned, bored);
+        }
+
+        #[test]
+        fn newest_notice_is_always_fully_visible(bored in any::<Bored>(), notice in any::<Notice>()) {
+            let mut bored = bored;
+            let top_left = notice.get_top_left();
+            match bored.add(notice.clone(), top_left) {
+                Ok(()) => {
+                    prop_assert_eq!(bored.check_invariants(), Ok(()));
+                    let newest = bored.notices().len() - 1;
+                    let whats_on_the_bored = WhatsOnTheBored::create(&bored).get_1d();
+                    let covered = whats_on_the_bored.iter().filter(|i| **i == Some(newest)).count();
+                    let dimensions = notice.get_dimensions();
+                    prop_assert_eq!(covered, dimensions.x as usize * dimensions.y as usize);
+                }
+                Err(e) => prop_assert!(matches!(e, BoredError::NoticeOutOfBounds(_, _))),
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_invariants_catches_hidden_notice() {
+        let mut bored = Bored::create("hidden", Coordinate { x: 20, y: 20 });
+        let mut hidden = Notice::create(Coordinate { x: 5, y: 5 });
+        hidden.set_notice_id("hidden".to_string());
+        bored.notices.push(hidden);
+        bored
+            .notices
+            .push(Notice::create(Coordinate { x: 10, y: 10 }));
+        assert_eq!(
+            bored.check_invariants(),
+            Err(BoredError::NoticeNotVisible("hidden".to_string()))
+        );
+    }
+}
@@ -0,0 +1,320 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::notice::{get_display, get_hyperlinks};
+use crate::url::{BoredAddress, URL};
+#[cfg(feature = "client")]
+use crate::x0x_client::X0xBoredClient;
+use crate::{Bored, BoredError};
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "client")]
+use std::collections::HashSet;
+
+/// How many links away from the seeds a crawl goes unless asked otherwise
+pub const DEFAULT_CRAWL_DEPTH: usize = 2;
+
+/// Most boreds one crawl fetches, so a well linked bored web can't keep it going for ever
+pub const MAX_CRAWL_BOREDS: usize = 100;
+
+/// Most characters kept of the text of each notice
+pub const SNIPPET_LENGTH: usize = 80;
+
+/// A bored found while crawling
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    pub address: BoredAddress,
+    pub name: String,
+    /// How many links from a seed the bored was found, 0 for the seeds
+    pub depth: usize,
+    /// The start of the text of each notice
+    pub snippets: Vec<String>,
+    /// The boreds the notices link to
+    pub links: Vec<BoredAddress>,
+}
+
+/// A bored in the index that matched a search, with the snippet that matched or none if its
+/// name did
+#[derive(Debug, PartialEq)]
+pub struct SearchHit<'a> {
+    pub entry: &'a IndexEntry,
+    pub snippet: Option<&'a str>,
+}
+
+/// Boreds found by following bored:// links out from some seed boreds, with enough of each to
+/// search them without fetching them again
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CrawlIndex {
+    entries: Vec<IndexEntry>,
+    /// Boreds linked to that couldn't be fetched, not kept with the index
+    #[serde(skip)]
+    failures: Vec<(BoredAddress, BoredError)>,
+}
+
+impl CrawlIndex {
+    /// Fetch the seeds then the boreds they link to, a level of links at a time, until
+    /// max_depth links from the seeds or MAX_CRAWL_BOREDS have been fetched. Each bored is
+    /// fetched once however many link to it.
+    #[cfg(feature = "client")]
+    pub async fn crawl(
+        client: &X0xBoredClient,
+        seeds: &[BoredAddress],
+        max_depth: usize,
+    ) -> CrawlIndex {
+        let mut index = CrawlIndex::default();
+        let mut seen = HashSet::new();
+        let mut next: Vec<BoredAddress> = seeds
+            .iter()
+            .filter(|address| seen.insert(address.get_topic()))
+            .cloned()
+            .collect();
+        for depth in 0..=max_depth {
+            let fetched = index.entries.len() + index.failures.len();
+            next.truncate(MAX_CRAWL_BOREDS.saturating_sub(fetched));
+            if next.is_empty() {
+                break;
+            }
+            let results = client.fetch_boreds(&next).await;
+            let mut linked = vec![];
+            for (address, result) in next.into_iter().zip(results) {
+                match result {
+                    Ok(bored) => linked.extend(index.add_bored(address, &bored, depth)),
+                    Err(e) => index.failures.push((address, e)),
+                }
+            }
+            next = linked
+                .into_iter()
+                .filter(|address| seen.insert(address.get_topic()))
+                .collect();
+        }
+        index
+    }
+
+    /// Index a bored found at a depth in place of what was there for it, returning the boreds
+    /// it links to. Notices hidden by moderation are left out.
+    pub fn add_bored(
+        &mut self,
+        address: BoredAddress,
+        bored: &Bored,
+        depth: usize,
+    ) -> Vec<BoredAddress> {
+        let bored = bored.redact_hidden();
+        let mut snippets = vec![];
+        let mut links: Vec<BoredAddress> = vec![];
+        for notice in bored.notices() {
+            let hyperlinks = get_hyperlinks(notice.get_content()).unwrap_or_default();
+            for hyperlink in &hyperlinks {
+                let linked = match URL::from_string(hyperlink.get_link()) {
+                    Ok(URL::BoredNet(linked)) => linked,
+                    Ok(URL::BoredShare(share_link)) => share_link.get_address().clone(),
+                    _ => continue,
+                };
+                if linked.get_topic() != address.get_topic()
+                    && !links.iter().any(|l| l.get_topic() == linked.get_topic())
+                {
+                    links.push(linked);
+                }
+            }
+            let snippet =
+                get_snippet(&get_display(notice.get_content(), hyperlinks).get_display_text());
+            if !snippet.is_empty() {
+                snippets.push(snippet);
+            }
+        }
+        self.entries
+            .retain(|entry| entry.address.get_topic() != address.get_topic());
+        self.entries.push(IndexEntry {
+            address,
+            name: bored.get_name().to_string(),
+            depth,
+            snippets,
+            links: links.clone(),
+        });
+        links
+    }
+
+    /// Boreds in the order they were found, nearest the seeds first
+    pub fn get_entries(&self) -> &Vec<IndexEntry> {
+        &self.entries
+    }
+
+    pub fn get_entry(&self, address: &BoredAddress) -> Option<&IndexEntry> {
+        self.entries
+            .iter()
+            .find(|entry| entry.address.get_topic() == address.get_topic())
+    }
+
+    /// Boreds that were linked to but could not be fetched and why
+    pub fn get_failures(&self) -> &Vec<(BoredAddress, BoredError)> {
+        &self.failures
+    }
+
+    /// Boreds whose name or notices contain the query, ignoring case, in the order they were
+    /// found. A bored can match once by name and once for each notice. An empty query
+    /// matches every bored by name.
+    pub fn search(&self, query: &str) -> Vec<SearchHit<'_>> {
+        let query = query.trim().to_lowercase();
+        let mut hits = vec![];
+        for entry in &self.entries {
+            if entry.name.to_lowercase().contains(&query) {
+                hits.push(SearchHit {
+                    entry,
+                    snippet: None,
+                });
+            }
+            if query.is_empty() {
+                continue;
+            }
+            for snippet in &entry.snippets {
+                if snippet.to_lowercase().contains(&query) {
+                    hits.push(SearchHit {
+                        entry,
+                        snippet: Some(snippet),
+                    });
+                }
+            }
+        }
+        hits
+    }
+}
+
+/// The text of a notice on one line with runs of whitespace made single spaces, cut to
+/// SNIPPET_LENGTH characters
+fn get_snippet(text: &str) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    match text.char_indices().nth(SNIPPET_LENGTH) {
+        Some((end, _)) => format!("{}…", &text[..end]),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coordinate;
+    use crate::notice::Notice;
+
+    fn bored_with(name: &str, texts: &[&str]) -> Result<Bored, BoredError> {
+        let mut bored = Bored::create(name, Coordinate { x: 120, y: 20 });
+        for (i, text) in texts.iter().enumerate() {
+            let mut notice = Notice::create(Coordinate { x: 40, y: 5 });
+            notice.write(text)?;
+            bored.add(
+                notice,
+                Coordinate {
+                    x: i as u16 * 40,
+                    y: 0,
+                },
+            )?;
+        }
+        Ok(bored)
+    }
+
+    #[test]
+    fn test_add_bored() -> Result<(), BoredError> {
+        let address = BoredAddress::from_string("bored://games")?;
+        let bored = bored_with(
+            "Games",
+            &[
+                "Play [chess](bored://chess)\nor   [go](bored://go)",
+                "Back to [games](bored://games) or [the web](https://example.com)",
+                "More [chess](bored.chess)",
+            ],
+        )?;
+        let mut index = CrawlIndex::default();
+        let links = index.add_bored(address.clone(), &bored, 1);
+        // itself and the web are left out, chess is there once
+        assert_eq!(
+            links,
+            vec![
+                BoredAddress::from_string("bored://chess")?,
+                BoredAddress::from_string("bored://go")?
+            ]
+        );
+        let entry = index.get_entry(&address).expect("indexed");
+        assert_eq!(entry.name, "Games");
+        assert_eq!(entry.depth, 1);
+        assert_eq!(entry.snippets[0], "Play chess or go");
+        // indexing a bored again replaces it
+        index.add_bored(address.clone(), &bored_with("Games again", &[])?, 0);
+        assert_eq!(index.get_entries().len(), 1);
+        assert_eq!(index.get_entries()[0].name, "Games again");
+        assert!(index.get_entries()[0].snippets.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_search() -> Result<(), BoredError> {
+        let mut index = CrawlIndex::default();
+        index.add_bored(
+            BoredAddress::from_string("bored://chess")?,
+            &bored_with("Chess", &["Openings", "Chess clubs near you"])?,
+            0,
+        );
+        index.add_bored(
+            BoredAddress::from_string("bored://cats")?,
+            &bored_with("Cats", &["Cats who play chess"])?,
+            1,
+        );
+        let found = |query| {
+            index
+                .search(query)
+                .iter()
+                .map(|hit| (hit.entry.name.clone(), hit.snippet.map(str::to_string)))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            found("CHESS"),
+            vec![
+                ("Chess".to_string(), None),
+                (
+                    "Chess".to_string(),
+                    Some("Chess clubs near you".to_string())
+                ),
+                ("Cats".to_string(), Some("Cats who play chess".to_string())),
+            ]
+        );
+        assert_eq!(
+            found(""),
+            vec![("Chess".to_string(), None), ("Cats".to_string(), None)]
+        );
+        assert!(found("dogs").is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_snippet() {
+        assert_eq!(get_snippet("  We are\n\n bored  "), "We are bored");
+        let long = "é".repeat(SNIPPET_LENGTH + 5);
+        assert_eq!(
+            get_snippet(&long),
+            format!("{}…", "é".repeat(SNIPPET_LENGTH))
+        );
+    }
+
+    #[test]
+    fn test_index_serialization() -> Result<(), BoredError> {
+        let mut index = CrawlIndex::default();
+        index.add_bored(
+            BoredAddress::from_string("bored://chess")?,
+            &bored_with("Chess", &["Openings"])?,
+            0,
+        );
+        let json = serde_json::to_string(&index)?;
+        assert_eq!(serde_json::from_str::<CrawlIndex>(&json)?, index);
+        Ok(())
+    }
+}
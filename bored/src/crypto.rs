@@ -0,0 +1,393 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Encrypting boreds so only a list of readers can see them. The bored is encrypted once with a
+//! random content key and that key is wrapped for each reader using x25519 key agreement with an
+//! ephemeral key, so the envelope doesn't say who the readers are. Reading is restricted this way
+//! but anyone who knows the topic can still publish to it.
+
+use base64::Engine;
+use base64::prelude::BASE64_URL_SAFE_NO_PAD;
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{ChaCha20Poly1305, Key, Nonce};
+use hkdf::Hkdf;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fmt;
+use std::path::Path;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::{Bored, BoredError};
+
+/// Most readers a bored can be encrypted for, each adds a wrapped key to the envelope
+pub const MAX_READERS: usize = 256;
+/// Label mixed into the key derivation so wrapping keys can't be confused with other uses
+const KEY_WRAP_INFO: &[u8] = b"we-are-bored reader key wrap v1";
+/// Label mixed into deriving a reader key from a seed phrase that doesn't hold one
+const SEED_PHRASE_INFO: &[u8] = b"we-are-bored reader key from seed phrase v1";
+
+fn encode(bytes: &[u8]) -> String {
+    BASE64_URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn decode_32(s: &str) -> Result<[u8; 32], BoredError> {
+    BASE64_URL_SAFE_NO_PAD
+        .decode(s.trim())
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| BoredError::InvalidKey(s.to_string()))
+}
+
+/// The public half of a reader's key, shared with whoever encrypts boreds for them
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct ReaderPublicKey(PublicKey);
+
+impl fmt::Display for ReaderPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", encode(self.0.as_bytes()))
+    }
+}
+
+impl fmt::Debug for ReaderPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ReaderPublicKey({self})")
+    }
+}
+
+impl ReaderPublicKey {
+    /// Parse a public key from the text it is displayed as
+    pub fn from_string(s: &str) -> Result<Self, BoredError> {
+        Ok(ReaderPublicKey(PublicKey::from(decode_32(s)?)))
+    }
+}
+
+/// A reader's secret key, needed to open boreds encrypted for its public key
+#[derive(Clone)]
+pub struct ReaderKey(StaticSecret);
+
+impl fmt::Debug for ReaderKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // never print the secret
+        write!(f, "ReaderKey({})", self.public_key())
+    }
+}
+
+impl ReaderKey {
+    /// Generate a new random reader key
+    pub fn generate() -> ReaderKey {
+        ReaderKey(StaticSecret::random_from_rng(OsRng))
+    }
+
+    pub fn public_key(&self) -> ReaderPublicKey {
+        ReaderPublicKey(PublicKey::from(&self.0))
+    }
+
+    /// The secret key as text, for saving or moving to another device
+    pub fn to_secret_string(&self) -> String {
+        encode(self.0.as_bytes())
+    }
+
+    pub fn from_secret_string(s: &str) -> Result<Self, BoredError> {
+        Ok(ReaderKey(StaticSecret::from(decode_32(s)?)))
+    }
+
+    /// The secret key as a 24 word BIP-39 seed phrase, easier to write down and type in than the
+    /// secret string
+    pub fn to_seed_phrase(&self) -> String {
+        bip39::Mnemonic::from_entropy(self.0.as_bytes())
+            .expect("32 bytes is a valid entropy length")
+            .to_string()
+    }
+
+    /// The key from a BIP-39 seed phrase. A 24 word phrase holds the key itself, as made by
+    /// to_seed_phrase, the key is derived from the seed of shorter phrases so one the user
+    /// already has can be used.
+    pub fn from_seed_phrase(phrase: &str) -> Result<Self, BoredError> {
+        // the phrase isn't put in the error as it is the secret
+        let mnemonic = bip39::Mnemonic::parse_normalized(&phrase.trim().to_lowercase())
+            .map_err(|_| BoredError::InvalidKey("seed phrase".to_string()))?;
+        let entropy = mnemonic.to_entropy();
+        let secret: [u8; 32] = match entropy.try_into() {
+            Ok(secret) => secret,
+            Err(_) => {
+                let mut secret = [0u8; 32];
+                Hkdf::<Sha256>::new(None, &mnemonic.to_seed(""))
+                    .expand(SEED_PHRASE_INFO, &mut secret)
+                    .map_err(|_| BoredError::EncryptionError)?;
+                secret
+            }
+        };
+        Ok(ReaderKey(StaticSecret::from(secret)))
+    }
+
+    /// The key from a secret string or a seed phrase, whichever was given
+    pub fn parse(s: &str) -> Result<Self, BoredError> {
+        if s.split_whitespace().count() > 1 {
+            ReaderKey::from_seed_phrase(s)
+        } else {
+            ReaderKey::from_secret_string(s)
+        }
+    }
+
+    /// Load the reader key saved at path, generating and saving a new one if there isn't one
+    pub fn load_or_create(path: &Path) -> Result<Self, BoredError> {
+        if path.exists() {
+            return ReaderKey::from_secret_string(&std::fs::read_to_string(path)?);
+        }
+        let key = ReaderKey::generate();
+        key.save(path)?;
+        Ok(key)
+    }
+
+    /// Save the secret key at path, where it can be set only its owner can read it
+    pub fn save(&self, path: &Path) -> Result<(), BoredError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, self.to_secret_string())?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+        }
+        Ok(())
+    }
+}
+
+/// The content key of an envelope encrypted for one reader
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+struct WrappedKey {
+    nonce: String,
+    key: String,
+}
+
+/// A bored encrypted for a list of readers, as published in place of the plain bored
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncryptedBored {
+    /// Public half of the one use key the content key was wrapped with
+    ephemeral_key: String,
+    readers: Vec<WrappedKey>,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// Key for wrapping the content key for one reader, from the x25519 shared secret and both
+/// public keys
+fn wrapping_key(shared: &[u8; 32], ephemeral: &PublicKey, reader: &PublicKey) -> Key {
+    let mut salt = [0u8; 64];
+    salt[..32].copy_from_slice(ephemeral.as_bytes());
+    salt[32..].copy_from_slice(reader.as_bytes());
+    let mut key = Key::default();
+    Hkdf::<Sha256>::new(Some(&salt), shared)
+        .expand(KEY_WRAP_INFO, &mut key)
+        .expect("32 bytes is a valid hkdf output length");
+    key
+}
+
+fn seal(key: &Key, plaintext: &[u8]) -> Result<(String, String), BoredError> {
+    let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = ChaCha20Poly1305::new(key)
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| BoredError::EncryptionError)?;
+    Ok((encode(&nonce), encode(&ciphertext)))
+}
+
+fn open(key: &Key, nonce: &str, ciphertext: &str) -> Option<Vec<u8>> {
+    let nonce = BASE64_URL_SAFE_NO_PAD.decode(nonce).ok()?;
+    if nonce.len() != 12 {
+        return None;
+    }
+    let ciphertext = BASE64_URL_SAFE_NO_PAD.decode(ciphertext).ok()?;
+    ChaCha20Poly1305::new(key)
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .ok()
+}
+
+impl EncryptedBored {
+    /// Encrypt a bored so that only the holders of the readers' secret keys can read it
+    pub fn seal(bored: &Bored, readers: &[ReaderPublicKey]) -> Result<Self, BoredError> {
+        if readers.is_empty() || readers.len() > MAX_READERS {
+            return Err(BoredError::InvalidReaders(readers.len()));
+        }
+        let content_key = ChaCha20Poly1305::generate_key(&mut OsRng);
+        let (nonce, ciphertext) = seal(&content_key, serde_json::to_string(bored)?.as_bytes())?;
+        let ephemeral = StaticSecret::random_from_rng(OsRng);
+        let ephemeral_public = PublicKey::from(&ephemeral);
+        let mut wrapped = vec![];
+        for reader in readers {
+            let shared = ephemeral.diffie_hellman(&reader.0);
+            let key = wrapping_key(shared.as_bytes(), &ephemeral_public, &reader.0);
+            let (nonce, key) = seal(&key, &content_key)?;
+            wrapped.push(WrappedKey { nonce, key });
+        }
+        Ok(EncryptedBored {
+            ephemeral_key: encode(ephemeral_public.as_bytes()),
+            readers: wrapped,
+            nonce,
+            ciphertext,
+        })
+    }
+
+    /// Decrypt the bored with a reader's secret key, trying each wrapped key in turn as the
+    /// envelope doesn't say which belongs to who
+    pub fn open(&self, reader_key: &ReaderKey) -> Result<Bored, BoredError> {
+        let ephemeral = PublicKey::from(decode_32(&self.ephemeral_key)?);
+        let reader = PublicKey::from(&reader_key.0);
+        let shared = reader_key.0.diffie_hellman(&ephemeral);
+        let key = wrapping_key(shared.as_bytes(), &ephemeral, &reader);
+        let content_key = self
+            .readers
+            .iter()
+            .find_map(|wrapped| open(&key, &wrapped.nonce, &wrapped.key))
+            .filter(|content_key| content_key.len() == 32)
+            .ok_or(BoredError::NotAReader)?;
+        let plaintext = open(Key::from_slice(&content_key), &self.nonce, &self.ciphertext)
+            .ok_or(BoredError::EncryptionError)?;
+        let json = String::from_utf8(plaintext).map_err(|_| BoredError::EncryptionError)?;
+        let mut bored = Bored::from_json(&json)?;
+        bored.sanitize()?;
+        Ok(bored)
+    }
+
+    /// How many readers the bored was encrypted for
+    pub fn reader_count(&self) -> usize {
+        self.readers.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coordinate;
+    use crate::notice::Notice;
+
+    fn secret_bored() -> Result<Bored, BoredError> {
+        let mut bored = Bored::create("Secret", Coordinate { x: 30, y: 10 });
+        let mut notice = Notice::create(Coordinate { x: 12, y: 4 });
+        notice.write("for your eyes only")?;
+        bored.add(notice, Coordinate { x: 1, y: 1 })?;
+        Ok(bored)
+    }
+
+    #[test]
+    fn test_seal_and_open() -> Result<(), BoredError> {
+        let bored = secret_bored()?;
+        let alice = ReaderKey::generate();
+        let bob = ReaderKey::generate();
+        let eve = ReaderKey::generate();
+        let sealed = EncryptedBored::seal(&bored, &[alice.public_key(), bob.public_key()])?;
+        assert_eq!(sealed.reader_count(), 2);
+        assert!(!serde_json::to_string(&sealed)?.contains("eyes"));
+        assert_eq!(sealed.open(&alice)?, bored);
+        assert_eq!(sealed.open(&bob)?, bored);
+        assert_eq!(sealed.open(&eve), Err(BoredError::NotAReader));
+
+        let mut tampered = sealed.clone();
+        let mut ciphertext = BASE64_URL_SAFE_NO_PAD.decode(&tampered.ciphertext).unwrap();
+        ciphertext[0] ^= 1;
+        tampered.ciphertext = encode(&ciphertext);
+        assert_eq!(tampered.open(&alice), Err(BoredError::EncryptionError));
+        assert_eq!(
+            EncryptedBored::seal(&bored, &[]),
+            Err(BoredError::InvalidReaders(0))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_bored_readers() -> Result<(), BoredError> {
+        let mut bored = secret_bored()?;
+        assert!(!bored.is_encrypted());
+        assert!(!serde_json::to_string(&bored)?.contains("readers"));
+        let alice = ReaderKey::generate();
+        bored.set_readers(&[alice.public_key()])?;
+        assert!(bored.is_encrypted());
+        // the readers are sealed with the bored so whoever opens it can seal it for them again
+        let opened = EncryptedBored::seal(&bored, &bored.get_readers())?.open(&alice)?;
+        assert_eq!(opened.get_readers(), vec![alice.public_key()]);
+        assert_eq!(
+            bored.set_readers(&vec![alice.public_key(); MAX_READERS + 1]),
+            Err(BoredError::InvalidReaders(MAX_READERS + 1))
+        );
+        bored.readers = vec![String::new(); MAX_READERS + 1];
+        assert_eq!(
+            bored.sanitize(),
+            Err(BoredError::InvalidReaders(MAX_READERS + 1))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_key_round_trips() -> Result<(), BoredError> {
+        let key = ReaderKey::generate();
+        let restored = ReaderKey::from_secret_string(&key.to_secret_string())?;
+        assert_eq!(restored.public_key(), key.public_key());
+        let public = key.public_key();
+        assert_eq!(ReaderPublicKey::from_string(&public.to_string())?, public);
+        assert!(!format!("{key:?}").contains(&key.to_secret_string()));
+        assert_eq!(
+            ReaderPublicKey::from_string("short"),
+            Err(BoredError::InvalidKey("short".to_string()))
+        );
+
+        let dir = std::env::temp_dir().join(format!("we-are-bored-key-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("reader_key");
+        let created = ReaderKey::load_or_create(&path)?;
+        let loaded = ReaderKey::load_or_create(&path)?;
+        assert_eq!(created.public_key(), loaded.public_key());
+        let other = ReaderKey::generate();
+        other.save(&path)?;
+        let loaded = ReaderKey::load_or_create(&path)?;
+        assert_eq!(loaded.public_key(), other.public_key());
+        let _ = std::fs::remove_dir_all(dir);
+        Ok(())
+    }
+
+    #[test]
+    fn test_seed_phrase() -> Result<(), BoredError> {
+        let key = ReaderKey::generate();
+        let phrase = key.to_seed_phrase();
+        assert_eq!(phrase.split_whitespace().count(), 24);
+        let restored = ReaderKey::from_seed_phrase(&phrase)?;
+        assert_eq!(restored.public_key(), key.public_key());
+        // boreds sealed for the key can be read on a device the phrase was typed into
+        let bored = secret_bored()?;
+        let sealed = EncryptedBored::seal(&bored, &[key.public_key()])?;
+        assert_eq!(sealed.open(&restored)?, bored);
+        // either form is taken, with stray spacing and capitals
+        let messy = format!("  {}\n", phrase.to_uppercase().replace(' ', "  "));
+        assert_eq!(ReaderKey::parse(&messy)?.public_key(), key.public_key());
+        assert_eq!(
+            ReaderKey::parse(&key.to_secret_string())?.public_key(),
+            key.public_key()
+        );
+        // a 12 word phrase the user already has gives the same key each time
+        let twelve = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+        let derived = ReaderKey::from_seed_phrase(twelve)?;
+        assert_eq!(
+            ReaderKey::from_seed_phrase(twelve)?.public_key(),
+            derived.public_key()
+        );
+        // a misspelt word or a bad checksum isn't taken, without saying what was typed
+        let misspelt = "legal winner thank year wave sausage worth useful legal winner thank thank";
+        assert_eq!(
+            ReaderKey::parse(misspelt).err(),
+            Some(BoredError::InvalidKey("seed phrase".to_string()))
+        );
+        Ok(())
+    }
+}
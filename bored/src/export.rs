@@ -0,0 +1,815 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Render a snapshot of a bored for sharing outside of a terminal ui, laid out the same way as
+//! surf-bored draws it: the frame and background pattern with notices on top, newest uppermost,
+//! or as an atom feed of its notices and a calendar of its events
+
+use crate::notice::{NoticeKind, char_width, get_hyperlinks};
+use crate::url::BoredAddress;
+use crate::{Bored, BoredError, Coordinate, FrameStyle};
+use chrono::{DateTime, SecondsFormat};
+
+/// Width in pixels of a character cell in svg exports
+const SVG_CHAR_WIDTH: usize = 10;
+/// Height in pixels of a character cell in svg exports
+const SVG_CHAR_HEIGHT: usize = 20;
+/// Box drawing characters for notice borders, matching surf-bored
+const THICK_BOX: [char; 6] = ['┏', '━', '┓', '┃', '┗', '┛'];
+/// Most characters of a notice's first line used as the title of its atom entry
+const ATOM_TITLE_LENGTH: usize = 60;
+/// Longest line in bytes of an icalendar file before it is folded onto the next
+const ICAL_LINE_LENGTH: usize = 75;
+
+/// What part of the bored a character belongs to, used to decide how to style it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CellKind {
+    Empty,
+    Frame,
+    Pattern,
+    NoticeBorder,
+    Text,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Cell {
+    /// What is drawn in the cell, empty when a wide character on the left covers it and more
+    /// than one character when zero width characters combine with the first
+    symbol: String,
+    kind: CellKind,
+    link: Option<String>,
+}
+
+impl Cell {
+    fn empty() -> Cell {
+        Cell {
+            symbol: String::from(" "),
+            kind: CellKind::Empty,
+            link: None,
+        }
+    }
+}
+
+/// A run of neighbouring cells on a row that share the same styling
+struct Segment {
+    text: String,
+    kind: CellKind,
+    link: Option<String>,
+}
+
+/// The bored flattened into a grid of characters
+struct Canvas {
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Canvas {
+    /// Draw the bored, with any notices moderation hides drawn empty
+    fn create(bored: &Bored) -> Result<Canvas, BoredError> {
+        let bored = &bored.redact_hidden();
+        let dimensions = bored.get_dimensions();
+        let mut canvas = Canvas {
+            rows: vec![vec![Cell::empty(); dimensions.x as usize]; dimensions.y as usize],
+        };
+        let background = bored.get_background();
+        let framed = background.get_frame() != FrameStyle::None;
+        let inset = if framed { 1 } else { 0 };
+        for y in inset..dimensions.y.saturating_sub(inset) {
+            for x in inset..dimensions.x.saturating_sub(inset) {
+                if let Some(char) = background.get_char_at(Coordinate { x, y }) {
+                    canvas.set(x, y, char, CellKind::Pattern, None);
+                }
+            }
+        }
+        if let Some(chars) = frame_chars(background.get_frame()) {
+            canvas.draw_box(
+                Coordinate { x: 0, y: 0 },
+                dimensions,
+                chars,
+                CellKind::Frame,
+            );
+        }
+        for notice in bored.notices() {
+            let top_left = notice.get_top_left();
+            let notice_dimensions = notice.get_dimensions();
+            for y in top_left.y..top_left.y + notice_dimensions.y {
+                for x in top_left.x..top_left.x + notice_dimensions.x {
+                    canvas.set(x, y, ' ', CellKind::Text, None);
+                }
+            }
+            canvas.draw_box(
+                top_left,
+                notice_dimensions,
+                THICK_BOX,
+                CellKind::NoticeBorder,
+            );
+            let hyperlinks = get_hyperlinks(notice.get_content())?;
+            let display = notice.get_display()?;
+            let hyperlink_locations = display.get_hyperlink_locations();
+            let positions = notice.get_char_positions()?;
+            for (char_index, (char, position)) in display
+                .get_display_text()
+                .chars()
+                .zip(positions)
+                .enumerate()
+            {
+                let Some(position) = position else {
+                    continue;
+                };
+                let width = char_width(char);
+                // text that would overflow the notice isn't shown by surf-bored either
+                if position.x + width.max(1) > notice.get_text_width()
+                    || position.y >= notice.get_text_height()
+                {
+                    continue;
+                }
+                let (x, y) = (top_left.x + 1 + position.x, top_left.y + 1 + position.y);
+                if width == 0 {
+                    canvas.combine(x, y, char);
+                    continue;
+                }
+                let link = hyperlink_locations
+                    .iter()
+                    .position(|(start, end)| (*start..*end).contains(&char_index))
+                    .and_then(|index| hyperlinks.get(index))
+                    .map(|hyperlink| hyperlink.get_link());
+                canvas.set(x, y, char, CellKind::Text, link.clone());
+                if width == 2 {
+                    canvas.set_cell(x + 1, y, String::new(), CellKind::Text, link);
+                }
+            }
+        }
+        Ok(canvas)
+    }
+
+    fn set(&mut self, x: u16, y: u16, char: char, kind: CellKind, link: Option<String>) {
+        self.set_cell(x, y, char.to_string(), kind, link);
+    }
+
+    fn set_cell(&mut self, x: u16, y: u16, symbol: String, kind: CellKind, link: Option<String>) {
+        let (x, y) = (x as usize, y as usize);
+        let Some(row) = self.rows.get_mut(y) else {
+            return;
+        };
+        if x >= row.len() {
+            return;
+        }
+        // a wide character is left whole or not at all, so replace any half covered one
+        if row[x].symbol.is_empty() && x > 0 {
+            row[x - 1].symbol = String::from(" ");
+        }
+        if row.get(x + 1).is_some_and(|next| next.symbol.is_empty()) {
+            row[x + 1].symbol = String::from(" ");
+        }
+        row[x] = Cell { symbol, kind, link };
+    }
+
+    /// Add a zero width character onto the character already drawn in a cell
+    fn combine(&mut self, x: u16, y: u16, char: char) {
+        if let Some(cell) = self
+            .rows
+            .get_mut(y as usize)
+            .and_then(|row| row.get_mut(x as usize))
+        {
+            cell.symbol.push(char);
+        }
+    }
+
+    /// Draw a box outline using chars in the order top left, horizontal, top right, vertical,
+    /// bottom left, bottom right
+    fn draw_box(
+        &mut self,
+        top_left: Coordinate,
+        dimensions: Coordinate,
+        chars: [char; 6],
+        kind: CellKind,
+    ) {
+        if dimensions.x < 2 || dimensions.y < 2 {
+            return;
+        }
+        let right = top_left.x + dimensions.x - 1;
+        let bottom = top_left.y + dimensions.y - 1;
+        for x in top_left.x + 1..right {
+            self.set(x, top_left.y, chars[1], kind, None);
+            self.set(x, bottom, chars[1], kind, None);
+        }
+        for y in top_left.y + 1..bottom {
+            self.set(top_left.x, y, chars[3], kind, None);
+            self.set(right, y, chars[3], kind, None);
+        }
+        self.set(top_left.x, top_left.y, chars[0], kind, None);
+        self.set(right, top_left.y, chars[2], kind, None);
+        self.set(top_left.x, bottom, chars[4], kind, None);
+        self.set(right, bottom, chars[5], kind, None);
+    }
+
+    fn segments(row: &[Cell]) -> Vec<Segment> {
+        let mut segments: Vec<Segment> = vec![];
+        for cell in row {
+            match segments.last_mut() {
+                Some(segment) if segment.kind == cell.kind && segment.link == cell.link => {
+                    segment.text.push_str(&cell.symbol);
+                }
+                _ => segments.push(Segment {
+                    text: cell.symbol.clone(),
+                    kind: cell.kind,
+                    link: cell.link.clone(),
+                }),
+            }
+        }
+        segments
+    }
+}
+
+fn frame_chars(frame: FrameStyle) -> Option<[char; 6]> {
+    match frame {
+        FrameStyle::Rounded => Some(['╭', '─', '╮', '│', '╰', '╯']),
+        FrameStyle::Plain => Some(['┌', '─', '┐', '│', '└', '┘']),
+        FrameStyle::Double => Some(['╔', '═', '╗', '║', '╚', '╝']),
+        FrameStyle::Thick => Some(THICK_BOX),
+        FrameStyle::None => None,
+    }
+}
+
+/// Only links that are safe to follow from a web page are exported as links, anything else
+/// (e.g. javascript:) is left as plain text
+fn is_exportable_link(link: &str) -> bool {
+    let link = link.to_lowercase();
+    ["http://", "https://", "bored://"]
+        .iter()
+        .any(|scheme| link.starts_with(scheme))
+}
+
+/// Escape text for use in html or svg, including inside attribute values
+pub fn escape_markup(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+/// Plain text drawing of the bored using box drawing characters, one line per row
+pub fn to_text(bored: &Bored) -> Result<String, BoredError> {
+    let canvas = Canvas::create(bored)?;
+    let mut text = String::new();
+    for row in &canvas.rows {
+        text.extend(row.iter().map(|cell| cell.symbol.as_str()));
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// Text drawing of the bored with ANSI escape codes, the background is dimmed and hyperlinks
+/// are underlined
+pub fn to_ansi(bored: &Bored) -> Result<String, BoredError> {
+    let canvas = Canvas::create(bored)?;
+    let mut text = String::new();
+    for row in &canvas.rows {
+        for segment in Canvas::segments(row) {
+            let code = match (segment.kind, &segment.link) {
+                (_, Some(_)) => Some("\x1b[4;34m"),
+                (CellKind::Pattern, None) => Some("\x1b[2m"),
+                (CellKind::NoticeBorder, None) => Some("\x1b[1m"),
+                _ => None,
+            };
+            match code {
+                Some(code) => {
+                    text.push_str(code);
+                    text.push_str(&segment.text);
+                    text.push_str("\x1b[0m");
+                }
+                None => text.push_str(&segment.text),
+            }
+        }
+        text.push('\n');
+    }
+    Ok(text)
+}
+
+/// Css hex for a colour of the bored's palette, the default if the palette doesn't have it
+fn css_colour(colour: Option<(u8, u8, u8)>, default: &str) -> String {
+    match colour {
+        Some((red, green, blue)) => format!("#{red:02x}{green:02x}{blue:02x}"),
+        None => default.to_string(),
+    }
+}
+
+/// Standalone html page showing the bored with working hyperlinks, in the bored's own colours
+/// if it has a palette
+pub fn to_html(bored: &Bored) -> Result<String, BoredError> {
+    to_html_with_links(bored, |link| {
+        is_exportable_link(link).then(|| link.to_string())
+    })
+}
+
+/// Html page like to_html with each hyperlink going where href says, such as a page for a
+/// bored rather than its bored:// address. Hyperlinks href gives nowhere for are left as their
+/// text. Links to the web are marked so search engines don't credit them to the page.
+pub fn to_html_with_links(
+    bored: &Bored,
+    href: impl Fn(&str) -> Option<String>,
+) -> Result<String, BoredError> {
+    let canvas = Canvas::create(bored)?;
+    let name = escape_markup(bored.get_name());
+    let palette = bored.get_palette();
+    let background = css_colour(palette.get_background(), "#000");
+    let text = css_colour(palette.get_text(), "#ddd");
+    let hyperlink = css_colour(palette.get_hyperlink(), "#6cf");
+    let mut html = format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{name}</title>\n\
+         <style>\n\
+         body {{ background: {background}; color: {text}; }}\n\
+         pre {{ font-family: monospace; line-height: 1; }}\n\
+         .pattern {{ color: #666; }}\n\
+         .notice-border {{ color: #fff; }}\n\
+         a {{ color: {hyperlink}; }}\n\
+         </style>\n</head>\n<body>\n<pre class=\"bored\">"
+    );
+    for row in &canvas.rows {
+        for segment in Canvas::segments(row) {
+            let text = escape_markup(&segment.text);
+            match (segment.link.as_deref().and_then(&href), segment.kind) {
+                (Some(link), _) => {
+                    let lowercase = link.to_lowercase();
+                    let is_web = ["http://", "https://"]
+                        .iter()
+                        .any(|scheme| lowercase.starts_with(scheme));
+                    let rel = if is_web {
+                        " rel=\"nofollow noopener noreferrer\""
+                    } else {
+                        ""
+                    };
+                    html.push_str(&format!(
+                        "<a href=\"{}\"{rel}>{text}</a>",
+                        escape_markup(&link)
+                    ));
+                }
+                (_, CellKind::Pattern) => {
+                    html.push_str(&format!("<span class=\"pattern\">{text}</span>"));
+                }
+                (_, CellKind::NoticeBorder) => {
+                    html.push_str(&format!("<span class=\"notice-border\">{text}</span>"));
+                }
+                _ => html.push_str(&text),
+            }
+        }
+        html.push('\n');
+    }
+    html.push_str("</pre>\n</body>\n</html>\n");
+    Ok(html)
+}
+
+/// Svg image of the bored with a monospace character grid, hyperlinks are svg links
+pub fn to_svg(bored: &Bored) -> Result<String, BoredError> {
+    let canvas = Canvas::create(bored)?;
+    let dimensions = bored.get_dimensions();
+    let width = dimensions.x as usize * SVG_CHAR_WIDTH;
+    let height = dimensions.y as usize * SVG_CHAR_HEIGHT;
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         viewBox=\"0 0 {width} {height}\" font-family=\"monospace\" font-size=\"16\">\n\
+         <title>{}</title>\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#000\"/>\n",
+        escape_markup(bored.get_name())
+    );
+    for (y, row) in canvas.rows.iter().enumerate() {
+        // baseline sits a little above the bottom of the cell so descenders fit
+        let baseline = (y + 1) * SVG_CHAR_HEIGHT - SVG_CHAR_HEIGHT / 4;
+        svg.push_str(&format!(
+            "<text x=\"0\" y=\"{baseline}\" xml:space=\"preserve\" textLength=\"{width}\" \
+             lengthAdjust=\"spacing\" fill=\"#ddd\">"
+        ));
+        for segment in Canvas::segments(row) {
+            let text = escape_markup(&segment.text);
+            match (&segment.link, segment.kind) {
+                (Some(link), _) if is_exportable_link(link) => {
+                    svg.push_str(&format!(
+                        "<a href=\"{}\"><tspan fill=\"#6cf\" text-decoration=\"underline\">{text}</tspan></a>",
+                        escape_markup(link)
+                    ));
+                }
+                (_, CellKind::Pattern) => {
+                    svg.push_str(&format!("<tspan fill=\"#666\">{text}</tspan>"));
+                }
+                (_, CellKind::NoticeBorder) => {
+                    svg.push_str(&format!("<tspan fill=\"#fff\">{text}</tspan>"));
+                }
+                _ => svg.push_str(&text),
+            }
+        }
+        svg.push_str("</text>\n");
+    }
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+/// Text escaped for xml 1.0, which can't have control characters other than tab, newline and
+/// carriage return even escaped, so any others peers sent are replaced
+fn escape_xml(text: &str) -> String {
+    let allowed: String = text
+        .chars()
+        .map(|char| match char {
+            '\t' | '\n' | '\r' => char,
+            '\u{0}'..='\u{1f}' | '\u{fffe}' | '\u{ffff}' => char::REPLACEMENT_CHARACTER,
+            _ => char,
+        })
+        .collect();
+    escape_markup(&allowed)
+}
+
+/// Rfc 3339 time for milliseconds since the unix epoch, the epoch itself if they are out of
+/// range
+fn atom_time(timestamp_ms: i64) -> String {
+    DateTime::from_timestamp_millis(timestamp_ms)
+        .unwrap_or_default()
+        .to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+/// The first line of a notice's text with something in it, cut to ATOM_TITLE_LENGTH
+fn atom_title(text: &str) -> String {
+    let line = text.lines().map(str::trim).find(|line| !line.is_empty());
+    let Some(line) = line else {
+        return String::from("Empty notice");
+    };
+    match line.char_indices().nth(ATOM_TITLE_LENGTH) {
+        Some((end, _)) => format!("{}…", &line[..end]),
+        None => line.to_string(),
+    }
+}
+
+/// Atom feed of the bored at the address with an entry for each notice, newest first, so it
+/// can be followed from a feed reader. Entries are identified by notice id, so a reader shows
+/// a notice as new once however often the feed is made again. Notices hidden by moderation are
+/// left out and notices without a time in their id are dated to the unix epoch.
+pub fn to_atom(bored: &Bored, address: &BoredAddress) -> Result<String, BoredError> {
+    let bored = bored.redact_hidden();
+    let mut notices: Vec<_> = bored.notices().iter().collect();
+    notices.sort_by_key(|notice| std::cmp::Reverse(notice.get_timestamp()));
+    let updated = atom_time(
+        notices
+            .iter()
+            .filter_map(|notice| notice.get_timestamp())
+            .max()
+            .unwrap_or_default(),
+    );
+    let link = escape_xml(&address.to_string());
+    let mut atom = format!(
+        "<?xml version=\"1.0\" encoding=\"utf-8\"?>\n\
+         <feed xmlns=\"http://www.w3.org/2005/Atom\">\n\
+         <title>{}</title>\n<id>{link}</id>\n<link href=\"{link}\"/>\n\
+         <updated>{updated}</updated>\n<author><name>We are bored</name></author>\n",
+        escape_xml(bored.get_name())
+    );
+    for notice in notices {
+        let text = notice.get_display()?.get_display_text();
+        atom.push_str(&format!(
+            "<entry>\n<title>{}</title>\n<id>{link}#{}</id>\n<link href=\"{link}\"/>\n\
+             <updated>{}</updated>\n<content type=\"text\">{}</content>\n</entry>\n",
+            escape_xml(&atom_title(&text)),
+            escape_xml(notice.get_notice_id()),
+            atom_time(notice.get_timestamp().unwrap_or_default()),
+            escape_xml(&text),
+        ));
+    }
+    atom.push_str("</feed>\n");
+    Ok(atom)
+}
+
+/// Time for milliseconds since the unix epoch in icalendar's utc form, e.g. 20260612T190000Z
+fn ical_time(timestamp_ms: i64) -> String {
+    DateTime::from_timestamp_millis(timestamp_ms)
+        .unwrap_or_default()
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+/// Text escaped for an icalendar property value
+fn escape_ical(text: &str) -> String {
+    let mut escaped = String::new();
+    for char in text.chars() {
+        match char {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(char),
+        }
+    }
+    escaped
+}
+
+/// Add a content line to an icalendar file, folded onto lines starting with a space where it
+/// is longer than ICAL_LINE_LENGTH bytes and never splitting a character
+fn push_ical_line(ical: &mut String, line: &str) {
+    let mut length = 0;
+    for char in line.chars() {
+        if length + char.len_utf8() > ICAL_LINE_LENGTH {
+            ical.push_str("\r\n ");
+            length = 1;
+        }
+        ical.push(char);
+        length += char.len_utf8();
+    }
+    ical.push_str("\r\n");
+}
+
+/// Icalendar file with an event for each event notice on the bored at the address, so they
+/// can be added to a calendar. Events keep the notice's text as their description and are
+/// identified by notice id, so adding them again updates rather than repeats them. Notices
+/// hidden by moderation are left out.
+pub fn to_ical(bored: &Bored, address: &BoredAddress) -> Result<String, BoredError> {
+    let bored = bored.redact_hidden();
+    let mut ical = String::new();
+    for line in [
+        "BEGIN:VCALENDAR",
+        "VERSION:2.0",
+        "PRODID:-//We are bored//bored//EN",
+    ] {
+        push_ical_line(&mut ical, line);
+    }
+    push_ical_line(
+        &mut ical,
+        &format!("X-WR-CALNAME:{}", escape_ical(bored.get_name())),
+    );
+    for notice in bored.notices() {
+        let NoticeKind::Event {
+            title,
+            start,
+            end,
+            location,
+        } = notice.get_kind()
+        else {
+            continue;
+        };
+        let text = notice.get_display()?.get_display_text();
+        push_ical_line(&mut ical, "BEGIN:VEVENT");
+        push_ical_line(
+            &mut ical,
+            &format!(
+                "UID:{}@{}",
+                escape_ical(notice.get_notice_id()),
+                address.get_topic()
+            ),
+        );
+        push_ical_line(
+            &mut ical,
+            &format!(
+                "DTSTAMP:{}",
+                ical_time(notice.get_timestamp().unwrap_or(*start))
+            ),
+        );
+        push_ical_line(&mut ical, &format!("DTSTART:{}", ical_time(*start)));
+        if let Some(end) = end {
+            push_ical_line(&mut ical, &format!("DTEND:{}", ical_time(*end)));
+        }
+        push_ical_line(&mut ical, &format!("SUMMARY:{}", escape_ical(title)));
+        if let Some(location) = location {
+            push_ical_line(&mut ical, &format!("LOCATION:{}", escape_ical(location)));
+        }
+        if !text.trim().is_empty() {
+            push_ical_line(&mut ical, &format!("DESCRIPTION:{}", escape_ical(&text)));
+        }
+        push_ical_line(&mut ical, &format!("URL:{address}"));
+        push_ical_line(&mut ical, "END:VEVENT");
+    }
+    push_ical_line(&mut ical, "END:VCALENDAR");
+    Ok(ical)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notice::Notice;
+    use crate::{Background, Palette};
+
+    fn example_bored() -> Result<Bored, BoredError> {
+        let mut bored = Bored::create("<Export>", Coordinate { x: 14, y: 6 });
+        bored.set_background(Background::create(".", FrameStyle::Plain)?);
+        let mut notice = Notice::create(Coordinate { x: 8, y: 4 });
+        notice.write("hi [a&b](https://x.y/?q=\"1\")")?;
+        bored.add(notice, Coordinate { x: 2, y: 1 })?;
+        Ok(bored)
+    }
+
+    #[test]
+    fn test_to_text() -> Result<(), BoredError> {
+        let text = to_text(&example_bored()?)?;
+        let expected = "\
+┌────────────┐
+│.┏━━━━━━┓...│
+│.┃hi a&b┃...│
+│.┃      ┃...│
+│.┗━━━━━━┛...│
+└────────────┘
+";
+        assert_eq!(text, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_hidden_notices_are_drawn_empty() -> Result<(), BoredError> {
+        let mut bored = example_bored()?;
+        let hash = bored.notices()[0].get_hash();
+        bored.set_hidden_notices(vec![hash])?;
+        for drawing in [
+            to_text(&bored)?,
+            to_ansi(&bored)?,
+            to_html(&bored)?,
+            to_svg(&bored)?,
+        ] {
+            assert!(!drawing.contains("hi"));
+            assert!(!drawing.contains("x.y"));
+        }
+        assert!(to_text(&bored)?.contains("│.┃      ┃...│"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_wide_characters() -> Result<(), BoredError> {
+        let mut bored = Bored::create("wide", Coordinate { x: 7, y: 4 });
+        bored.set_background(Background::create(" ", FrameStyle::None)?);
+        let mut notice = Notice::create(Coordinate { x: 6, y: 4 });
+        notice.write("日本語e\u{301}")?;
+        bored.add(notice, Coordinate { x: 0, y: 0 })?;
+        // a later notice covering half of a wide character blanks all of it
+        let mut notice = Notice::create(Coordinate { x: 3, y: 3 });
+        notice.write("x")?;
+        bored.add(notice, Coordinate { x: 4, y: 1 })?;
+        let expected = "\
+┏━━━━┓ 
+┃日 ┏━┓
+┃語e\u{301}┃x┃
+┗━━━┗━┛
+";
+        assert_eq!(to_text(&bored)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_ansi() -> Result<(), BoredError> {
+        let ansi = to_ansi(&example_bored()?)?;
+        assert!(ansi.contains("hi \x1b[4;34ma&b\x1b[0m"));
+        assert!(ansi.contains("\x1b[2m.\x1b[0m"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_html() -> Result<(), BoredError> {
+        let html = to_html(&example_bored()?)?;
+        assert!(html.contains("<title>&lt;Export&gt;</title>"));
+        assert!(html.contains(
+            "<a href=\"https://x.y/?q=&quot;1&quot;\" rel=\"nofollow noopener noreferrer\">a&amp;b</a>"
+        ));
+
+        let mut bored = Bored::create("js", Coordinate { x: 30, y: 5 });
+        let mut notice = Notice::create(Coordinate { x: 20, y: 3 });
+        notice.write("[click](javascript:alert)")?;
+        bored.add(notice, Coordinate { x: 0, y: 0 })?;
+        let html = to_html(&bored)?;
+        assert!(!html.contains("<a "));
+        assert!(html.contains("click"));
+        assert!(html.contains("body { background: #000; color: #ddd; }"));
+
+        let palette = Palette::create(None, Some("#FFF8E7"), None, Some("#aa0000"))?;
+        bored.set_palette(palette);
+        let html = to_html(&bored)?;
+        assert!(html.contains("body { background: #fff8e7; color: #ddd; }"));
+        assert!(html.contains("a { color: #aa0000; }"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_svg() -> Result<(), BoredError> {
+        let svg = to_svg(&example_bored()?)?;
+        assert!(
+            svg.starts_with(
+                "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"140\" height=\"120\""
+            )
+        );
+        assert_eq!(svg.matches("<text ").count(), 6);
+        assert!(svg.contains("<a href=\"https://x.y/?q=&quot;1&quot;\">"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_atom() -> Result<(), BoredError> {
+        let mut bored = Bored::create("<Export>", Coordinate { x: 40, y: 10 });
+        let mut older = Notice::create(Coordinate { x: 20, y: 5 });
+        older.write("hi [a&b](https://x.y/)")?;
+        older.set_notice_id("notice:1779796800000:ab12".to_string());
+        bored.add(older, Coordinate { x: 2, y: 1 })?;
+        let mut newer = Notice::create(Coordinate { x: 20, y: 5 });
+        newer.write("\n  second\nnotice")?;
+        newer.set_notice_id("notice:1779800400000:cd34".to_string());
+        bored.add(newer, Coordinate { x: 4, y: 1 })?;
+        let address = BoredAddress::from_string("bored://export")?;
+        let atom = to_atom(&bored, &address)?;
+        assert!(atom.contains("<title>&lt;Export&gt;</title>\n<id>bored://export</id>"));
+        assert!(atom.contains("<updated>2026-05-26T13:00:00Z</updated>\n<author>"));
+        let entries: Vec<&str> = atom.split("<entry>").skip(1).collect();
+        assert_eq!(entries.len(), 2);
+        assert!(entries[0].contains("<title>second</title>"));
+        assert!(entries[0].contains("<id>bored://export#notice:1779800400000:cd34</id>"));
+        assert!(entries[1].contains("<title>hi a&amp;b</title>"));
+        assert!(entries[1].contains("<updated>2026-05-26T12:00:00Z</updated>"));
+        assert!(entries[1].ends_with("</entry>\n</feed>\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_to_ical() -> Result<(), BoredError> {
+        let mut bored = Bored::create("Village hall", Coordinate { x: 40, y: 10 });
+        bored.add(
+            Notice::create(Coordinate { x: 10, y: 4 }),
+            Coordinate { x: 0, y: 0 },
+        )?;
+        let mut event = Notice::create(Coordinate { x: 20, y: 5 });
+        event.write("Bring cakes, tea; and more")?;
+        event.set_notice_id("notice:1779796800000:ab12".to_string());
+        event.set_kind(NoticeKind::Event {
+            title: "Jumble sale".to_string(),
+            start: 1781290800000,
+            end: Some(1781298000000),
+            location: Some("The hall".to_string()),
+        })?;
+        bored.add(event, Coordinate { x: 20, y: 0 })?;
+        let address = BoredAddress::from_string("bored://village")?;
+        let ical = to_ical(&bored, &address)?;
+        assert!(ical.starts_with("BEGIN:VCALENDAR\r\nVERSION:2.0\r\n"));
+        assert_eq!(ical.matches("BEGIN:VEVENT").count(), 1);
+        for line in [
+            "UID:notice:1779796800000:ab12@bored.village",
+            "DTSTAMP:20260526T120000Z",
+            "DTSTART:20260612T190000Z",
+            "DTEND:20260612T210000Z",
+            "SUMMARY:Jumble sale",
+            "LOCATION:The hall",
+            "DESCRIPTION:Bring cakes\\, tea\\; and more",
+            "URL:bored://village",
+        ] {
+            assert!(ical.contains(&format!("\r\n{line}\r\n")), "{line}");
+        }
+        assert!(ical.ends_with("END:VEVENT\r\nEND:VCALENDAR\r\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_ical_line() {
+        let mut ical = String::new();
+        push_ical_line(&mut ical, &format!("SUMMARY:{}", "é".repeat(40)));
+        let lines: Vec<&str> = ical.split("\r\n").collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| line.len() <= ICAL_LINE_LENGTH));
+        assert!(lines[1].starts_with(' '));
+        assert_eq!(lines[2], "");
+    }
+
+    #[test]
+    fn test_escape_xml() -> Result<(), BoredError> {
+        assert_eq!(
+            escape_xml("a\u{0}b\u{1b}<\tc\n"),
+            "a\u{fffd}b\u{fffd}&lt;\tc\n"
+        );
+        // what peers send goes into the feed as it is
+        let mut bored = Bored::create("bell\u{7}", Coordinate { x: 40, y: 10 });
+        let mut notice = Notice::create(Coordinate { x: 20, y: 5 });
+        notice.set_notice_id("notice:1779796800000:ab\u{1}12".to_string());
+        bored.add(notice, Coordinate { x: 2, y: 1 })?;
+        let atom = to_atom(&bored, &BoredAddress::from_string("bored://export")?)?;
+        assert!(!atom.chars().any(|char| char.is_control() && char != '\n'));
+        assert!(atom.contains("<title>bell\u{fffd}</title>"));
+        assert!(atom.contains("#notice:1779796800000:ab\u{fffd}12</id>"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_atom_title() {
+        assert_eq!(atom_title(""), "Empty notice");
+        let long = "a".repeat(ATOM_TITLE_LENGTH + 1);
+        assert_eq!(
+            atom_title(&long),
+            format!("{}…", "a".repeat(ATOM_TITLE_LENGTH))
+        );
+    }
+}
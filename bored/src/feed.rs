@@ -0,0 +1,138 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::notice::Notice;
+use crate::url::BoredAddress;
+#[cfg(feature = "client")]
+use crate::x0x_client::X0xBoredClient;
+use crate::{Bored, BoredError};
+
+/// A notice in a feed along with where it came from
+#[derive(Debug, PartialEq, Clone)]
+pub struct FeedItem {
+    pub bored_address: BoredAddress,
+    pub bored_name: String,
+    pub notice: Notice,
+}
+
+/// Notices from several boreds merged into one list, newest first
+#[derive(Debug, Default)]
+pub struct Feed {
+    items: Vec<FeedItem>,
+    failures: Vec<(BoredAddress, BoredError)>,
+}
+
+impl Feed {
+    /// Fetch all the boreds and merge their notices, boreds that can't be fetched are recorded
+    /// as failures rather than stopping the rest of the feed
+    #[cfg(feature = "client")]
+    pub async fn collect(client: &X0xBoredClient, addresses: &[BoredAddress]) -> Feed {
+        let results = client.fetch_boreds(addresses).await;
+        let mut boreds = vec![];
+        let mut failures = vec![];
+        for (address, result) in addresses.iter().zip(results) {
+            match result {
+                Ok(bored) => boreds.push((address.clone(), bored)),
+                Err(e) => failures.push((address.clone(), e)),
+            }
+        }
+        let mut feed = Feed::from_boreds(boreds);
+        feed.failures = failures;
+        feed
+    }
+
+    /// Merge the notices of already fetched boreds, sorted by the time they were posted with
+    /// notices that have no timestamp at the end
+    pub fn from_boreds(boreds: Vec<(BoredAddress, Bored)>) -> Feed {
+        let mut items = vec![];
+        for (bored_address, bored) in boreds {
+            for notice in bored.get_notices() {
+                items.push(FeedItem {
+                    bored_address: bored_address.clone(),
+                    bored_name: bored.get_name().to_string(),
+                    notice,
+                });
+            }
+        }
+        // Option orders None first so reverse to get newest first and None last
+        items.sort_by_key(|item| std::cmp::Reverse(item.notice.get_timestamp()));
+        Feed {
+            items,
+            failures: vec![],
+        }
+    }
+
+    pub fn get_items(&self) -> &Vec<FeedItem> {
+        &self.items
+    }
+
+    /// Boreds that could not be fetched and why
+    pub fn get_failures(&self) -> &Vec<(BoredAddress, BoredError)> {
+        &self.failures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coordinate;
+
+    fn notice_posted_at(notice_id: &str, top_left: Coordinate) -> (Notice, Coordinate) {
+        let mut notice = Notice::create(Coordinate { x: 5, y: 5 });
+        notice.set_notice_id(notice_id.to_string());
+        (notice, top_left)
+    }
+
+    #[test]
+    fn test_from_boreds_sorts_newest_first() -> Result<(), BoredError> {
+        let address_a = BoredAddress::from_string("bored://a")?;
+        let address_b = BoredAddress::from_string("bored://b")?;
+        let mut bored_a = Bored::create("A", Coordinate { x: 40, y: 20 });
+        let mut bored_b = Bored::create("B", Coordinate { x: 40, y: 20 });
+        for (notice, top_left) in [
+            notice_posted_at("notice:100:agent", Coordinate { x: 0, y: 0 }),
+            notice_posted_at("custom-id", Coordinate { x: 10, y: 0 }),
+            notice_posted_at("notice:300:agent", Coordinate { x: 20, y: 0 }),
+        ] {
+            bored_a.add(notice, top_left)?;
+        }
+        let (notice, top_left) = notice_posted_at("notice:200:agent", Coordinate { x: 0, y: 0 });
+        bored_b.add(notice, top_left)?;
+        let feed = Feed::from_boreds(vec![
+            (address_a.clone(), bored_a),
+            (address_b.clone(), bored_b),
+        ]);
+        let ids: Vec<&str> = feed
+            .get_items()
+            .iter()
+            .map(|i| i.notice.get_notice_id())
+            .collect();
+        assert_eq!(
+            ids,
+            vec![
+                "notice:300:agent",
+                "notice:200:agent",
+                "notice:100:agent",
+                "custom-id"
+            ]
+        );
+        assert_eq!(feed.get_items()[1].bored_address, address_b);
+        assert_eq!(feed.get_items()[1].bored_name, "B");
+        assert!(feed.get_failures().is_empty());
+        Ok(())
+    }
+}
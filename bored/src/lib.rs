@@ -15,15 +15,37 @@ You should have received a copy of the GNU Affero General Public License
 along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
-use notice::{Notice, NoticeHyperlinkMap};
+use notice::{Notice, NoticeHyperlinkMap, NoticeKind};
+use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fmt::{self};
 use std::ops::Add;
+use std::sync::Arc;
 
+#[cfg(any(test, feature = "proptest"))]
+pub mod arbitrary;
+pub mod crawler;
+pub mod crypto;
+pub mod export;
+pub mod feed;
+pub mod migrations;
+pub mod templates;
+pub mod views;
+#[cfg(feature = "client")]
 pub mod x0x_client;
 pub mod notice;
 pub mod url;
 
+/// Largest dimensions a bored may have, anything bigger is too large to lay out
+pub const MAX_BORED_DIMENSIONS: Coordinate = Coordinate { x: 1024, y: 1024 };
+
+/// Most notices that can be hidden by moderation on one bored
+pub const MAX_HIDDEN_NOTICES: usize = 1024;
+
+/// Largest size in bytes a serialized bored may be, above this peers may fail to sync it as the
+/// whole bored is sent in a single gossip message
+pub const MAX_BORED_SIZE: usize = 4 * 1024 * 1024;
+
 // Should be entered in order as created as default looks at last element
 const PROTOCOL_VERSIONS: [ProtocolVersion; 3] = [ProtocolVersion(1), ProtocolVersion(2), ProtocolVersion(3)];
 
@@ -52,6 +74,38 @@ impl ProtocolVersion {
     }
 }
 
+/// The underlying error that caused a BoredError, shared so errors stay cheap to clone and
+/// compared by message so they can still be checked for equality. It derefs to the error
+/// itself, so that is what BoredError gives as its source.
+#[derive(Debug, Clone)]
+pub struct ErrorSource(Arc<dyn std::error::Error + Send + Sync>);
+
+impl ErrorSource {
+    pub fn new(error: impl std::error::Error + Send + Sync + 'static) -> ErrorSource {
+        ErrorSource(Arc::new(error))
+    }
+}
+
+impl fmt::Display for ErrorSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::ops::Deref for ErrorSource {
+    type Target = dyn std::error::Error + Send + Sync;
+
+    fn deref(&self) -> &Self::Target {
+        self.0.as_ref()
+    }
+}
+
+impl PartialEq for ErrorSource {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.to_string() == other.0.to_string()
+    }
+}
+
 /// Errors that can occur when using Bored client
 #[derive(Debug, thiserror::Error, PartialEq, Clone)]
 pub enum BoredError {
@@ -67,8 +121,8 @@ pub enum BoredError {
     TooMuchText,
     #[error("Could not connect to x0x daemon")]
     ClientConnectionError,
-    #[error("JSON serializing/deserializing error: {0}")]
-    JSONError(String),
+    #[error("JSON serializing/deserializing error")]
+    JSONError(#[source] ErrorSource),
     #[error("Binary serializing/deserializing error")]
     BinaryError,
     #[error("No notice in that directions")]
@@ -82,39 +136,164 @@ pub enum BoredError {
     #[error("Hyperlink url is too long at max is {}", notice::MAX_URL_LENGTH)]
     URLTooLong,
     #[error("Error performing regular expression search")]
-    RegexError,
-    #[error("{0}")]
-    IOError(String),
+    RegexError(#[source] Option<ErrorSource>),
+    #[error("IO error")]
+    IOError(#[source] ErrorSource),
     #[error("The URL name: {0} is already taken.\nPlease choose a different one.")]
     URLNameAlreadyExists(String),
     #[error("x0x API error: {0}")]
     X0xError(String),
+    #[error("Network error talking to x0x daemon")]
+    NetworkError(#[source] ErrorSource),
+    #[error("Bored would be {0} bytes which is over the maximum size of {max} bytes", max = MAX_BORED_SIZE)]
+    BoredTooBig(usize),
+    #[error("Background pattern is too long or contains control characters: {0}")]
+    InvalidBackground(String),
+    #[error("Colour should be written as #rrggbb hex: {0}")]
+    InvalidColour(String),
+    #[error("Invalid layout on line {0}: {1}")]
+    InvalidLayout(usize, String),
+    #[error("Can't hide {0} notices, the most that can be hidden is {max}", max = MAX_HIDDEN_NOTICES)]
+    TooManyHiddenNotices(usize),
+    #[error("Notice needs a proof of work of {0} leading zero bits")]
+    InsufficientProofOfWork(u8),
+    #[error("Proof of work difficulty {0} is above the maximum of {max}", max = notice::MAX_POW_DIFFICULTY)]
+    InvalidPowDifficulty(u8),
+    #[error("Posting here needs proof of work at difficulty {0}, over your limit of {1}")]
+    PowOverLimit(u8, u8),
+    #[error("Posting would take this session over its limit of {0} hashes of proof of work")]
+    WorkLimitReached(u64),
+    #[error("{0} can't say it has moved to itself")]
+    RedirectLoop(String),
+    #[error("Text contains control characters that are not allowed")]
+    DisallowedCharacters,
+    #[error("Dimensions {0} are outside the allowed range")]
+    InvalidDimensions(Coordinate),
+    #[error("Notice at {0} with dimensions {1} reaches past the largest coordinate there can be")]
+    CoordinateOverflow(Coordinate, Coordinate),
+    #[error("Notice {0} is entirely covered by other notices so should have been removed")]
+    NoticeNotVisible(String),
+    #[error("This bored was opened with a read only link so can't be changed")]
+    ReadOnlyBored,
+    #[error("Started read only so boreds can't be created or changed")]
+    ReadOnlyClient,
+    #[error("This share link expired at {0} (unix time)")]
+    ShareLinkExpired(u64),
+    #[error("Not a valid key: {0}")]
+    InvalidKey(String),
+    #[error("This bored was not encrypted for this reader key")]
+    NotAReader,
+    #[error("Encrypting or decrypting the bored failed")]
+    EncryptionError,
+    #[error("Boreds can be encrypted for between 1 and {max} readers, not {0}", max = crypto::MAX_READERS)]
+    InvalidReaders(usize),
+    #[error("Not a valid attachment: {0}")]
+    InvalidAttachment(String),
+    #[error("Attachment is {0} bytes which is over the maximum size of {max} bytes", max = notice::MAX_ATTACHMENT_SIZE)]
+    AttachmentTooBig(u64),
+    #[error("Downloaded file doesn't match its address {0}")]
+    ChecksumMismatch(String),
+    #[error("No peer sent the file {0}, whoever attached it may be offline")]
+    FileNotFound(String),
     #[error("The board '{0}' does not exist on the network. You must create it first using the create command.")]
     BoardDoesNotExist(String),
+    #[error("There is no free space on the bored big enough for a {0} notice")]
+    NoFreeSpace(Coordinate),
+    #[error("Not a valid event, {0}")]
+    InvalidEvent(String),
+    #[error("Can't embed the notice {0}, it isn't a posted notice's id")]
+    InvalidEmbed(String),
+}
+
+impl BoredError {
+    /// Stable identifier for the kind of error, so callers can match on errors without relying
+    /// on the wording of messages
+    pub fn code(&self) -> &'static str {
+        match self {
+            BoredError::InvalidProtocolVersion(_) => "invalid_protocol_version",
+            BoredError::MethodNotInProtocol => "method_not_in_protocol",
+            BoredError::NoticeOutOfBounds(_, _) => "notice_out_of_bounds",
+            BoredError::TooMuchText => "too_much_text",
+            BoredError::ClientConnectionError => "client_connection",
+            BoredError::JSONError(_) => "json",
+            BoredError::BinaryError => "binary",
+            BoredError::NoNotice => "no_notice",
+            BoredError::NoBored => "no_bored",
+            BoredError::NotBoredURL(_) => "not_bored_url",
+            BoredError::UnknownURLType(_) => "unknown_url_type",
+            BoredError::URLTooLong => "url_too_long",
+            BoredError::RegexError(_) => "regex",
+            BoredError::IOError(_) => "io",
+            BoredError::URLNameAlreadyExists(_) => "url_name_already_exists",
+            BoredError::X0xError(_) => "x0x_api",
+            BoredError::NetworkError(_) => "network",
+            BoredError::BoredTooBig(_) => "bored_too_big",
+            BoredError::InvalidBackground(_) => "invalid_background",
+            BoredError::InvalidColour(_) => "invalid_colour",
+            BoredError::InvalidLayout(_, _) => "invalid_layout",
+            BoredError::NoticeNotVisible(_) => "notice_not_visible",
+            BoredError::InvalidDimensions(_) => "invalid_dimensions",
+            BoredError::CoordinateOverflow(_, _) => "coordinate_overflow",
+            BoredError::DisallowedCharacters => "disallowed_characters",
+            BoredError::InsufficientProofOfWork(_) => "insufficient_proof_of_work",
+            BoredError::InvalidPowDifficulty(_) => "invalid_pow_difficulty",
+            BoredError::PowOverLimit(_, _) => "pow_over_limit",
+            BoredError::WorkLimitReached(_) => "work_limit_reached",
+            BoredError::TooManyHiddenNotices(_) => "too_many_hidden_notices",
+            BoredError::RedirectLoop(_) => "redirect_loop",
+            BoredError::ReadOnlyBored => "read_only_bored",
+            BoredError::ReadOnlyClient => "read_only_client",
+            BoredError::ShareLinkExpired(_) => "share_link_expired",
+            BoredError::InvalidKey(_) => "invalid_key",
+            BoredError::NotAReader => "not_a_reader",
+            BoredError::EncryptionError => "encryption",
+            BoredError::InvalidReaders(_) => "invalid_readers",
+            BoredError::InvalidAttachment(_) => "invalid_attachment",
+            BoredError::AttachmentTooBig(_) => "attachment_too_big",
+            BoredError::ChecksumMismatch(_) => "checksum_mismatch",
+            BoredError::FileNotFound(_) => "file_not_found",
+            BoredError::BoardDoesNotExist(_) => "board_does_not_exist",
+            BoredError::NoFreeSpace(_) => "no_free_space",
+            BoredError::InvalidEvent(_) => "invalid_event",
+            BoredError::InvalidEmbed(_) => "invalid_embed",
+        }
+    }
+
+    /// Whether trying the same thing again later might work, i.e. the x0x daemon could not be
+    /// reached or the request to it failed in transit. Anything else such as an invalid notice
+    /// or a message the daemon rejected will fail the same way every time.
+    pub fn is_transient(&self) -> bool {
+        matches!(
+            self,
+            BoredError::ClientConnectionError
+                | BoredError::NetworkError(_)
+                | BoredError::FileNotFound(_)
+        )
+    }
 }
 
 impl From<serde_json::Error> for BoredError {
     fn from(e: serde_json::Error) -> Self {
-        Self::JSONError(format!("{e}"))
+        Self::JSONError(ErrorSource::new(e))
     }
 }
 
 impl From<regex::Error> for BoredError {
-    fn from(_: regex::Error) -> Self {
-        BoredError::RegexError
+    fn from(e: regex::Error) -> Self {
+        BoredError::RegexError(Some(ErrorSource::new(e)))
     }
 }
 
 impl From<std::io::Error> for BoredError {
     fn from(e: std::io::Error) -> Self {
-        let message = format!("{e}");
-        BoredError::IOError(message)
+        BoredError::IOError(ErrorSource::new(e))
     }
 }
 
+#[cfg(feature = "client")]
 impl From<reqwest::Error> for BoredError {
     fn from(e: reqwest::Error) -> Self {
-        BoredError::X0xError(format!("{e}"))
+        BoredError::NetworkError(ErrorSource::new(e))
     }
 }
 
@@ -147,6 +326,14 @@ impl Coordinate {
         }
     }
 
+    /// None if the sum doesn't fit in a u16, for coordinates that come from peers
+    pub fn checked_add(&self, other: &Self) -> Option<Coordinate> {
+        Some(Coordinate {
+            x: self.x.checked_add(other.x)?,
+            y: self.y.checked_add(other.y)?,
+        })
+    }
+
     /// will not subtract below zero
     pub fn subtact(&self, other: &Self) -> Coordinate {
         let x = if self.x >= other.x { other.x } else { 0 };
@@ -250,6 +437,23 @@ impl BoredHyperlinkMap {
     pub fn get_map(&self) -> Vec<Vec<Option<(usize, usize)>>> {
         self.visible.clone()
     }
+
+    /// The notice and hyperlink index of the hyperlink showing at coordinate, if there is one
+    pub fn get_at(&self, coordinate: Coordinate) -> Option<(usize, usize)> {
+        *self
+            .visible
+            .get(coordinate.y as usize)?
+            .get(coordinate.x as usize)?
+    }
+}
+
+/// How much of a notice something placed on top of it would cover
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Coverage {
+    /// Some of the notice would still show
+    Partial,
+    /// None of the notice would show so it would be pruned from the bored
+    Full,
 }
 
 /// a 2d vector of option<uszie> representing the visible contents of the bored
@@ -303,6 +507,53 @@ impl WhatsOnTheBored {
         WhatsOnTheBored { visible }
     }
 
+    /// The index of the notice showing at coordinate, none if it is empty or off the bored
+    pub fn get_at(&self, coordinate: Coordinate) -> Option<usize> {
+        *self
+            .visible
+            .get(coordinate.y as usize)?
+            .get(coordinate.x as usize)?
+    }
+
+    /// The notices showing in an area and how much of each would be covered by something
+    /// placed over it, in the order the notices were added
+    pub fn get_covered(
+        &self,
+        top_left: Coordinate,
+        dimensions: Coordinate,
+    ) -> Vec<(usize, Coverage)> {
+        let bottom_right = top_left.add(&dimensions);
+        // notice index to its count of showing cells and count of those in the area
+        let mut counts: std::collections::BTreeMap<usize, (usize, usize)> =
+            std::collections::BTreeMap::new();
+        for (y, row) in self.visible.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                let Some(notice_index) = cell else {
+                    continue;
+                };
+                let count = counts.entry(*notice_index).or_default();
+                count.0 += 1;
+                if (top_left.x as usize..bottom_right.x as usize).contains(&x)
+                    && (top_left.y as usize..bottom_right.y as usize).contains(&y)
+                {
+                    count.1 += 1;
+                }
+            }
+        }
+        counts
+            .into_iter()
+            .filter(|(_, (_, covered))| *covered > 0)
+            .map(|(notice_index, (showing, covered))| {
+                let coverage = if covered == showing {
+                    Coverage::Full
+                } else {
+                    Coverage::Partial
+                };
+                (notice_index, coverage)
+            })
+            .collect()
+    }
+
     /// flattens into a one dimesonal vectors
     pub fn get_1d(&self) -> Vec<Option<usize>> {
         let mut whats_on_the_bored_1d = vec![];
@@ -314,12 +565,267 @@ impl WhatsOnTheBored {
         whats_on_the_bored_1d
     }
 
+    /// Finds the largest empty rectangle returning it's top left and dimensions, none if full
+    // Treats each row as the base of a histogram of empty cells above it and finds the largest
+    // rectangle under each histogram using a stack of increasing heights
+    pub fn get_largest_free_rectangle(&self) -> Option<(Coordinate, Coordinate)> {
+        let width = self.visible.first().map(|row| row.len()).unwrap_or(0);
+        let mut heights = vec![0usize; width];
+        let mut largest: Option<(Coordinate, Coordinate)> = None;
+        let mut largest_area = 0;
+        for (y, row) in self.visible.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                heights[x] = if cell.is_none() { heights[x] + 1 } else { 0 };
+            }
+            let mut stack: Vec<usize> = vec![];
+            for x in 0..=width {
+                let height = if x < width { heights[x] } else { 0 };
+                while let Some(&top) = stack.last() {
+                    if heights[top] < height {
+                        break;
+                    }
+                    stack.pop();
+                    let left = stack.last().map(|&i| i + 1).unwrap_or(0);
+                    let rectangle_width = x - left;
+                    let area = heights[top] * rectangle_width;
+                    if area > largest_area {
+                        largest_area = area;
+                        largest = Some((
+                            Coordinate {
+                                x: left as u16,
+                                y: (y + 1 - heights[top]) as u16,
+                            },
+                            Coordinate {
+                                x: rectangle_width as u16,
+                                y: heights[top] as u16,
+                            },
+                        ));
+                    }
+                }
+                stack.push(x);
+            }
+        }
+        largest
+    }
+
+    /// The top left of the first empty space big enough for something of the dimensions,
+    /// looking left to right along each row then down the rows, none if there isn't one
+    pub fn get_first_free_in_reading_order(&self, dimensions: Coordinate) -> Option<Coordinate> {
+        let width = self.visible.first().map(|row| row.len()).unwrap_or(0);
+        let (notice_width, notice_height) = (dimensions.x as usize, dimensions.y as usize);
+        if notice_width == 0 || notice_height == 0 {
+            return None;
+        }
+        for y in 0..=self.visible.len().checked_sub(notice_height)? {
+            let rows = &self.visible[y..y + notice_height];
+            let mut x = 0;
+            while x + notice_width <= width {
+                // start again after the rightmost filled cell in the way
+                match (x..x + notice_width)
+                    .rev()
+                    .find(|&column| rows.iter().any(|row| row[column].is_some()))
+                {
+                    Some(filled) => x = filled + 1,
+                    None => {
+                        return Some(Coordinate {
+                            x: x as u16,
+                            y: y as u16,
+                        });
+                    }
+                }
+            }
+        }
+        None
+    }
+
     /// get value at coordiante
     fn get_vaule_at_coordinate(&self, coordinate: Coordinate) -> Option<usize> {
         self.visible[coordinate.y as usize][coordinate.x as usize]
     }
 }
 
+/// Summary figures about a bored, useful for deciding when a bored is getting full
+#[derive(Debug, PartialEq, Clone)]
+pub struct BoredStats {
+    pub notice_count: usize,
+    /// Visible characters across all notices, not counting hyperlink markdown and urls
+    pub total_characters: usize,
+    /// Percentage of the bored's area covered by notices
+    pub fill_percentage: f64,
+    /// Top left and dimensions of the largest rectangle with no notice on it
+    pub largest_free_rectangle: Option<(Coordinate, Coordinate)>,
+    /// Size in bytes of the bored as it is cached and sent to peers
+    pub serialized_size: usize,
+}
+
+/// What to look for when searching a bored
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SearchPattern {
+    /// Case insensitive plain text
+    Text(String),
+    /// Regular expression using the syntax of the regex crate
+    Regex(String),
+}
+
+/// A match found when searching the text of a bored
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SearchHit {
+    pub notice_index: usize,
+    /// Byte range of the match within the notice's display text
+    pub byte_range: (usize, usize),
+    /// Position on the bored of each visible character of the match
+    pub coordinates: Vec<Coordinate>,
+}
+
+/// Maximum number of characters in a background pattern
+pub const MAX_BACKGROUND_PATTERN_LENGTH: usize = 16;
+
+/// Style of the frame drawn around the edge of a bored
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Copy, Default)]
+pub enum FrameStyle {
+    #[default]
+    Rounded,
+    Plain,
+    Double,
+    Thick,
+    None,
+}
+
+/// Decoration rendered underneath all the notices of a bored, a fill pattern repeated across
+/// the board plus the style of the frame around its edge
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+pub struct Background {
+    pattern: String,
+    frame: FrameStyle,
+}
+
+impl Background {
+    /// Create a background, the pattern is repeated along each row shifting one character each
+    /// row so multi character patterns make diagonal textures, an empty pattern leaves it blank
+    pub fn create(pattern: &str, frame: FrameStyle) -> Result<Background, BoredError> {
+        let background = Background {
+            pattern: pattern.to_string(),
+            frame,
+        };
+        if !background.is_valid() {
+            return Err(BoredError::InvalidBackground(pattern.to_string()));
+        }
+        Ok(background)
+    }
+
+    /// Whether the pattern can be drawn safely, short and without control characters
+    fn is_valid(&self) -> bool {
+        self.pattern.chars().count() <= MAX_BACKGROUND_PATTERN_LENGTH
+            && !self.pattern.chars().any(|c| c.is_control())
+    }
+
+    pub fn get_pattern(&self) -> &str {
+        &self.pattern
+    }
+
+    pub fn get_frame(&self) -> FrameStyle {
+        self.frame
+    }
+
+    /// Get the pattern character that should be shown at a coordinate, none if blank
+    pub fn get_char_at(&self, coordinate: Coordinate) -> Option<char> {
+        let pattern_length = self.pattern.chars().count();
+        if pattern_length == 0 {
+            return None;
+        }
+        let index = (coordinate.x as usize + coordinate.y as usize) % pattern_length;
+        self.pattern.chars().nth(index)
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Background::default()
+    }
+}
+
+/// Red, green and blue of a "#rrggbb" hex colour
+fn parse_hex_colour(colour: &str) -> Option<(u8, u8, u8)> {
+    let hex = colour.strip_prefix('#')?;
+    if hex.len() != 6 || !hex.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+    Some((channel(0)?, channel(2)?, channel(4)?))
+}
+
+/// Colours a bored would like to be shown in, each one optional and written as "#rrggbb" hex.
+/// They are only a preference, readers may use their own colours instead.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone, Default)]
+pub struct Palette {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    text: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    background: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    header: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hyperlink: Option<String>,
+}
+
+impl Palette {
+    /// Create a palette from the text, background, header and hyperlink colours
+    pub fn create(
+        text: Option<&str>,
+        background: Option<&str>,
+        header: Option<&str>,
+        hyperlink: Option<&str>,
+    ) -> Result<Palette, BoredError> {
+        let check = |colour: Option<&str>| match colour {
+            Some(colour) if parse_hex_colour(colour).is_none() => {
+                Err(BoredError::InvalidColour(colour.to_string()))
+            }
+            _ => Ok(colour.map(|colour| colour.to_ascii_lowercase())),
+        };
+        Ok(Palette {
+            text: check(text)?,
+            background: check(background)?,
+            header: check(header)?,
+            hyperlink: check(hyperlink)?,
+        })
+    }
+
+    pub fn get_text(&self) -> Option<(u8, u8, u8)> {
+        parse_hex_colour(self.text.as_ref()?)
+    }
+
+    pub fn get_background(&self) -> Option<(u8, u8, u8)> {
+        parse_hex_colour(self.background.as_ref()?)
+    }
+
+    pub fn get_header(&self) -> Option<(u8, u8, u8)> {
+        parse_hex_colour(self.header.as_ref()?)
+    }
+
+    pub fn get_hyperlink(&self) -> Option<(u8, u8, u8)> {
+        parse_hex_colour(self.hyperlink.as_ref()?)
+    }
+
+    /// Whether every colour is either missing or valid hex
+    fn is_valid(&self) -> bool {
+        [&self.text, &self.background, &self.header, &self.hyperlink]
+            .into_iter()
+            .flatten()
+            .all(|colour| parse_hex_colour(colour).is_some())
+    }
+
+    pub fn is_default(&self) -> bool {
+        *self == Palette::default()
+    }
+}
+
+/// Parse two numbers separated by a character, as used for positions and sizes in layouts
+fn parse_layout_pair(value: &str, separator: char) -> Option<Coordinate> {
+    let (x, y) = value.trim().split_once(separator)?;
+    Some(Coordinate {
+        x: x.trim().parse().ok()?,
+        y: y.trim().parse().ok()?,
+    })
+}
+
 /// Bored, inspired by a pin board a 2d area onto which notices can be placed.
 /// If a notice becomes entirley occluded it no longer exists. Once placed notices cannot be
 /// moved/edited but can be covered by new ones.
@@ -329,6 +835,26 @@ pub struct Bored {
     name: String,
     dimensions: Coordinate, // the board will range from (0,0) up to this
     notices: Vec<Notice>,
+    #[serde(default, skip_serializing_if = "Background::is_default")]
+    background: Background,
+    #[serde(default, skip_serializing_if = "Palette::is_default")]
+    palette: Palette,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pow_difficulty: u8,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pow_changed_at: i64,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    settings_changed_at: i64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    hidden_notices: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    moved_to: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    readers: Vec<String>,
+}
+
+fn is_zero<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
 }
 
 // only methods dealing with the interal items of bored need to perform the protocol check
@@ -342,26 +868,79 @@ impl Bored {
             name: name.to_string(),
             dimensions,
             notices: Vec::new(),
+            background: Background::default(),
+            palette: Palette::default(),
+            pow_difficulty: 0,
+            pow_changed_at: 0,
+            settings_changed_at: 0,
+            hidden_notices: vec![],
+            moved_to: None,
+            readers: vec![],
         }
     }
 
     /// Add a notice to the board in the specified position returns an error if out of bounds
     // Takes cordinate parametre to make sure it is correct with respect to self even
     // though relocate performs a check to a specfifed bored
-    pub fn add(&mut self, mut notice: Notice, top_left: Coordinate) -> Result<(), BoredError> {
+    pub fn add(&mut self, notice: Notice, top_left: Coordinate) -> Result<(), BoredError> {
+        self.add_with_difficulty(notice, top_left, self.pow_difficulty)
+    }
+
+    /// Add a notice that peers already have, as when syncing. Notices posted before the proof
+    /// of work last changed need none as they may have been posted before the bored asked for
+    /// it, any others need it as they would to be added.
+    pub fn add_synced(&mut self, notice: Notice, top_left: Coordinate) -> Result<(), BoredError> {
+        let posted_before_change = notice
+            .get_timestamp()
+            .is_some_and(|posted| posted < self.pow_changed_at);
+        let pow_difficulty = if posted_before_change {
+            0
+        } else {
+            self.pow_difficulty
+        };
+        self.add_with_difficulty(notice, top_left, pow_difficulty)
+    }
+
+    fn add_with_difficulty(
+        &mut self,
+        mut notice: Notice,
+        top_left: Coordinate,
+        pow_difficulty: u8,
+    ) -> Result<(), BoredError> {
         if self.protocol_version.get_version() < 1 {
             return Err(BoredError::MethodNotInProtocol);
         }
+        notice.validate()?;
         notice.relocate(&self, top_left)?;
+        if !notice.has_proof_of_work(pow_difficulty) {
+            return Err(BoredError::InsufficientProofOfWork(pow_difficulty));
+        }
         self.notices.push(notice);
         self.prune_non_visible()?;
         return Ok(());
     }
 
+    /// Add the notice in the first free space big enough for it, reading left to right then top
+    /// to bottom, returning where it went. Notices added one after another this way follow on
+    /// from each other like entries in a guestbook, without covering anything already there.
+    pub fn append_in_reading_order(&mut self, notice: Notice) -> Result<Coordinate, BoredError> {
+        let top_left = WhatsOnTheBored::create(self)
+            .get_first_free_in_reading_order(notice.get_dimensions())
+            .ok_or(BoredError::NoFreeSpace(notice.get_dimensions()))?;
+        self.add(notice, top_left)?;
+        Ok(top_left)
+    }
+
+    /// Copy of the notices, use notices to look at them without copying
     pub fn get_notices(&self) -> Vec<Notice> {
         self.notices.clone()
     }
 
+    /// The notices oldest first, so later ones are on top of those before them
+    pub fn notices(&self) -> &[Notice] {
+        &self.notices
+    }
+
     pub fn get_name(&self) -> &str {
         &self.name
     }
@@ -407,6 +986,282 @@ impl Bored {
         self.dimensions
     }
 
+    pub fn get_background(&self) -> &Background {
+        &self.background
+    }
+
+    pub fn set_background(&mut self, background: Background) {
+        self.background = background;
+    }
+
+    pub fn get_palette(&self) -> &Palette {
+        &self.palette
+    }
+
+    pub fn set_palette(&mut self, palette: Palette) {
+        self.palette = palette;
+    }
+
+    /// Number of leading zero bits the proof of work of new notices needs, 0 if none is needed
+    pub fn get_pow_difficulty(&self) -> u8 {
+        self.pow_difficulty
+    }
+
+    /// Whether moderation says the notice shouldn't be shown, renderers should show hidden
+    /// notices empty rather than leave them out so the layout doesn't change. The list is only
+    /// advisory as there is no owner key, anyone with the address can change it.
+    pub fn is_hidden(&self, notice: &Notice) -> bool {
+        self.hidden_notices.contains(&notice.get_hash())
+    }
+
+    /// Hashes of the notices hidden by moderation
+    pub fn get_hidden_notices(&self) -> &Vec<String> {
+        &self.hidden_notices
+    }
+
+    /// Replace the list of hashes of notices hidden by moderation
+    pub fn set_hidden_notices(&mut self, hidden_notices: Vec<String>) -> Result<(), BoredError> {
+        if hidden_notices.len() > MAX_HIDDEN_NOTICES {
+            return Err(BoredError::TooManyHiddenNotices(hidden_notices.len()));
+        }
+        self.hidden_notices = hidden_notices;
+        Ok(())
+    }
+
+    /// Address the bored says it has moved to, for readers to choose to follow as anyone who
+    /// knows the bored can set it. Addresses that can't be read are ignored.
+    pub fn get_moved_to(&self) -> Option<url::BoredAddress> {
+        url::BoredAddress::from_string(self.moved_to.as_deref()?).ok()
+    }
+
+    /// Say the bored has moved to another address, or that it hasn't with none
+    pub fn set_moved_to(&mut self, moved_to: Option<&url::BoredAddress>) {
+        self.moved_to = moved_to.map(|address| address.to_string());
+    }
+
+    /// Public keys of the readers the bored is encrypted for, keys that can't be read are
+    /// left out
+    pub fn get_readers(&self) -> Vec<crypto::ReaderPublicKey> {
+        self.readers
+            .iter()
+            .filter_map(|reader| crypto::ReaderPublicKey::from_string(reader).ok())
+            .collect()
+    }
+
+    /// Encrypt the bored for these readers when it is published, none publishes it in the clear
+    pub fn set_readers(&mut self, readers: &[crypto::ReaderPublicKey]) -> Result<(), BoredError> {
+        if readers.len() > crypto::MAX_READERS {
+            return Err(BoredError::InvalidReaders(readers.len()));
+        }
+        self.readers = readers.iter().map(|reader| reader.to_string()).collect();
+        Ok(())
+    }
+
+    /// Whether the bored is only published encrypted for its readers
+    pub fn is_encrypted(&self) -> bool {
+        !self.get_readers().is_empty()
+    }
+
+    /// The boreds notices on this one are embedded from, once each
+    pub fn get_embedded_addresses(&self) -> Vec<url::BoredAddress> {
+        let mut addresses: Vec<url::BoredAddress> = vec![];
+        for notice in &self.notices {
+            if let NoticeKind::Embed { address, .. } = notice.get_kind()
+                && !addresses
+                    .iter()
+                    .any(|a| a.get_topic() == address.get_topic())
+            {
+                addresses.push(address.clone());
+            }
+        }
+        addresses
+    }
+
+    /// Copy of the bored for display with each notice embedded from another bored shown as that
+    /// notice, from the boreds find gives for their addresses. Notices that aren't found are
+    /// left showing their own content.
+    pub fn with_embeds(&self, find: impl Fn(&url::BoredAddress) -> Option<Arc<Bored>>) -> Bored {
+        let mut bored = self.clone();
+        for notice in bored.notices.iter_mut() {
+            let NoticeKind::Embed { address, notice_id } = notice.get_kind() else {
+                continue;
+            };
+            let Some(source_bored) = find(address) else {
+                continue;
+            };
+            if let Some(source) = source_bored
+                .notices
+                .iter()
+                .find(|source| source.get_notice_id() == notice_id)
+            {
+                *notice = notice.embed(source, address, source_bored.get_name());
+            }
+        }
+        bored
+    }
+
+    /// Copy of the bored with hidden notices emptied of their content, for display
+    pub fn redact_hidden(&self) -> Bored {
+        let mut bored = self.clone();
+        for notice in bored.notices.iter_mut() {
+            if self.is_hidden(notice) {
+                *notice = notice.redacted();
+            }
+        }
+        bored
+    }
+
+    /// Indexes of every notice covering the coordinate, the one showing on top first
+    pub fn get_notice_stack_at(&self, coordinate: Coordinate) -> Vec<usize> {
+        self.notices
+            .iter()
+            .enumerate()
+            .rev()
+            .filter(|(_, notice)| {
+                let top_left = notice.get_top_left();
+                let bottom_right = top_left.add(&notice.get_dimensions());
+                (top_left.x..bottom_right.x).contains(&coordinate.x)
+                    && (top_left.y..bottom_right.y).contains(&coordinate.y)
+            })
+            .map(|(notice_index, _)| notice_index)
+            .collect()
+    }
+
+    /// Copy of the bored with the notice at the index moved on top of the others, for display
+    pub fn with_notice_raised(&self, notice_index: usize) -> Bored {
+        let mut bored = self.clone();
+        if notice_index < bored.notices.len() {
+            let notice = bored.notices.remove(notice_index);
+            bored.notices.push(notice);
+        }
+        bored
+    }
+
+    /// Files attached to notices on the bored, newest first, leaving out hidden notices and
+    /// repeats of the same file
+    pub fn get_attachments(&self) -> Vec<notice::Attachment> {
+        let mut attachments: Vec<notice::Attachment> = vec![];
+        for notice in self.notices.iter().rev() {
+            if self.is_hidden(notice) {
+                continue;
+            }
+            if let Some(attachment) = notice.get_attachment()
+                && !attachments
+                    .iter()
+                    .any(|a| a.get_address() == attachment.get_address())
+            {
+                attachments.push(attachment.clone());
+            }
+        }
+        attachments
+    }
+
+    /// Set the proof of work new notices need, notices already on the bored are unaffected
+    pub fn set_pow_difficulty(&mut self, difficulty: u8) -> Result<(), BoredError> {
+        if difficulty > notice::MAX_POW_DIFFICULTY {
+            return Err(BoredError::InvalidPowDifficulty(difficulty));
+        }
+        if self.pow_difficulty != difficulty {
+            self.pow_difficulty = difficulty;
+            self.pow_changed_at = chrono::Utc::now().timestamp_millis();
+        }
+        Ok(())
+    }
+
+    /// Milliseconds since the unix epoch the proof of work new notices need last changed, 0 if
+    /// it never has. Synced notices posted before then are kept without proof of work.
+    pub fn get_pow_changed_at(&self) -> i64 {
+        self.pow_changed_at
+    }
+
+    /// Build a bored from a text layout so a whole bored can be written in an editor, e.g.
+    ///
+    /// ```text
+    /// # lines starting with a hash outside of notices are comments
+    /// name: Village hall
+    /// size: 120x40
+    /// background: double .
+    ///
+    /// notice at 2,1 size 30x6
+    /// Jumble sale on Saturday
+    /// [Details](https://example.com)
+    /// end
+    /// ```
+    ///
+    /// name and size are required, background is optional and takes a frame style (rounded,
+    /// plain, double, thick or none) then an optional pattern. Notice content is everything up
+    /// to a line that is just `end` and notices are added in order so later ones go on top.
+    pub fn from_layout_str(layout: &str) -> Result<Bored, BoredError> {
+        let mut name = None;
+        let mut dimensions = None;
+        let mut background = Background::default();
+        let mut notices = vec![];
+        let mut lines = layout.lines().enumerate().map(|(i, line)| (i + 1, line));
+        while let Some((line_number, line)) = lines.next() {
+            let line = line.trim();
+            let invalid = |reason: &str| BoredError::InvalidLayout(line_number, reason.to_string());
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            } else if let Some(value) = line.strip_prefix("name:") {
+                name = Some(value.trim().to_string());
+            } else if let Some(value) = line.strip_prefix("size:") {
+                dimensions = Some(
+                    parse_layout_pair(value, 'x').ok_or(invalid("size must be WIDTHxHEIGHT"))?,
+                );
+            } else if let Some(value) = line.strip_prefix("background:") {
+                let mut parts = value.trim().splitn(2, ' ');
+                let frame = match parts.next().unwrap_or_default() {
+                    "rounded" => FrameStyle::Rounded,
+                    "plain" => FrameStyle::Plain,
+                    "double" => FrameStyle::Double,
+                    "thick" => FrameStyle::Thick,
+                    "none" => FrameStyle::None,
+                    _ => return Err(invalid("unknown frame style")),
+                };
+                let pattern = parts.next().unwrap_or_default().trim();
+                background = Background::create(pattern, frame)
+                    .map_err(|_| invalid("invalid background pattern"))?;
+            } else if let Some(value) = line.strip_prefix("notice ") {
+                let header: Vec<&str> = value.split_whitespace().collect();
+                let placement = match header[..] {
+                    ["at", top_left, "size", notice_dimensions] => parse_layout_pair(top_left, ',')
+                        .zip(parse_layout_pair(notice_dimensions, 'x')),
+                    _ => None,
+                };
+                let Some((top_left, notice_dimensions)) = placement else {
+                    return Err(invalid("notice must be: notice at X,Y size WIDTHxHEIGHT"));
+                };
+                let mut content = vec![];
+                loop {
+                    match lines.next() {
+                        Some((_, "end")) => break,
+                        Some((_, content_line)) => content.push(content_line),
+                        None => return Err(invalid("notice has no end line")),
+                    }
+                }
+                let mut notice = Notice::create(notice_dimensions);
+                notice
+                    .write(&content.join("\n"))
+                    .map_err(|e| BoredError::InvalidLayout(line_number, e.to_string()))?;
+                notices.push((line_number, notice, top_left));
+            } else {
+                return Err(invalid("unrecognised line"));
+            }
+        }
+        let end_line = layout.lines().count();
+        let name = name.ok_or(BoredError::InvalidLayout(end_line, "missing name".to_string()))?;
+        let dimensions =
+            dimensions.ok_or(BoredError::InvalidLayout(end_line, "missing size".to_string()))?;
+        let mut bored = Bored::create(&name, dimensions);
+        bored.set_background(background);
+        for (line_number, notice, top_left) in notices {
+            bored
+                .add(notice, top_left)
+                .map_err(|e| BoredError::InvalidLayout(line_number, e.to_string()))?;
+        }
+        Ok(bored)
+    }
+
     /// Get all the coordiantes to check going up from a notice
     fn get_up_coordinates(&self, notice: &Notice) -> [Vec<Coordinate>; 2] {
         let mut coordinate_sets: [Vec<Coordinate>; 2] = [vec![], vec![]];
@@ -571,6 +1426,169 @@ impl Bored {
         None
     }
 
+    /// Check the bored is one that could have been made by adding notices to it, i.e. it is a
+    /// supported protocol version and every notice is within bounds, holds no more text than
+    /// it has space for, has no control characters and is at least partly visible
+    pub fn check_invariants(&self) -> Result<(), BoredError> {
+        ProtocolVersion::check(self.protocol_version.get_version())?;
+        if !self.dimensions.within(&MAX_BORED_DIMENSIONS) {
+            return Err(BoredError::InvalidDimensions(self.dimensions));
+        }
+        for notice in &self.notices {
+            let Some(bottom_right) = notice.get_top_left().checked_add(&notice.get_dimensions())
+            else {
+                return Err(BoredError::CoordinateOverflow(
+                    notice.get_top_left(),
+                    notice.get_dimensions(),
+                ));
+            };
+            if !bottom_right.within(&self.dimensions) {
+                return Err(BoredError::NoticeOutOfBounds(self.dimensions, bottom_right));
+            }
+            notice.validate()?;
+        }
+        let visible: std::collections::HashSet<usize> = WhatsOnTheBored::create(self)
+            .get_1d()
+            .into_iter()
+            .flatten()
+            .collect();
+        if let Some(hidden) = (0..self.notices.len()).find(|i| !visible.contains(i)) {
+            return Err(BoredError::NoticeNotVisible(
+                self.notices[hidden].get_notice_id().to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Make a bored from elsewhere safe to use by stripping control characters and removing
+    /// any notices that break the invariants, returning how many were removed. Boreds that
+    /// can't be fixed by removing notices, because they are too big or of an unknown version,
+    /// are rejected.
+    pub fn sanitize(&mut self) -> Result<usize, BoredError> {
+        ProtocolVersion::check(self.protocol_version.get_version())?;
+        if !self.dimensions.within(&MAX_BORED_DIMENSIONS) {
+            return Err(BoredError::InvalidDimensions(self.dimensions));
+        }
+        if self.pow_difficulty > notice::MAX_POW_DIFFICULTY {
+            return Err(BoredError::InvalidPowDifficulty(self.pow_difficulty));
+        }
+        if self.hidden_notices.len() > MAX_HIDDEN_NOTICES {
+            return Err(BoredError::TooManyHiddenNotices(self.hidden_notices.len()));
+        }
+        if self.readers.len() > crypto::MAX_READERS {
+            return Err(BoredError::InvalidReaders(self.readers.len()));
+        }
+        let size = self.serialized_size()?;
+        if size > MAX_BORED_SIZE {
+            return Err(BoredError::BoredTooBig(size));
+        }
+        // colours are only a preference so ones that can't be read are dropped
+        if !self.palette.is_valid() {
+            self.palette = Palette::default();
+        }
+        // the pattern is drawn straight to the terminal so one that is still too long or has
+        // line breaks after stripping is dropped
+        self.background.pattern = notice::strip_disallowed_chars(&self.background.pattern);
+        if !self.background.is_valid() {
+            self.background = Background::default();
+        }
+        self.name = notice::strip_disallowed_chars(&self.name);
+        for notice in self.notices.iter_mut() {
+            notice.strip_disallowed_chars();
+        }
+        let notice_count = self.notices.len();
+        let dimensions = self.dimensions;
+        self.notices.retain(|notice| {
+            notice
+                .get_top_left()
+                .checked_add(&notice.get_dimensions())
+                .is_some_and(|bottom_right| bottom_right.within(&dimensions))
+                && notice.validate().is_ok()
+        });
+        self.prune_non_visible()?;
+        Ok(notice_count - self.notices.len())
+    }
+
+    /// Size in bytes of the bored serialized as it is cached and sent to peers
+    pub fn serialized_size(&self) -> Result<usize, BoredError> {
+        Ok(serde_json::to_string(self)?.len())
+    }
+
+    /// Check if the bored would still be within MAX_BORED_SIZE once the notice is added,
+    /// notices that would be pruned as a result of adding it are taken into account
+    pub fn would_fit_with(&self, notice: &Notice) -> Result<bool, BoredError> {
+        Ok(self.size_with(notice)? <= MAX_BORED_SIZE)
+    }
+
+    /// Size in bytes the bored would be serialized once the notice is added
+    pub fn size_with(&self, notice: &Notice) -> Result<usize, BoredError> {
+        let mut bored = self.clone();
+        bored.add(notice.clone(), notice.get_top_left())?;
+        bored.serialized_size()
+    }
+
+    /// Search the visible text of all notices (hyperlink urls are not searched), hits are
+    /// returned in notice order then in order within each notice
+    pub fn search(&self, pattern: &SearchPattern) -> Result<Vec<SearchHit>, BoredError> {
+        let re = match pattern {
+            SearchPattern::Text(text) => Regex::new(&format!("(?i){}", regex::escape(text)))?,
+            SearchPattern::Regex(expression) => Regex::new(expression)?,
+        };
+        let mut hits = vec![];
+        for (notice_index, notice) in self.notices.iter().enumerate() {
+            let display_text = notice.get_display()?.get_display_text();
+            let char_positions = notice.get_char_positions()?;
+            for found in re.find_iter(&display_text) {
+                // empty matches are meaningless to a reader
+                if found.is_empty() {
+                    continue;
+                }
+                let mut coordinates = vec![];
+                for (char_index, (byte_index, _)) in display_text.char_indices().enumerate() {
+                    if byte_index >= found.start()
+                        && byte_index < found.end()
+                        && let Some(Some(position)) = char_positions.get(char_index)
+                    {
+                        // + 1 to account for border
+                        coordinates.push(Coordinate {
+                            x: notice.get_top_left().x + position.x + 1,
+                            y: notice.get_top_left().y + position.y + 1,
+                        });
+                    }
+                }
+                hits.push(SearchHit {
+                    notice_index,
+                    byte_range: (found.start(), found.end()),
+                    coordinates,
+                });
+            }
+        }
+        Ok(hits)
+    }
+
+    /// Get summary statistics about the bored
+    pub fn stats(&self) -> Result<BoredStats, BoredError> {
+        let mut total_characters = 0;
+        for notice in &self.notices {
+            total_characters += notice.get_display()?.get_display_text().chars().count();
+        }
+        let whats_on_the_bored = WhatsOnTheBored::create(self);
+        let area = self.dimensions.x as usize * self.dimensions.y as usize;
+        let covered = whats_on_the_bored.get_1d().iter().flatten().count();
+        let fill_percentage = if area == 0 {
+            0.0
+        } else {
+            (covered * 100) as f64 / area as f64
+        };
+        Ok(BoredStats {
+            notice_count: self.notices.len(),
+            total_characters,
+            fill_percentage,
+            largest_free_rectangle: whats_on_the_bored.get_largest_free_rectangle(),
+            serialized_size: self.serialized_size()?,
+        })
+    }
+
     /// Get the index of the notice closest to the coordinate 0 0
     pub fn get_upper_left_most_notice(&self) -> Option<usize> {
         if self.notices.is_empty() {
@@ -730,6 +1748,73 @@ mod tests {
 ****************************************
 "#;
         assert_eq!(expected_output, format!("{}", bored_hyperlink_map));
+        assert_eq!(bored_hyperlink_map.get_at(Coordinate { x: 13, y: 4 }), Some((0, 0)));
+        assert_eq!(bored_hyperlink_map.get_at(Coordinate { x: 0, y: 0 }), None);
+        assert_eq!(bored_hyperlink_map.get_at(Coordinate { x: 40, y: 4 }), None);
+        let whats_on_the_bored = WhatsOnTheBored::create(&bored);
+        assert_eq!(whats_on_the_bored.get_at(Coordinate { x: 14, y: 7 }), Some(2));
+        assert_eq!(whats_on_the_bored.get_at(Coordinate { x: 0, y: 0 }), None);
+        assert_eq!(whats_on_the_bored.get_at(Coordinate { x: 5, y: 20 }), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_notice_stack_at() -> Result<(), BoredError> {
+        let mut bored = Bored::create("Test", Coordinate { x: 40, y: 20 });
+        for (x, y) in [(0, 0), (5, 0), (25, 10)] {
+            let notice = Notice::create(Coordinate { x: 10, y: 5 });
+            bored.add(notice, Coordinate { x, y })?;
+        }
+        assert_eq!(
+            bored.get_notice_stack_at(Coordinate { x: 6, y: 2 }),
+            vec![1, 0]
+        );
+        assert_eq!(
+            bored.get_notice_stack_at(Coordinate { x: 2, y: 2 }),
+            vec![0]
+        );
+        assert!(
+            bored
+                .get_notice_stack_at(Coordinate { x: 20, y: 2 })
+                .is_empty()
+        );
+        let raised = bored.with_notice_raised(0);
+        assert_eq!(
+            raised.get_notice_stack_at(Coordinate { x: 6, y: 2 }),
+            vec![2, 0]
+        );
+        assert_eq!(
+            raised.notices().last().map(|notice| notice.get_top_left()),
+            Some(Coordinate { x: 0, y: 0 })
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_covered() -> Result<(), BoredError> {
+        let mut bored = Bored::create("Test", Coordinate { x: 40, y: 20 });
+        for (x, y) in [(0, 0), (5, 0), (25, 10)] {
+            let notice = Notice::create(Coordinate { x: 10, y: 5 });
+            bored.add(notice, Coordinate { x, y })?;
+        }
+        let whats_on_the_bored = WhatsOnTheBored::create(&bored);
+        // the first notice only shows to the left of the second
+        assert_eq!(
+            whats_on_the_bored.get_covered(Coordinate { x: 0, y: 0 }, Coordinate { x: 5, y: 5 }),
+            vec![(0, Coverage::Full)]
+        );
+        assert_eq!(
+            whats_on_the_bored.get_covered(Coordinate { x: 3, y: 2 }, Coordinate { x: 10, y: 10 }),
+            vec![(0, Coverage::Partial), (1, Coverage::Partial)]
+        );
+        assert_eq!(
+            whats_on_the_bored.get_covered(Coordinate { x: 20, y: 8 }, Coordinate { x: 20, y: 12 }),
+            vec![(2, Coverage::Full)]
+        );
+        assert_eq!(
+            whats_on_the_bored.get_covered(Coordinate { x: 0, y: 5 }, Coordinate { x: 20, y: 5 }),
+            vec![]
+        );
         Ok(())
     }
 
@@ -773,4 +1858,362 @@ mod tests {
         bored.add(notice, Coordinate { x: 17, y: 10 }).unwrap();
         assert_eq!(bored.get_upper_left_most_notice(), Some(0));
     }
+
+    #[test]
+    fn test_palette() -> Result<(), BoredError> {
+        assert_eq!(
+            Palette::create(Some("red"), None, None, None),
+            Err(BoredError::InvalidColour("red".to_string()))
+        );
+        assert!(Palette::create(None, Some("#12345"), None, None).is_err());
+        assert!(Palette::create(None, None, Some("#12345g"), None).is_err());
+        assert!(Palette::create(None, None, None, Some("#ééé")).is_err());
+        let palette = Palette::create(Some("#CD98D3"), Some("#171529"), None, None)?;
+        assert_eq!(palette.get_text(), Some((205, 152, 211)));
+        assert_eq!(palette.get_background(), Some((23, 21, 41)));
+        assert_eq!(palette.get_header(), None);
+        assert_eq!(palette.get_hyperlink(), None);
+        let mut bored = Bored::create("", Coordinate { x: 10, y: 10 });
+        assert!(bored.get_palette().is_default());
+        let json = serde_json::to_string(&bored)?;
+        assert!(!json.contains("palette"));
+        bored.set_palette(palette.clone());
+        let json = serde_json::to_string(&bored)?;
+        assert!(json.contains(r##""palette":{"text":"#cd98d3","background":"#171529"}"##));
+        assert_eq!(serde_json::from_str::<Bored>(&json)?, bored);
+        // colours that can't be read are dropped rather than the bored being rejected
+        let json = json.replace("#171529", "blue");
+        let mut bored: Bored = serde_json::from_str(&json)?;
+        assert_eq!(bored.get_palette().get_background(), None);
+        bored.sanitize()?;
+        assert!(bored.get_palette().is_default());
+        Ok(())
+    }
+
+    #[test]
+    fn test_background() {
+        assert_eq!(
+            Background::create(&"x".repeat(MAX_BACKGROUND_PATTERN_LENGTH + 1), FrameStyle::Plain),
+            Err(BoredError::InvalidBackground("x".repeat(MAX_BACKGROUND_PATTERN_LENGTH + 1)))
+        );
+        assert!(Background::create("\x1b[31m", FrameStyle::Plain).is_err());
+        let background = Background::default();
+        assert!(background.is_default());
+        assert_eq!(background.get_char_at(Coordinate { x: 3, y: 4 }), None);
+        let background = Background::create("ab.", FrameStyle::Double).unwrap();
+        assert_eq!(background.get_char_at(Coordinate { x: 0, y: 0 }), Some('a'));
+        assert_eq!(background.get_char_at(Coordinate { x: 1, y: 0 }), Some('b'));
+        assert_eq!(background.get_char_at(Coordinate { x: 1, y: 1 }), Some('.'));
+        assert_eq!(background.get_char_at(Coordinate { x: 2, y: 1 }), Some('a'));
+        let mut bored = Bored::create("", Coordinate { x: 10, y: 10 });
+        bored.set_background(background.clone());
+        assert_eq!(bored.get_background(), &background);
+    }
+
+    #[test]
+    fn test_stats() -> Result<(), BoredError> {
+        let mut bored = Bored::create("Stats", Coordinate { x: 10, y: 10 });
+        let stats = bored.stats()?;
+        assert_eq!(stats.notice_count, 0);
+        assert_eq!(stats.total_characters, 0);
+        assert_eq!(stats.fill_percentage, 0.0);
+        assert_eq!(
+            stats.largest_free_rectangle,
+            Some((Coordinate { x: 0, y: 0 }, Coordinate { x: 10, y: 10 }))
+        );
+        assert_eq!(stats.serialized_size, serde_json::to_string(&bored)?.len());
+        let mut notice = Notice::create(Coordinate { x: 10, y: 4 });
+        notice.write("a [b](url)")?;
+        bored.add(notice, Coordinate { x: 0, y: 0 })?;
+        let notice = Notice::create(Coordinate { x: 3, y: 6 });
+        bored.add(notice, Coordinate { x: 0, y: 4 })?;
+        let stats = bored.stats()?;
+        assert_eq!(stats.notice_count, 2);
+        assert_eq!(stats.total_characters, 3);
+        assert_eq!(stats.fill_percentage, 58.0);
+        assert_eq!(
+            stats.largest_free_rectangle,
+            Some((Coordinate { x: 3, y: 4 }, Coordinate { x: 7, y: 6 }))
+        );
+        let notice = Notice::create(Coordinate { x: 10, y: 10 });
+        bored.add(notice, Coordinate { x: 0, y: 0 })?;
+        assert_eq!(bored.stats()?.largest_free_rectangle, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_append_in_reading_order() -> Result<(), BoredError> {
+        let mut bored = Bored::create("Guestbook", Coordinate { x: 25, y: 8 });
+        let entry = || Notice::create(Coordinate { x: 10, y: 4 });
+        assert_eq!(
+            bored.append_in_reading_order(entry())?,
+            Coordinate { x: 0, y: 0 }
+        );
+        assert_eq!(
+            bored.append_in_reading_order(entry())?,
+            Coordinate { x: 10, y: 0 }
+        );
+        // too little room is left at the end of the row so it goes on the next
+        assert_eq!(
+            bored.append_in_reading_order(entry())?,
+            Coordinate { x: 0, y: 4 }
+        );
+        // something in the way is gone round
+        bored.add(
+            Notice::create(Coordinate { x: 3, y: 3 }),
+            Coordinate { x: 12, y: 5 },
+        )?;
+        assert_eq!(
+            bored.append_in_reading_order(entry())?,
+            Coordinate { x: 15, y: 4 }
+        );
+        assert_eq!(
+            bored.append_in_reading_order(entry()),
+            Err(BoredError::NoFreeSpace(Coordinate { x: 10, y: 4 }))
+        );
+        assert_eq!(bored.notices().len(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_embeds() -> Result<(), BoredError> {
+        let address = url::BoredAddress::from_string("bored://news")?;
+        let mut news = Bored::create("News", Coordinate { x: 40, y: 10 });
+        let mut announcement = Notice::create(Coordinate { x: 30, y: 5 });
+        announcement.set_notice_id("notice:1779796800000:ab12".to_string());
+        announcement.write("Fair on Saturday")?;
+        news.add(announcement, Coordinate { x: 0, y: 0 })?;
+        let mut bored = Bored::create("Village", Coordinate { x: 40, y: 10 });
+        for notice_id in ["notice:1779796800000:ab12", "notice:1:gone"] {
+            let mut mirror = Notice::create(Coordinate { x: 30, y: 5 });
+            mirror.write("Copy of the news")?;
+            mirror.set_kind(NoticeKind::Embed {
+                address: address.clone(),
+                notice_id: notice_id.to_string(),
+            })?;
+            bored.append_in_reading_order(mirror)?;
+        }
+        assert_eq!(bored.get_embedded_addresses(), vec![address.clone()]);
+        let news = Arc::new(news);
+        let shown = bored.with_embeds(|_| Some(news.clone()));
+        assert_eq!(
+            shown.notices()[0].get_content(),
+            "Fair on Saturday\n[From News](bored://news)"
+        );
+        // a notice that isn't on the bored, or a bored that isn't found, leaves the copy
+        assert_eq!(shown.notices()[1].get_content(), "Copy of the news");
+        let not_found = bored.with_embeds(|_| None);
+        assert_eq!(not_found.notices()[0].get_content(), "Copy of the news");
+        Ok(())
+    }
+
+    #[test]
+    fn test_would_fit_with() -> Result<(), BoredError> {
+        let bored = Bored::create("Small", Coordinate { x: 200, y: 100 });
+        assert_eq!(bored.serialized_size()?, serde_json::to_string(&bored)?.len());
+        let mut notice = Notice::create(Coordinate { x: 10, y: 5 });
+        notice.write("small")?;
+        assert!(bored.would_fit_with(&notice)?);
+        // use a massive name to get the bored close to the limit
+        let name = "n".repeat(MAX_BORED_SIZE - 200);
+        let bored = Bored::create(&name, Coordinate { x: 200, y: 100 });
+        assert!(bored.would_fit_with(&notice)?);
+        // a long url is not visible so makes the notice much bigger than it appears
+        let mut notice = Notice::create(Coordinate { x: 10, y: 5 });
+        notice.write(&format!("[x]({})", "u".repeat(notice::MAX_URL_LENGTH)))?;
+        assert!(!bored.would_fit_with(&notice)?);
+        assert!(bored.size_with(&notice)? > MAX_BORED_SIZE);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_layout_str() -> Result<(), BoredError> {
+        let layout = "\
+# the village hall
+name: Village hall
+size: 40x12
+background: double .
+
+notice at 1,1 size 20x5
+Jumble sale
+[Details](https://example.com)
+end
+notice at 10,3 size 12x4
+on top
+end
+";
+        let bored = Bored::from_layout_str(layout)?;
+        assert_eq!(bored.get_name(), "Village hall");
+        assert_eq!(bored.get_dimensions(), Coordinate { x: 40, y: 12 });
+        assert_eq!(bored.get_background().get_frame(), FrameStyle::Double);
+        assert_eq!(bored.get_background().get_pattern(), ".");
+        let notices = bored.get_notices();
+        assert_eq!(notices.len(), 2);
+        assert_eq!(notices[0].get_content(), "Jumble sale\n[Details](https://example.com)");
+        assert_eq!(notices[1].get_top_left(), Coordinate { x: 10, y: 3 });
+
+        assert_eq!(
+            Bored::from_layout_str("name: a\nsize: 10x10\nnotice at 1,1 size 5x5\nhi\n"),
+            Err(BoredError::InvalidLayout(3, "notice has no end line".to_string()))
+        );
+        assert_eq!(
+            Bored::from_layout_str("name: a\nsize: ten"),
+            Err(BoredError::InvalidLayout(2, "size must be WIDTHxHEIGHT".to_string()))
+        );
+        assert_eq!(
+            Bored::from_layout_str("name: a"),
+            Err(BoredError::InvalidLayout(1, "missing size".to_string()))
+        );
+        assert!(matches!(
+            Bored::from_layout_str("name: a\nsize: 10x10\nnotice at 8,8 size 5x5\nhi\nend"),
+            Err(BoredError::InvalidLayout(3, _))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sanitize() -> Result<(), BoredError> {
+        let mut bored = Bored::create("hostile", Coordinate { x: 20, y: 10 });
+        let mut notice = Notice::create(Coordinate { x: 5, y: 4 });
+        notice.write("fine")?;
+        bored.add(notice.clone(), Coordinate { x: 0, y: 0 })?;
+        let mut too_much_text = serde_json::to_value(&notice)?;
+        too_much_text["content"] = serde_json::json!("x".repeat(1000));
+        let too_much_text: Notice = serde_json::from_value(too_much_text)?;
+        assert_eq!(
+            bored.clone().add(too_much_text.clone(), Coordinate { x: 10, y: 0 }),
+            Err(BoredError::TooMuchText)
+        );
+        bored.notices.push(too_much_text);
+        assert_eq!(bored.check_invariants(), Err(BoredError::TooMuchText));
+        assert_eq!(bored.sanitize()?, 1);
+        assert_eq!(bored.check_invariants(), Ok(()));
+
+        // a notice placed where its far corner can't be counted to
+        let mut overflowing = serde_json::to_value(&notice)?;
+        overflowing["top_left"] = serde_json::json!({ "x": u16::MAX, "y": 0 });
+        let overflowing: Notice = serde_json::from_value(overflowing)?;
+        bored.notices.push(overflowing.clone());
+        assert_eq!(
+            bored.check_invariants(),
+            Err(BoredError::CoordinateOverflow(
+                overflowing.get_top_left(),
+                overflowing.get_dimensions()
+            ))
+        );
+        assert_eq!(bored.sanitize()?, 1);
+        assert_eq!(
+            overflowing
+                .clone()
+                .relocate(&bored, overflowing.get_top_left()),
+            Err(BoredError::CoordinateOverflow(
+                overflowing.get_top_left(),
+                overflowing.get_dimensions()
+            ))
+        );
+
+        let mut huge = Bored::create("huge", Coordinate { x: 2000, y: 10 });
+        assert_eq!(
+            huge.sanitize(),
+            Err(BoredError::InvalidDimensions(Coordinate { x: 2000, y: 10 }))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_hidden_notices() -> Result<(), BoredError> {
+        let mut bored = Bored::create("moderated", Coordinate { x: 30, y: 10 });
+        let mut notice = Notice::create(Coordinate { x: 10, y: 5 });
+        notice.write("spam")?;
+        bored.add(notice.clone(), Coordinate { x: 0, y: 0 })?;
+        let notice = bored.get_notices()[0].clone();
+        assert!(!bored.is_hidden(&notice));
+        bored.set_hidden_notices(vec![notice.get_hash()])?;
+        assert!(bored.is_hidden(&notice));
+        let redacted = bored.redact_hidden();
+        assert_eq!(redacted.get_notices()[0].get_content(), "");
+        assert_eq!(redacted.get_notices()[0].get_top_left(), notice.get_top_left());
+        // the original is kept so moderation can be undone
+        assert_eq!(bored.get_notices()[0].get_content(), "spam");
+        assert_eq!(
+            bored.set_hidden_notices(vec![String::new(); MAX_HIDDEN_NOTICES + 1]),
+            Err(BoredError::TooManyHiddenNotices(MAX_HIDDEN_NOTICES + 1))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_moved_to() -> Result<(), BoredError> {
+        let mut bored = Bored::create("old", Coordinate { x: 30, y: 10 });
+        assert_eq!(bored.get_moved_to(), None);
+        assert!(!serde_json::to_string(&bored).unwrap().contains("moved_to"));
+        let new_address = url::BoredAddress::from_string("bored://new")?;
+        bored.set_moved_to(Some(&new_address));
+        let json = serde_json::to_string(&bored).unwrap();
+        assert!(json.contains(r#""moved_to":"bored://new""#));
+        let read: Bored = serde_json::from_str(&json).unwrap();
+        assert_eq!(read.get_moved_to(), Some(new_address));
+        bored.set_moved_to(None);
+        assert_eq!(bored.get_moved_to(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_attachments() -> Result<(), BoredError> {
+        let mut bored = Bored::create("files", Coordinate { x: 40, y: 10 });
+        let first = notice::Attachment::create(b"first", "first.txt")?;
+        let second = notice::Attachment::create(b"second", "second.txt")?;
+        for (x, attachment) in [(0, &first), (10, &second), (20, &first), (30, &second)] {
+            let mut notice = Notice::create(Coordinate { x: 10, y: 5 });
+            notice.set_attachment(Some(attachment.clone()))?;
+            bored.add(notice, Coordinate { x, y: 0 })?;
+        }
+        assert_eq!(bored.get_attachments(), vec![second.clone(), first.clone()]);
+        let newest = bored.get_notices()[3].clone();
+        let previous = bored.get_notices()[1].clone();
+        bored.set_hidden_notices(vec![newest.get_hash(), previous.get_hash()])?;
+        assert_eq!(bored.get_attachments(), vec![first]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_search() -> Result<(), BoredError> {
+        let mut bored = Bored::create("Search", Coordinate { x: 40, y: 20 });
+        let mut notice = Notice::create(Coordinate { x: 8, y: 5 });
+        notice.write("We are [bored](url)\nso BORED")?;
+        bored.add(notice, Coordinate { x: 2, y: 1 })?;
+        let mut notice = Notice::create(Coordinate { x: 10, y: 3 });
+        notice.write("not here")?;
+        bored.add(notice, Coordinate { x: 20, y: 10 })?;
+        let hits = bored.search(&SearchPattern::Text("bored".to_string()))?;
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].notice_index, 0);
+        assert_eq!(hits[0].byte_range, (7, 12));
+        // "We are" fills the first line so the match is on the second after the space
+        assert_eq!(
+            hits[0].coordinates,
+            vec![
+                Coordinate { x: 4, y: 3 },
+                Coordinate { x: 5, y: 3 },
+                Coordinate { x: 6, y: 3 },
+                Coordinate { x: 7, y: 3 },
+                Coordinate { x: 8, y: 3 },
+            ]
+        );
+        assert_eq!(hits[1].byte_range, (16, 21));
+        assert_eq!(hits[1].coordinates[0], Coordinate { x: 6, y: 4 });
+        // urls are not visible so should not be found
+        assert!(bored.search(&SearchPattern::Text("url".to_string()))?.is_empty());
+        let hits = bored.search(&SearchPattern::Regex("h[a-z]+e".to_string()))?;
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].notice_index, 1);
+        assert_eq!(hits[0].coordinates[0], Coordinate { x: 25, y: 11 });
+        assert_eq!(
+            bored
+                .search(&SearchPattern::Regex("(".to_string()))
+                .map_err(|e| e.code()),
+            Err("regex")
+        );
+        Ok(())
+    }
 }
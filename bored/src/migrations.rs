@@ -0,0 +1,138 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Converting boreds saved with older versions of the protocol to the latest version. Each
+//! converter takes the json of a bored at one version and returns it at the next, so adding a
+//! protocol version only needs a converter from the version before it.
+
+use serde_json::Value;
+
+use crate::{Bored, BoredError, ProtocolVersion};
+
+/// Converts the json of a bored from one protocol version to the next
+type Migration = fn(Value) -> Result<Value, BoredError>;
+
+/// Converters paired with the version they upgrade from
+const MIGRATIONS: [(u64, Migration); 2] = [(1, v1_to_v2), (2, v2_to_v3)];
+
+/// Missing or malformed versions are treated as version 0 which has never existed
+fn get_version(value: &Value) -> u64 {
+    value
+        .get("protocol_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+}
+
+fn set_version(value: &mut Value, version: u64) {
+    if let Some(object) = value.as_object_mut() {
+        object.insert("protocol_version".to_string(), Value::from(version));
+    }
+}
+
+/// The layout of a bored is the same in versions 1 and 2 so only the version changes
+pub fn v1_to_v2(mut value: Value) -> Result<Value, BoredError> {
+    set_version(&mut value, 2);
+    Ok(value)
+}
+
+/// Version 3 gives every notice an id so peers can deduplicate them when syncing. Older notices
+/// are given ids from their position in the bored so every peer upgrading the same bored ends
+/// up with the same ids.
+pub fn v2_to_v3(mut value: Value) -> Result<Value, BoredError> {
+    if let Some(notices) = value.get_mut("notices").and_then(Value::as_array_mut) {
+        for (index, notice) in notices.iter_mut().enumerate() {
+            if let Some(notice) = notice.as_object_mut() {
+                notice
+                    .entry("notice_id")
+                    .or_insert_with(|| Value::from(format!("legacy:{index}")));
+            }
+        }
+    }
+    set_version(&mut value, 3);
+    Ok(value)
+}
+
+impl Bored {
+    /// Deserialize a bored saved with any supported protocol version, applying converters in
+    /// turn until it is at the latest version
+    pub fn upgrade_to_latest(mut value: Value) -> Result<Bored, BoredError> {
+        let latest = ProtocolVersion::new().get_version();
+        let mut version = ProtocolVersion::check(get_version(&value))?.get_version();
+        while version < latest {
+            let Some((_, migration)) = MIGRATIONS.iter().find(|(from, _)| *from == version) else {
+                return Err(BoredError::InvalidProtocolVersion(version));
+            };
+            value = migration(value)?;
+            let upgraded = get_version(&value);
+            // a converter that didn't move the version on would loop forever
+            if upgraded <= version {
+                return Err(BoredError::InvalidProtocolVersion(upgraded));
+            }
+            version = upgraded;
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Parse the json of a bored saved with any supported protocol version
+    pub fn from_json(json: &str) -> Result<Bored, BoredError> {
+        Bored::upgrade_to_latest(serde_json::from_str(json)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Coordinate;
+
+    const V1_FIXTURE: &str = include_str!("../tests/fixtures/bored_v1.json");
+    const V2_FIXTURE: &str = include_str!("../tests/fixtures/bored_v2.json");
+
+    #[test]
+    fn test_upgrade_v1_fixture() -> Result<(), BoredError> {
+        let bored = Bored::from_json(V1_FIXTURE)?;
+        assert_eq!(bored.protocol_version, ProtocolVersion::new());
+        assert_eq!(bored.get_name(), "The genesis bored");
+        assert_eq!(bored.get_dimensions(), Coordinate { x: 120, y: 40 });
+        let notices = bored.get_notices();
+        assert_eq!(notices.len(), 2);
+        assert_eq!(notices[0].get_notice_id(), "legacy:0");
+        assert_eq!(notices[1].get_notice_id(), "legacy:1");
+        assert_eq!(
+            notices[1].get_content(),
+            "This is a [link to another board](bored://welcome)"
+        );
+        // upgrading is deterministic and the latest version round trips unchanged
+        assert_eq!(Bored::from_json(V2_FIXTURE)?, bored);
+        let json = serde_json::to_string(&bored)?;
+        assert_eq!(Bored::from_json(&json)?, bored);
+        Ok(())
+    }
+
+    #[test]
+    fn test_upgrade_unknown_version() {
+        let mut value: Value = serde_json::from_str(V1_FIXTURE).unwrap();
+        set_version(&mut value, 99);
+        assert_eq!(
+            Bored::upgrade_to_latest(value),
+            Err(BoredError::InvalidProtocolVersion(99))
+        );
+        assert_eq!(
+            Bored::from_json("{}"),
+            Err(BoredError::InvalidProtocolVersion(0))
+        );
+    }
+}
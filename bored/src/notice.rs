@@ -15,15 +15,114 @@ You should have received a copy of the GNU Affero General Public License
 along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
+use crate::url::BoredAddress;
 use crate::{Bored, BoredError, Coordinate};
+use chrono::{DateTime, NaiveDateTime, TimeZone};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::fmt::{self};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// Limit to avoid massive amount of text being accidentally put into hyperlink and making
 /// bored to big to fit in scratchpadlonges
 pub const MAX_URL_LENGTH: usize = 2048;
 
+/// Largest proof of work difficulty a bored can ask for, in leading zero bits of the hash, at
+/// this level a notice takes around 16 million hashes to prove
+pub const MAX_POW_DIFFICULTY: u8 = 24;
+
+/// Smallest a notice can be and still have room for its border
+pub const MIN_NOTICE_DIMENSIONS: Coordinate = Coordinate { x: 2, y: 2 };
+
+/// Largest file that can be attached to a notice, peers send attachments over gossip so this
+/// is kept small
+pub const MAX_ATTACHMENT_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Longest attachment file name in characters
+pub const MAX_FILENAME_LENGTH: usize = 255;
+
+/// Prefix of attachment addresses, followed by the hex SHA-256 of the file's content
+pub const ATTACHMENT_ADDRESS_PREFIX: &str = "sha256:";
+
+/// Longest title or location of an event in characters
+pub const MAX_EVENT_TEXT_LENGTH: usize = 100;
+
+/// Longest notice id an embedded notice can refer to
+pub const MAX_NOTICE_ID_LENGTH: usize = 100;
+
+/// How event times are typed and shown, in the reader's own time zone
+pub const EVENT_TIME_FORMAT: &str = "%Y-%m-%d %H:%M";
+
+/// Columns a character takes up in a terminal, wide characters such as CJK and most emoji
+/// take two and combining marks take none
+pub fn char_width(char: char) -> u16 {
+    char.width().unwrap_or(0) as u16
+}
+
+/// Where each character of display text goes in a text area of the given width, relative to
+/// its top left. Newlines have no position so are none and combining marks share the position
+/// of the character before them. A character that doesn't fit on the rest of a line goes on
+/// to the next, so wide characters are never split, and a newline straight after a line has
+/// filled up doesn't leave an empty line. surf-bored, the hyperlink maps, search and exports
+/// all lay text out with this so they line up.
+pub fn layout_text(display_text: &str, text_width: u16) -> Vec<Option<Coordinate>> {
+    let mut positions = vec![];
+    let (mut x, mut y) = (0, 0);
+    let mut previous: Option<Coordinate> = None;
+    let mut prev_char = '\n';
+    for char in display_text.chars() {
+        let width = char_width(char);
+        if char == '\n' {
+            positions.push(None);
+            previous = None;
+            if x != 0 || prev_char == '\n' {
+                y += 1;
+                x = 0;
+            }
+        } else if width == 0 {
+            positions.push(Some(previous.unwrap_or(Coordinate { x, y })));
+        } else {
+            if x > 0 && x + width > text_width {
+                y += 1;
+                x = 0;
+            }
+            let position = Coordinate { x, y };
+            positions.push(Some(position));
+            previous = Some(position);
+            x += width;
+            // a full line wraps straight away so a newline typed next doesn't add a line
+            if x >= text_width {
+                y += 1;
+                x = 0;
+            }
+        }
+        prev_char = char;
+    }
+    positions
+}
+
+/// Whether a character may appear in text shown on a bored. Newlines break lines so are
+/// allowed but other control characters, e.g. the escape that starts ANSI terminal sequences,
+/// and the invisible characters that reorder text direction could be used to corrupt the
+/// terminal or disguise text so are not.
+pub fn is_allowed_char(char: char) -> bool {
+    match char {
+        '\n' => true,
+        // bidirectional embeddings, overrides and isolates
+        '\u{202A}'..='\u{202E}' | '\u{2066}'..='\u{2069}' => false,
+        _ => !char.is_control(),
+    }
+}
+
+/// Remove characters that are not allowed from text, tabs become a space
+pub fn strip_disallowed_chars(text: &str) -> String {
+    text.chars()
+        .map(|char| if char == '\t' { ' ' } else { char })
+        .filter(|char| is_allowed_char(*char))
+        .collect()
+}
+
 /// Hyperlinks with maximum url length
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
 pub struct Hyperlink {
@@ -57,6 +156,11 @@ impl Hyperlink {
     pub fn get_text(&self) -> String {
         self.text.clone()
     }
+
+    /// Where the whole [text](link) of the hyperlink is in the notice's content
+    pub fn get_markdown_range(&self) -> std::ops::Range<usize> {
+        self.text_location.0.saturating_sub(1)..self.link_location.1 + 1
+    }
 }
 
 /// a 2d vector of option<uszie> representing the location of hyperlinks
@@ -96,30 +200,30 @@ impl NoticeHyperlinkMap {
         let display = get_display(content, get_hyperlinks(content)?);
         let mut visible =
             vec![vec![None; notice.dimensions.x as usize - 2]; notice.dimensions.y as usize - 2];
-        let (mut x, mut y) = (0, 0);
-        let mut prev_char = '\n';
-        for (char_index, char) in display.display_text.chars().enumerate() {
-            for (hyperlink_index, hyperlink_location) in
-                display.hyperlink_locations.iter().enumerate()
-            {
-                for i in hyperlink_location.0..hyperlink_location.1 {
-                    if char_index == i && char != '\n' {
-                        visible[y][x] = Some(hyperlink_index);
-                    }
+        let positions = layout_text(&display.display_text, notice.get_text_width());
+        for (char_index, (char, position)) in
+            display.display_text.chars().zip(positions).enumerate()
+        {
+            let Some(position) = position else {
+                continue;
+            };
+            let Some(hyperlink_index) = display
+                .hyperlink_locations
+                .iter()
+                .position(|(start, end)| (*start..*end).contains(&char_index))
+            else {
+                continue;
+            };
+            // wide characters are linked across both of their columns
+            let x = position.x as usize;
+            for x in x..x + char_width(char).max(1) as usize {
+                if let Some(cell) = visible
+                    .get_mut(position.y as usize)
+                    .and_then(|row| row.get_mut(x))
+                {
+                    *cell = Some(hyperlink_index);
                 }
             }
-            if char == '\n' && (x != 0 || prev_char == '\n') {
-                y += 1;
-                x = 0;
-            // do nothing if newline typed at end of line
-            } else if char == '\n' && x == 0 {
-            } else if x < notice.get_text_width() as usize - 1 {
-                x += 1;
-            } else {
-                y += 1;
-                x = 0;
-            }
-            prev_char = char;
         }
         Ok(NoticeHyperlinkMap { visible })
     }
@@ -130,7 +234,7 @@ impl NoticeHyperlinkMap {
 }
 
 /// Display contains the text to display plus a collections of the hyperlinks locations from left
-/// to right
+/// to right, as character indices of the display text
 #[derive(Default, Debug)]
 pub struct Display {
     display_text: String,
@@ -171,6 +275,286 @@ impl Display {
     }
 }
 
+/// A file attached to a notice. Files are addressed by a hash of their content so they can be
+/// fetched from any peer that has them and checked once downloaded.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
+pub struct Attachment {
+    address: String,
+    filename: String,
+    size: u64,
+    /// Hint at what the file contains guessed from its name, e.g. image/png
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    mime: Option<String>,
+}
+
+impl Attachment {
+    /// Describe a file with the given content and name
+    pub fn create(content: &[u8], filename: &str) -> Result<Attachment, BoredError> {
+        let attachment = Attachment {
+            address: attachment_address(content),
+            filename: filename.to_string(),
+            size: content.len() as u64,
+            mime: mime_hint(filename).map(str::to_string),
+        };
+        attachment.validate()?;
+        Ok(attachment)
+    }
+
+    pub fn get_address(&self) -> &str {
+        &self.address
+    }
+
+    pub fn get_filename(&self) -> &str {
+        &self.filename
+    }
+
+    pub fn get_size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn get_mime(&self) -> Option<&str> {
+        self.mime.as_deref()
+    }
+
+    /// The hex hash part of the address
+    pub fn get_hash(&self) -> &str {
+        self.address
+            .strip_prefix(ATTACHMENT_ADDRESS_PREFIX)
+            .unwrap_or(&self.address)
+    }
+
+    /// The file name made safe to save as in a directory: path separators and anything before
+    /// them are dropped, leading dots are removed so it can't be hidden or refer to a parent
+    /// directory, and characters that are awkward in file names are replaced
+    pub fn safe_filename(&self) -> String {
+        let name = self
+            .filename
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .trim_start_matches('.');
+        let name: String = name
+            .chars()
+            .map(|char| match char {
+                ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+                char if !is_allowed_char(char) || char == '\n' => '_',
+                char => char,
+            })
+            .collect();
+        if name.trim().is_empty() {
+            return format!("attachment-{}", &self.get_hash()[..self.get_hash().len().min(8)]);
+        }
+        name
+    }
+
+    /// Check an attachment received from a peer is well formed
+    pub fn validate(&self) -> Result<(), BoredError> {
+        let hash = self.address.strip_prefix(ATTACHMENT_ADDRESS_PREFIX);
+        if !hash.is_some_and(|hash| {
+            hash.len() == 64
+                && hash
+                    .chars()
+                    .all(|char| char.is_ascii_digit() || ('a'..='f').contains(&char))
+        }) {
+            return Err(BoredError::InvalidAttachment(self.address.clone()));
+        }
+        if self.filename.trim().is_empty()
+            || self.filename.chars().count() > MAX_FILENAME_LENGTH
+            || !self
+                .filename
+                .chars()
+                .all(|char| char != '\n' && is_allowed_char(char))
+        {
+            return Err(BoredError::InvalidAttachment(self.filename.clone()));
+        }
+        if self.size > MAX_ATTACHMENT_SIZE {
+            return Err(BoredError::AttachmentTooBig(self.size));
+        }
+        if let Some(mime) = &self.mime
+            && (mime.len() > 100 || !mime.chars().all(|char| char.is_ascii_graphic()))
+        {
+            return Err(BoredError::InvalidAttachment(mime.clone()));
+        }
+        Ok(())
+    }
+}
+
+/// Address of a file with the given content
+pub fn attachment_address(content: &[u8]) -> String {
+    address_from_hasher(Sha256::new_with_prefix(content))
+}
+
+/// Address of a file read from reader, without holding it all in memory
+pub fn attachment_address_from_reader(
+    mut reader: impl std::io::Read,
+) -> Result<String, BoredError> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut reader, &mut hasher)?;
+    Ok(address_from_hasher(hasher))
+}
+
+fn address_from_hasher(hasher: Sha256) -> String {
+    let hash: String = hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("{ATTACHMENT_ADDRESS_PREFIX}{hash}")
+}
+
+/// Guess the type of a file from the extension of its name
+pub fn mime_hint(filename: &str) -> Option<&'static str> {
+    let (_, extension) = filename.rsplit_once('.')?;
+    let mime = match extension.to_lowercase().as_str() {
+        "txt" | "md" => "text/plain",
+        "html" | "htm" => "text/html",
+        "json" => "application/json",
+        "pdf" => "application/pdf",
+        "zip" => "application/zip",
+        "tar" => "application/x-tar",
+        "gz" => "application/gzip",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "mp3" => "audio/mpeg",
+        "ogg" => "audio/ogg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "webm" => "video/webm",
+        _ => return None,
+    };
+    Some(mime)
+}
+
+/// What a notice is, most are text to read but some carry details readers can do more with
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq, Eq, Clone)]
+#[serde(tag = "type")]
+pub enum NoticeKind {
+    #[default]
+    Text,
+    /// An event happening at a time, which readers can show as one and add to calendars. Times
+    /// are milliseconds since the unix epoch.
+    Event {
+        title: String,
+        start: i64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        end: Option<i64>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        location: Option<String>,
+    },
+    /// Shows a notice from a bored in its place, read only, so an announcement can be on
+    /// several boreds without the copies drifting apart. Its own content is shown until the
+    /// notice is found.
+    Embed {
+        address: BoredAddress,
+        notice_id: String,
+    },
+}
+
+impl NoticeKind {
+    pub fn is_text(&self) -> bool {
+        *self == NoticeKind::Text
+    }
+
+    /// Checks an event has a title, its texts are short and on one line and it doesn't end
+    /// before it starts, and an embedded notice's id is short and printable
+    pub fn validate(&self) -> Result<(), BoredError> {
+        if let NoticeKind::Embed { notice_id, .. } = self {
+            if notice_id.is_empty()
+                || notice_id.len() > MAX_NOTICE_ID_LENGTH
+                || !notice_id.chars().all(|char| char.is_ascii_graphic())
+            {
+                return Err(BoredError::InvalidEmbed(notice_id.clone()));
+            }
+            return Ok(());
+        }
+        let NoticeKind::Event {
+            title,
+            start,
+            end,
+            location,
+        } = self
+        else {
+            return Ok(());
+        };
+        for text in std::iter::once(title).chain(location) {
+            if text.chars().count() > MAX_EVENT_TEXT_LENGTH
+                || !text
+                    .chars()
+                    .all(|char| char != '\n' && is_allowed_char(char))
+            {
+                return Err(BoredError::InvalidEvent(format!(
+                    "{text} is too long or has control characters"
+                )));
+            }
+        }
+        if title.trim().is_empty() {
+            return Err(BoredError::InvalidEvent("it has no title".to_string()));
+        }
+        if end.is_some_and(|end| end < *start) {
+            return Err(BoredError::InvalidEvent(
+                "it ends before it starts".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// When and where an event is on one line, times in the time zone, nothing for text
+    pub fn describe_event<Tz: TimeZone>(&self, zone: &Tz) -> Option<String>
+    where
+        Tz::Offset: fmt::Display,
+    {
+        let NoticeKind::Event {
+            start,
+            end,
+            location,
+            ..
+        } = self
+        else {
+            return None;
+        };
+        let mut when = format_event_time(*start, zone);
+        if let Some(end) = end {
+            let end = format_event_time(*end, zone);
+            // the date is left off the end when it is the same day
+            match (when.split_once(' '), end.split_once(' ')) {
+                (Some((start_day, _)), Some((end_day, end_time))) if start_day == end_day => {
+                    when = format!("{when} to {end_time}")
+                }
+                _ => when = format!("{when} to {end}"),
+            }
+        }
+        Some(match location {
+            Some(location) => format!("{when} at {location}"),
+            None => when,
+        })
+    }
+}
+
+/// Milliseconds since the unix epoch for a time typed as EVENT_TIME_FORMAT in the time zone
+pub fn parse_event_time<Tz: TimeZone>(text: &str, zone: &Tz) -> Result<i64, BoredError> {
+    NaiveDateTime::parse_from_str(text.trim(), EVENT_TIME_FORMAT)
+        .ok()
+        .and_then(|time| zone.from_local_datetime(&time).earliest())
+        .map(|time| time.timestamp_millis())
+        .ok_or_else(|| BoredError::InvalidEvent(format!("{} isn't a time", text.trim())))
+}
+
+/// A time in milliseconds since the unix epoch written as EVENT_TIME_FORMAT in the time zone
+pub fn format_event_time<Tz: TimeZone>(time_ms: i64, zone: &Tz) -> String
+where
+    Tz::Offset: fmt::Display,
+{
+    DateTime::from_timestamp_millis(time_ms)
+        .unwrap_or_default()
+        .with_timezone(zone)
+        .format(EVENT_TIME_FORMAT)
+        .to_string()
+}
+
 /// A notice the may be attached to a bored containing only as much text as would be visible
 /// within it's bounds (not counting not visble parts of hyperlinks)
 #[derive(Debug, Serialize, Deserialize, PartialEq, Eq, Clone)]
@@ -179,6 +563,16 @@ pub struct Notice {
     top_left: Coordinate,
     dimensions: Coordinate, // the notice will range from (0,0) up to
     content: String,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pow_nonce: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    attachment: Option<Attachment>,
+    #[serde(default, skip_serializing_if = "NoticeKind::is_text")]
+    kind: NoticeKind,
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
 }
 
 impl Notice {
@@ -189,6 +583,9 @@ impl Notice {
             top_left: Coordinate { x: 0, y: 0 },
             dimensions: Coordinate { x: 60, y: 18 },
             content: String::new(),
+            pow_nonce: 0,
+            attachment: None,
+            kind: NoticeKind::Text,
         }
     }
 
@@ -199,9 +596,36 @@ impl Notice {
             top_left: Coordinate { x: 0, y: 0 },
             dimensions,
             content: String::new(),
+            pow_nonce: 0,
+            attachment: None,
+            kind: NoticeKind::Text,
         }
     }
 
+    pub fn get_kind(&self) -> &NoticeKind {
+        &self.kind
+    }
+
+    /// Make the notice an event or text again
+    pub fn set_kind(&mut self, kind: NoticeKind) -> Result<(), BoredError> {
+        kind.validate()?;
+        self.kind = kind;
+        Ok(())
+    }
+
+    pub fn get_attachment(&self) -> Option<&Attachment> {
+        self.attachment.as_ref()
+    }
+
+    /// Attach a file to the notice, or remove the attachment with None
+    pub fn set_attachment(&mut self, attachment: Option<Attachment>) -> Result<(), BoredError> {
+        if let Some(attachment) = &attachment {
+            attachment.validate()?;
+        }
+        self.attachment = attachment;
+        Ok(())
+    }
+
     pub fn get_notice_id(&self) -> &str {
         &self.notice_id
     }
@@ -210,6 +634,28 @@ impl Notice {
         self.notice_id = id;
     }
 
+    /// Milliseconds since the unix epoch the notice was posted, taken from the notice id which
+    /// is in the form notice:<timestamp_ms>:<agent_id_prefix>, none if not in that form
+    pub fn get_timestamp(&self) -> Option<i64> {
+        let mut parts = self.notice_id.split(':');
+        if parts.next() != Some("notice") {
+            return None;
+        }
+        parts.next()?.parse().ok()
+    }
+
+    /// Who posted the notice, the start of their x0x agent id taken from the notice id, none if
+    /// it isn't in that form. Anyone can give a notice any id, so it only tells posters apart.
+    pub fn get_author(&self) -> Option<&str> {
+        let mut parts = self.notice_id.split(':');
+        if parts.next() != Some("notice") {
+            return None;
+        }
+        parts
+            .nth(1)
+            .filter(|author| !author.is_empty() && author.chars().all(|c| c.is_ascii_hexdigit()))
+    }
+
     pub fn get_top_left(&self) -> Coordinate {
         self.top_left
     }
@@ -247,9 +693,21 @@ impl Notice {
         ))
     }
 
+    /// Get the position of each character of the display text relative to the top left of the
+    /// text area inside the border, newlines have no position so are none
+    pub fn get_char_positions(&self) -> Result<Vec<Option<Coordinate>>, BoredError> {
+        let display_text = self.get_display()?.display_text;
+        Ok(layout_text(&display_text, self.get_text_width()))
+    }
+
     /// moves notices position on board, both prior to placing and is called by Bored.add()
     pub fn relocate(&mut self, bored: &Bored, new_top_left: Coordinate) -> Result<(), BoredError> {
-        let new_bottom_right = new_top_left.add(&self.dimensions);
+        let Some(new_bottom_right) = new_top_left.checked_add(&self.dimensions) else {
+            return Err(BoredError::CoordinateOverflow(
+                new_top_left,
+                self.dimensions,
+            ));
+        };
         if new_bottom_right.within(&bored.dimensions) {
             self.top_left = new_top_left;
             return Ok(());
@@ -260,6 +718,26 @@ impl Notice {
         ))
     }
 
+    /// Change the size of the notice keeping its top left, it has to stay on the bored and its
+    /// text has to still fit
+    pub fn resize(&mut self, bored: &Bored, new_dimensions: Coordinate) -> Result<(), BoredError> {
+        if !MIN_NOTICE_DIMENSIONS.within(&new_dimensions) {
+            return Err(BoredError::InvalidDimensions(new_dimensions));
+        }
+        let new_bottom_right = self.top_left.add(&new_dimensions);
+        if !new_bottom_right.within(&bored.dimensions) {
+            return Err(BoredError::NoticeOutOfBounds(
+                bored.dimensions,
+                new_bottom_right,
+            ));
+        }
+        let mut resized = self.clone();
+        resized.dimensions = new_dimensions;
+        resized.write(&self.content)?;
+        *self = resized;
+        Ok(())
+    }
+
     /// Get maximun nubmer of unicode scarlar value that can be written on the notice
     // If you wanted to handle some other langauge you might need to work out hot to implement
     // for graphem clusters instead
@@ -284,8 +762,161 @@ impl Notice {
         }
     }
 
+    /// Check a notice from elsewhere is one that could have been made with write, i.e. it is
+    /// big enough to have a border and its content fits inside it
+    pub fn validate(&self) -> Result<(), BoredError> {
+        if !MIN_NOTICE_DIMENSIONS.within(&self.dimensions) {
+            return Err(BoredError::InvalidDimensions(self.dimensions));
+        }
+        if !self.content.chars().all(is_allowed_char) {
+            return Err(BoredError::DisallowedCharacters);
+        }
+        if let Some(attachment) = &self.attachment {
+            attachment.validate()?;
+        }
+        self.kind.validate()?;
+        // counted in characters rather than columns as notices written before wide characters
+        // took up two columns were, and they should still be accepted
+        Notice::create(self.dimensions).write_measured(&self.content, |line| line.chars().count())
+    }
+
+    /// Add everything that identifies the notice to a hash
+    fn hash_identity(&self, hasher: &mut Sha256) {
+        hasher.update(self.notice_id.as_bytes());
+        hasher.update([0]);
+        for value in [
+            self.top_left.x,
+            self.top_left.y,
+            self.dimensions.x,
+            self.dimensions.y,
+        ] {
+            hasher.update(value.to_be_bytes());
+        }
+        hasher.update(self.content.as_bytes());
+        hasher.update([0]);
+        // only hashed when present so notices without attachments keep their hashes
+        if let Some(attachment) = &self.attachment {
+            hasher.update(attachment.address.as_bytes());
+            hasher.update([0]);
+        }
+        // likewise only events have their details hashed
+        if let NoticeKind::Event {
+            title,
+            start,
+            end,
+            location,
+        } = &self.kind
+        {
+            hasher.update(title.as_bytes());
+            hasher.update([0]);
+            hasher.update(start.to_be_bytes());
+            hasher.update(end.unwrap_or(*start).to_be_bytes());
+            hasher.update(location.as_deref().unwrap_or_default().as_bytes());
+            hasher.update([0]);
+        }
+        if let NoticeKind::Embed { address, notice_id } = &self.kind {
+            hasher.update(address.get_topic().as_bytes());
+            hasher.update([0]);
+            hasher.update(notice_id.as_bytes());
+            hasher.update([0]);
+        }
+    }
+
+    /// Hash of everything that identifies the notice plus a nonce, used for proof of work
+    fn pow_hash(&self, nonce: u64) -> [u8; 32] {
+        let mut hasher = Sha256::new();
+        self.hash_identity(&mut hasher);
+        hasher.update(nonce.to_be_bytes());
+        hasher.finalize().into()
+    }
+
+    /// Hex SHA-256 hash identifying the notice, used to refer to it in moderation lists
+    pub fn get_hash(&self) -> String {
+        let mut hasher = Sha256::new();
+        self.hash_identity(&mut hasher);
+        hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect()
+    }
+
+    /// Copy of the notice showing the source notice from the bored at the address in its place,
+    /// as much of the source's text as fits followed by a link back to the bored. An event
+    /// stays an event. The notice is left as it is if not even the link fits.
+    pub fn embed(&self, source: &Notice, address: &BoredAddress, bored_name: &str) -> Notice {
+        let attribution = format!("[From {}]({address})", bored_name.replace(['[', ']'], ""));
+        let mut embedded = Notice {
+            attachment: source.attachment.clone(),
+            kind: match source.kind {
+                NoticeKind::Event { .. } => source.kind.clone(),
+                _ => NoticeKind::Text,
+            },
+            ..self.clone()
+        };
+        let content = source.get_content().trim_end();
+        if embedded.write(&format!("{content}\n{attribution}")).is_ok() {
+            return embedded;
+        }
+        // cut the text short where the most of it fits, with an ellipsis to show it goes on
+        let cut_text = |end: usize| format!("{}…\n{attribution}", content[..end].trim_end());
+        let ends: Vec<usize> = content.char_indices().map(|(end, _)| end).collect();
+        let fitting = ends.partition_point(|end| embedded.clone().write(&cut_text(*end)).is_ok());
+        if fitting == 0 || embedded.write(&cut_text(ends[fitting - 1])).is_err() {
+            return self.clone();
+        }
+        embedded
+    }
+
+    /// Copy of the notice with its content, attachment and event removed, leaving an empty
+    /// notice in its place
+    pub fn redacted(&self) -> Notice {
+        Notice {
+            content: String::new(),
+            attachment: None,
+            kind: NoticeKind::Text,
+            ..self.clone()
+        }
+    }
+
+    /// Whether the notice carries a proof of work with at least difficulty leading zero bits,
+    /// always true for a difficulty of 0
+    pub fn has_proof_of_work(&self, difficulty: u8) -> bool {
+        if difficulty == 0 {
+            return true;
+        }
+        leading_zero_bits(&self.pow_hash(self.pow_nonce)) >= difficulty as u32
+    }
+
+    /// Search for a nonce that gives a proof of work of difficulty leading zero bits, each
+    /// extra bit doubles how long this takes on average. Must be done after the id, position
+    /// and content are final as they are all part of what is hashed.
+    pub fn prove_work(&mut self, difficulty: u8) {
+        let mut nonce = 0;
+        while leading_zero_bits(&self.pow_hash(nonce)) < difficulty as u32 {
+            nonce += 1;
+        }
+        self.pow_nonce = nonce;
+    }
+
+    /// Remove characters that are not allowed from the content
+    pub fn strip_disallowed_chars(&mut self) {
+        self.content = strip_disallowed_chars(&self.content);
+    }
+
     /// Add textual content to the notice, will only allow as much text and lines as will fit in
+    /// and characters that are not allowed are removed
     pub fn write(&mut self, content: &str) -> Result<(), BoredError> {
+        self.write_measured(content, UnicodeWidthStr::width)
+    }
+
+    /// Write measuring how many columns a line of text takes with measure
+    fn write_measured(
+        &mut self,
+        content: &str,
+        measure: fn(&str) -> usize,
+    ) -> Result<(), BoredError> {
+        let content = &strip_disallowed_chars(content);
         let display_text = get_display(&content, get_hyperlinks(content)?).display_text;
         let display_lines = display_text.lines().count();
         let last_line = display_text.lines().last().unwrap_or_default();
@@ -294,13 +925,13 @@ impl Notice {
         } else {
             0
         } * self.get_text_width() as usize
-            + last_line.chars().count();
+            + measure(last_line);
         if used_chars > self.get_max_chars()
             || display_lines > self.get_max_lines()
             || (display_lines == self.get_max_lines()
                 && last_line.chars().last().unwrap_or_default() == '\n')
             || (display_lines == self.get_max_lines()
-                && last_line.chars().count() > self.get_text_width() as usize)
+                && measure(last_line) > self.get_text_width() as usize)
         {
             return Err(BoredError::TooMuchText);
         }
@@ -320,13 +951,24 @@ impl Notice {
     }
 }
 
+fn leading_zero_bits(hash: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in hash {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
 /// Returns a vector of all the hyperlinks in the text using markdown link notation
 pub fn get_hyperlinks(content: &str) -> Result<Vec<Hyperlink>, BoredError> {
     let re = Regex::new(r"\[(?<text>[^\]]*)\]\((?<url>[^)]*)\)")?;
     let mut results = vec![];
     for captures in re.captures_iter(&content) {
-        let text_match = captures.get(1).ok_or(BoredError::RegexError)?;
-        let url_match = captures.get(2).ok_or(BoredError::RegexError)?;
+        let text_match = captures.get(1).ok_or(BoredError::RegexError(None))?;
+        let url_match = captures.get(2).ok_or(BoredError::RegexError(None))?;
         if let Ok(hyperlink) = Hyperlink::create(
             &captures["text"],
             (text_match.start(), text_match.end()),
@@ -364,10 +1006,130 @@ pub fn get_display(content: &str, hyperlinks: Vec<Hyperlink>) -> Display {
             .push((hyperlink.text_location.0 - 1, hyperlink.text_location.1 - 1));
     }
     display.hyperlink_locations.reverse();
+    // the locations so far are byte offsets, everything laying the text out goes by character
+    let char_index = |byte_index: usize| display_text[..byte_index].chars().count();
+    display.hyperlink_locations = display
+        .hyperlink_locations
+        .iter()
+        .map(|(start, end)| (char_index(*start), char_index(*end)))
+        .collect();
     display.display_text = display_text;
     display
 }
 
+/// Fonts available for rendering banner text with [`banner`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BannerFont {
+    /// Solid block characters, best on terminals with good unicode support
+    Block,
+    /// Hash characters, safe on any terminal
+    Hash,
+}
+
+impl BannerFont {
+    fn fill_char(&self) -> char {
+        match self {
+            BannerFont::Block => '█',
+            BannerFont::Hash => '#',
+        }
+    }
+}
+
+/// Height in lines of every banner glyph
+pub const BANNER_HEIGHT: usize = 5;
+
+/// Get the five row bitmap for a character, unknown characters are shown as ?
+// Glyphs avoid [ ] ( ) so that a banner can never be mistaken for a hyperlink
+fn banner_glyph(char: char) -> [&'static str; BANNER_HEIGHT] {
+    match char.to_ascii_uppercase() {
+        'A' => [" ## ", "#  #", "####", "#  #", "#  #"],
+        'B' => ["### ", "#  #", "### ", "#  #", "### "],
+        'C' => [" ###", "#   ", "#   ", "#   ", " ###"],
+        'D' => ["### ", "#  #", "#  #", "#  #", "### "],
+        'E' => ["####", "#   ", "### ", "#   ", "####"],
+        'F' => ["####", "#   ", "### ", "#   ", "#   "],
+        'G' => [" ###", "#   ", "# ##", "#  #", " ###"],
+        'H' => ["#  #", "#  #", "####", "#  #", "#  #"],
+        'I' => ["###", " # ", " # ", " # ", "###"],
+        'J' => ["  ##", "   #", "   #", "#  #", " ## "],
+        'K' => ["#  #", "# # ", "##  ", "# # ", "#  #"],
+        'L' => ["#   ", "#   ", "#   ", "#   ", "####"],
+        'M' => ["#   #", "## ##", "# # #", "#   #", "#   #"],
+        'N' => ["#   #", "##  #", "# # #", "#  ##", "#   #"],
+        'O' => [" ## ", "#  #", "#  #", "#  #", " ## "],
+        'P' => ["### ", "#  #", "### ", "#   ", "#   "],
+        'Q' => [" ## ", "#  #", "#  #", "# ##", " ###"],
+        'R' => ["### ", "#  #", "### ", "# # ", "#  #"],
+        'S' => [" ###", "#   ", " ## ", "   #", "### "],
+        'T' => ["#####", "  #  ", "  #  ", "  #  ", "  #  "],
+        'U' => ["#  #", "#  #", "#  #", "#  #", " ## "],
+        'V' => ["#   #", "#   #", "#   #", " # # ", "  #  "],
+        'W' => ["#   #", "#   #", "# # #", "## ##", "#   #"],
+        'X' => ["#   #", " # # ", "  #  ", " # # ", "#   #"],
+        'Y' => ["#   #", " # # ", "  #  ", "  #  ", "  #  "],
+        'Z' => ["####", "   #", " ## ", "#   ", "####"],
+        '0' => [" ## ", "# ##", "## #", "#  #", " ## "],
+        '1' => [" # ", "## ", " # ", " # ", "###"],
+        '2' => ["### ", "   #", " ## ", "#   ", "####"],
+        '3' => ["### ", "   #", " ## ", "   #", "### "],
+        '4' => ["#  #", "#  #", "####", "   #", "   #"],
+        '5' => ["####", "#   ", "### ", "   #", "### "],
+        '6' => [" ## ", "#   ", "### ", "#  #", " ## "],
+        '7' => ["####", "   #", "  # ", " #  ", " #  "],
+        '8' => [" ## ", "#  #", " ## ", "#  #", " ## "],
+        '9' => [" ## ", "#  #", " ###", "   #", " ## "],
+        ' ' => ["  ", "  ", "  ", "  ", "  "],
+        '!' => ["#", "#", "#", " ", "#"],
+        '.' => [" ", " ", " ", " ", "#"],
+        ',' => ["  ", "  ", "  ", " #", "# "],
+        ':' => [" ", "#", " ", "#", " "],
+        '-' => ["   ", "   ", "###", "   ", "   "],
+        '\'' => ["#", "#", " ", " ", " "],
+        '/' => ["   #", "  # ", " #  ", "#   ", "    "],
+        _ => ["### ", "   #", " ## ", "    ", " #  "],
+    }
+}
+
+/// Render text as big FIGlet style letters, each line of the text becoming a row of letters
+/// five lines high. The result can be passed straight to Notice.write() on a notice at least
+/// as big as banner_dimensions() of the result.
+pub fn banner(text: &str, font: BannerFont) -> String {
+    let fill = font.fill_char();
+    let mut rows = vec![];
+    for text_line in text.lines() {
+        let mut banner_rows = vec![String::new(); BANNER_HEIGHT];
+        for (char_index, char) in text_line.chars().enumerate() {
+            for (row_index, glyph_row) in banner_glyph(char).iter().enumerate() {
+                // single space between letters
+                if char_index > 0 {
+                    banner_rows[row_index].push(' ');
+                }
+                banner_rows[row_index].extend(glyph_row.chars().map(|c| match c {
+                    '#' => fill,
+                    _ => ' ',
+                }));
+            }
+        }
+        rows.extend(banner_rows);
+    }
+    // pad so every row is the same width, making the banner a solid rectangle of text
+    let width = rows.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+    rows.iter()
+        .map(|row| format!("{row:width$}"))
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Dimensions of the smallest notice that can hold the banner, including its border
+pub fn banner_dimensions(banner: &str) -> Coordinate {
+    let width = banner.lines().map(|l| l.chars().count()).max().unwrap_or(0);
+    let height = banner.lines().count();
+    Coordinate {
+        x: width as u16 + 2,
+        y: height as u16 + 2,
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -479,6 +1241,10 @@ mod tests {
             Hyperlink::create("autonomi", (5, 13), "https://autonomi.com/", (15, 36)).unwrap();
         links.push(link);
         assert_eq!(hyperlinks, links);
+        assert_eq!(
+            &notice.get_content()[hyperlinks[0].get_markdown_range()],
+            "[autonomi](https://autonomi.com/)"
+        );
         let display_text = "The autonomi website";
         let display = get_display(&notice.get_content(), hyperlinks);
         assert_eq!(display.display_text, display_text);
@@ -580,6 +1346,69 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_layout_text() -> Result<(), BoredError> {
+        let at = |x, y| Some(Coordinate { x, y });
+        // the wide character that doesn't fit on the first line goes on to the next
+        assert_eq!(
+            layout_text("ab日本", 5),
+            vec![at(0, 0), at(1, 0), at(2, 0), at(0, 1)]
+        );
+        // combining marks share the position of the character they combine with
+        assert_eq!(
+            layout_text("e\u{301}x\n\u{301}", 4),
+            vec![at(0, 0), at(0, 0), at(1, 0), None, at(0, 1)]
+        );
+        // a newline after a full line doesn't add another
+        assert_eq!(
+            layout_text("日本\nx", 4),
+            vec![at(0, 0), at(2, 0), None, at(0, 1)]
+        );
+        let mut notice = Notice::create(Coordinate { x: 8, y: 4 });
+        notice.write("😀 [日本](url)")?;
+        let expected_output = r#"***00*
+00****
+"#;
+        assert_eq!(
+            expected_output,
+            format!("{}", NoticeHyperlinkMap::create(&notice)?)
+        );
+        // wide characters take up two columns of the space to write in
+        notice.write("日本語日本語")?;
+        assert_eq!(notice.write("日本語日本語日"), Err(BoredError::TooMuchText));
+        Ok(())
+    }
+
+    #[test]
+    fn test_resize() -> Result<(), BoredError> {
+        let bored = Bored::create("resize", Coordinate { x: 20, y: 10 });
+        let mut notice = Notice::create(Coordinate { x: 10, y: 4 });
+        notice.relocate(&bored, Coordinate { x: 5, y: 5 })?;
+        notice.write("We are bored")?;
+        notice.resize(&bored, Coordinate { x: 15, y: 5 })?;
+        assert_eq!(notice.get_dimensions(), Coordinate { x: 15, y: 5 });
+        assert_eq!(notice.get_top_left(), Coordinate { x: 5, y: 5 });
+        assert_eq!(
+            notice.resize(&bored, Coordinate { x: 16, y: 5 }),
+            Err(BoredError::NoticeOutOfBounds(
+                Coordinate { x: 20, y: 10 },
+                Coordinate { x: 21, y: 10 }
+            ))
+        );
+        // too small for the text
+        assert_eq!(
+            notice.resize(&bored, Coordinate { x: 6, y: 4 }),
+            Err(BoredError::TooMuchText)
+        );
+        assert_eq!(
+            notice.resize(&bored, Coordinate { x: 1, y: 4 }),
+            Err(BoredError::InvalidDimensions(Coordinate { x: 1, y: 4 }))
+        );
+        assert_eq!(notice.get_dimensions(), Coordinate { x: 15, y: 5 });
+        assert_eq!(notice.get_content(), "We are bored");
+        Ok(())
+    }
+
     #[test]
     fn test_remove_tail_link() -> Result<(), BoredError> {
         let mut notice = Notice::create(Coordinate { x: 10, y: 13 });
@@ -596,4 +1425,229 @@ mod tests {
         assert_eq!(notice.content, text);
         Ok(())
     }
+
+    #[test]
+    fn test_banner() -> Result<(), BoredError> {
+        let hi = banner("Hi", BannerFont::Hash);
+        let expected_output = "#  # ###\n#  #  # \n####  # \n#  #  # \n#  # ###";
+        assert_eq!(hi, expected_output);
+        assert_eq!(banner_dimensions(&hi), Coordinate { x: 10, y: 7 });
+        let mut notice = Notice::create(banner_dimensions(&hi));
+        notice.write(&hi)?;
+        assert_eq!(notice.get_content(), hi);
+        // two lines of text give ten rows all padded to the same width
+        let two_lines = banner("A\nBORED", BannerFont::Block);
+        assert_eq!(two_lines.lines().count(), 2 * BANNER_HEIGHT);
+        let width = two_lines.lines().next().unwrap().chars().count();
+        assert!(two_lines.lines().all(|l| l.chars().count() == width));
+        let mut notice = Notice::create(banner_dimensions(&two_lines));
+        notice.write(&two_lines)?;
+        assert!(get_hyperlinks(&banner("[x](y)", BannerFont::Hash))?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_disallowed_chars() -> Result<(), BoredError> {
+        let mut notice = Notice::create(Coordinate { x: 30, y: 5 });
+        notice.write("\x1b[31mred\x1b[0m\tand\r\n\u{202E}reversed")?;
+        assert_eq!(notice.get_content(), "[31mred[0m and\nreversed");
+        assert_eq!(notice.validate(), Ok(()));
+
+        let mut hostile = serde_json::to_value(&notice)?;
+        hostile["content"] = serde_json::json!("\x1b]0;title\x07");
+        let mut hostile: Notice = serde_json::from_value(hostile)?;
+        assert_eq!(hostile.validate(), Err(BoredError::DisallowedCharacters));
+        hostile.strip_disallowed_chars();
+        assert_eq!(hostile.get_content(), "]0;title");
+        assert_eq!(hostile.validate(), Ok(()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_attachment() -> Result<(), BoredError> {
+        let attachment = Attachment::create(b"hello", "greeting.TXT")?;
+        assert_eq!(
+            attachment.get_address(),
+            "sha256:2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_eq!(attachment.get_size(), 5);
+        assert_eq!(attachment.get_mime(), Some("text/plain"));
+        assert_eq!(
+            Attachment::create(b"", "bad\nname"),
+            Err(BoredError::InvalidAttachment("bad\nname".to_string()))
+        );
+
+        for (filename, safe) in [
+            ("../../.bashrc", "bashrc"),
+            ("C:\\Windows\\evil.exe", "evil.exe"),
+            ("..", "attachment-2cf24dba"),
+            ("what?.txt", "what_.txt"),
+        ] {
+            let mut named = attachment.clone();
+            named.filename = filename.to_string();
+            assert_eq!(named.safe_filename(), safe);
+        }
+
+        let mut notice = Notice::create(Coordinate { x: 10, y: 5 });
+        let hash = notice.get_hash();
+        notice.set_attachment(Some(attachment.clone()))?;
+        assert_ne!(notice.get_hash(), hash);
+        assert_eq!(notice.redacted().get_attachment(), None);
+
+        let mut forged = attachment;
+        forged.address = "sha256:../../etc/passwd".to_string();
+        assert_eq!(
+            notice.set_attachment(Some(forged.clone())),
+            Err(BoredError::InvalidAttachment(forged.address.clone()))
+        );
+        notice.attachment = Some(forged);
+        assert!(notice.validate().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_event() -> Result<(), BoredError> {
+        let start = parse_event_time("2026-06-12 19:00", &chrono::Utc)?;
+        assert_eq!(start, 1781290800000);
+        assert_eq!(format_event_time(start, &chrono::Utc), "2026-06-12 19:00");
+        assert!(parse_event_time("next tuesday", &chrono::Utc).is_err());
+        let event = |end, location: Option<&str>| NoticeKind::Event {
+            title: "Jumble sale".to_string(),
+            start,
+            end,
+            location: location.map(str::to_string),
+        };
+        let evening = event(Some(start + 2 * 60 * 60 * 1000), Some("The village hall"));
+        assert_eq!(
+            evening.describe_event(&chrono::Utc).as_deref(),
+            Some("2026-06-12 19:00 to 21:00 at The village hall")
+        );
+        let overnight = event(Some(start + 24 * 60 * 60 * 1000), None);
+        assert_eq!(
+            overnight.describe_event(&chrono::Utc).as_deref(),
+            Some("2026-06-12 19:00 to 2026-06-13 19:00")
+        );
+        assert_eq!(NoticeKind::Text.describe_event(&chrono::Utc), None);
+
+        let mut notice = Notice::create(Coordinate { x: 10, y: 5 });
+        let hash = notice.get_hash();
+        notice.set_kind(evening.clone())?;
+        assert_ne!(notice.get_hash(), hash);
+        let json = serde_json::to_string(&notice)?;
+        assert!(json.contains(r#""kind":{"type":"Event","title":"Jumble sale""#));
+        assert_eq!(serde_json::from_str::<Notice>(&json)?, notice);
+        assert!(notice.redacted().get_kind().is_text());
+        // text notices are written as they were before there were kinds
+        assert!(!serde_json::to_string(&notice.redacted())?.contains("kind"));
+
+        assert!(notice.set_kind(event(Some(start - 1), None)).is_err());
+        assert!(notice.set_kind(event(None, Some("two\nlines"))).is_err());
+        let untitled = NoticeKind::Event {
+            title: " ".to_string(),
+            start,
+            end: None,
+            location: None,
+        };
+        assert!(notice.set_kind(untitled).is_err());
+        assert_eq!(notice.get_kind(), &evening);
+        Ok(())
+    }
+
+    #[test]
+    fn test_embed() -> Result<(), BoredError> {
+        let address = BoredAddress::from_string("bored://news")?;
+        let mut source = Notice::create(Coordinate { x: 30, y: 6 });
+        source.write("Fair on Saturday\nBring cakes\nAnd bunting")?;
+        let mut mirror = Notice::create(Coordinate { x: 24, y: 4 });
+        let hash = mirror.get_hash();
+        let kind = NoticeKind::Embed {
+            address: address.clone(),
+            notice_id: "notice:1779796800000:ab12".to_string(),
+        };
+        mirror.set_kind(kind.clone())?;
+        assert_ne!(mirror.get_hash(), hash);
+        let json = serde_json::to_string(&mirror)?;
+        assert_eq!(serde_json::from_str::<Notice>(&json)?, mirror);
+        // too long to fit with the link back so it is cut short
+        let embedded = mirror.embed(&source, &address, "The [news]");
+        assert_eq!(
+            embedded.get_content(),
+            "Fair on Saturday…\n[From The news](bored://news)"
+        );
+        assert!(embedded.get_kind().is_text());
+        let tiny = Notice::create(Coordinate { x: 6, y: 3 });
+        assert_eq!(tiny.embed(&source, &address, "News"), tiny);
+
+        for notice_id in [
+            "",
+            "notice:1:two words",
+            &"a".repeat(MAX_NOTICE_ID_LENGTH + 1),
+        ] {
+            let kind = NoticeKind::Embed {
+                address: address.clone(),
+                notice_id: notice_id.to_string(),
+            };
+            assert!(mirror.set_kind(kind).is_err());
+        }
+        assert_eq!(mirror.get_kind(), &kind);
+        Ok(())
+    }
+
+    #[test]
+    fn test_proof_of_work() -> Result<(), BoredError> {
+        let mut notice = Notice::create(Coordinate { x: 10, y: 5 });
+        notice.set_notice_id("notice:1:abcdefgh".to_string());
+        notice.write("work")?;
+        assert!(notice.has_proof_of_work(0));
+        notice.prove_work(8);
+        assert!(notice.has_proof_of_work(8));
+        assert_eq!(leading_zero_bits(&[0, 0b0001_0000, 0]), 11);
+        // changing anything hashed changes the hash the proof was for
+        let proven = notice.clone();
+        notice.write("play")?;
+        assert_ne!(
+            notice.pow_hash(proven.pow_nonce),
+            proven.pow_hash(proven.pow_nonce)
+        );
+        let mut bored = Bored::create("pow", Coordinate { x: 20, y: 20 });
+        bored.set_pow_difficulty(8)?;
+        let mut unproven = proven.clone();
+        while unproven.has_proof_of_work(8) {
+            unproven.pow_nonce += 1;
+        }
+        assert_eq!(
+            bored.add(unproven, Coordinate { x: 0, y: 0 }),
+            Err(BoredError::InsufficientProofOfWork(8))
+        );
+        bored.add(proven, Coordinate { x: 0, y: 0 })?;
+        assert_eq!(
+            bored.set_pow_difficulty(MAX_POW_DIFFICULTY + 1),
+            Err(BoredError::InvalidPowDifficulty(MAX_POW_DIFFICULTY + 1))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_timestamp() {
+        let mut notice = Notice::new();
+        assert_eq!(notice.get_timestamp(), None);
+        notice.set_notice_id("notice:1779796800000:abc123ef".to_string());
+        assert_eq!(notice.get_timestamp(), Some(1779796800000));
+        notice.set_notice_id("note:1779796800000:abc123ef".to_string());
+        assert_eq!(notice.get_timestamp(), None);
+        notice.set_notice_id("notice:soon:abc123ef".to_string());
+        assert_eq!(notice.get_timestamp(), None);
+    }
+
+    #[test]
+    fn test_get_author() {
+        let mut notice = Notice::new();
+        assert_eq!(notice.get_author(), None);
+        notice.set_notice_id("notice:1779796800000:abc123ef".to_string());
+        assert_eq!(notice.get_author(), Some("abc123ef"));
+        notice.set_notice_id("note:1779796800000:abc123ef".to_string());
+        assert_eq!(notice.get_author(), None);
+        notice.set_notice_id("notice:1779796800000:<script>".to_string());
+        assert_eq!(notice.get_author(), None);
+    }
 }
@@ -0,0 +1,129 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+//! Ready made notices to start a draft from, each with the text to fill in and a size it fits
+
+use crate::notice::Notice;
+use crate::{BoredError, Coordinate};
+
+/// A notice to start writing from
+#[derive(Debug, PartialEq)]
+pub struct NoticeTemplate {
+    pub name: &'static str,
+    /// What the template is for, shown when picking one
+    pub description: &'static str,
+    pub dimensions: Coordinate,
+    /// Text of the notice with hyperlinks written as they are typed, [text](url)
+    pub content: &'static str,
+}
+
+impl NoticeTemplate {
+    /// A notice of the template's size with its text written in
+    pub fn create_notice(&self) -> Result<Notice, BoredError> {
+        let mut notice = Notice::create(self.dimensions);
+        notice.write(self.content)?;
+        Ok(notice)
+    }
+}
+
+/// The templates there are to pick from
+pub const TEMPLATES: &[NoticeTemplate] = &[
+    NoticeTemplate {
+        name: "Event",
+        description: "Announce an event, when and where it is",
+        dimensions: Coordinate { x: 40, y: 9 },
+        content: "EVENT: What is happening\n\
+                  When: Saturday at 7pm\n\
+                  Where: The village hall\n\
+                  Bring: Yourself and a friend\n\
+                  \n\
+                  All welcome!\n\
+                  [More details](https://example.com)",
+    },
+    NoticeTemplate {
+        name: "Link list",
+        description: "A list of places worth going",
+        dimensions: Coordinate { x: 36, y: 7 },
+        content: "Links worth a look\n\
+                  - [Welcome](bored://welcome)\n\
+                  - [A website](https://example.com)\n\
+                  - [Another bored](bored://games)",
+    },
+    NoticeTemplate {
+        name: "Frame",
+        description: "Words in a frame drawn with ascii characters",
+        dimensions: Coordinate { x: 30, y: 7 },
+        content: "+--------------------------+\n\
+                  |                          |\n\
+                  |    Your words go here    |\n\
+                  |                          |\n\
+                  +--------------------------+",
+    },
+    NoticeTemplate {
+        name: "Poll",
+        description: "A question with choices to mark, for whoever reads it to vote on",
+        dimensions: Coordinate { x: 36, y: 8 },
+        content: "POLL: What should we ask?\n\
+                  Put an x in a box to vote\n\
+                  \n\
+                  [ ] The first choice\n\
+                  [ ] The second choice\n\
+                  [ ] The third choice",
+    },
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_templates_fit() -> Result<(), BoredError> {
+        for template in TEMPLATES {
+            let notice = template.create_notice()?;
+            assert_eq!(notice.get_content(), template.content);
+            assert_eq!(notice.get_dimensions(), template.dimensions);
+            // lines are laid out as written, none are wrapped
+            let display_text = notice.get_display()?.get_display_text();
+            assert!(display_text.lines().count() <= notice.get_text_height() as usize);
+            assert!(
+                display_text
+                    .lines()
+                    .all(|line| line.chars().count() <= notice.get_text_width() as usize)
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_template_hyperlinks() -> Result<(), BoredError> {
+        let link_list = TEMPLATES
+            .iter()
+            .find(|template| template.name == "Link list")
+            .expect("link list template");
+        let display = link_list.create_notice()?.get_display()?;
+        assert_eq!(display.get_hyperlink_locations().len(), 3);
+        let poll = TEMPLATES
+            .iter()
+            .find(|template| template.name == "Poll")
+            .expect("poll template");
+        // the boxes to mark aren't links
+        let display = poll.create_notice()?.get_display()?;
+        assert!(display.get_hyperlink_locations().is_empty());
+        assert!(display.get_display_text().contains("[ ] The first choice"));
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 use crate::BoredError;
 use std::fmt::{self};
 
+/// Prefix of every x0x topic used for a bored, marking gossip as bored traffic
+pub const TOPIC_PREFIX: &str = "bored.";
+
 /// The address of a bored, now represented as an x0x store topic.
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
 pub enum BoredAddress {
@@ -21,7 +24,7 @@ impl BoredAddress {
     /// Generates a new random BoredAddress (Topic)
     pub fn new() -> BoredAddress {
         let id = uuid::Uuid::new_v4().to_string();
-        BoredAddress::Topic(format!("bored.{}", id))
+        BoredAddress::Topic(format!("{}{}", TOPIC_PREFIX, id))
     }
 
     /// Tries to create bored URL from string
@@ -37,7 +40,7 @@ impl BoredAddress {
             return Err(BoredError::NotBoredURL(s.to_string()));
         }
 
-        if s.starts_with("bored.") {
+        if s.starts_with(TOPIC_PREFIX) {
             return Ok(BoredAddress::Topic(s.to_string()));
         }
 
@@ -48,15 +51,174 @@ impl BoredAddress {
     pub fn get_topic(&self) -> String {
         match &self {
             BoredAddress::Topic(topic) => topic.clone(),
-            BoredAddress::DerivedName(name) => format!("bored.{}", name),
+            BoredAddress::DerivedName(name) => format!("{}{}", TOPIC_PREFIX, name),
+        }
+    }
+
+    /// The address in the bored://name form where the topic was derived from a name, so
+    /// bored://bored.cats shows as bored://cats. Names are the topic after the prefix so no
+    /// lookup is needed, only the random topics made by new have no name to show.
+    pub fn to_derived_name(&self) -> BoredAddress {
+        match self {
+            BoredAddress::Topic(topic) => match topic.strip_prefix(TOPIC_PREFIX) {
+                // a name starting with the prefix would be read back as a topic
+                Some(name)
+                    if !name.is_empty()
+                        && !name.starts_with(TOPIC_PREFIX)
+                        && uuid::Uuid::parse_str(name).is_err() =>
+                {
+                    BoredAddress::DerivedName(name.to_string())
+                }
+                _ => self.clone(),
+            },
+            BoredAddress::DerivedName(_) => self.clone(),
+        }
+    }
+
+    /// Address of the bored an x0x topic belongs to, topics without the bored prefix are not
+    /// bored traffic so are rejected
+    pub fn from_topic(topic: &str) -> Result<Self, BoredError> {
+        match topic.strip_prefix(TOPIC_PREFIX) {
+            Some(name) if !name.is_empty() => Ok(BoredAddress::Topic(topic.to_string())),
+            _ => Err(BoredError::NotBoredURL(topic.to_string())),
+        }
+    }
+}
+
+/// Url names close to the one given, to offer when it is taken, in the order to offer them.
+/// The name is numbered or has words added to it, the parts after any full stop are kept.
+pub fn suggest_url_names(url_name: &str) -> Vec<String> {
+    let url_name = url_name.trim();
+    let url_name = url_name.strip_prefix("bored://").unwrap_or(url_name);
+    if url_name.is_empty() {
+        return vec![];
+    }
+    let (first, rest) = match url_name.split_once('.') {
+        Some((first, rest)) => (first, format!(".{rest}")),
+        None => (url_name, String::new()),
+    };
+    let mut suggestions: Vec<String> = (2..=4).map(|n| format!("{first}-{n}{rest}")).collect();
+    if !first.starts_with("the-") {
+        suggestions.push(format!("the-{first}{rest}"));
+    }
+    suggestions.push(format!("{first}-bored{rest}"));
+    suggestions
+}
+
+/// What the holder of a share link is allowed to do with a bored
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Capability {
+    ReadOnly,
+    ReadWrite,
+}
+
+impl Capability {
+    fn scheme(&self) -> &'static str {
+        match self {
+            Capability::ReadOnly => "bored+ro://",
+            Capability::ReadWrite => "bored+rw://",
         }
     }
 }
 
+/// A link to a bored that says what its holder may do with it and optionally until when, e.g.
+/// bored+ro://bored.genesis?expires=1767225600 for read only access until the start of 2026.
+/// x0x topics have no access control so the capability is honoured by clients rather than
+/// enforced by the network.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ShareLink {
+    address: BoredAddress,
+    capability: Capability,
+    /// Unix time in seconds after which the link no longer grants anything
+    expires: Option<u64>,
+}
+
+impl fmt::Display for ShareLink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let address = self.address.to_string();
+        let address = address.strip_prefix("bored://").unwrap_or(&address);
+        write!(f, "{}{}", self.capability.scheme(), address)?;
+        if let Some(expires) = self.expires {
+            write!(f, "?expires={}", expires)?;
+        }
+        Ok(())
+    }
+}
+
+impl ShareLink {
+    pub fn create(address: BoredAddress, capability: Capability, expires: Option<u64>) -> Self {
+        ShareLink {
+            address,
+            capability,
+            expires,
+        }
+    }
+
+    /// Tries to create a share link from a string, plain bored:// addresses are read write
+    /// links that never expire
+    pub fn from_string(s: &str) -> Result<Self, BoredError> {
+        let s = s.trim();
+        let (capability, rest) = if let Some(rest) = s.strip_prefix(Capability::ReadOnly.scheme())
+        {
+            (Capability::ReadOnly, rest)
+        } else if let Some(rest) = s.strip_prefix(Capability::ReadWrite.scheme()) {
+            (Capability::ReadWrite, rest)
+        } else {
+            return Ok(ShareLink::create(
+                BoredAddress::from_string(s)?,
+                Capability::ReadWrite,
+                None,
+            ));
+        };
+        let (address, expires) = match rest.split_once('?') {
+            Some((address, query)) => {
+                let expires = query
+                    .strip_prefix("expires=")
+                    .and_then(|expires| expires.parse::<u64>().ok())
+                    .ok_or_else(|| BoredError::NotBoredURL(s.to_string()))?;
+                (address, Some(expires))
+            }
+            None => (rest, None),
+        };
+        // an empty address would otherwise be accepted by BoredAddress with a bored:// prefix
+        if address.is_empty() || address.contains(['?', '/']) {
+            return Err(BoredError::NotBoredURL(s.to_string()));
+        }
+        Ok(ShareLink::create(
+            BoredAddress::from_string(address)?,
+            capability,
+            expires,
+        ))
+    }
+
+    pub fn get_address(&self) -> &BoredAddress {
+        &self.address
+    }
+
+    pub fn get_capability(&self) -> Capability {
+        self.capability
+    }
+
+    pub fn get_expires(&self) -> Option<u64> {
+        self.expires
+    }
+
+    /// Whether the link has expired at the given unix time in seconds
+    pub fn is_expired(&self, now: u64) -> bool {
+        self.expires.is_some_and(|expires| now >= expires)
+    }
+
+    /// Whether the link lets its holder change the bored at the given unix time in seconds
+    pub fn can_write(&self, now: u64) -> bool {
+        self.capability == Capability::ReadWrite && !self.is_expired(now)
+    }
+}
+
 /// A parsed URL that can be handled by a client application
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum URL {
     BoredNet(BoredAddress),
+    BoredShare(ShareLink),
     BoredApp(String),
     ClearNet(String),
 }
@@ -64,19 +226,32 @@ pub enum URL {
 impl URL {
     pub fn from_string(s: String) -> Result<Self, BoredError> {
         let s = s.trim();
-        if s.len() > 7 {
-            if &s[0..8] == "https://" || &s[0..7] == "http://" {
-                return Ok(URL::ClearNet(s.to_string()));
-            } else if &s[0..6] == "app://" {
-                return Ok(URL::BoredApp(s[6..].to_string()));
-            } else if let Ok(bored_address) = BoredAddress::from_string(s) {
-                return Ok(URL::BoredNet(bored_address));
-            }
+        if s.starts_with(Capability::ReadOnly.scheme()) || s.starts_with(Capability::ReadWrite.scheme())
+        {
+            return Ok(URL::BoredShare(ShareLink::from_string(s)?));
+        }
+        if s.starts_with("https://") || s.starts_with("http://") {
+            return Ok(URL::ClearNet(s.to_string()));
+        } else if let Some(command) = s.strip_prefix("app://") {
+            return Ok(URL::BoredApp(command.to_string()));
         } else if let Ok(bored_address) = BoredAddress::from_string(s) {
             return Ok(URL::BoredNet(bored_address));
         }
         Err(BoredError::UnknownURLType(s.to_string()))
     }
+
+    /// The scheme of the url, to tell the user what following it will do
+    pub fn get_scheme(&self) -> &'static str {
+        match self {
+            URL::BoredNet(_) => "bored",
+            URL::BoredShare(share_link) => {
+                share_link.get_capability().scheme().trim_end_matches("://")
+            }
+            URL::BoredApp(_) => "app",
+            URL::ClearNet(url) if url.starts_with("https://") => "https",
+            URL::ClearNet(_) => "http",
+        }
+    }
 }
 
 #[cfg(test)]
@@ -110,6 +285,92 @@ mod tests {
         assert_eq!(bored_address.get_topic(), "bored.genesis");
     }
 
+    #[test]
+    fn test_suggest_url_names() {
+        assert_eq!(
+            suggest_url_names("bored://cats"),
+            vec!["cats-2", "cats-3", "cats-4", "the-cats", "cats-bored"]
+        );
+        assert_eq!(
+            suggest_url_names("the-cats.pets"),
+            vec![
+                "the-cats-2.pets",
+                "the-cats-3.pets",
+                "the-cats-4.pets",
+                "the-cats-bored.pets"
+            ]
+        );
+        assert!(suggest_url_names(" ").is_empty());
+    }
+
+    #[test]
+    fn test_to_derived_name() {
+        let address = BoredAddress::from_string("bored://bored.cats.pets").unwrap();
+        let derived = address.to_derived_name();
+        assert_eq!(derived.to_string(), "bored://cats.pets");
+        assert_eq!(derived.get_topic(), address.get_topic());
+        assert_eq!(
+            BoredAddress::from_string(&derived.to_string()).unwrap(),
+            derived
+        );
+        // random topics and names that would read back as topics have no name to show
+        let random = BoredAddress::new();
+        assert_eq!(random.to_derived_name(), random);
+        let nested = BoredAddress::Topic("bored.bored.cats".to_string());
+        assert_eq!(nested.to_derived_name(), nested);
+    }
+
+    #[test]
+    fn test_bored_address_from_topic() {
+        let bored_address = BoredAddress::from_topic("bored.genesis").unwrap();
+        assert_eq!(bored_address.get_topic(), "bored.genesis");
+        assert_eq!(
+            BoredAddress::from_topic("chat.genesis"),
+            Err(BoredError::NotBoredURL("chat.genesis".to_string()))
+        );
+        assert_eq!(
+            BoredAddress::from_topic("bored."),
+            Err(BoredError::NotBoredURL("bored.".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_share_link_from_string() {
+        let link = ShareLink::from_string("bored+ro://bored.genesis?expires=1767225600").unwrap();
+        assert_eq!(link.get_address().get_topic(), "bored.genesis");
+        assert_eq!(link.get_capability(), Capability::ReadOnly);
+        assert_eq!(link.get_expires(), Some(1767225600));
+        assert_eq!(
+            link.to_string(),
+            "bored+ro://bored.genesis?expires=1767225600"
+        );
+        assert!(!link.is_expired(1767225599));
+        assert!(link.is_expired(1767225600));
+        assert!(!link.can_write(0));
+
+        let link = ShareLink::from_string("bored+rw://genesis").unwrap();
+        assert_eq!(link.get_capability(), Capability::ReadWrite);
+        assert_eq!(link.get_expires(), None);
+        assert!(link.can_write(u64::MAX));
+        assert_eq!(link.to_string(), "bored+rw://genesis");
+
+        // plain addresses grant everything
+        let link = ShareLink::from_string("bored://genesis").unwrap();
+        assert_eq!(link.get_capability(), Capability::ReadWrite);
+
+        for invalid in [
+            "bored+ro://",
+            "bored+ro://genesis?expires=soon",
+            "bored+ro://genesis?colour=red",
+            "bored+ro://bored://genesis",
+        ] {
+            assert_eq!(
+                ShareLink::from_string(invalid),
+                Err(BoredError::NotBoredURL(invalid.to_string()))
+            );
+        }
+    }
+
     #[test]
     fn test_url_from_string() {
         let url = URL::from_string("bored://bored.test-uuid".to_string()).unwrap();
@@ -118,6 +379,16 @@ mod tests {
             URL::BoredNet(BoredAddress::Topic("bored.test-uuid".to_string()))
         );
 
+        let url = URL::from_string("bored+ro://genesis".to_string()).unwrap();
+        assert_eq!(
+            url,
+            URL::BoredShare(ShareLink::create(
+                BoredAddress::DerivedName("genesis".to_string()),
+                Capability::ReadOnly,
+                None
+            ))
+        );
+
         let url = URL::from_string("app://about".to_string()).unwrap();
         assert_eq!(url, URL::BoredApp("about".to_string()));
 
@@ -126,5 +397,30 @@ mod tests {
 
         let url_result = URL::from_string("".to_string());
         assert_eq!(url_result, Err(BoredError::UnknownURLType("".to_string())));
+
+        let url = URL::from_string("app://é".to_string()).unwrap();
+        assert_eq!(url, URL::BoredApp("é".to_string()));
+        let url = URL::from_string("htéééé".to_string()).unwrap();
+        assert_eq!(
+            url,
+            URL::BoredNet(BoredAddress::from_string("htéééé").unwrap())
+        );
+    }
+
+    #[test]
+    fn test_url_scheme() {
+        for (url, scheme) in [
+            ("bored://genesis", "bored"),
+            ("genesis", "bored"),
+            ("bored+ro://genesis", "bored+ro"),
+            ("app://about", "app"),
+            ("https://autonomi.com", "https"),
+            ("http://autonomi.com", "http"),
+        ] {
+            assert_eq!(
+                URL::from_string(url.to_string()).unwrap().get_scheme(),
+                scheme
+            );
+        }
     }
 }
@@ -0,0 +1,143 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::Bored;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Most readers remembered for each notice, so made up readers can't fill the disk
+pub const MAX_READERS_PER_NOTICE: usize = 10_000;
+
+/// Length of a reader id in hex characters
+pub const READER_ID_LENGTH: usize = 16;
+
+/// How many readers opened a notice
+#[derive(Clone, Debug, PartialEq)]
+pub struct NoticeStats {
+    pub notice_id: String,
+    pub views: usize,
+}
+
+/// The readers that have opened each notice on a bored, by reader ids derived from each notice
+/// id so the same reader can't be followed from one notice to the next
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct NoticeViews {
+    readers: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl NoticeViews {
+    /// Count the reader as having opened the notice, returning whether they hadn't already.
+    /// Readers whose id isn't READER_ID_LENGTH hex characters aren't counted.
+    pub fn add(&mut self, notice_id: &str, reader_id: &str) -> bool {
+        if reader_id.len() != READER_ID_LENGTH
+            || !reader_id.chars().all(|char| char.is_ascii_hexdigit())
+        {
+            return false;
+        }
+        let readers = self.readers.entry(notice_id.to_string()).or_default();
+        readers.len() < MAX_READERS_PER_NOTICE && readers.insert(reader_id.to_string())
+    }
+
+    pub fn get_views(&self, notice_id: &str) -> usize {
+        self.readers.get(notice_id).map_or(0, BTreeSet::len)
+    }
+
+    /// How many readers opened each notice on the bored, in the order of its notices
+    pub fn get_stats(&self, bored: &Bored) -> Vec<NoticeStats> {
+        bored
+            .notices()
+            .iter()
+            .map(|notice| NoticeStats {
+                notice_id: notice.get_notice_id().to_string(),
+                views: self.get_views(notice.get_notice_id()),
+            })
+            .collect()
+    }
+
+    /// Forget the readers of notices that are no longer on the bored
+    pub fn retain_notices(&mut self, bored: &Bored) {
+        self.readers.retain(|notice_id, _| {
+            bored
+                .notices()
+                .iter()
+                .any(|notice| notice.get_notice_id() == notice_id)
+        });
+    }
+}
+
+/// The id a reader is counted with when they open a notice, a hash of who they are and the
+/// notice id so it is the same each time they open that notice and says nothing about them
+pub fn get_reader_id(agent_id: &str, notice_id: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(agent_id.as_bytes());
+    hasher.update([0]);
+    hasher.update(notice_id.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .take(READER_ID_LENGTH / 2)
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::notice::Notice;
+    use crate::{BoredError, Coordinate};
+
+    #[test]
+    fn test_notice_views() -> Result<(), BoredError> {
+        let mut bored = Bored::create("Views", Coordinate { x: 40, y: 10 });
+        for notice_id in ["notice:1:aaaa", "notice:2:bbbb"] {
+            let mut notice = Notice::create(Coordinate { x: 10, y: 4 });
+            notice.set_notice_id(notice_id.to_string());
+            bored.append_in_reading_order(notice)?;
+        }
+        let reader = get_reader_id("agent", "notice:1:aaaa");
+        assert_eq!(reader.len(), READER_ID_LENGTH);
+        // the same reader has a different id for each notice
+        assert_ne!(reader, get_reader_id("agent", "notice:2:bbbb"));
+        assert_ne!(reader, get_reader_id("other agent", "notice:1:aaaa"));
+
+        let mut views = NoticeViews::default();
+        assert!(views.add("notice:1:aaaa", &reader));
+        assert!(!views.add("notice:1:aaaa", &reader));
+        let other_reader = get_reader_id("other agent", "notice:1:aaaa");
+        assert!(views.add("notice:1:aaaa", &other_reader));
+        assert!(!views.add("notice:1:aaaa", "not a reader"));
+        assert!(views.add("notice:9:gone", &reader));
+        assert_eq!(
+            views.get_stats(&bored),
+            vec![
+                NoticeStats {
+                    notice_id: "notice:1:aaaa".to_string(),
+                    views: 2
+                },
+                NoticeStats {
+                    notice_id: "notice:2:bbbb".to_string(),
+                    views: 0
+                },
+            ]
+        );
+        views.retain_notices(&bored);
+        assert_eq!(views.get_views("notice:9:gone"), 0);
+        assert_eq!(views.get_views("notice:1:aaaa"), 2);
+        Ok(())
+    }
+}
@@ -15,9 +15,17 @@ You should have received a copy of the GNU Affero General Public License
 along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
-use crate::notice::Notice;
-use crate::url::BoredAddress;
-use crate::{Bored, BoredError, Coordinate};
+use crate::crypto::{EncryptedBored, ReaderKey, ReaderPublicKey};
+use crate::notice::{
+    ATTACHMENT_ADDRESS_PREFIX, Attachment, MAX_ATTACHMENT_SIZE, Notice, NoticeKind,
+    attachment_address_from_reader,
+};
+use crate::url::{BoredAddress, Capability, ShareLink};
+use crate::views::{NoticeStats, NoticeViews, get_reader_id};
+use crate::{
+    Background, Bored, BoredError, Coordinate, ErrorSource, MAX_BORED_DIMENSIONS, MAX_BORED_SIZE,
+    Palette, WhatsOnTheBored, is_zero,
+};
 
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 #[serde(tag = "type")]
@@ -26,6 +34,8 @@ enum GossipMsg {
     Meta {
         name: String,
         dimensions: Coordinate,
+        #[serde(flatten)]
+        settings: GossipSettings,
     },
     #[serde(rename = "notice")]
     NoticeMsg {
@@ -38,13 +48,260 @@ enum GossipMsg {
         name: String,
         dimensions: Coordinate,
         notices: Vec<Notice>,
+        #[serde(flatten)]
+        settings: GossipSettings,
     },
+    /// Ask any peer that has an attached file to send it
+    #[serde(rename = "file-request")]
+    FileRequest {
+        address: String,
+        /// Indices of the chunks wanted, all of them when empty
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        chunks: Vec<u32>,
+    },
+    /// One piece of an attached file, base64 encoded
+    #[serde(rename = "file-chunk")]
+    FileChunk {
+        address: String,
+        index: u32,
+        total: u32,
+        data: String,
+    },
+    /// A reader that counts views opened a notice, by an id derived from the notice id
+    #[serde(rename = "view")]
+    View { notice_id: String, reader: String },
+    /// The whole of a bored sealed for its readers, sent in place of its meta, notice and sync
+    /// response messages. Replies to sync requests say so as their settings may be stale.
+    #[serde(rename = "encrypted")]
+    Encrypted {
+        bored: EncryptedBored,
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        sync: bool,
+    },
+}
+
+impl GossipMsg {
+    /// Meta message announcing the settings of a bored
+    fn meta(bored: &Bored) -> GossipMsg {
+        GossipMsg::Meta {
+            name: bored.get_name().to_string(),
+            dimensions: bored.get_dimensions(),
+            settings: GossipSettings::of(bored),
+        }
+    }
+
+    /// Sync response carrying the whole of a bored
+    fn sync_response(bored: &Bored) -> GossipMsg {
+        GossipMsg::SyncResponse {
+            name: bored.get_name().to_string(),
+            dimensions: bored.get_dimensions(),
+            notices: bored.get_notices(),
+            settings: GossipSettings::of(bored),
+        }
+    }
+
+    /// The message as it is published for the bored, sealed for its readers if it has any
+    fn for_readers(self, bored: &Bored) -> Result<GossipMsg, BoredError> {
+        let readers = bored.get_readers();
+        if readers.is_empty() {
+            return Ok(self);
+        }
+        Ok(GossipMsg::Encrypted {
+            bored: EncryptedBored::seal(bored, &readers)?,
+            sync: matches!(self, GossipMsg::SyncResponse { .. }),
+        })
+    }
+
+    #[cfg(feature = "tracing")]
+    fn kind(&self) -> &'static str {
+        match self {
+            GossipMsg::Meta { .. } => "meta",
+            GossipMsg::NoticeMsg { .. } => "notice",
+            GossipMsg::SyncRequest => "sync-request",
+            GossipMsg::SyncResponse { .. } => "sync-response",
+            GossipMsg::FileRequest { .. } => "file-request",
+            GossipMsg::FileChunk { .. } => "file-chunk",
+            GossipMsg::View { .. } => "view",
+            GossipMsg::Encrypted { .. } => "encrypted",
+        }
+    }
+}
+
+/// Settings of a bored as they are gossiped. Any a message leaves out, as peers that don't know
+/// of them do, are kept as they are. Anyone with the address can send them, so settings changed
+/// before those already known are ignored.
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct GossipSettings {
+    /// Milliseconds since the unix epoch the settings were changed, 0 if not known
+    #[serde(default, skip_serializing_if = "is_zero")]
+    settings_changed_at: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    background: Option<Background>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    palette: Option<Palette>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pow_difficulty: Option<u8>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pow_changed_at: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    hidden_notices: Option<Vec<String>>,
+    /// Null when the bored hasn't moved, told apart from being left out
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_present"
+    )]
+    moved_to: Option<Option<String>>,
+}
+
+impl GossipSettings {
+    /// All the settings of the bored
+    fn of(bored: &Bored) -> GossipSettings {
+        GossipSettings {
+            settings_changed_at: bored.settings_changed_at,
+            background: Some(bored.get_background().clone()),
+            palette: Some(bored.get_palette().clone()),
+            pow_difficulty: Some(bored.get_pow_difficulty()),
+            pow_changed_at: bored.get_pow_changed_at(),
+            hidden_notices: Some(bored.get_hidden_notices().clone()),
+            moved_to: Some(bored.moved_to.clone()),
+        }
+    }
+
+    /// When the settings were changed, but no later than now so a peer can't stop them ever
+    /// changing again
+    fn changed_at(&self) -> i64 {
+        self.settings_changed_at
+            .min(chrono::Utc::now().timestamp_millis())
+    }
+
+    /// Whether the settings were changed after those of the bored
+    fn is_newer_than(&self, bored: &Bored) -> bool {
+        self.changed_at() > bored.settings_changed_at
+    }
+
+    /// Whether the settings were changed before those of the bored, so are stale
+    fn is_older_than(&self, bored: &Bored) -> bool {
+        self.changed_at() < bored.settings_changed_at
+    }
+
+    /// Change the settings of the bored to those sent, returning whether any changed
+    fn apply(self, bored: &mut Bored) -> Result<bool, BoredError> {
+        let changed_at = self.changed_at();
+        let mut changed = false;
+        if let Some(background) = self.background
+            && bored.background != background
+        {
+            bored.background = background;
+            changed = true;
+        }
+        if let Some(palette) = self.palette
+            && bored.palette != palette
+        {
+            bored.palette = palette;
+            changed = true;
+        }
+        if let Some(pow_difficulty) = self.pow_difficulty
+            && bored.pow_difficulty != pow_difficulty
+        {
+            bored.set_pow_difficulty(pow_difficulty)?;
+            // when the peer changed it, but no later than now
+            bored.pow_changed_at = bored.pow_changed_at.min(self.pow_changed_at);
+            changed = true;
+        }
+        if let Some(hidden_notices) = self.hidden_notices
+            && bored.hidden_notices != hidden_notices
+        {
+            bored.set_hidden_notices(hidden_notices)?;
+            changed = true;
+        }
+        if let Some(moved_to) = self.moved_to
+            && bored.moved_to != moved_to
+        {
+            bored.moved_to = moved_to;
+            changed = true;
+        }
+        if changed_at > bored.settings_changed_at {
+            bored.settings_changed_at = changed_at;
+            changed = true;
+        }
+        Ok(changed)
+    }
+}
+
+/// Some of a field that is there, even when it is null, so it can be told apart from one that
+/// is left out
+fn deserialize_present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: serde::Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Logs how long a call to the daemon took when dropped, within the span of the call, does
+/// nothing unless built with the tracing feature
+struct CallTimer {
+    #[cfg(feature = "tracing")]
+    started: std::time::Instant,
+}
+
+impl CallTimer {
+    fn start() -> CallTimer {
+        CallTimer {
+            #[cfg(feature = "tracing")]
+            started: std::time::Instant::now(),
+        }
+    }
+}
+
+#[cfg(feature = "tracing")]
+impl Drop for CallTimer {
+    fn drop(&mut self) {
+        tracing::debug!(
+            duration_ms = self.started.elapsed().as_millis() as u64,
+            "x0x call finished"
+        );
+    }
 }
 
 const DISCOVERY_SYNC_ATTEMPTS: usize = 5;
 const DISCOVERY_SYNC_WAIT: tokio::time::Duration = tokio::time::Duration::from_secs(1);
 const REFRESH_SYNC_ATTEMPTS: usize = 3;
 const REFRESH_SYNC_WAIT: tokio::time::Duration = tokio::time::Duration::from_millis(700);
+/// How long after sending a sync request replies to it are taken, any others are dropped
+const SYNC_REPLY_WINDOW: std::time::Duration = std::time::Duration::from_secs(30);
+/// Bytes of an attached file sent in each gossip message
+const ATTACHMENT_CHUNK_SIZE: usize = 48 * 1024;
+const DOWNLOAD_ATTEMPTS: usize = 60;
+const DOWNLOAD_WAIT: tokio::time::Duration = tokio::time::Duration::from_millis(500);
+/// Ask for a file again after this many waits in case the request or some chunks were lost
+const DOWNLOAD_REQUEST_EVERY: usize = 10;
+/// Most changes to boreds published in UPDATE_WINDOW, changes past it are kept in the cache and
+/// published together once there is room
+const MAX_UPDATES: usize = 6;
+const UPDATE_WINDOW: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Number of chunks a file of size bytes is sent in, empty files are sent as one empty chunk
+fn chunk_count(size: u64) -> u32 {
+    size.div_ceil(ATTACHMENT_CHUNK_SIZE as u64).max(1) as u32
+}
+
+/// How far through a download is, sent each time more of the file arrives
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// Bytes received so far, including any kept from an earlier attempt
+    pub received: u64,
+    pub total: u64,
+}
+
+/// The hex hash of a well formed attachment address, addresses come from peers so only these
+/// are safe to use as file names
+fn attachment_hash(address: &str) -> Option<&str> {
+    address
+        .strip_prefix(ATTACHMENT_ADDRESS_PREFIX)
+        .filter(|hash| hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit()))
+}
 
 pub fn get_x0x_data_dir() -> Option<std::path::PathBuf> {
     #[cfg(target_os = "macos")]
@@ -121,29 +378,284 @@ pub fn get_we_are_bored_data_dir() -> Option<std::path::PathBuf> {
     None
 }
 
-fn get_api_credentials() -> Option<(String, String)> {
-    let path = get_x0x_data_dir()?;
-    let port_str = std::fs::read_to_string(path.join("api.port")).ok()?.trim().to_string();
-    let token = std::fs::read_to_string(path.join("api-token")).ok()?.trim().to_string();
-    
-    let api_base = if port_str.contains(':') {
-        format!("http://{}", port_str)
+/// Which x0x daemon to connect to, each daemon is on the network its config points it at
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum X0xDaemon {
+    /// The daemon started with `x0x start`
+    #[default]
+    Standard,
+    /// An instance started with `x0x start --name <name>`, such as one on a test network,
+    /// which keeps its api address and token in its own data directory
+    Named(String),
+    /// The daemon's api at this address, a port on this machine or host:port, with the token
+    /// of the standard daemon if there is one
+    Custom(String),
+}
+
+impl X0xDaemon {
+    /// Where the daemon keeps its api address and token
+    fn get_data_dir(&self) -> Option<std::path::PathBuf> {
+        let data_dir = get_x0x_data_dir()?;
+        match self {
+            X0xDaemon::Named(name) => {
+                let mut named = data_dir.into_os_string();
+                named.push(format!("-{name}"));
+                Some(named.into())
+            }
+            _ => Some(data_dir),
+        }
+    }
+
+    /// Where boreds are cached in the data directory, boreds from a named instance are kept
+    /// apart as it may be on another network
+    pub fn get_cache_dir(&self, data_dir: &std::path::Path) -> std::path::PathBuf {
+        match self {
+            X0xDaemon::Named(name) => data_dir.join(format!("cache-{name}")),
+            _ => data_dir.join("cache"),
+        }
+    }
+
+    /// The daemon's api address and token, the standard address without a token if the daemon
+    /// hasn't written them
+    fn get_api_credentials(&self) -> (String, String) {
+        let read = |name: &str| {
+            let path = self.get_data_dir()?.join(name);
+            Some(std::fs::read_to_string(path).ok()?.trim().to_string())
+        };
+        let address = match self {
+            X0xDaemon::Custom(address) => Some(address.clone()),
+            _ => read("api.port"),
+        };
+        match address {
+            Some(address) => (get_api_base(&address), read("api-token").unwrap_or_default()),
+            None => ("http://127.0.0.1:12700".to_string(), String::new()),
+        }
+    }
+}
+
+/// The base url of a daemon's api from its address, a port on this machine or host:port
+fn get_api_base(address: &str) -> String {
+    if address.starts_with("http://") || address.starts_with("https://") {
+        address.trim_end_matches('/').to_string()
+    } else if address.contains(':') {
+        format!("http://{}", address)
     } else {
-        format!("http://127.0.0.1:{}", port_str)
-    };
-    Some((api_base, token))
+        format!("http://127.0.0.1:{}", address)
+    }
 }
 
-/// A client implementing the Bored protocol via gossip pub/sub and local caching
-pub struct X0xBoredClient {
-    http: reqwest::Client,
+/// What has changed on a bored locally without being published, as too many changes had been
+/// published already
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct PendingUpdate {
+    /// Milliseconds since the unix epoch notices have been posted from, each notice this client
+    /// posted since then is sent in a notice message
+    notices_since: Option<i64>,
+    /// Settings have changed, sent in a meta message
+    settings: bool,
+}
+
+/// Stops a client from flooding peers with changes, such as posts made in quick succession or
+/// by a loop gone wrong. Changes past the limit wait and those to the same bored are sent as
+/// one.
+#[derive(Debug, Default)]
+struct UpdateLimiter {
+    /// When the changes in the last UPDATE_WINDOW were published
+    published: std::collections::VecDeque<std::time::Instant>,
+    /// Boreds with changes waiting, in the order they were first held back
+    pending: Vec<(BoredAddress, PendingUpdate)>,
+}
+
+impl UpdateLimiter {
+    /// Take room for a change if there is any, forgetting changes from before the window
+    fn take(&mut self, now: std::time::Instant) -> bool {
+        while self
+            .published
+            .front()
+            .is_some_and(|published| now.duration_since(*published) >= UPDATE_WINDOW)
+        {
+            self.published.pop_front();
+        }
+        if self.published.len() >= MAX_UPDATES {
+            return false;
+        }
+        self.published.push_back(now);
+        true
+    }
+
+    /// Whether a change to a bored can be published now, if not it is kept to send later along
+    /// with any other waiting changes to the same bored
+    fn allow(
+        &mut self,
+        address: &BoredAddress,
+        update: PendingUpdate,
+        now: std::time::Instant,
+    ) -> bool {
+        // later changes go out with those already waiting
+        if let Some((_, pending)) = self
+            .pending
+            .iter_mut()
+            .find(|(pending, _)| pending == address)
+        {
+            pending.notices_since = pending
+                .notices_since
+                .into_iter()
+                .chain(update.notices_since)
+                .min();
+            pending.settings |= update.settings;
+            return false;
+        }
+        if self.take(now) {
+            return true;
+        }
+        self.pending.push((address.clone(), update));
+        false
+    }
+
+    /// A bored with waiting changes if there is room to publish them
+    fn take_pending(&mut self, now: std::time::Instant) -> Option<(BoredAddress, PendingUpdate)> {
+        if self.pending.is_empty() || !self.take(now) {
+            return None;
+        }
+        Some(self.pending.remove(0))
+    }
+}
+
+/// What posting a notice would take and do, worked out without publishing anything
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PostingEstimate {
+    /// Proof of work the notice needs, each step up doubles the hashing it takes
+    pub pow_difficulty: u8,
+    /// Bytes the bored would be with the notice on it
+    pub bored_size: usize,
+    /// Notices that would be pruned as the new one hides them entirely
+    pub pruned_notices: usize,
+}
+
+/// Most proof of work the client will do to post, checked against what a bored asks for before
+/// any hashing starts. None is no limit.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkLimit {
+    /// Highest difficulty to prove for any one notice
+    pub per_notice: Option<u8>,
+    /// Most hashes to expect to do over the session
+    pub per_session: Option<u64>,
+}
+
+/// Hashes proving work at the difficulty takes on average, each step up doubles it
+pub fn expected_hashes(pow_difficulty: u8) -> u64 {
+    match pow_difficulty {
+        0 => 0,
+        difficulty => 1u64 << difficulty,
+    }
+}
+
+/// What a change published by this client did
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LedgerOperation {
+    /// Created a bored, or published a whole one such as a fork
+    CreatedBored,
+    /// Changed the settings of a bored
+    ChangedSettings,
+    PostedNotice,
+    /// Offered a file attached to a posted notice to peers
+    SharedFile,
+}
+
+/// A change this client published and what it took, kept in a local ledger to look back on
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct LedgerEntry {
+    /// Milliseconds since the unix epoch it was published
+    pub at: i64,
+    pub operation: LedgerOperation,
+    /// Address of the bored, or of the file for a shared file
+    pub address: String,
+    /// Proof of work done for it, 0 if none was needed
+    pub pow_difficulty: u8,
+    /// Bytes of the bored after the change, or of the file
+    pub size: u64,
+}
+
+impl LedgerEntry {
+    fn new(operation: LedgerOperation, address: &str, pow_difficulty: u8, size: u64) -> LedgerEntry {
+        LedgerEntry {
+            at: chrono::Utc::now().timestamp_millis(),
+            operation,
+            address: address.to_string(),
+            pow_difficulty,
+            size,
+        }
+    }
+}
+
+/// A previous version of a bored kept locally before it was updated
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArchivedBored {
+    /// Milliseconds since the unix epoch the version was archived
+    pub archived_at: i64,
+    pub bored: Bored,
+}
+
+/// The current bored handed out by current_bored
+struct SharedBored {
+    /// The cache it was parsed from, none if it was the copy in memory
+    source: Option<String>,
+    bored: std::sync::Arc<Bored>,
+}
+
+/// Where the daemon's api is and the token to use it with
+#[derive(Debug, Clone, Default)]
+struct Endpoint {
     api_base: String,
     api_token: String,
+}
+
+impl Endpoint {
+    /// Whether the daemon at the endpoint is up and responsive
+    async fn check_health(&self, http: &reqwest::Client) -> Result<(), BoredError> {
+        let health_url = format!("{}/health", self.api_base);
+        let mut request = http.get(&health_url).timeout(std::time::Duration::from_secs(5));
+        if !self.api_token.is_empty() {
+            request = request.bearer_auth(&self.api_token);
+        }
+        match request.send().await {
+            Ok(resp) if resp.status().is_success() => Ok(()),
+            _ => Err(BoredError::ClientConnectionError),
+        }
+    }
+}
+
+/// A client implementing the Bored protocol via gossip pub/sub and local caching. Clones share
+/// the connection to the daemon, so a clone can be moved into a background task and a
+/// reconnect from any of them is seen by all.
+#[derive(Clone)]
+pub struct X0xBoredClient {
+    http: reqwest::Client,
+    endpoint: std::sync::Arc<std::sync::RwLock<Endpoint>>,
+    /// The daemon connected to, its api address and token are read again to reconnect
+    daemon: X0xDaemon,
     agent_id: String,
     current_bored: Option<Bored>,
     draft_notice: Option<Notice>,
     bored_address: Option<BoredAddress>,
     cache_dir: std::path::PathBuf,
+    keep_history: bool,
+    /// Refuse to create or change any bored
+    read_only: bool,
+    /// Tell the boreds read which notices are opened
+    count_views: bool,
+    /// Link the current bored was opened with, None when it was opened with its plain address
+    share_link: Option<ShareLink>,
+    /// Shared by clones so they are held back together
+    limiter: std::sync::Arc<std::sync::Mutex<UpdateLimiter>>,
+    /// The current bored as last read, so it is only parsed again once the cache changes
+    shared_bored: std::sync::Arc<std::sync::Mutex<Option<SharedBored>>>,
+    work_limit: WorkLimit,
+    /// Hashes expected for the work done this session, shared by clones so it is counted once
+    work_done: std::sync::Arc<std::sync::atomic::AtomicU64>,
+    /// Key for opening boreds encrypted for this reader, shared with the background task
+    reader_key: std::sync::Arc<std::sync::RwLock<Option<ReaderKey>>>,
 }
 
 impl X0xBoredClient {
@@ -154,35 +666,36 @@ impl X0xBoredClient {
         Self::init_with_data_dir(data_dir).await
     }
 
-    async fn init_with_data_dir(data_dir: std::path::PathBuf) -> Result<X0xBoredClient, BoredError> {
-        let (api_base, api_token) = match get_api_credentials() {
-            Some(creds) => creds,
-            None => ("http://127.0.0.1:12700".to_string(), String::new()),
-        };
+    /// Initialize the client with its cache kept in the data directory given rather than the
+    /// standard one
+    pub async fn init_with_data_dir(data_dir: std::path::PathBuf) -> Result<X0xBoredClient, BoredError> {
+        Self::init_with_daemon(data_dir, &X0xDaemon::Standard).await
+    }
+
+    /// Initialize the client connected to the daemon given, with its cache kept in the data
+    /// directory
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(api_base)))]
+    pub async fn init_with_daemon(
+        data_dir: std::path::PathBuf,
+        daemon: &X0xDaemon,
+    ) -> Result<X0xBoredClient, BoredError> {
+        let _timer = CallTimer::start();
+        let (api_base, api_token) = daemon.get_api_credentials();
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("api_base", api_base.as_str());
 
         let http = reqwest::Client::new();
 
         // Validate local daemon is running and reachable
-        let health_url = format!("{}/health", api_base);
-        let mut request = http.get(&health_url).timeout(std::time::Duration::from_secs(5));
-        if !api_token.is_empty() {
-            request = request.bearer_auth(&api_token);
-        }
-
-        let resp = match request.send().await {
-            Ok(resp) => resp,
-            Err(_) => return Err(BoredError::ClientConnectionError),
-        };
-
-        if !resp.status().is_success() {
-            return Err(BoredError::ClientConnectionError);
-        }
+        let endpoint = Endpoint { api_base, api_token };
+        endpoint.check_health(&http).await?;
+        let Endpoint { api_base, api_token } = &endpoint;
 
         // Fetch local Agent ID
         let agent_url = format!("{}/agent", api_base);
         let mut request = http.get(&agent_url).timeout(std::time::Duration::from_secs(5));
         if !api_token.is_empty() {
-            request = request.bearer_auth(&api_token);
+            request = request.bearer_auth(api_token);
         }
 
         let resp = match request.send().await {
@@ -203,18 +716,28 @@ impl X0xBoredClient {
             .ok_or_else(|| BoredError::X0xError("agent_id missing in response".to_string()))?
             .to_string();
 
-        let cache_dir = data_dir.join("cache");
+        let cache_dir = daemon.get_cache_dir(&data_dir);
         let _ = std::fs::create_dir_all(&cache_dir);
 
         // Spawn background listener task to monitor all `/events` (gossip updates)
+        let endpoint = std::sync::Arc::new(std::sync::RwLock::new(endpoint));
         let http_clone = http.clone();
-        let api_base_clone = api_base.clone();
-        let api_token_clone = api_token.clone();
+        let endpoint_clone = endpoint.clone();
         let cache_dir_clone = cache_dir.clone();
+        let reader_key = std::sync::Arc::new(std::sync::RwLock::new(None));
+        let reader_key_clone = reader_key.clone();
 
         tokio::spawn(async move {
             let mut buffer = String::new();
             loop {
+                // read each time in case the client has reconnected to the daemon elsewhere
+                let Endpoint {
+                    api_base: api_base_clone,
+                    api_token: api_token_clone,
+                } = endpoint_clone
+                    .read()
+                    .map(|endpoint| endpoint.clone())
+                    .unwrap_or_default();
                 let url = format!("{}/events", api_base_clone);
                 let mut request = http_clone.get(&url);
                 if !api_token_clone.is_empty() {
@@ -223,7 +746,9 @@ impl X0xBoredClient {
 
                 let resp = match request.send().await {
                     Ok(resp) => resp,
-                    Err(_) => {
+                    Err(_e) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::warn!(error = %_e, "could not connect to x0x events, retrying");
                         tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
                         continue;
                     }
@@ -254,10 +779,15 @@ impl X0xBoredClient {
                                             ) {
                                                 if let Ok(decoded) = base64::Engine::decode(&base64::prelude::BASE64_STANDARD, payload_base64) {
                                                     if let Ok(msg) = serde_json::from_slice::<GossipMsg>(&decoded) {
+                                                        let reader_key =
+                                                            reader_key_clone.read().ok().and_then(
+                                                                |reader_key| reader_key.clone(),
+                                                            );
                                                         let _ = Self::handle_background_msg(
                                                             &http_clone,
                                                             &api_base_clone,
                                                             &api_token_clone,
+                                                            reader_key.as_ref(),
                                                             &cache_dir_clone,
                                                             topic,
                                                             msg
@@ -282,25 +812,90 @@ impl X0xBoredClient {
 
         Ok(X0xBoredClient {
             http,
-            api_base,
-            api_token,
+            endpoint,
+            daemon: daemon.clone(),
             agent_id,
             current_bored: None,
             draft_notice: None,
             bored_address: None,
             cache_dir,
+            keep_history: false,
+            read_only: false,
+            count_views: false,
+            share_link: None,
+            limiter: Default::default(),
+            shared_bored: Default::default(),
+            work_limit: WorkLimit::default(),
+            work_done: Default::default(),
+            reader_key,
         })
     }
 
+    /// A client that isn't connected to the daemon, only the daemon's cache in the data
+    /// directory is read so boreds that have been visited can be shown while connecting
+    pub fn offline(data_dir: &std::path::Path, daemon: &X0xDaemon) -> X0xBoredClient {
+        X0xBoredClient {
+            http: reqwest::Client::new(),
+            endpoint: Default::default(),
+            daemon: daemon.clone(),
+            agent_id: String::new(),
+            current_bored: None,
+            draft_notice: None,
+            bored_address: None,
+            cache_dir: daemon.get_cache_dir(data_dir),
+            keep_history: false,
+            read_only: false,
+            count_views: false,
+            share_link: None,
+            limiter: Default::default(),
+            shared_bored: Default::default(),
+            work_limit: WorkLimit::default(),
+            work_done: Default::default(),
+            reader_key: Default::default(),
+        }
+    }
+
     /// Check if the x0xd daemon is up and responsive
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
     pub async fn check_daemon(&self) -> Result<bool, BoredError> {
-        let health_url = format!("{}/health", self.api_base);
-        let mut request = self.http.get(&health_url).timeout(std::time::Duration::from_secs(5));
-        if !self.api_token.is_empty() {
-            request = request.bearer_auth(&self.api_token);
+        Ok(self.health_check().await.is_ok())
+    }
+
+    /// Check the daemon is still up and responsive, ClientConnectionError if it isn't
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn health_check(&self) -> Result<(), BoredError> {
+        let _timer = CallTimer::start();
+        self.get_endpoint().check_health(&self.http).await
+    }
+
+    /// Connect to the daemon again after losing it. Its api address and token are read again
+    /// in case it was restarted with new ones, and the current bored is subscribed to again as
+    /// a restarted daemon has forgotten it. Clones of the client use the new connection too.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn reconnect(&self) -> Result<(), BoredError> {
+        let _timer = CallTimer::start();
+        // an offline client never connected so there is nothing listening to reconnect
+        if !self.is_available() {
+            return Err(BoredError::ClientConnectionError);
         }
-        let resp = request.send().await;
-        Ok(resp.is_ok() && resp.unwrap().status().is_success())
+        let (api_base, api_token) = self.daemon.get_api_credentials();
+        let endpoint = Endpoint { api_base, api_token };
+        endpoint.check_health(&self.http).await?;
+        if let Ok(mut shared) = self.endpoint.write() {
+            *shared = endpoint;
+        }
+        if let Some(bored_address) = &self.bored_address {
+            self.subscribe(&bored_address.get_topic()).await?;
+        }
+        Ok(())
+    }
+
+    /// Where the daemon is being reached now
+    fn get_endpoint(&self) -> Endpoint {
+        self.endpoint
+            .read()
+            .map(|endpoint| endpoint.clone())
+            .unwrap_or_default()
     }
 
     /// Is daemon integration initialized successfully
@@ -314,12 +909,26 @@ impl X0xBoredClient {
     }
 
     fn load_cache(cache_dir: &std::path::Path, address: &BoredAddress) -> Option<Bored> {
+        Self::read_cache(cache_dir, address).ok().flatten()
+    }
+
+    /// Read a cached bored, none if it isn't cached. Caches are written from what peers send
+    /// so notices that break the rules are removed and boreds too big to use are rejected.
+    fn read_cache(
+        cache_dir: &std::path::Path,
+        address: &BoredAddress,
+    ) -> Result<Option<Bored>, BoredError> {
         let path = Self::cache_path(cache_dir, address);
-        if let Ok(content) = std::fs::read_to_string(path) {
-            serde_json::from_str(&content).ok()
-        } else {
-            None
-        }
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return Ok(None);
+        };
+        Self::parse_cache(&content).map(Some)
+    }
+
+    fn parse_cache(content: &str) -> Result<Bored, BoredError> {
+        let mut bored = Bored::from_json(content)?;
+        bored.sanitize()?;
+        Ok(bored)
     }
 
     fn save_cache(cache_dir: &std::path::Path, address: &BoredAddress, bored: &Bored) -> Result<(), BoredError> {
@@ -332,97 +941,592 @@ impl X0xBoredClient {
         Ok(())
     }
 
-    async fn subscribe(&self, topic: &str) -> Result<(), BoredError> {
-        let url = format!("{}/subscribe", self.api_base);
-        let mut request = self.http.post(&url).timeout(std::time::Duration::from_secs(5)).json(&serde_json::json!({
-            "topic": topic
-        }));
-        if !self.api_token.is_empty() {
-            request = request.bearer_auth(&self.api_token);
-        }
-        let resp = request.send().await?;
-        if !resp.status().is_success() {
-            let err_body = resp.text().await.unwrap_or_default();
-            return Err(BoredError::X0xError(err_body));
-        }
-        Ok(())
+    /// Where a file attached to a notice by this client is kept to send to peers, by the hex
+    /// hash of its content
+    fn attachment_path(cache_dir: &std::path::Path, hash: &str) -> std::path::PathBuf {
+        cache_dir.join("attachments").join(hash)
     }
 
-    async fn publish_msg(&self, topic: &str, msg: &GossipMsg) -> Result<(), BoredError> {
-        let serialized = serde_json::to_string(msg)?;
-        let base64_payload = base64::Engine::encode(&base64::prelude::BASE64_STANDARD, serialized.as_bytes());
+    /// Where the chunks of a file are collected while it is downloaded, each chunk is a file
+    /// named <index>.<total>
+    fn download_dir(cache_dir: &std::path::Path, hash: &str) -> std::path::PathBuf {
+        cache_dir.join("downloads").join(hash)
+    }
 
-        let url = format!("{}/publish", self.api_base);
-        let mut request = self.http.post(&url).timeout(std::time::Duration::from_secs(5)).json(&serde_json::json!({
-            "topic": topic,
-            "payload": base64_payload
-        }));
-        if !self.api_token.is_empty() {
-            request = request.bearer_auth(&self.api_token);
-        }
+    /// Marks when this client last asked peers for a bored, so only replies it asked for are taken
+    fn sync_request_path(
+        cache_dir: &std::path::Path,
+        address: &BoredAddress,
+    ) -> std::path::PathBuf {
+        cache_dir.join("sync-requests").join(address.get_topic())
+    }
 
-        let resp = request.send().await?;
-        if !resp.status().is_success() {
-            let err_body = resp.text().await.unwrap_or_default();
-            return Err(BoredError::X0xError(err_body));
+    /// Remember a sync request was sent for the bored, for sync_requested
+    fn note_sync_request(cache_dir: &std::path::Path, address: &BoredAddress) {
+        let path = Self::sync_request_path(cache_dir, address);
+        if let Some(parent) = path.parent() {
+            let _ = std::fs::create_dir_all(parent);
         }
-        Ok(())
+        // written again each time so it is modified when the last request was sent
+        let _ = std::fs::write(path, []);
     }
 
-    async fn handle_background_msg(
-        http: &reqwest::Client,
-        api_base: &str,
-        api_token: &str,
+    /// Whether this client asked peers for the bored recently enough to take their replies
+    fn sync_requested(cache_dir: &std::path::Path, address: &BoredAddress) -> bool {
+        std::fs::metadata(Self::sync_request_path(cache_dir, address))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|elapsed| elapsed < SYNC_REPLY_WINDOW)
+    }
+
+    /// Where the readers counted opening each notice on a bored are kept
+    fn views_path(cache_dir: &std::path::Path, address: &BoredAddress) -> std::path::PathBuf {
+        cache_dir
+            .join("views")
+            .join(format!("{}.json", address.get_topic()))
+    }
+
+    fn load_views(cache_dir: &std::path::Path, address: &BoredAddress) -> NoticeViews {
+        std::fs::read_to_string(Self::views_path(cache_dir, address))
+            .ok()
+            .and_then(|views| serde_json::from_str(&views).ok())
+            .unwrap_or_default()
+    }
+
+    /// Count a reader opening a notice on a cached bored, notices that aren't on it aren't
+    /// counted and those that have gone from it are forgotten
+    fn count_view(
         cache_dir: &std::path::Path,
-        topic: &str,
-        msg: GossipMsg,
+        address: &BoredAddress,
+        notice_id: &str,
+        reader_id: &str,
     ) -> Result<(), BoredError> {
-        // topic is usually "bored.bum"
-        let name = if topic.starts_with("bored.") {
-            &topic["bored.".len()..]
-        } else {
-            topic
+        let Some(bored) = Self::load_cache(cache_dir, address) else {
+            return Ok(());
         };
-        let address = BoredAddress::from_string(name)?;
+        if !bored
+            .notices()
+            .iter()
+            .any(|n| n.get_notice_id() == notice_id)
+        {
+            return Ok(());
+        }
+        let mut views = Self::load_views(cache_dir, address);
+        if views.add(notice_id, reader_id) {
+            views.retain_notices(&bored);
+            let path = Self::views_path(cache_dir, address);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, serde_json::to_string(&views)?)?;
+        }
+        Ok(())
+    }
 
-        // Only process events if we have joined/created the board (indicated by cache existence),
-        // or if the message is a SyncResponse (which we can use to discover/join a board from the network).
-        let path = Self::cache_path(cache_dir, &address);
-        if !path.exists() {
-            if !matches!(msg, GossipMsg::SyncResponse { .. }) {
-                return Ok(());
+    fn chunk_path(download_dir: &std::path::Path, index: u32, total: u32) -> std::path::PathBuf {
+        download_dir.join(format!("{index}.{total}"))
+    }
+
+    /// The chunks of a download that haven't arrived yet and how many bytes have
+    fn missing_chunks(
+        download_dir: &std::path::Path,
+        total: u32,
+    ) -> Result<(Vec<u32>, u64), BoredError> {
+        let mut missing = vec![];
+        let mut received = 0;
+        for index in 0..total {
+            match std::fs::metadata(Self::chunk_path(download_dir, index, total)) {
+                Ok(metadata) => received += metadata.len(),
+                Err(_) => missing.push(index),
             }
         }
+        Ok((missing, received))
+    }
 
-        match msg {
-            GossipMsg::SyncRequest => {
-                if let Some(bored) = Self::load_cache(cache_dir, &address) {
-                    let response_msg = GossipMsg::SyncResponse {
-                        name: bored.get_name().to_string(),
-                        dimensions: bored.get_dimensions(),
-                        notices: bored.get_notices(),
-                    };
-                    let serialized = serde_json::to_string(&response_msg)?;
-                    let base64_payload = base64::Engine::encode(&base64::prelude::BASE64_STANDARD, serialized.as_bytes());
-
-                    let url = format!("{}/publish", api_base);
-                    let mut request = http.post(&url).timeout(std::time::Duration::from_secs(5)).json(&serde_json::json!({
-                        "topic": topic,
-                        "payload": base64_payload
-                    }));
-                    if !api_token.is_empty() {
-                        request = request.bearer_auth(api_token);
-                    }
-                    let _ = request.send().await;
+    /// Join the chunks of a complete download into the attachment store, checking the content
+    /// matches its address as it is written. A download that doesn't match is thrown away so
+    /// the next attempt starts again.
+    fn finish_download(
+        cache_dir: &std::path::Path,
+        attachment: &Attachment,
+        total: u32,
+    ) -> Result<(), BoredError> {
+        let download_dir = Self::download_dir(cache_dir, attachment.get_hash());
+        let stored = Self::attachment_path(cache_dir, attachment.get_hash());
+        if let Some(parent) = stored.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let partial = stored.with_extension("part");
+        let mut file = std::fs::File::create(&partial)?;
+        for index in 0..total {
+            let mut chunk = std::fs::File::open(Self::chunk_path(&download_dir, index, total))?;
+            std::io::copy(&mut chunk, &mut file)?;
+        }
+        drop(file);
+        let _ = std::fs::remove_dir_all(&download_dir);
+        if attachment_address_from_reader(std::fs::File::open(&partial)?)?
+            != attachment.get_address()
+        {
+            let _ = std::fs::remove_file(&partial);
+            return Err(BoredError::ChecksumMismatch(attachment.get_address().to_string()));
+        }
+        std::fs::rename(partial, stored)?;
+        Ok(())
+    }
+
+    /// Write a downloaded file into dir without replacing anything already there, adding a
+    /// number to the name if it is taken, e.g. notes (1).md
+    fn save_download(
+        dir: &std::path::Path,
+        filename: &str,
+        source: &std::path::Path,
+    ) -> Result<std::path::PathBuf, BoredError> {
+        std::fs::create_dir_all(dir)?;
+        let (stem, extension) = match filename.rsplit_once('.') {
+            Some((stem, extension)) if !stem.is_empty() => (stem, format!(".{extension}")),
+            _ => (filename, String::new()),
+        };
+        for copy in 0..u32::MAX {
+            let name = match copy {
+                0 => filename.to_string(),
+                copy => format!("{stem} ({copy}){extension}"),
+            };
+            let path = dir.join(name);
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    std::io::copy(&mut std::fs::File::open(source)?, &mut file)?;
+                    return Ok(path);
                 }
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                Err(e) => return Err(e.into()),
             }
-            GossipMsg::Meta { name, dimensions } => {
-                if let Some(mut bored) = Self::load_cache(cache_dir, &address) {
-                    if bored.name == "Untitled Bored" || bored.name == address.get_topic() {
-                        bored.name = name;
-                        bored.dimensions = dimensions;
-                        Self::save_cache(cache_dir, &address, &bored)?;
-                    }
+        }
+        Err(std::io::Error::from(std::io::ErrorKind::AlreadyExists).into())
+    }
+
+    fn history_dir(cache_dir: &std::path::Path, address: &BoredAddress) -> std::path::PathBuf {
+        cache_dir.join("history").join(address.get_topic())
+    }
+
+    /// Write a version of the bored to its own file that is never modified afterwards
+    fn archive_version(
+        cache_dir: &std::path::Path,
+        address: &BoredAddress,
+        bored: &Bored,
+    ) -> Result<(), BoredError> {
+        let history_dir = Self::history_dir(cache_dir, address);
+        std::fs::create_dir_all(&history_dir)?;
+        let mut archived_at = chrono::Utc::now().timestamp_millis();
+        // never overwrite an existing version if two updates happen in the same millisecond
+        while history_dir.join(format!("{archived_at}.json")).exists() {
+            archived_at += 1;
+        }
+        let content = serde_json::to_string(bored)?;
+        std::fs::write(history_dir.join(format!("{archived_at}.json")), content)?;
+        Ok(())
+    }
+
+    fn ledger_path(cache_dir: &std::path::Path) -> std::path::PathBuf {
+        cache_dir.join("ledger.jsonl")
+    }
+
+    /// Add an entry to the end of the ledger. The change has already been made by then, so
+    /// failing to write it down doesn't fail the change.
+    fn record(cache_dir: &std::path::Path, entry: LedgerEntry) {
+        use std::io::Write;
+        let Ok(line) = serde_json::to_string(&entry) else {
+            return;
+        };
+        let _ = std::fs::create_dir_all(cache_dir);
+        if let Ok(mut file) = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(Self::ledger_path(cache_dir))
+        {
+            let _ = writeln!(file, "{line}");
+        }
+    }
+
+    /// Everything this client has published, newest first, with the proof of work and bytes
+    /// each took
+    pub fn get_ledger(&self) -> Result<Vec<LedgerEntry>, BoredError> {
+        let content = match std::fs::read_to_string(Self::ledger_path(&self.cache_dir)) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(e) => return Err(e.into()),
+        };
+        // a line cut short by a crash is skipped rather than losing the rest
+        Ok(content
+            .lines()
+            .rev()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect())
+    }
+
+    /// Turn on or off archiving the previous version of the bored each time it is updated
+    pub fn set_keep_history(&mut self, keep_history: bool) {
+        self.keep_history = keep_history;
+    }
+
+    /// Turn on or off refusing to create or change any bored, whatever link it was opened with
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// Turn on or off telling the boreds read which notices are opened, so whoever posted them
+    /// can see if they are read. Who opened them isn't told.
+    pub fn set_count_views(&mut self, count_views: bool) {
+        self.count_views = count_views;
+    }
+
+    /// Set the most proof of work to do to post, a bored asking for more is refused before any
+    /// hashing starts
+    pub fn set_work_limit(&mut self, work_limit: WorkLimit) {
+        self.work_limit = work_limit;
+    }
+
+    /// Set the key boreds encrypted for this reader are opened with as they arrive, none to
+    /// ignore encrypted boreds. Clones share the key.
+    pub fn set_reader_key(&mut self, reader_key: Option<ReaderKey>) {
+        if let Ok(mut current) = self.reader_key.write() {
+            *current = reader_key;
+        }
+    }
+
+    /// Hashes expected for the proof of work done so far this session
+    pub fn get_work_done(&self) -> u64 {
+        self.work_done.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Refuse proof of work at the difficulty if it is over the limit for a notice or would take
+    /// the session over its limit
+    fn check_work_limit(&self, pow_difficulty: u8) -> Result<(), BoredError> {
+        if let Some(per_notice) = self.work_limit.per_notice
+            && pow_difficulty > per_notice
+        {
+            return Err(BoredError::PowOverLimit(pow_difficulty, per_notice));
+        }
+        if let Some(per_session) = self.work_limit.per_session
+            && self.get_work_done().saturating_add(expected_hashes(pow_difficulty)) > per_session
+        {
+            return Err(BoredError::WorkLimitReached(per_session));
+        }
+        Ok(())
+    }
+
+    /// Get the archived previous versions of the current bored, newest first. Versions that
+    /// can't be read are left out rather than losing the rest of the history.
+    pub fn get_history(&self) -> Result<Vec<ArchivedBored>, BoredError> {
+        let Some(bored_address) = &self.bored_address else {
+            return Err(BoredError::NoBored);
+        };
+        let history_dir = Self::history_dir(&self.cache_dir, bored_address);
+        let mut history = vec![];
+        let Ok(entries) = std::fs::read_dir(history_dir) else {
+            return Ok(history);
+        };
+        for entry in entries {
+            let path = entry?.path();
+            let Some(archived_at) = path
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .and_then(|s| s.parse().ok())
+            else {
+                continue;
+            };
+            let read = std::fs::read_to_string(&path)
+                .map_err(BoredError::from)
+                .and_then(|content| Ok(serde_json::from_str(&content)?));
+            match read {
+                Ok(bored) => history.push(ArchivedBored { archived_at, bored }),
+                Err(_e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::warn!(error = %_e, path = %path.display(), "skipping unreadable archived bored");
+                }
+            }
+        }
+        history.sort_by_key(|a| std::cmp::Reverse(a.archived_at));
+        Ok(history)
+    }
+
+    /// Subscribe to the topic, returning the id the daemon gave the subscription if it gave one
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    async fn subscribe(&self, topic: &str) -> Result<Option<String>, BoredError> {
+        let _timer = CallTimer::start();
+        let endpoint = self.get_endpoint();
+        let url = format!("{}/subscribe", endpoint.api_base);
+        let mut request = self.http.post(&url).timeout(std::time::Duration::from_secs(5)).json(&serde_json::json!({
+            "topic": topic
+        }));
+        if !endpoint.api_token.is_empty() {
+            request = request.bearer_auth(&endpoint.api_token);
+        }
+        let resp = request.send().await?;
+        if !resp.status().is_success() {
+            let err_body = resp.text().await.unwrap_or_default();
+            return Err(BoredError::X0xError(err_body));
+        }
+        let json = resp.json::<serde_json::Value>().await.unwrap_or_default();
+        Ok(json
+            .get("subscription_id")
+            .and_then(|id| id.as_str())
+            .map(str::to_string))
+    }
+
+    /// Stop the daemon sending messages for a subscription, by the id it gave it
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    async fn unsubscribe(&self, subscription_id: &str) -> Result<(), BoredError> {
+        let _timer = CallTimer::start();
+        let endpoint = self.get_endpoint();
+        let url = format!("{}/subscribe/{}", endpoint.api_base, subscription_id);
+        let mut request = self
+            .http
+            .delete(&url)
+            .timeout(std::time::Duration::from_secs(5));
+        if !endpoint.api_token.is_empty() {
+            request = request.bearer_auth(&endpoint.api_token);
+        }
+        let resp = request.send().await?;
+        if !resp.status().is_success() {
+            let err_body = resp.text().await.unwrap_or_default();
+            return Err(BoredError::X0xError(err_body));
+        }
+        Ok(())
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip(self, msg), fields(kind = msg.kind(), payload_size))
+    )]
+    async fn publish_msg(&self, topic: &str, msg: &GossipMsg) -> Result<(), BoredError> {
+        // noted before it is sent as replies can arrive before publishing returns
+        if matches!(msg, GossipMsg::SyncRequest)
+            && let Ok(address) = BoredAddress::from_topic(topic)
+        {
+            Self::note_sync_request(&self.cache_dir, &address);
+        }
+        let endpoint = self.get_endpoint();
+        Self::publish_to(&self.http, &endpoint.api_base, &endpoint.api_token, topic, msg).await
+    }
+
+    /// Publish a message without a client, for use from the background event task
+    async fn publish_to(
+        http: &reqwest::Client,
+        api_base: &str,
+        api_token: &str,
+        topic: &str,
+        msg: &GossipMsg,
+    ) -> Result<(), BoredError> {
+        let _timer = CallTimer::start();
+        let serialized = serde_json::to_string(msg)?;
+        #[cfg(feature = "tracing")]
+        tracing::Span::current().record("payload_size", serialized.len());
+        let base64_payload = base64::Engine::encode(&base64::prelude::BASE64_STANDARD, serialized.as_bytes());
+
+        let url = format!("{}/publish", api_base);
+        let mut request = http.post(&url).timeout(std::time::Duration::from_secs(5)).json(&serde_json::json!({
+            "topic": topic,
+            "payload": base64_payload
+        }));
+        if !api_token.is_empty() {
+            request = request.bearer_auth(api_token);
+        }
+
+        let resp = request.send().await?;
+        if !resp.status().is_success() {
+            let err_body = resp.text().await.unwrap_or_default();
+            return Err(BoredError::X0xError(err_body));
+        }
+        Ok(())
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(skip(http, api_base, api_token, reader_key, cache_dir, msg), fields(kind = msg.kind()))
+    )]
+    async fn handle_background_msg(
+        http: &reqwest::Client,
+        api_base: &str,
+        api_token: &str,
+        reader_key: Option<&ReaderKey>,
+        cache_dir: &std::path::Path,
+        topic: &str,
+        msg: GossipMsg,
+    ) -> Result<(), BoredError> {
+        // ignore anything on topics that aren't for a bored
+        let address = BoredAddress::from_topic(topic)?;
+
+        // Only process events if we have joined/created the board (indicated by cache existence),
+        // or if the message carries a whole bored (which we can use to discover/join a board from the network).
+        let path = Self::cache_path(cache_dir, &address);
+        if !path.exists() {
+            if !matches!(
+                msg,
+                GossipMsg::SyncResponse { .. } | GossipMsg::Encrypted { .. }
+            ) {
+                return Ok(());
+            }
+        }
+        // anyone can send a whole bored, so it is only taken in reply to this client asking
+        if matches!(
+            msg,
+            GossipMsg::SyncResponse { .. } | GossipMsg::Encrypted { sync: true, .. }
+        ) && !Self::sync_requested(cache_dir, &address)
+        {
+            return Ok(());
+        }
+
+        match msg {
+            GossipMsg::SyncRequest => {
+                if let Some(bored) = Self::load_cache(cache_dir, &address) {
+                    let response_msg = GossipMsg::sync_response(&bored).for_readers(&bored)?;
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!("answering sync request");
+                    let _ = Self::publish_to(http, api_base, api_token, topic, &response_msg).await;
+                }
+            }
+            GossipMsg::FileRequest { address, chunks } => {
+                use std::io::{Read, Seek};
+
+                let Some(hash) = attachment_hash(&address) else {
+                    return Err(BoredError::InvalidAttachment(address));
+                };
+                let Ok(mut file) = std::fs::File::open(Self::attachment_path(cache_dir, hash))
+                else {
+                    return Ok(());
+                };
+                let total = chunk_count(file.metadata()?.len());
+                let chunks = if chunks.is_empty() {
+                    (0..total).collect()
+                } else {
+                    chunks
+                };
+                for index in chunks.into_iter().filter(|index| *index < total) {
+                    // read one chunk at a time so big files aren't held in memory
+                    file.seek(std::io::SeekFrom::Start(
+                        index as u64 * ATTACHMENT_CHUNK_SIZE as u64,
+                    ))?;
+                    let mut chunk = Vec::with_capacity(ATTACHMENT_CHUNK_SIZE);
+                    (&mut file)
+                        .take(ATTACHMENT_CHUNK_SIZE as u64)
+                        .read_to_end(&mut chunk)?;
+                    let chunk_msg = GossipMsg::FileChunk {
+                        address: address.clone(),
+                        index,
+                        total,
+                        data: base64::Engine::encode(&base64::prelude::BASE64_STANDARD, chunk),
+                    };
+                    Self::publish_to(http, api_base, api_token, topic, &chunk_msg).await?;
+                }
+            }
+            GossipMsg::FileChunk {
+                address,
+                index,
+                total,
+                data,
+            } => {
+                let Some(hash) = attachment_hash(&address) else {
+                    return Err(BoredError::InvalidAttachment(address));
+                };
+                // only collect chunks of files this client is downloading
+                let download_dir = Self::download_dir(cache_dir, hash);
+                if !download_dir.exists() {
+                    return Ok(());
+                }
+                let max_chunks = MAX_ATTACHMENT_SIZE.div_ceil(ATTACHMENT_CHUNK_SIZE as u64);
+                if total == 0 || index >= total || total as u64 > max_chunks {
+                    return Err(BoredError::InvalidAttachment(address));
+                }
+                let chunk = base64::Engine::decode(&base64::prelude::BASE64_STANDARD, data)
+                    .map_err(|_| BoredError::InvalidAttachment(address.clone()))?;
+                if chunk.len() > ATTACHMENT_CHUNK_SIZE {
+                    return Err(BoredError::InvalidAttachment(address));
+                }
+                // written under another name first so a chunk is never seen half written
+                let path = Self::chunk_path(&download_dir, index, total);
+                let partial = path.with_extension(format!("{total}.part"));
+                std::fs::write(&partial, chunk)?;
+                std::fs::rename(partial, path)?;
+            }
+            GossipMsg::View { notice_id, reader } => {
+                Self::count_view(cache_dir, &address, &notice_id, &reader)?;
+            }
+            GossipMsg::Encrypted {
+                bored: sealed,
+                sync,
+            } => {
+                let Some(reader_key) = reader_key else {
+                    return Ok(());
+                };
+                let opened = match sealed.open(reader_key) {
+                    Ok(opened) => opened,
+                    // sealed for other readers
+                    Err(BoredError::NotAReader) => return Ok(()),
+                    Err(e) => return Err(e),
+                };
+                let is_new = !path.exists();
+                // handled as the messages it stands for, a reply to a sync request like a sync
+                // response and anything else like a meta message and a notice message for each
+                // of its notices
+                let opened_msgs = if sync {
+                    vec![GossipMsg::sync_response(&opened)]
+                } else {
+                    let notices = opened.notices.iter().cloned();
+                    std::iter::once(GossipMsg::meta(&opened))
+                        .chain(notices.map(|notice| GossipMsg::NoticeMsg { notice }))
+                        .collect()
+                };
+                for msg in opened_msgs {
+                    Box::pin(Self::handle_background_msg(
+                        http,
+                        api_base,
+                        api_token,
+                        Some(reader_key),
+                        cache_dir,
+                        topic,
+                        msg,
+                    ))
+                    .await?;
+                }
+                // readers only change on a bored that was already encrypted, so a peer can't
+                // have the posts to one in the clear sealed away from everyone else
+                if let Some(mut bored) = Self::load_cache(cache_dir, &address)
+                    && (is_new || (bored.is_encrypted() && !sync))
+                    && bored.readers != opened.readers
+                {
+                    bored.readers = opened.readers;
+                    Self::save_cache(cache_dir, &address, &bored)?;
+                }
+            }
+            GossipMsg::Meta {
+                name,
+                dimensions,
+                settings,
+            } => {
+                if !dimensions.within(&MAX_BORED_DIMENSIONS) {
+                    return Err(BoredError::InvalidDimensions(dimensions));
+                }
+                if let Some(mut bored) = Self::load_cache(cache_dir, &address) {
+                    let mut changed = false;
+                    if bored.name == "Untitled Bored" || bored.name == address.get_topic() {
+                        bored.name = name;
+                        bored.dimensions = dimensions;
+                        changed = true;
+                    }
+                    // a meta message is sent as settings change, unless it is from a peer that
+                    // hasn't seen the latest change
+                    if !settings.is_older_than(&bored) && settings.apply(&mut bored)? {
+                        changed = true;
+                    }
+                    if changed {
+                        bored.sanitize()?;
+                        Self::save_cache(cache_dir, &address, &bored)?;
+                    }
                 }
             }
             GossipMsg::NoticeMsg { notice } => {
@@ -435,7 +1539,16 @@ impl X0xBoredClient {
                     }
                 }
             }
-            GossipMsg::SyncResponse { name, dimensions, notices } => {
+            GossipMsg::SyncResponse {
+                name,
+                dimensions,
+                notices,
+                settings,
+            } => {
+                if !dimensions.within(&MAX_BORED_DIMENSIONS) {
+                    return Err(BoredError::InvalidDimensions(dimensions));
+                }
+                let is_new = !Self::cache_path(cache_dir, &address).exists();
                 let mut bored = if let Some(bored) = Self::load_cache(cache_dir, &address) {
                     bored
                 } else {
@@ -449,16 +1562,25 @@ impl X0xBoredClient {
                         changed = true;
                     }
                 }
+                // a responder may be stale, so only settings changed after those known are taken
+                if (is_new || settings.is_newer_than(&bored)) && settings.apply(&mut bored)? {
+                    changed = true;
+                }
+                // notices from a sync may have been posted before the bored asked for proof of
+                // work, those posted since need it as gossiped ones do
                 for notice in notices {
                     let already_exists = bored.notices.iter().any(|n| n.get_notice_id() == notice.get_notice_id());
-                    if !already_exists {
-                        let _ = bored.add(notice.clone(), notice.get_top_left());
+                    if !already_exists
+                        && bored
+                            .add_synced(notice.clone(), notice.get_top_left())
+                            .is_ok()
+                    {
                         changed = true;
                     }
                 }
-                let is_new = !Self::cache_path(cache_dir, &address).exists();
                 if changed || is_new {
-                    let _ = bored.prune_non_visible();
+                    // adopting new dimensions can leave notices out of bounds
+                    bored.sanitize()?;
                     Self::save_cache(cache_dir, &address, &bored)?;
                 }
             }
@@ -468,12 +1590,16 @@ impl X0xBoredClient {
     }
 
     /// Create a new board by subscribing to topic and initializing cache
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
     pub async fn create_bored(
         &mut self,
         name: &str,
         dimensions: Coordinate,
         url_name: Option<&str>,
     ) -> Result<(), BoredError> {
+        if self.read_only {
+            return Err(BoredError::ReadOnlyClient);
+        }
         let address = match url_name {
             None => BoredAddress::new(),
             Some(name) => BoredAddress::from_string(name)?,
@@ -488,22 +1614,370 @@ impl X0xBoredClient {
 
         Self::save_cache(&self.cache_dir, &address, &bored)?;
 
-        let meta_msg = GossipMsg::Meta {
-            name: name.to_string(),
-            dimensions,
+        let update = PendingUpdate {
+            notices_since: None,
+            settings: true,
+        };
+        if Self::allow_update(&self.limiter, &address, update) {
+            self.publish_msg(&topic, &GossipMsg::meta(&bored)).await?;
+        }
+        let size = bored.serialized_size()? as u64;
+        Self::record(
+            &self.cache_dir,
+            LedgerEntry::new(LedgerOperation::CreatedBored, &address.to_string(), 0, size),
+        );
+
+        Ok(())
+    }
+
+    /// Copy the current bored, notices and all, to a new address and make the copy the current
+    /// bored. The address is random unless a url name is given, there are no keys in x0x so
+    /// whoever forks a bored has the same control of the copy as anyone else who knows it.
+    pub async fn fork_bored(&mut self, url_name: Option<&str>) -> Result<BoredAddress, BoredError> {
+        let Some(bored) = self.current_bored.clone() else {
+            return Err(BoredError::NoBored);
+        };
+        self.publish_bored(bored, url_name).await
+    }
+
+    /// Publish a whole bored, such as one built with Bored::from_layout_str, to a new address
+    /// and make it the current bored. Notices without an id are given one in the order they
+    /// are on the bored.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, bored)))]
+    pub async fn publish_bored(
+        &mut self,
+        mut bored: Bored,
+        url_name: Option<&str>,
+    ) -> Result<BoredAddress, BoredError> {
+        let address = match url_name {
+            None => BoredAddress::new(),
+            Some(name) => BoredAddress::from_string(name)?,
+        };
+        if Self::cache_path(&self.cache_dir, &address).exists() {
+            return Err(BoredError::URLNameAlreadyExists(address.to_string()));
+        }
+        let timestamp = chrono::Utc::now().timestamp_millis();
+        for (i, notice) in bored.notices.iter_mut().enumerate() {
+            if notice.get_notice_id().is_empty() {
+                notice.set_notice_id(Self::notice_id(&self.agent_id, timestamp + i as i64));
+            }
+        }
+        let size = bored.serialized_size()?;
+        if size > MAX_BORED_SIZE {
+            return Err(BoredError::BoredTooBig(size));
+        }
+        let topic = address.get_topic();
+        self.subscribe(&topic).await?;
+        Self::save_cache(&self.cache_dir, &address, &bored)?;
+        self.bored_address = Some(address.clone());
+        self.current_bored = Some(bored.clone());
+
+        // peers that don't have the bored yet get its notices by asking for it
+        let update = PendingUpdate {
+            notices_since: None,
+            settings: true,
+        };
+        if Self::allow_update(&self.limiter, &address, update) {
+            self.publish_msg(&topic, &GossipMsg::meta(&bored).for_readers(&bored)?)
+                .await?;
+        }
+        Self::record(
+            &self.cache_dir,
+            LedgerEntry::new(LedgerOperation::CreatedBored, &address.to_string(), 0, size as u64),
+        );
+        Ok(address)
+    }
+
+    /// Whether a change to a bored can be published now, if too many have been it is kept in
+    /// the cache to be sent by publish_pending
+    fn allow_update(
+        limiter: &std::sync::Mutex<UpdateLimiter>,
+        address: &BoredAddress,
+        update: PendingUpdate,
+    ) -> bool {
+        limiter
+            .lock()
+            .map(|mut limiter| limiter.allow(address, update, std::time::Instant::now()))
+            .unwrap_or(true)
+    }
+
+    /// Whether there are changes held back for posting too quickly that publish_pending
+    /// should send
+    pub fn has_pending_updates(&self) -> bool {
+        self.limiter
+            .lock()
+            .is_ok_and(|limiter| !limiter.pending.is_empty())
+    }
+
+    /// Publish changes held back for posting too quickly as there is room for them, the changes
+    /// to each bored are sent together, its settings as they are in the cache. Returns how many
+    /// boreds were published.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn publish_pending(&self) -> Result<usize, BoredError> {
+        let mut published = 0;
+        loop {
+            let Some((address, update)) = self
+                .limiter
+                .lock()
+                .ok()
+                .and_then(|mut limiter| limiter.take_pending(std::time::Instant::now()))
+            else {
+                return Ok(published);
+            };
+            let Some(bored) = Self::load_cache(&self.cache_dir, &address) else {
+                continue;
+            };
+            let topic = address.get_topic();
+            let mut result = Ok(());
+            if bored.is_encrypted() {
+                // sealed it carries both the settings and the notices
+                result = match GossipMsg::meta(&bored).for_readers(&bored) {
+                    Ok(msg) => self.publish_msg(&topic, &msg).await,
+                    Err(e) => Err(e),
+                };
+            } else if update.settings {
+                result = self.publish_msg(&topic, &GossipMsg::meta(&bored)).await;
+            }
+            if let Some(since) = update.notices_since
+                && result.is_ok()
+                && !bored.is_encrypted()
+            {
+                let own_suffix = format!(":{}", Self::agent_prefix(&self.agent_id));
+                let posted = bored.notices.iter().filter(|notice| {
+                    notice.get_timestamp().is_some_and(|posted| posted >= since)
+                        && notice.get_notice_id().ends_with(&own_suffix)
+                });
+                for notice in posted {
+                    let notice_msg = GossipMsg::NoticeMsg {
+                        notice: notice.clone(),
+                    };
+                    result = self.publish_msg(&topic, &notice_msg).await;
+                    if result.is_err() {
+                        break;
+                    }
+                }
+            }
+            if let Err(e) = result {
+                // keep it to try again next time
+                if let Ok(mut limiter) = self.limiter.lock() {
+                    limiter.pending.insert(0, (address, update));
+                }
+                return Err(e);
+            }
+            published += 1;
+        }
+    }
+
+    /// Globally unique notice key: notice:<timestamp>:<agent_id_prefix>
+    fn notice_id(agent_id: &str, timestamp: i64) -> String {
+        format!("notice:{}:{}", timestamp, Self::agent_prefix(agent_id))
+    }
+
+    /// The part of an agent id that ends the ids of the notices it posts
+    fn agent_prefix(agent_id: &str) -> &str {
+        if agent_id.len() >= 8 {
+            &agent_id[0..8]
+        } else {
+            "local"
+        }
+    }
+
+    /// Refuse to change the current bored if it was opened with a read only or expired link
+    fn check_can_write(&self) -> Result<(), BoredError> {
+        if self.read_only {
+            return Err(BoredError::ReadOnlyClient);
+        }
+        let Some(share_link) = &self.share_link else {
+            return Ok(());
+        };
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+        if let Some(expires) = share_link.get_expires()
+            && share_link.is_expired(now)
+        {
+            return Err(BoredError::ShareLinkExpired(expires));
+        }
+        if !share_link.can_write(now) {
+            return Err(BoredError::ReadOnlyBored);
+        }
+        Ok(())
+    }
+
+    /// Change a setting of the current bored and broadcast the new settings to peers
+    async fn update_settings(
+        &mut self,
+        update: impl FnOnce(&mut Bored) -> Result<(), BoredError>,
+    ) -> Result<(), BoredError> {
+        self.check_can_write()?;
+        let Some(bored) = &mut self.current_bored else {
+            return Err(BoredError::NoBored);
+        };
+        let Some(bored_address) = &self.bored_address else {
+            return Err(BoredError::NoBored);
+        };
+        let topic = bored_address.get_topic();
+        let previous = bored.clone();
+        update(bored)?;
+        // later than any change already known so peers take it
+        bored.settings_changed_at = chrono::Utc::now()
+            .timestamp_millis()
+            .max(previous.settings_changed_at + 1);
+        if self.keep_history {
+            Self::archive_version(&self.cache_dir, bored_address, &previous)?;
+        }
+        Self::save_cache(&self.cache_dir, bored_address, bored)?;
+
+        let size = bored.serialized_size()? as u64;
+        let entry =
+            LedgerEntry::new(LedgerOperation::ChangedSettings, &bored_address.to_string(), 0, size);
+
+        let update = PendingUpdate {
+            notices_since: None,
+            settings: true,
         };
-        self.publish_msg(&topic, &meta_msg).await?;
+        if Self::allow_update(&self.limiter, bored_address, update) {
+            let meta_msg = GossipMsg::meta(bored).for_readers(bored)?;
+            self.publish_msg(&topic, &meta_msg).await?;
+        }
+        Self::record(&self.cache_dir, entry);
+        Ok(())
+    }
+
+    /// Set the background decoration of the current bored and broadcast it to peers
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn set_background(&mut self, background: Background) -> Result<(), BoredError> {
+        self.update_settings(|bored| {
+            bored.set_background(background);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Set the colours the current bored would like to be shown in and broadcast them to peers
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn set_palette(&mut self, palette: Palette) -> Result<(), BoredError> {
+        self.update_settings(|bored| {
+            bored.set_palette(palette);
+            Ok(())
+        })
+        .await
+    }
+
+    /// Require notices added to the current bored from now on to carry a proof of work of
+    /// this many leading zero bits, 0 turns the requirement off. As anyone who knows a bored
+    /// can change its settings this slows spammers down rather than stopping them.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn set_pow_difficulty(&mut self, difficulty: u8) -> Result<(), BoredError> {
+        self.update_settings(|bored| bored.set_pow_difficulty(difficulty))
+            .await
+    }
+
+    /// Hide a notice on the current bored from everyone viewing it without removing it. There
+    /// are no owner keys in x0x so, like the other settings, anyone who knows the bored can
+    /// change what is hidden.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn hide_notice(&mut self, notice: &Notice) -> Result<(), BoredError> {
+        let hash = notice.get_hash();
+        self.update_settings(|bored| {
+            let mut hidden_notices = bored.get_hidden_notices().clone();
+            if !hidden_notices.contains(&hash) {
+                hidden_notices.push(hash);
+            }
+            bored.set_hidden_notices(hidden_notices)
+        })
+        .await
+    }
+
+    /// Show a notice hidden with hide_notice again, by its hash
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn unhide_notice(&mut self, hash: &str) -> Result<(), BoredError> {
+        self.update_settings(|bored| {
+            let mut hidden_notices = bored.get_hidden_notices().clone();
+            hidden_notices.retain(|hidden| hidden != hash);
+            bored.set_hidden_notices(hidden_notices)
+        })
+        .await
+    }
+
+    /// Encrypt the current bored for these readers from now on, so only they can read what is
+    /// published to it, none to publish it in the clear. Anyone who knows the bored can still
+    /// publish to it and what was published before stays readable to those who saw it.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn set_readers(&mut self, readers: &[ReaderPublicKey]) -> Result<(), BoredError> {
+        self.update_settings(|bored| bored.set_readers(readers))
+            .await
+    }
 
+    /// Retrieve and enter the bored a share link points to, changes to it are refused for the
+    /// rest of the visit if the link is read only or once it expires
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(link = %share_link)))]
+    pub async fn go_to_share_link(&mut self, share_link: &ShareLink) -> Result<(), BoredError> {
+        let now = chrono::Utc::now().timestamp().max(0) as u64;
+        if let Some(expires) = share_link.get_expires()
+            && share_link.is_expired(now)
+        {
+            return Err(BoredError::ShareLinkExpired(expires));
+        }
+        self.go_to_bored(share_link.get_address()).await?;
+        self.share_link = Some(share_link.clone());
         Ok(())
     }
 
-    /// Retrieve and enter an existing bored topic
+    /// What the current bored was opened with permission to do
+    pub fn get_capability(&self) -> Capability {
+        match &self.share_link {
+            Some(share_link) => share_link.get_capability(),
+            None => Capability::ReadWrite,
+        }
+    }
+
+    /// Make a link to the current bored for sharing, which can't grant more than the link it was
+    /// opened with or outlive it
+    pub fn share_link(
+        &self,
+        capability: Capability,
+        expires: Option<u64>,
+    ) -> Result<ShareLink, BoredError> {
+        let bored_address = self.get_bored_address()?;
+        let Some(own_link) = &self.share_link else {
+            return Ok(ShareLink::create(bored_address, capability, expires));
+        };
+        if capability == Capability::ReadWrite {
+            self.check_can_write()?;
+        }
+        let expires = match (expires, own_link.get_expires()) {
+            (Some(expires), Some(own_expires)) => Some(expires.min(own_expires)),
+            (expires, own_expires) => expires.or(own_expires),
+        };
+        Ok(ShareLink::create(bored_address, capability, expires))
+    }
+
+    /// Say the current bored has moved to another address so readers are told where it is now,
+    /// none to stop telling them. Like the other settings anyone who knows the bored can change
+    /// it, so readers are never sent there without choosing to go.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn set_moved_to(&mut self, moved_to: Option<BoredAddress>) -> Result<(), BoredError> {
+        if let (Some(moved_to), Ok(address)) = (&moved_to, self.get_bored_address())
+            && moved_to.get_topic() == address.get_topic()
+        {
+            return Err(BoredError::RedirectLoop(address.to_string()));
+        }
+        self.update_settings(|bored| {
+            bored.set_moved_to(moved_to.as_ref());
+            Ok(())
+        })
+        .await
+    }
+
+    /// Retrieve and enter an existing bored topic. One that says it has moved isn't followed, as
+    /// anyone who knows it can say so, see Bored::get_moved_to.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(address = %bored_address)))]
     pub async fn go_to_bored(&mut self, bored_address: &BoredAddress) -> Result<(), BoredError> {
         let bored_address = bored_address.clone();
         let topic = bored_address.get_topic();
 
         self.subscribe(&topic).await?;
         self.bored_address = Some(bored_address.clone());
+        self.share_link = None;
 
         let cache_path = Self::cache_path(&self.cache_dir, &bored_address);
         if !cache_path.exists() {
@@ -537,26 +2011,37 @@ impl X0xBoredClient {
         }
     }
 
+    /// Enter a bored as it is in the cache, without asking peers for anything newer
+    pub fn open_cached(&mut self, bored_address: &BoredAddress) -> Result<(), BoredError> {
+        let bored = self.get_cached_bored(bored_address)?;
+        self.bored_address = Some(bored_address.clone());
+        self.current_bored = Some(bored);
+        self.share_link = None;
+        Ok(())
+    }
+
     /// Retrieve and process gossip events for Bored Address
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self), fields(address = %bored_address)))]
     pub async fn retrieve_bored(
         &mut self,
         bored_address: &BoredAddress,
     ) -> Result<(Bored, u64), BoredError> {
-        if let Some(bored) = Self::load_cache(&self.cache_dir, bored_address) {
+        if let Some(bored) = Self::read_cache(&self.cache_dir, bored_address)? {
             self.current_bored = Some(bored.clone());
-            return Ok((bored.clone(), bored.get_notices().len() as u64));
+            return Ok((bored.clone(), bored.notices().len() as u64));
         }
 
         if self.bored_address.as_ref() == Some(bored_address)
             && let Some(ref bored) = self.current_bored
         {
-            return Ok((bored.clone(), bored.get_notices().len() as u64));
+            return Ok((bored.clone(), bored.notices().len() as u64));
         }
 
         Err(BoredError::NoBored)
     }
 
     /// Refresh the current bored state from network
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
     pub async fn refresh_bored(&mut self) -> Result<(), BoredError> {
         let Some(address) = self.bored_address.clone() else {
             return Err(BoredError::NoBored);
@@ -577,17 +2062,148 @@ impl X0xBoredClient {
         }
     }
 
+    /// Ask peers for what they have of the current bored without waiting for them, their replies
+    /// reach the cache in the background
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub async fn request_sync(&self) -> Result<(), BoredError> {
+        let Some(address) = &self.bored_address else {
+            return Err(BoredError::NoBored);
+        };
+        self.publish_msg(&address.get_topic(), &GossipMsg::SyncRequest)
+            .await
+    }
+
+    /// Tell the current bored the notice was opened, if views are counted. Readers are told
+    /// apart by an id derived from the notice id, so opening it again isn't counted twice.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all))]
+    pub async fn record_view(&self, notice: &Notice) -> Result<(), BoredError> {
+        if !self.count_views || self.agent_id.is_empty() || notice.get_notice_id().is_empty() {
+            return Ok(());
+        }
+        let Some(address) = &self.bored_address else {
+            return Err(BoredError::NoBored);
+        };
+        let notice_id = notice.get_notice_id().to_string();
+        let reader = get_reader_id(&self.agent_id, &notice_id);
+        // counted here too in case the daemon doesn't hand back what was published
+        Self::count_view(&self.cache_dir, address, &notice_id, &reader)?;
+        self.publish_msg(&address.get_topic(), &GossipMsg::View { notice_id, reader })
+            .await
+    }
+
+    /// How many readers have opened each notice on the current bored, counted from what
+    /// readers that count views have told it while this client's daemon was listening
+    pub fn get_notice_stats(&self) -> Result<Vec<NoticeStats>, BoredError> {
+        let Some(address) = &self.bored_address else {
+            return Err(BoredError::NoBored);
+        };
+        let bored = self.current_bored()?;
+        Ok(Self::load_views(&self.cache_dir, address).get_stats(&bored))
+    }
+
+    /// Fetch several boreds at once without changing the current bored, sync requests for all
+    /// of them are sent together so the waits for peers to respond overlap. Results are in the
+    /// same order as the addresses. Only the current bored stays subscribed to afterwards, so
+    /// fetching many boreds doesn't leave the daemon listening to all of them.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip_all, fields(count = addresses.len())))]
+    pub async fn fetch_boreds(
+        &self,
+        addresses: &[BoredAddress],
+    ) -> Vec<Result<Bored, BoredError>> {
+        let mut subscribed = vec![];
+        for address in addresses {
+            subscribed.push(self.subscribe(&address.get_topic()).await);
+        }
+        let all_cached = addresses
+            .iter()
+            .all(|a| Self::cache_path(&self.cache_dir, a).exists());
+        let (attempts, wait) = if all_cached {
+            (REFRESH_SYNC_ATTEMPTS, REFRESH_SYNC_WAIT)
+        } else {
+            (DISCOVERY_SYNC_ATTEMPTS, DISCOVERY_SYNC_WAIT)
+        };
+        for _ in 0..attempts {
+            for (address, subscribed) in addresses.iter().zip(&subscribed) {
+                if subscribed.is_ok() {
+                    let _ = self
+                        .publish_msg(&address.get_topic(), &GossipMsg::SyncRequest)
+                        .await;
+                }
+            }
+            tokio::time::sleep(wait).await;
+            if !all_cached
+                && addresses
+                    .iter()
+                    .all(|a| Self::cache_path(&self.cache_dir, a).exists())
+            {
+                break;
+            }
+        }
+        let current_topic = self.bored_address.as_ref().map(BoredAddress::get_topic);
+        for (address, subscribed) in addresses.iter().zip(&subscribed) {
+            if let Ok(Some(subscription_id)) = subscribed
+                && current_topic != Some(address.get_topic())
+            {
+                // a subscription left open only costs the daemon some work, so failing is ignored
+                let _ = self.unsubscribe(subscription_id).await;
+            }
+        }
+        addresses
+            .iter()
+            .zip(subscribed)
+            .map(|(address, subscribed)| {
+                subscribed?;
+                Self::load_cache(&self.cache_dir, address)
+                    .ok_or_else(|| BoredError::BoardDoesNotExist(address.to_string()))
+            })
+            .collect()
+    }
+
     /// Returns the cached current bored
     pub fn get_current_bored(&self) -> Result<Bored, BoredError> {
+        self.current_bored().map(std::sync::Arc::unwrap_or_clone)
+    }
+
+    /// The current bored shared rather than copied, the same one is handed out until the bored
+    /// changes so looking at it often, such as every frame, is cheap
+    pub fn current_bored(&self) -> Result<std::sync::Arc<Bored>, BoredError> {
+        let mut shared_bored = self
+            .shared_bored
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        // the cache is written by the background loop so is newer than memory
         if let Some(address) = &self.bored_address
-            && let Some(bored) = Self::load_cache(&self.cache_dir, address)
+            && let Ok(content) = std::fs::read_to_string(Self::cache_path(&self.cache_dir, address))
         {
-            return Ok(bored);
+            if let Some(shared) = shared_bored.as_ref()
+                && shared.source.as_ref() == Some(&content)
+            {
+                return Ok(shared.bored.clone());
+            }
+            if let Ok(bored) = Self::parse_cache(&content) {
+                let bored = std::sync::Arc::new(bored);
+                *shared_bored = Some(SharedBored {
+                    source: Some(content),
+                    bored: bored.clone(),
+                });
+                return Ok(bored);
+            }
         }
 
-        let Some(bored) = self.current_bored.clone() else {
+        let Some(current_bored) = &self.current_bored else {
             return Err(BoredError::NoBored);
         };
+        if let Some(shared) = shared_bored.as_ref()
+            && shared.source.is_none()
+            && *shared.bored == *current_bored
+        {
+            return Ok(shared.bored.clone());
+        }
+        let bored = std::sync::Arc::new(current_bored.clone());
+        *shared_bored = Some(SharedBored {
+            source: None,
+            bored: bored.clone(),
+        });
         Ok(bored)
     }
 
@@ -599,6 +2215,38 @@ impl X0xBoredClient {
         Ok(bored_address.clone())
     }
 
+    /// Addresses and names of the boreds in the cache, which are those this client has been to
+    /// or created, sorted by name
+    pub fn get_cached_boreds(&self) -> Vec<(BoredAddress, String)> {
+        let mut boreds = vec![];
+        let Ok(entries) = std::fs::read_dir(&self.cache_dir) else {
+            return boreds;
+        };
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.extension().is_none_or(|extension| extension != "json") {
+                continue;
+            }
+            let Some(address) = path
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|topic| BoredAddress::from_topic(topic).ok())
+            else {
+                continue;
+            };
+            if let Ok(Some(bored)) = Self::read_cache(&self.cache_dir, &address) {
+                boreds.push((address, bored.name));
+            }
+        }
+        boreds.sort_by(|a, b| a.1.cmp(&b.1));
+        boreds
+    }
+
+    /// The bored as it is in the cache, without asking peers for anything newer
+    pub fn get_cached_bored(&self, bored_address: &BoredAddress) -> Result<Bored, BoredError> {
+        Self::read_cache(&self.cache_dir, bored_address)?
+            .ok_or_else(|| BoredError::BoardDoesNotExist(bored_address.to_string()))
+    }
+
     /// Get current bored name
     pub fn get_bored_name(&self) -> Result<&str, BoredError> {
         let Some(bored) = &self.current_bored else {
@@ -609,6 +2257,7 @@ impl X0xBoredClient {
 
     /// Create a draft notice that fits on the board
     pub fn create_draft(&mut self, dimensions: Coordinate) -> Result<(), BoredError> {
+        self.check_can_write()?;
         let Some(bored) = &self.current_bored else {
             return Err(BoredError::NoBored);
         };
@@ -636,23 +2285,182 @@ impl X0xBoredClient {
             notice.write(content)?;
             self.draft_notice = Some(notice);
         }
-        Ok(())
+        Ok(())
+    }
+
+    /// Relocate the draft notice
+    pub fn position_draft(&mut self, new_top_left: Coordinate) -> Result<(), BoredError> {
+        let Some(bored) = &self.current_bored else {
+            return Err(BoredError::NoBored);
+        };
+        if let Some(mut notice) = self.draft_notice.clone() {
+            notice.relocate(bored, new_top_left)?;
+            self.draft_notice = Some(notice);
+        }
+        Ok(())
+    }
+
+    /// Make the draft notice an event, or text again
+    pub fn set_draft_kind(&mut self, kind: NoticeKind) -> Result<(), BoredError> {
+        let Some(notice) = &mut self.draft_notice else {
+            return Err(BoredError::NoNotice);
+        };
+        notice.set_kind(kind)
+    }
+
+    /// Move the draft notice to where Bored::append_in_reading_order would put it, the first
+    /// free space on the board big enough for it reading left to right then top to bottom
+    pub fn place_draft_in_reading_order(&mut self) -> Result<Coordinate, BoredError> {
+        let Some(bored) = &self.current_bored else {
+            return Err(BoredError::NoBored);
+        };
+        let Some(notice) = &mut self.draft_notice else {
+            return Err(BoredError::NoNotice);
+        };
+        let top_left = WhatsOnTheBored::create(bored)
+            .get_first_free_in_reading_order(notice.get_dimensions())
+            .ok_or(BoredError::NoFreeSpace(notice.get_dimensions()))?;
+        notice.relocate(bored, top_left)?;
+        Ok(top_left)
+    }
+
+    /// Change the size of the draft notice, it has to stay on the board and fit its text
+    pub fn resize_draft(&mut self, new_dimensions: Coordinate) -> Result<(), BoredError> {
+        let Some(bored) = &self.current_bored else {
+            return Err(BoredError::NoBored);
+        };
+        if let Some(mut notice) = self.draft_notice.clone() {
+            notice.resize(bored, new_dimensions)?;
+            self.draft_notice = Some(notice);
+        }
+        Ok(())
+    }
+
+    /// Attach a file to the draft notice. The file is kept with the cache and sent to peers
+    /// that ask for it, so it stays available while this client or another that downloaded it
+    /// is online.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
+    pub fn attach_file(&mut self, path: &std::path::Path) -> Result<Attachment, BoredError> {
+        self.check_can_write()?;
+        let Some(notice) = &mut self.draft_notice else {
+            return Err(BoredError::NoNotice);
+        };
+        let size = std::fs::metadata(path)?.len();
+        if size > MAX_ATTACHMENT_SIZE {
+            return Err(BoredError::AttachmentTooBig(size));
+        }
+        let filename = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let content = std::fs::read(path)?;
+        let attachment = Attachment::create(&content, &filename)?;
+        let stored = Self::attachment_path(&self.cache_dir, attachment.get_hash());
+        if let Some(parent) = stored.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(stored, content)?;
+        notice.set_attachment(Some(attachment.clone()))?;
+        Ok(attachment)
+    }
+
+    /// Download a file attached to a notice on the current bored into dir, returning where it
+    /// was saved. The content is checked against the attachment's address before it is saved
+    /// and existing files are never replaced.
+    pub async fn download_file(
+        &self,
+        attachment: &Attachment,
+        dir: &std::path::Path,
+    ) -> Result<std::path::PathBuf, BoredError> {
+        self.download_file_with_progress(attachment, dir, None).await
+    }
+
+    /// Download a file like download_file, sending progress as chunks arrive. Chunks are
+    /// written to disk as they arrive and kept if the download fails, so downloading the same
+    /// file again only asks peers for the chunks still missing.
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self, attachment, progress), fields(address = attachment.get_address())))]
+    pub async fn download_file_with_progress(
+        &self,
+        attachment: &Attachment,
+        dir: &std::path::Path,
+        progress: Option<tokio::sync::mpsc::UnboundedSender<DownloadProgress>>,
+    ) -> Result<std::path::PathBuf, BoredError> {
+        attachment.validate()?;
+        let stored = Self::attachment_path(&self.cache_dir, attachment.get_hash());
+        if stored.exists() {
+            if attachment_address_from_reader(std::fs::File::open(&stored)?)?
+                != attachment.get_address()
+            {
+                let _ = std::fs::remove_file(&stored);
+                return Err(BoredError::ChecksumMismatch(attachment.get_address().to_string()));
+            }
+        } else {
+            // fetching keeps a copy in the store so this client can send it to peers too
+            self.fetch_attachment(attachment, progress.as_ref()).await?;
+        }
+        if let Some(progress) = &progress {
+            let total = attachment.get_size();
+            let _ = progress.send(DownloadProgress {
+                received: total,
+                total,
+            });
+        }
+        Self::save_download(dir, &attachment.safe_filename(), &stored)
     }
 
-    /// Relocate the draft notice
-    pub fn position_draft(&mut self, new_top_left: Coordinate) -> Result<(), BoredError> {
-        let Some(bored) = &self.current_bored else {
+    /// Ask peers on the current bored for the chunks of a file still missing and wait for
+    /// them, then add the file to the attachment store
+    async fn fetch_attachment(
+        &self,
+        attachment: &Attachment,
+        progress: Option<&tokio::sync::mpsc::UnboundedSender<DownloadProgress>>,
+    ) -> Result<(), BoredError> {
+        let Some(bored_address) = &self.bored_address else {
             return Err(BoredError::NoBored);
         };
-        if let Some(mut notice) = self.draft_notice.clone() {
-            notice.relocate(bored, new_top_left)?;
-            self.draft_notice = Some(notice);
+        let topic = bored_address.get_topic();
+        let download_dir = Self::download_dir(&self.cache_dir, attachment.get_hash());
+        std::fs::create_dir_all(&download_dir)?;
+        let total = chunk_count(attachment.get_size());
+        let mut reported = None;
+        for attempt in 0..DOWNLOAD_ATTEMPTS {
+            let (missing, received) = Self::missing_chunks(&download_dir, total)?;
+            if let Some(progress) = progress
+                && reported != Some(received)
+            {
+                reported = Some(received);
+                let _ = progress.send(DownloadProgress {
+                    received,
+                    total: attachment.get_size(),
+                });
+            }
+            if missing.is_empty() {
+                return Self::finish_download(&self.cache_dir, attachment, total);
+            }
+            if attempt % DOWNLOAD_REQUEST_EVERY == 0 {
+                let chunks = if missing.len() == total as usize {
+                    vec![]
+                } else {
+                    missing
+                };
+                let request = GossipMsg::FileRequest {
+                    address: attachment.get_address().to_string(),
+                    chunks,
+                };
+                self.publish_msg(&topic, &request).await?;
+            }
+            tokio::time::sleep(DOWNLOAD_WAIT).await;
         }
-        Ok(())
+        Err(BoredError::FileNotFound(attachment.get_address().to_string()))
     }
 
     /// Write notice and publish via gossip message
+    #[cfg_attr(feature = "tracing", tracing::instrument(skip(self)))]
     pub async fn add_draft_to_bored(&mut self) -> Result<(), BoredError> {
+        self.check_can_write()?;
+        if let Some(bored) = &self.current_bored {
+            self.check_work_limit(bored.get_pow_difficulty())?;
+        }
         let Some(bored) = &mut self.current_bored else {
             return Err(BoredError::NoBored);
         };
@@ -662,28 +2470,72 @@ impl X0xBoredClient {
         let topic = bored_address.get_topic();
 
         if let Some(mut notice) = self.draft_notice.clone() {
-            // Generate globally unique notice key: notice:<timestamp>:<agent_id_prefix>
             let timestamp = chrono::Utc::now().timestamp_millis();
-            let agent_prefix = if self.agent_id.len() >= 8 {
-                &self.agent_id[0..8]
-            } else {
-                "local"
-            };
-            let notice_key = format!("notice:{}:{}", timestamp, agent_prefix);
-            notice.set_notice_id(notice_key);
+            notice.set_notice_id(Self::notice_id(&self.agent_id, timestamp));
+            let pow_difficulty = bored.get_pow_difficulty();
+            if pow_difficulty > 0 {
+                // hashing can take a while so keep it off the async runtime
+                notice = tokio::task::spawn_blocking(move || {
+                    notice.prove_work(pow_difficulty);
+                    notice
+                })
+                .await
+                .map_err(|e| BoredError::IOError(ErrorSource::new(e)))?;
+                self.work_done.fetch_add(
+                    expected_hashes(pow_difficulty),
+                    std::sync::atomic::Ordering::Relaxed,
+                );
+            }
+
+            // Refuse before publishing anything peers would be unable to sync
+            if !bored.would_fit_with(&notice)? {
+                return Err(BoredError::BoredTooBig(bored.size_with(&notice)?));
+            }
+
+            if self.keep_history {
+                Self::archive_version(&self.cache_dir, bored_address, bored)?;
+            }
 
             // Add locally
             bored.add(notice.clone(), notice.get_top_left())?;
             bored.prune_non_visible()?;
+            let bored_size = bored.serialized_size()?;
 
             // Save cache
             Self::save_cache(&self.cache_dir, bored_address, bored)?;
 
-            // Publish notice via gossip Msg
-            let notice_msg = GossipMsg::NoticeMsg {
-                notice: notice.clone(),
+            // Publish notice via gossip Msg, or later with any others if posting too quickly
+            let update = PendingUpdate {
+                notices_since: Some(timestamp),
+                settings: false,
             };
-            self.publish_msg(&topic, &notice_msg).await?;
+            if Self::allow_update(&self.limiter, bored_address, update) {
+                let notice_msg = GossipMsg::NoticeMsg {
+                    notice: notice.clone(),
+                }
+                .for_readers(bored)?;
+                self.publish_msg(&topic, &notice_msg).await?;
+            }
+            Self::record(
+                &self.cache_dir,
+                LedgerEntry::new(
+                    LedgerOperation::PostedNotice,
+                    &bored_address.to_string(),
+                    pow_difficulty,
+                    bored_size as u64,
+                ),
+            );
+            if let Some(attachment) = notice.get_attachment() {
+                Self::record(
+                    &self.cache_dir,
+                    LedgerEntry::new(
+                        LedgerOperation::SharedFile,
+                        attachment.get_address(),
+                        0,
+                        attachment.get_size(),
+                    ),
+                );
+            }
 
             self.draft_notice = None;
         }
@@ -691,10 +2543,34 @@ impl X0xBoredClient {
         Ok(())
     }
 
+    /// Dry run of posting a notice to the current bored, so the user can see what it will take
+    /// before committing to it. Fails for the same reasons posting would.
+    pub fn estimate_posting(&self, notice: &Notice) -> Result<PostingEstimate, BoredError> {
+        self.check_can_write()?;
+        let Some(bored) = &self.current_bored else {
+            return Err(BoredError::NoBored);
+        };
+        self.check_work_limit(bored.get_pow_difficulty())?;
+        let mut updated = bored.clone();
+        // the work hasn't been done yet so don't ask for it
+        updated.set_pow_difficulty(0)?;
+        updated.add(notice.clone(), notice.get_top_left())?;
+        let bored_size = updated.serialized_size()?;
+        if bored_size > MAX_BORED_SIZE {
+            return Err(BoredError::BoredTooBig(bored_size));
+        }
+        Ok(PostingEstimate {
+            pow_difficulty: bored.get_pow_difficulty(),
+            bored_size,
+            pruned_notices: bored.notices().len() + 1 - updated.notices().len(),
+        })
+    }
+
     /// Load standard board
     pub fn load_app_bored(&mut self, bored: Bored) {
         self.current_bored = Some(bored);
         self.bored_address = None;
+        self.share_link = None;
     }
 }
 
@@ -718,16 +2594,71 @@ mod x0x_tests {
     ) -> X0xBoredClient {
         X0xBoredClient {
             http: reqwest::Client::new(),
-            api_base: "http://127.0.0.1:0".to_string(),
-            api_token: String::new(),
+            endpoint: std::sync::Arc::new(std::sync::RwLock::new(Endpoint {
+                api_base: "http://127.0.0.1:0".to_string(),
+                api_token: String::new(),
+            })),
+            daemon: X0xDaemon::Standard,
             agent_id: "test-agent".to_string(),
             current_bored: Some(current_bored),
             draft_notice: None,
             bored_address: Some(address),
             cache_dir,
+            keep_history: false,
+            read_only: false,
+            count_views: false,
+            share_link: None,
+            limiter: Default::default(),
+            shared_bored: Default::default(),
+            work_limit: WorkLimit::default(),
+            work_done: Default::default(),
+            reader_key: Default::default(),
         }
     }
 
+    /// A stand in for the daemon's api that answers every request, handing out numbered
+    /// subscription ids, and sends the method and path of each request down the channel
+    async fn fake_daemon() -> (String, tokio::sync::mpsc::UnboundedReceiver<String>) {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind fake daemon");
+        let address = listener.local_addr().expect("fake daemon address");
+        let api_base = format!("http://{address}");
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut subscriptions = 0;
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = vec![];
+                let mut buffer = [0; 4096];
+                // each request is on its own connection so reading the head is enough
+                while !request.windows(4).any(|end| end == b"\r\n\r\n") {
+                    match stream.read(&mut buffer).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => request.extend_from_slice(&buffer[..read]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request);
+                let line: Vec<&str> = request.split_whitespace().take(2).collect();
+                let line = line.join(" ");
+                let body = if line == "POST /subscribe" {
+                    subscriptions += 1;
+                    format!(r#"{{"ok":true,"subscription_id":"sub-{subscriptions}"}}"#)
+                } else {
+                    r#"{"ok":true}"#.to_string()
+                };
+                let _ = sender.send(line);
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        (api_base, receiver)
+    }
+
     #[test]
     fn get_current_bored_prefers_cache_over_stale_memory() {
         let cache_dir = test_cache_dir();
@@ -744,6 +2675,1012 @@ mod x0x_tests {
         let _ = std::fs::remove_dir_all(cache_dir);
     }
 
+    #[test]
+    fn current_bored_is_shared_until_it_changes() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.shared").expect("valid address");
+        let bored = Bored::create("shared", Coordinate { x: 20, y: 20 });
+        X0xBoredClient::save_cache(&cache_dir, &address, &bored).expect("save cache");
+        let mut client = test_client(cache_dir.clone(), address.clone(), bored);
+
+        let first = client.current_bored().expect("current bored");
+        let second = client.clone().current_bored().expect("current bored");
+        assert!(std::sync::Arc::ptr_eq(&first, &second));
+        // the background loop writes changes from peers to the cache
+        let changed = Bored::create("changed", Coordinate { x: 20, y: 20 });
+        X0xBoredClient::save_cache(&cache_dir, &address, &changed).expect("save cache");
+        let third = client.current_bored().expect("current bored");
+        assert!(!std::sync::Arc::ptr_eq(&second, &third));
+        assert_eq!(*third, changed);
+
+        // boreds only in memory are shared too
+        let app_bored = Bored::create("app", Coordinate { x: 30, y: 10 });
+        client.load_app_bored(app_bored.clone());
+        let first = client.current_bored().expect("current bored");
+        assert!(std::sync::Arc::ptr_eq(&first, &client.current_bored().expect("current bored")));
+        assert_eq!(*first, app_bored);
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[test]
+    fn get_cached_bored_reads_only_the_cache() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.cached").expect("valid address");
+        let other = BoredAddress::from_string("bored.test.not-cached").expect("valid address");
+        let cached = Bored::create("cached", Coordinate { x: 20, y: 20 });
+        X0xBoredClient::save_cache(&cache_dir, &address, &cached).expect("save cache");
+
+        let client = test_client(cache_dir.clone(), address.clone(), cached.clone());
+        assert_eq!(client.get_cached_bored(&address), Ok(cached.clone()));
+        // the current bored isn't used in place of one that isn't cached
+        let client = test_client(cache_dir.clone(), other.clone(), cached);
+        assert_eq!(
+            client.get_cached_bored(&other),
+            Err(BoredError::BoardDoesNotExist(other.to_string()))
+        );
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[test]
+    fn daemon_api_and_cache() {
+        assert_eq!(get_api_base("12701"), "http://127.0.0.1:12701");
+        assert_eq!(get_api_base("10.0.0.2:12700"), "http://10.0.0.2:12700");
+        assert_eq!(get_api_base("https://x0x.example/"), "https://x0x.example");
+        let (api_base, _) = X0xDaemon::Custom("10.0.0.2:12700".to_string()).get_api_credentials();
+        assert_eq!(api_base, "http://10.0.0.2:12700");
+
+        let data_dir = std::path::Path::new("data");
+        assert_eq!(X0xDaemon::Standard.get_cache_dir(data_dir), data_dir.join("cache"));
+        assert_eq!(
+            X0xDaemon::Custom("12701".to_string()).get_cache_dir(data_dir),
+            data_dir.join("cache")
+        );
+        assert_eq!(
+            X0xDaemon::Named("testnet".to_string()).get_cache_dir(data_dir),
+            data_dir.join("cache-testnet")
+        );
+        if let Some(x0x_data_dir) = get_x0x_data_dir() {
+            assert_eq!(
+                X0xDaemon::Named("testnet".to_string()).get_data_dir(),
+                Some(std::path::PathBuf::from(format!("{}-testnet", x0x_data_dir.display())))
+            );
+        }
+    }
+
+    #[test]
+    fn update_limiter_holds_back_and_coalesces() {
+        let mut limiter = UpdateLimiter::default();
+        let first = BoredAddress::from_string("bored.test.limit-first").expect("valid address");
+        let second = BoredAddress::from_string("bored.test.limit-second").expect("valid address");
+        let notices = PendingUpdate {
+            notices_since: Some(20),
+            settings: false,
+        };
+        let settings = PendingUpdate {
+            notices_since: None,
+            settings: true,
+        };
+        let start = std::time::Instant::now();
+        for _ in 0..MAX_UPDATES {
+            assert!(limiter.allow(&first, notices, start));
+        }
+        assert!(!limiter.allow(&first, notices, start));
+        // the earliest of the notices waiting is kept so none are left out
+        let earlier = PendingUpdate {
+            notices_since: Some(10),
+            ..notices
+        };
+        assert!(!limiter.allow(&first, earlier, start));
+        assert!(!limiter.allow(&second, notices, start));
+        assert!(!limiter.allow(&first, settings, start));
+        // no room until the window has passed
+        assert_eq!(limiter.take_pending(start), None);
+        let later = start + UPDATE_WINDOW;
+        // a change to a bored with changes waiting waits with them even when there is room
+        assert!(!limiter.allow(&second, settings, later));
+        assert_eq!(
+            limiter.take_pending(later),
+            Some((
+                first,
+                PendingUpdate {
+                    notices_since: Some(10),
+                    settings: true
+                }
+            ))
+        );
+        assert_eq!(
+            limiter.take_pending(later),
+            Some((
+                second,
+                PendingUpdate {
+                    notices_since: Some(20),
+                    settings: true
+                }
+            ))
+        );
+        assert_eq!(limiter.take_pending(later), None);
+    }
+
+    #[tokio::test]
+    async fn unreachable_daemon_fails_health_check() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.health").expect("valid address");
+        let bored = Bored::create("health", Coordinate { x: 10, y: 10 });
+        let client = test_client(cache_dir.clone(), address, bored);
+        assert_eq!(client.health_check().await, Err(BoredError::ClientConnectionError));
+        assert!(!client.check_daemon().await.expect("checked"));
+        // an offline client has no connection to go back to
+        let offline = X0xBoredClient::offline(&cache_dir, &X0xDaemon::Standard);
+        assert_eq!(offline.reconnect().await, Err(BoredError::ClientConnectionError));
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[test]
+    fn offline_client_opens_cached_boreds() {
+        let data_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.offline").expect("valid address");
+        let other = BoredAddress::from_string("bored.test.offline-missing").expect("valid address");
+        let cached = Bored::create("cached", Coordinate { x: 20, y: 20 });
+        X0xBoredClient::save_cache(&data_dir.join("cache"), &address, &cached)
+            .expect("save cache");
+
+        let mut client = X0xBoredClient::offline(&data_dir, &X0xDaemon::Standard);
+        assert!(!client.is_available());
+        assert_eq!(client.open_cached(&address), Ok(()));
+        assert_eq!(client.get_current_bored(), Ok(cached));
+        assert_eq!(client.get_bored_address(), Ok(address.clone()));
+        // a bored that isn't cached leaves the current one as it was
+        assert!(client.open_cached(&other).is_err());
+        assert_eq!(client.get_bored_address(), Ok(address));
+        let _ = std::fs::remove_dir_all(data_dir);
+    }
+
+    #[test]
+    fn estimate_posting_is_a_dry_run() -> Result<(), BoredError> {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.estimate").expect("valid address");
+        let mut bored = Bored::create("estimate", Coordinate { x: 40, y: 20 });
+        let notice = Notice::create(Coordinate { x: 10, y: 5 });
+        bored.add(notice, Coordinate { x: 0, y: 0 })?;
+        bored.set_pow_difficulty(8)?;
+        let client = test_client(cache_dir.clone(), address, bored.clone());
+
+        let mut notice = Notice::create(Coordinate { x: 20, y: 10 });
+        notice.write("covers the first")?;
+        let estimate = client.estimate_posting(&notice)?;
+        assert_eq!(estimate.pow_difficulty, 8);
+        assert_eq!(estimate.pruned_notices, 1);
+        assert!(estimate.bored_size > 0);
+        // nothing has been posted
+        assert_eq!(client.current_bored, Some(bored));
+        // off the edge of the bored
+        let big = Bored::create("big", Coordinate { x: 100, y: 100 });
+        let mut notice = Notice::create(Coordinate { x: 10, y: 5 });
+        notice.relocate(&big, Coordinate { x: 35, y: 0 })?;
+        assert!(client.estimate_posting(&notice).is_err());
+        let _ = std::fs::remove_dir_all(cache_dir);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn work_limit_refuses_before_hashing() -> Result<(), BoredError> {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.work-limit").expect("valid address");
+        let mut bored = Bored::create("work limit", Coordinate { x: 40, y: 20 });
+        bored.set_pow_difficulty(20)?;
+        let mut client = test_client(cache_dir.clone(), address, bored.clone());
+        let notice = Notice::create(Coordinate { x: 10, y: 5 });
+        assert!(client.estimate_posting(&notice).is_ok());
+
+        client.set_work_limit(WorkLimit {
+            per_notice: Some(16),
+            per_session: None,
+        });
+        assert_eq!(
+            client.estimate_posting(&notice),
+            Err(BoredError::PowOverLimit(20, 16))
+        );
+        client.create_draft(Coordinate { x: 10, y: 5 })?;
+        assert_eq!(
+            client.add_draft_to_bored().await,
+            Err(BoredError::PowOverLimit(20, 16))
+        );
+
+        // work already done this session counts towards its limit, clones share the count
+        client.set_work_limit(WorkLimit {
+            per_notice: None,
+            per_session: Some(expected_hashes(20) * 2),
+        });
+        assert!(client.estimate_posting(&notice).is_ok());
+        client
+            .clone()
+            .work_done
+            .fetch_add(expected_hashes(21), std::sync::atomic::Ordering::Relaxed);
+        assert_eq!(client.get_work_done(), expected_hashes(21));
+        assert_eq!(
+            client.add_draft_to_bored().await,
+            Err(BoredError::WorkLimitReached(expected_hashes(20) * 2))
+        );
+        // nothing was posted
+        assert_eq!(client.current_bored, Some(bored));
+        let _ = std::fs::remove_dir_all(cache_dir);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn ledger_records_what_was_published() -> Result<(), BoredError> {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.ledger").expect("valid address");
+        let mut bored = Bored::create("ledger", Coordinate { x: 40, y: 20 });
+        bored.set_pow_difficulty(4)?;
+        let mut client = test_client(cache_dir.clone(), address.clone(), bored);
+        assert_eq!(client.get_ledger(), Ok(vec![]));
+        // hold every change back so nothing needs the daemon
+        {
+            let mut limiter = client.limiter.lock().expect("limiter");
+            let now = std::time::Instant::now();
+            for _ in 0..MAX_UPDATES {
+                limiter.take(now);
+            }
+        }
+
+        client.set_pow_difficulty(6).await?;
+        client.create_draft(Coordinate { x: 10, y: 5 })?;
+        let file = cache_dir.join("notes.txt");
+        std::fs::write(&file, "some notes").expect("write file");
+        let attachment = client.attach_file(&file)?;
+        client.add_draft_to_bored().await?;
+        // a line cut short is skipped
+        let mut ledger_file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(X0xBoredClient::ledger_path(&cache_dir))
+            .expect("open ledger");
+        std::io::Write::write_all(&mut ledger_file, b"{\"at\":1").expect("write ledger");
+
+        let ledger = client.get_ledger()?;
+        let operations: Vec<LedgerOperation> = ledger.iter().map(|entry| entry.operation).collect();
+        assert_eq!(
+            operations,
+            vec![
+                LedgerOperation::SharedFile,
+                LedgerOperation::PostedNotice,
+                LedgerOperation::ChangedSettings,
+            ]
+        );
+        assert_eq!(ledger[0].address, attachment.get_address());
+        assert_eq!(ledger[0].size, 10);
+        assert_eq!(ledger[1].address, address.to_string());
+        assert_eq!(ledger[1].pow_difficulty, 6);
+        assert_eq!(
+            ledger[1].size,
+            client.get_current_bored()?.serialized_size()? as u64
+        );
+        assert!(ledger[1].at >= ledger[2].at);
+        let _ = std::fs::remove_dir_all(cache_dir);
+        Ok(())
+    }
+
+    #[test]
+    fn get_cached_boreds_lists_boreds_by_name() {
+        let cache_dir = test_cache_dir();
+        let zebras = BoredAddress::from_string("bored.test.zebras").expect("valid address");
+        let apples = BoredAddress::from_string("bored://apples").expect("valid address");
+        let bored = Bored::create("Zebras", Coordinate { x: 10, y: 10 });
+        X0xBoredClient::save_cache(&cache_dir, &zebras, &bored).expect("save cache");
+        let bored = Bored::create("Apples", Coordinate { x: 10, y: 10 });
+        X0xBoredClient::save_cache(&cache_dir, &apples, &bored).expect("save cache");
+        std::fs::write(cache_dir.join("notes.txt"), "not a bored").expect("write file");
+
+        let client = test_client(cache_dir.clone(), zebras.clone(), bored);
+        let cached = client.get_cached_boreds();
+        assert_eq!(
+            cached,
+            vec![
+                (
+                    BoredAddress::Topic(apples.get_topic()),
+                    "Apples".to_string()
+                ),
+                (zebras, "Zebras".to_string()),
+            ]
+        );
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[test]
+    fn get_history_returns_archived_versions_newest_first() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.history").expect("valid address");
+        let current = Bored::create("current", Coordinate { x: 10, y: 10 });
+        let client = test_client(cache_dir.clone(), address.clone(), current);
+        assert_eq!(client.get_history().expect("empty history"), vec![]);
+
+        let first = Bored::create("first", Coordinate { x: 10, y: 10 });
+        let second = Bored::create("second", Coordinate { x: 10, y: 10 });
+        X0xBoredClient::archive_version(&cache_dir, &address, &first).expect("archive first");
+        X0xBoredClient::archive_version(&cache_dir, &address, &second).expect("archive second");
+        let history = client.get_history().expect("history");
+
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].bored.get_name(), "second");
+        assert_eq!(history[1].bored.get_name(), "first");
+        assert!(history[0].archived_at > history[1].archived_at);
+
+        // a damaged version doesn't lose the others
+        let history_dir = X0xBoredClient::history_dir(&cache_dir, &address);
+        std::fs::write(history_dir.join("1.json"), "{ not a bored").expect("write bad version");
+        let history = client.get_history().expect("history with a bad version");
+        assert_eq!(history.len(), 2);
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn fork_bored_refuses_existing_url_name() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.fork-source").expect("valid address");
+        let taken = BoredAddress::from_string("bored.test.fork-taken").expect("valid address");
+        let current = Bored::create("source", Coordinate { x: 10, y: 10 });
+        X0xBoredClient::save_cache(&cache_dir, &taken, &current).expect("save cache");
+
+        let mut client = test_client(cache_dir.clone(), address.clone(), current);
+        let res = client.fork_bored(Some("bored.test.fork-taken")).await;
+
+        assert_eq!(res, Err(BoredError::URLNameAlreadyExists(taken.to_string())));
+        assert_eq!(client.get_bored_address(), Ok(address));
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[test]
+    fn attach_file_stores_file_for_peers() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.attach").expect("valid address");
+        let current = Bored::create("attach", Coordinate { x: 30, y: 10 });
+        let mut client = test_client(cache_dir.clone(), address, current);
+        let file = cache_dir.join("notes.md");
+        std::fs::write(&file, "# notes").expect("write file");
+
+        assert_eq!(client.attach_file(&file), Err(BoredError::NoNotice));
+        client
+            .create_draft(Coordinate { x: 10, y: 5 })
+            .expect("create draft");
+        let attachment = client.attach_file(&file).expect("attach file");
+        assert_eq!(attachment.get_filename(), "notes.md");
+        assert_eq!(attachment.get_size(), 7);
+        assert_eq!(
+            client.get_draft().and_then(|d| d.get_attachment().cloned()),
+            Some(attachment.clone())
+        );
+        let stored = X0xBoredClient::attachment_path(&cache_dir, attachment.get_hash());
+        assert_eq!(std::fs::read(stored).expect("stored file"), b"# notes");
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn download_file_checks_content_and_never_replaces_files() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.download").expect("valid address");
+        let current = Bored::create("download", Coordinate { x: 30, y: 10 });
+        let client = test_client(cache_dir.clone(), address, current);
+        let attachment = Attachment::create(b"# notes", "../notes.md").expect("attachment");
+        let stored = X0xBoredClient::attachment_path(&cache_dir, attachment.get_hash());
+        std::fs::create_dir_all(stored.parent().unwrap()).expect("create store");
+        std::fs::write(&stored, "# notes").expect("store file");
+
+        let dir = cache_dir.join("saved");
+        let first = client.download_file(&attachment, &dir).await;
+        assert_eq!(first, Ok(dir.join("notes.md")));
+        let second = client.download_file(&attachment, &dir).await;
+        assert_eq!(second, Ok(dir.join("notes (1).md")));
+        assert_eq!(std::fs::read(dir.join("notes (1).md")).unwrap(), b"# notes");
+
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        let third = client
+            .download_file_with_progress(&attachment, &dir, Some(sender))
+            .await;
+        assert_eq!(third, Ok(dir.join("notes (2).md")));
+        assert_eq!(
+            receiver.recv().await,
+            Some(DownloadProgress {
+                received: 7,
+                total: 7
+            })
+        );
+
+        std::fs::write(&stored, "# forged").expect("corrupt file");
+        assert_eq!(
+            client.download_file(&attachment, &dir).await,
+            Err(BoredError::ChecksumMismatch(attachment.get_address().to_string()))
+        );
+        assert!(!stored.exists());
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn file_chunks_are_collected_for_downloads() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.chunks").expect("valid address");
+        let bored = Bored::create("chunks", Coordinate { x: 30, y: 10 });
+        X0xBoredClient::save_cache(&cache_dir, &address, &bored).expect("save cache");
+        let content = vec![7u8; ATTACHMENT_CHUNK_SIZE + 10];
+        let attachment = Attachment::create(&content, "big.bin").expect("attachment");
+        let download_dir = X0xBoredClient::download_dir(&cache_dir, attachment.get_hash());
+        std::fs::create_dir_all(&download_dir).expect("create download dir");
+
+        let http = reqwest::Client::new();
+        let chunk = |index: u32| GossipMsg::FileChunk {
+            address: attachment.get_address().to_string(),
+            index,
+            total: 2,
+            data: base64::Engine::encode(
+                &base64::prelude::BASE64_STANDARD,
+                content
+                    .chunks(ATTACHMENT_CHUNK_SIZE)
+                    .nth(index as usize)
+                    .unwrap(),
+            ),
+        };
+        let topic = address.get_topic();
+        X0xBoredClient::handle_background_msg(&http, "", "", None, &cache_dir, &topic, chunk(1))
+            .await
+            .expect("handle chunk");
+        // a resumed download only asks for what is missing
+        assert_eq!(
+            X0xBoredClient::missing_chunks(&download_dir, 2),
+            Ok((vec![0], 10))
+        );
+        X0xBoredClient::handle_background_msg(&http, "", "", None, &cache_dir, &topic, chunk(0))
+            .await
+            .expect("handle chunk");
+        assert_eq!(
+            X0xBoredClient::missing_chunks(&download_dir, 2),
+            Ok((vec![], content.len() as u64))
+        );
+        X0xBoredClient::finish_download(&cache_dir, &attachment, 2).expect("finish download");
+        let stored = X0xBoredClient::attachment_path(&cache_dir, attachment.get_hash());
+        assert_eq!(std::fs::read(stored).expect("stored file"), content);
+        assert!(!download_dir.exists());
+
+        std::fs::create_dir_all(&download_dir).expect("create download dir");
+        let out_of_range = GossipMsg::FileChunk {
+            address: attachment.get_address().to_string(),
+            index: 2,
+            total: 2,
+            data: String::new(),
+        };
+        assert!(
+            X0xBoredClient::handle_background_msg(
+                &http,
+                "",
+                "",
+                None,
+                &cache_dir,
+                &topic,
+                out_of_range
+            )
+            .await
+            .is_err()
+        );
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn meta_messages_carry_the_palette() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.palette").expect("valid address");
+        let bored = Bored::create("colours", Coordinate { x: 30, y: 10 });
+        X0xBoredClient::save_cache(&cache_dir, &address, &bored).expect("save cache");
+
+        let mut coloured = bored.clone();
+        let palette =
+            Palette::create(Some("#ffffff"), Some("#003366"), None, None).expect("valid palette");
+        coloured.set_palette(palette.clone());
+        let http = reqwest::Client::new();
+        let topic = address.get_topic();
+        X0xBoredClient::handle_background_msg(
+            &http,
+            "",
+            "",
+            None,
+            &cache_dir,
+            &topic,
+            GossipMsg::meta(&coloured),
+        )
+        .await
+        .expect("handle meta");
+        let cached = X0xBoredClient::load_cache(&cache_dir, &address).expect("cached bored");
+        assert_eq!(cached.get_palette(), &palette);
+
+        // peers that don't know of palettes leave the field out, which keeps it
+        let mut json = serde_json::to_value(GossipMsg::meta(&bored)).expect("serialize meta");
+        json.as_object_mut()
+            .expect("meta is an object")
+            .remove("palette");
+        let msg = serde_json::from_value(json).expect("deserialize meta");
+        X0xBoredClient::handle_background_msg(&http, "", "", None, &cache_dir, &topic, msg)
+            .await
+            .expect("handle meta");
+        let cached = X0xBoredClient::load_cache(&cache_dir, &address).expect("cached bored");
+        assert_eq!(cached.get_palette(), &palette);
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn stale_metas_do_not_undo_settings() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.stale").expect("valid address");
+        let elsewhere = BoredAddress::from_string("bored.test.moved").expect("valid address");
+        let bored = Bored::create("stale", Coordinate { x: 30, y: 10 });
+        let mut moderated = bored.clone();
+        moderated
+            .set_hidden_notices(vec!["ab".repeat(32)])
+            .expect("hide notice");
+        moderated.set_moved_to(Some(&elsewhere));
+        moderated.settings_changed_at = 200;
+        X0xBoredClient::save_cache(&cache_dir, &address, &moderated).expect("save cache");
+
+        let http = reqwest::Client::new();
+        let topic = address.get_topic();
+        let receive = async |json: serde_json::Value| {
+            let msg = serde_json::from_value(json).expect("deserialize meta");
+            X0xBoredClient::handle_background_msg(&http, "", "", None, &cache_dir, &topic, msg)
+                .await
+                .expect("handle meta");
+            X0xBoredClient::load_cache(&cache_dir, &address).expect("cached bored")
+        };
+        // from a peer that hadn't seen the bored moderated
+        let mut stale = bored.clone();
+        stale.settings_changed_at = 100;
+        let cached =
+            receive(serde_json::to_value(GossipMsg::meta(&stale)).expect("serialize")).await;
+        assert_eq!(cached, moderated);
+        // from a peer that doesn't know of hiding notices or moving
+        let old_peer = serde_json::json!({
+            "type": "meta",
+            "name": "stale",
+            "dimensions": { "x": 30, "y": 10 },
+            "settings_changed_at": 300,
+        });
+        let cached = receive(old_peer).await;
+        assert_eq!(cached.get_hidden_notices(), moderated.get_hidden_notices());
+        assert_eq!(cached.get_moved_to(), Some(elsewhere));
+        // changed since, so undone, and from the future it is taken as changed now
+        let mut undone = bored.clone();
+        undone.settings_changed_at = i64::MAX;
+        let cached =
+            receive(serde_json::to_value(GossipMsg::meta(&undone)).expect("serialize")).await;
+        assert!(cached.get_hidden_notices().is_empty());
+        assert_eq!(cached.get_moved_to(), None);
+        assert!(cached.settings_changed_at <= chrono::Utc::now().timestamp_millis());
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn fetch_boreds_only_stays_subscribed_to_the_current_bored() {
+        let cache_dir = test_cache_dir();
+        let current = BoredAddress::from_string("bored.test.current").expect("valid address");
+        let other = BoredAddress::from_string("bored.test.other").expect("valid address");
+        let bored = Bored::create("fetched", Coordinate { x: 30, y: 10 });
+        for address in [&current, &other] {
+            X0xBoredClient::save_cache(&cache_dir, address, &bored).expect("save cache");
+        }
+        let (api_base, mut requests) = fake_daemon().await;
+        let client = test_client(cache_dir.clone(), current.clone(), bored);
+        client.endpoint.write().expect("endpoint lock").api_base = api_base;
+
+        let fetched = client.fetch_boreds(&[current, other]).await;
+        assert!(fetched.iter().all(Result::is_ok));
+        let mut seen = vec![];
+        while let Ok(request) = requests.try_recv() {
+            seen.push(request);
+        }
+        assert_eq!(seen.iter().filter(|r| *r == "POST /subscribe").count(), 2);
+        assert!(seen.contains(&"DELETE /subscribe/sub-2".to_string()));
+        assert!(!seen.contains(&"DELETE /subscribe/sub-1".to_string()));
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn creating_boreds_is_limited() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.create-first").expect("valid address");
+        let bored = Bored::create("first", Coordinate { x: 30, y: 10 });
+        let (api_base, mut requests) = fake_daemon().await;
+        let mut client = test_client(cache_dir.clone(), address, bored);
+        client.endpoint.write().expect("endpoint lock").api_base = api_base;
+
+        for i in 0..MAX_UPDATES + 2 {
+            let url_name = format!("bored.test.create-{i}");
+            client
+                .create_bored("created", Coordinate { x: 30, y: 10 }, Some(&url_name))
+                .await
+                .expect("create bored");
+        }
+        let mut published = 0;
+        while let Ok(request) = requests.try_recv() {
+            if request == "POST /publish" {
+                published += 1;
+            }
+        }
+        // the rest wait to be sent when there is room
+        assert_eq!(published, MAX_UPDATES);
+        assert!(client.has_pending_updates());
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn held_back_notices_are_sent_as_notice_messages() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.held-back").expect("valid address");
+        let mut bored = Bored::create("held back", Coordinate { x: 40, y: 10 });
+        let posted = [
+            "notice:50:test-age",
+            "notice:100:test-age",
+            "notice:200:ab12cd34",
+            "notice:300:test-age",
+        ];
+        for (i, notice_id) in posted.into_iter().enumerate() {
+            let mut notice = Notice::create(Coordinate { x: 10, y: 4 });
+            notice.set_notice_id(notice_id.to_string());
+            bored
+                .add(
+                    notice,
+                    Coordinate {
+                        x: i as u16 * 10,
+                        y: 0,
+                    },
+                )
+                .expect("add notice");
+        }
+        X0xBoredClient::save_cache(&cache_dir, &address, &bored).expect("save cache");
+        let (api_base, mut requests) = fake_daemon().await;
+        let client = test_client(cache_dir.clone(), address.clone(), bored);
+        client.endpoint.write().expect("endpoint lock").api_base = api_base;
+        client.limiter.lock().expect("limiter lock").pending.push((
+            address,
+            PendingUpdate {
+                notices_since: Some(100),
+                settings: false,
+            },
+        ));
+
+        assert_eq!(client.publish_pending().await, Ok(1));
+        // only those this client posted since they were held back
+        let mut published = 0;
+        while let Ok(request) = requests.try_recv() {
+            assert_eq!(request, "POST /publish");
+            published += 1;
+        }
+        assert_eq!(published, 2);
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn a_strangers_meta_does_not_redirect_readers() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.stay").expect("valid address");
+        let elsewhere = BoredAddress::from_string("bored.test.elsewhere").expect("valid address");
+        let bored = Bored::create("stay", Coordinate { x: 30, y: 10 });
+        X0xBoredClient::save_cache(&cache_dir, &address, &bored).expect("save cache");
+        X0xBoredClient::save_cache(&cache_dir, &elsewhere, &bored).expect("save cache");
+
+        // anyone who knows the bored can say it has moved
+        let mut moved = bored.clone();
+        moved.set_moved_to(Some(&elsewhere));
+        let http = reqwest::Client::new();
+        let topic = address.get_topic();
+        X0xBoredClient::handle_background_msg(
+            &http,
+            "",
+            "",
+            None,
+            &cache_dir,
+            &topic,
+            GossipMsg::meta(&moved),
+        )
+        .await
+        .expect("handle meta");
+
+        let (api_base, _requests) = fake_daemon().await;
+        let mut client = test_client(cache_dir.clone(), elsewhere.clone(), bored);
+        client.endpoint.write().expect("endpoint lock").api_base = api_base;
+        client.go_to_bored(&address).await.expect("go to bored");
+        // the reader stays and is only told where it says it has gone
+        assert_eq!(client.get_bored_address(), Ok(address));
+        assert_eq!(
+            client.get_current_bored().map(|bored| bored.get_moved_to()),
+            Ok(Some(elsewhere))
+        );
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn synced_notices_from_before_proof_of_work_are_kept() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.syncpow").expect("valid address");
+        let mut bored = Bored::create("syncpow", Coordinate { x: 30, y: 10 });
+        for (i, x) in [0, 10, 20].into_iter().enumerate() {
+            let mut notice = Notice::create(Coordinate { x: 10, y: 4 });
+            notice.set_notice_id(format!("notice:177979680000{i}:ab12"));
+            bored
+                .add(notice, Coordinate { x, y: 0 })
+                .expect("add notice");
+        }
+        // proof of work asked for after the notices were posted
+        bored.set_pow_difficulty(16).expect("valid difficulty");
+        assert!(bored.notices().iter().all(|n| !n.has_proof_of_work(16)));
+        // and one posted since without doing it
+        let mut late = Notice::create(Coordinate { x: 10, y: 4 });
+        late.set_notice_id(format!("notice:{}:ab12", bored.get_pow_changed_at() + 1));
+        late.relocate(&bored, Coordinate { x: 0, y: 5 })
+            .expect("relocate notice");
+        bored.notices.push(late);
+
+        let http = reqwest::Client::new();
+        let topic = address.get_topic();
+        let sync = async || {
+            X0xBoredClient::handle_background_msg(
+                &http,
+                "",
+                "",
+                None,
+                &cache_dir,
+                &topic,
+                GossipMsg::sync_response(&bored),
+            )
+            .await
+            .expect("handle sync response");
+        };
+        // not asked for so it is dropped
+        sync().await;
+        assert!(X0xBoredClient::load_cache(&cache_dir, &address).is_none());
+        X0xBoredClient::note_sync_request(&cache_dir, &address);
+        sync().await;
+        let cached = X0xBoredClient::load_cache(&cache_dir, &address).expect("cached bored");
+        assert_eq!(cached.get_pow_difficulty(), 16);
+        assert_eq!(cached.get_pow_changed_at(), bored.get_pow_changed_at());
+        assert_eq!(cached.notices().len(), 3);
+
+        // a newly gossiped notice still needs the work done
+        let mut notice = Notice::create(Coordinate { x: 10, y: 4 });
+        notice.set_notice_id("notice:1779796800009:ab12".to_string());
+        notice
+            .relocate(&cached, Coordinate { x: 0, y: 5 })
+            .expect("relocate notice");
+        X0xBoredClient::handle_background_msg(
+            &http,
+            "",
+            "",
+            None,
+            &cache_dir,
+            &topic,
+            GossipMsg::NoticeMsg { notice },
+        )
+        .await
+        .expect("handle notice");
+        let cached = X0xBoredClient::load_cache(&cache_dir, &address).expect("cached bored");
+        assert_eq!(cached.notices().len(), 3);
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn encrypted_boreds_are_only_read_by_their_readers() {
+        let address = BoredAddress::from_string("bored.test.sealed").expect("valid address");
+        let topic = address.get_topic();
+        let alice = ReaderKey::generate();
+        let bob = ReaderKey::generate();
+        let mut bored = Bored::create("sealed", Coordinate { x: 30, y: 10 });
+        let mut notice = Notice::create(Coordinate { x: 12, y: 4 });
+        notice.set_notice_id("notice:1779796800000:ab12".to_string());
+        notice.write("for alice only").expect("write notice");
+        bored
+            .add(notice, Coordinate { x: 0, y: 0 })
+            .expect("add notice");
+        bored
+            .set_readers(&[alice.public_key()])
+            .expect("set readers");
+        let sealed = GossipMsg::meta(&bored)
+            .for_readers(&bored)
+            .expect("seal bored");
+        assert!(matches!(sealed, GossipMsg::Encrypted { sync: false, .. }));
+        let json = serde_json::to_string(&sealed).expect("serialize sealed");
+        assert!(!json.contains("alice"));
+
+        let http = reqwest::Client::new();
+        let receive = async |reader_key: Option<&ReaderKey>, json: &str| {
+            let cache_dir = test_cache_dir();
+            X0xBoredClient::note_sync_request(&cache_dir, &address);
+            let msg: GossipMsg = serde_json::from_str(json).expect("deserialize sealed");
+            X0xBoredClient::handle_background_msg(
+                &http, "", "", reader_key, &cache_dir, &topic, msg,
+            )
+            .await
+            .expect("handle sealed");
+            let cached = X0xBoredClient::load_cache(&cache_dir, &address);
+            let _ = std::fs::remove_dir_all(cache_dir);
+            cached
+        };
+        // only a reply to asking for the bored is enough to start reading it
+        assert!(receive(Some(&alice), &json).await.is_none());
+        let reply = GossipMsg::sync_response(&bored)
+            .for_readers(&bored)
+            .expect("seal reply");
+        assert!(matches!(reply, GossipMsg::Encrypted { sync: true, .. }));
+        let reply = serde_json::to_string(&reply).expect("serialize reply");
+        let cached = receive(Some(&alice), &reply)
+            .await
+            .expect("alice can read it");
+        assert_eq!(cached.notices()[0].get_content(), "for alice only");
+        // kept encrypted so what alice posts and answers with is sealed too
+        assert_eq!(cached.get_readers(), vec![alice.public_key()]);
+        assert!(receive(Some(&bob), &reply).await.is_none());
+        assert!(receive(None, &reply).await.is_none());
+
+        // a bored alice already reads in the clear isn't made encrypted by a peer
+        let cache_dir = test_cache_dir();
+        let public = Bored::create("sealed", Coordinate { x: 30, y: 10 });
+        X0xBoredClient::save_cache(&cache_dir, &address, &public).expect("save cache");
+        let msg: GossipMsg = serde_json::from_str(&json).expect("deserialize sealed");
+        X0xBoredClient::handle_background_msg(&http, "", "", Some(&alice), &cache_dir, &topic, msg)
+            .await
+            .expect("handle sealed");
+        let cached = X0xBoredClient::load_cache(&cache_dir, &address).expect("cached bored");
+        assert_eq!(cached.notices().len(), 1);
+        assert!(!cached.is_encrypted());
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn gossiped_background_patterns_are_checked() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.pattern").expect("valid address");
+        let bored = Bored::create("pattern", Coordinate { x: 30, y: 10 });
+        X0xBoredClient::save_cache(&cache_dir, &address, &bored).expect("save cache");
+
+        let mut patterned = bored.clone();
+        patterned.set_background(
+            Background::create("#", crate::FrameStyle::Double).expect("valid background"),
+        );
+        // a peer can send any pattern, not only ones Background::create would make
+        let mut msg = serde_json::to_value(GossipMsg::meta(&patterned)).expect("serialize meta");
+        msg["background"]["pattern"] = "\x1b[2J#".into();
+        let msg: GossipMsg = serde_json::from_value(msg).expect("deserialize meta");
+        let http = reqwest::Client::new();
+        let topic = address.get_topic();
+        X0xBoredClient::handle_background_msg(&http, "", "", None, &cache_dir, &topic, msg)
+            .await
+            .expect("handle meta");
+        let cached = X0xBoredClient::load_cache(&cache_dir, &address).expect("cached bored");
+        assert_eq!(cached.get_background().get_pattern(), "[2J#");
+        assert_eq!(
+            cached.get_background().get_frame(),
+            crate::FrameStyle::Double
+        );
+
+        // stripping can't make one that is too long fit so it is dropped
+        let mut msg = serde_json::to_value(GossipMsg::meta(&patterned)).expect("serialize meta");
+        msg["background"]["pattern"] = "#".repeat(crate::MAX_BACKGROUND_PATTERN_LENGTH + 1).into();
+        let msg: GossipMsg = serde_json::from_value(msg).expect("deserialize meta");
+        X0xBoredClient::handle_background_msg(&http, "", "", None, &cache_dir, &topic, msg)
+            .await
+            .expect("handle meta");
+        let cached = X0xBoredClient::load_cache(&cache_dir, &address).expect("cached bored");
+        assert!(cached.get_background().is_default());
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn views_are_counted_once_per_reader() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.views").expect("valid address");
+        let mut bored = Bored::create("views", Coordinate { x: 30, y: 10 });
+        let mut notice = Notice::create(Coordinate { x: 10, y: 4 });
+        notice.set_notice_id("notice:1779796800000:ab12".to_string());
+        bored
+            .add(notice.clone(), Coordinate { x: 0, y: 0 })
+            .expect("add notice");
+        X0xBoredClient::save_cache(&cache_dir, &address, &bored).expect("save cache");
+
+        let http = reqwest::Client::new();
+        let topic = address.get_topic();
+        let view = |notice_id: &str, agent_id: &str| GossipMsg::View {
+            notice_id: notice_id.to_string(),
+            reader: get_reader_id(agent_id, notice_id),
+        };
+        for msg in [
+            view(notice.get_notice_id(), "reader one"),
+            view(notice.get_notice_id(), "reader one"),
+            view(notice.get_notice_id(), "reader two"),
+            view("notice:1:not-here", "reader one"),
+        ] {
+            X0xBoredClient::handle_background_msg(&http, "", "", None, &cache_dir, &topic, msg)
+                .await
+                .expect("handle view");
+        }
+        let mut client = test_client(cache_dir.clone(), address, bored);
+        let stats = client.get_notice_stats().expect("stats");
+        assert_eq!(
+            stats,
+            vec![NoticeStats {
+                notice_id: notice.get_notice_id().to_string(),
+                views: 2,
+            }]
+        );
+        // nothing is sent or counted unless asked to
+        client.record_view(&notice).await.expect("not recorded");
+        assert_eq!(client.get_notice_stats().expect("stats"), stats);
+        client.set_count_views(true);
+        // there is no daemon to publish to but it is still counted here
+        assert!(client.record_view(&notice).await.is_err());
+        assert_eq!(client.get_notice_stats().expect("stats")[0].views, 3);
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn read_only_link_refuses_writes() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.read-only").expect("valid address");
+        let current = Bored::create("read only", Coordinate { x: 10, y: 10 });
+        let mut client = test_client(cache_dir.clone(), address.clone(), current);
+        client.share_link = Some(ShareLink::create(
+            address.clone(),
+            Capability::ReadOnly,
+            None,
+        ));
+
+        assert_eq!(client.get_capability(), Capability::ReadOnly);
+        assert_eq!(
+            client.create_draft(Coordinate { x: 5, y: 5 }),
+            Err(BoredError::ReadOnlyBored)
+        );
+        assert_eq!(
+            client.set_pow_difficulty(4).await,
+            Err(BoredError::ReadOnlyBored)
+        );
+        assert_eq!(
+            client.share_link(Capability::ReadWrite, None),
+            Err(BoredError::ReadOnlyBored)
+        );
+        assert_eq!(
+            client.share_link(Capability::ReadOnly, Some(100)),
+            Ok(ShareLink::create(address.clone(), Capability::ReadOnly, Some(100)))
+        );
+
+        client.share_link = Some(ShareLink::create(
+            address,
+            Capability::ReadWrite,
+            Some(1),
+        ));
+        assert_eq!(
+            client.create_draft(Coordinate { x: 5, y: 5 }),
+            Err(BoredError::ShareLinkExpired(1))
+        );
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
+    #[tokio::test]
+    async fn read_only_client_refuses_writes() {
+        let cache_dir = test_cache_dir();
+        let address =
+            BoredAddress::from_string("bored.test.read-only-client").expect("valid address");
+        let current = Bored::create("read only", Coordinate { x: 10, y: 10 });
+        let mut client = test_client(cache_dir.clone(), address, current);
+        client.set_read_only(true);
+
+        assert_eq!(
+            client.create_draft(Coordinate { x: 5, y: 5 }),
+            Err(BoredError::ReadOnlyClient)
+        );
+        assert_eq!(
+            client
+                .create_bored("new", Coordinate { x: 10, y: 10 }, None)
+                .await,
+            Err(BoredError::ReadOnlyClient)
+        );
+
+        client.set_read_only(false);
+        assert!(client.create_draft(Coordinate { x: 5, y: 5 }).is_ok());
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
     #[tokio::test]
     async fn retrieve_bored_reloads_cache_and_updates_current_bored() {
         let cache_dir = test_cache_dir();
@@ -762,6 +3699,37 @@ mod x0x_tests {
         let _ = std::fs::remove_dir_all(cache_dir);
     }
 
+    #[tokio::test]
+    async fn retrieve_bored_rejects_and_sanitizes_hostile_cache() {
+        let cache_dir = test_cache_dir();
+        let address = BoredAddress::from_string("bored.test.hostile").expect("valid address");
+        let current = Bored::create("current", Coordinate { x: 10, y: 10 });
+        let mut client = test_client(cache_dir.clone(), address.clone(), current);
+
+        let huge = Coordinate { x: 65535, y: 65535 };
+        X0xBoredClient::save_cache(&cache_dir, &address, &Bored::create("huge", huge))
+            .expect("save cache");
+        assert_eq!(
+            client.retrieve_bored(&address).await,
+            Err(BoredError::InvalidDimensions(huge))
+        );
+
+        let mut hostile = Bored::create("hostile", Coordinate { x: 10, y: 10 });
+        let mut notice = Notice::create(Coordinate { x: 5, y: 5 });
+        notice.write("ok").expect("fits");
+        hostile.notices.push(notice.clone());
+        let mut out_of_bounds = serde_json::to_value(&notice).expect("serialize");
+        out_of_bounds["top_left"]["x"] = serde_json::json!(65535);
+        hostile
+            .notices
+            .push(serde_json::from_value(out_of_bounds).expect("deserialize"));
+        X0xBoredClient::save_cache(&cache_dir, &address, &hostile).expect("save cache");
+        let (loaded, count) = client.retrieve_bored(&address).await.expect("sanitized");
+        assert_eq!(count, 1);
+        assert_eq!(loaded.get_notices(), vec![notice]);
+        let _ = std::fs::remove_dir_all(cache_dir);
+    }
+
     #[tokio::test]
     async fn test_create_bored_integration() {
         let mut client = X0xBoredClient::init().await.expect("Failed init");
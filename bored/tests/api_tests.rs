@@ -576,7 +576,33 @@ fn error_from_io() {
     let io_err = std::io::Error::new(std::io::ErrorKind::NotFound, "gone");
     let e: BoredError = io_err.into();
     match e {
-        BoredError::IOError(msg) => assert!(msg.contains("gone")),
+        BoredError::IOError(ref source) => assert!(source.to_string().contains("gone")),
         _ => panic!("Expected IOError"),
     }
+    // the message isn't repeated by the source, which is the io error itself
+    use std::error::Error;
+    assert_eq!(e.to_string(), "IO error");
+    let source = e.source().expect("io error source");
+    assert_eq!(source.to_string(), "gone");
+    assert!(source.downcast_ref::<std::io::Error>().is_some());
+    assert!(source.source().is_none());
+}
+
+#[test]
+fn error_keeps_source() {
+    use std::error::Error;
+    let bad: Result<Bored, _> = serde_json::from_str("not json");
+    let e: BoredError = bad.unwrap_err().into();
+    // the cause is left to the source so reporters walking the chain don't show it twice
+    let source = e.source().expect("json error source");
+    assert!(!e.to_string().contains(&source.to_string()));
+    assert_eq!(e.code(), "json");
+    assert!(!e.is_transient());
+}
+
+#[test]
+fn error_transient() {
+    assert!(BoredError::ClientConnectionError.is_transient());
+    assert!(!BoredError::TooMuchText.is_transient());
+    assert!(!BoredError::X0xError("rejected".to_string()).is_transient());
 }
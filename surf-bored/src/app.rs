@@ -15,17 +15,73 @@ You should have received a copy of the GNU Affero General Public License
 along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
-use bored::notice::{Hyperlink, Notice, get_hyperlinks};
-use bored::url::{BoredAddress, URL};
-use bored::x0x_client::X0xBoredClient;
-use bored::{Bored, BoredError, Coordinate, Direction};
-use ratatui::{Terminal, backend::Backend, buffer::Buffer};
+use arboard::Clipboard;
+use bored::crypto::ReaderKey;
+use bored::export;
+use bored::notice::{
+    Attachment, Hyperlink, MAX_URL_LENGTH, Notice, NoticeKind, format_event_time, get_hyperlinks,
+    parse_event_time, strip_disallowed_chars,
+};
+use bored::templates::TEMPLATES;
+use bored::url::{BoredAddress, ShareLink, URL, suggest_url_names};
+use bored::x0x_client::{LedgerEntry, PostingEstimate, WorkLimit, X0xBoredClient, X0xDaemon};
+use bored::{
+    Bored, BoredError, Coordinate, Coverage, Direction, Palette, SearchHit, SearchPattern,
+    WhatsOnTheBored,
+};
+use chrono::Local;
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashSet;
+use std::fs;
 use std::io::Error;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use crate::directory::{self, Directory, Listing};
-use crate::display_bored::BoredViewPort;
-use crate::theme::Theme;
-use crate::ui::wait_pop_up;
+use crate::directory::{self, Directory, DirectoryFormat, History, HistoryDirection, Listing};
+use crate::display_bored::{self, BoredViewPort, Overview};
+use crate::downloads::Downloads;
+use crate::drafts::{Draft, Drafts};
+use crate::embeds::Embeds;
+use crate::error_log::ErrorLog;
+use crate::graph::Graph;
+use crate::help;
+use crate::index::Index;
+use crate::keymap::{self, Keymap, VimCommand, VimKeys};
+use crate::openers::{self, Opener, OpenersFile};
+use crate::petnames::Petnames;
+use crate::previews::Previews;
+use crate::session::Session;
+use crate::tasks::{ConnectionCheck, OnFinished, TASK_TIMEOUT, Tasks};
+use crate::theme::{ColourDepth, Theme, ThemeFile};
+use crate::toasts::{ToastKind, Toasts};
+use crate::watch::{self, Watcher};
+
+/// Most suggestions shown below the address being typed in the goto view
+pub const MAX_GOTO_SUGGESTIONS: usize = 8;
+
+/// Environment variable holding the path of a copy of the directory to keep in step with
+pub const DIRECTORY_SYNC_VAR: &str = "SURF_BORED_DIRECTORY_SYNC";
+
+/// Environment variable holding the seconds between asking peers for changes to the bored being
+/// viewed, 0 to never ask
+pub const AUTO_REFRESH_VAR: &str = "SURF_BORED_REFRESH_SECONDS";
+
+/// Time between asking peers for changes when SURF_BORED_REFRESH_SECONDS isn't set
+const DEFAULT_AUTO_REFRESH: Duration = Duration::from_secs(60);
+
+/// Time between checking the daemon is still there
+const CONNECTION_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Time between trying to reconnect once the daemon has gone
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Environment variable holding the schemes of links that are opened without asking, separated
+/// by commas, e.g. "https,http". Downloaded files are opened without asking if it has "file".
+pub const TRUSTED_SCHEMES_VAR: &str = "SURF_BORED_TRUSTED_SCHEMES";
+
+/// Cells between the gridlines shown while positioning a draft
+pub const DRAFT_GRID_SPACING: u16 = 10;
 
 #[derive(Debug, thiserror::Error, Clone, PartialEq)]
 pub enum SurfBoredError {
@@ -41,8 +97,54 @@ pub enum SurfBoredError {
     DirectorySerialzationError,
     #[error("Could not derserialize directory file so directory is empty.")]
     DirectoryDeserialzationError,
-    #[error("Failed to render waiting pop up")]
-    CannotRenderWait,
+    #[error("Could not import directory from {0}")]
+    DirectoryImportError(String),
+    #[error("Could not export directory to {0}")]
+    DirectoryExportError(String),
+    #[error("Can only import and export directories as .html bookmarks or .json, not {0}")]
+    UnknownDirectoryFormat(String),
+    #[error("Session not saved to disk as could not write to file.")]
+    SessionFileWriteError,
+    #[error("Could not serialize session so it was not saved.")]
+    SessionSerialzationError,
+    #[error("Drafts not saved to disk as could not write to file.")]
+    DraftsFileWriteError,
+    #[error("Could not serialize drafts so they were not saved.")]
+    DraftsSerialzationError,
+    #[error("Could not save the events to {0}")]
+    EventsExportError(String),
+    #[error("Index not saved to disk as could not write to file.")]
+    IndexFileWriteError,
+    #[error("Could not serialize the index so it was not saved.")]
+    IndexSerialzationError,
+    #[error("Could not read themes file so the built in themes are used.")]
+    ThemeFileReadError,
+    #[error("Themes file not used as it could not be understood: {0}")]
+    ThemeFileError(String),
+    #[error("Default theme not saved as could not write to themes file.")]
+    ThemeFileWriteError,
+    #[error("Theme uses the colour \"{0}\" which is not a colour name, index or #hex")]
+    UnknownColour(String),
+    #[error("There is no theme called \"{0}\"")]
+    UnknownTheme(String),
+    #[error("Could not use the clipboard: {0}")]
+    ClipboardError(String),
+    #[error("Could not run the editor {0}, set $VISUAL or $EDITOR to the one to use")]
+    EditorError(String),
+    #[error("Could not read openers file so links and files are opened by the system.")]
+    OpenersFileReadError,
+    #[error("Openers file not used as it could not be understood: {0}")]
+    OpenersFileError(String),
+    #[error("Could not run {0} to open it, check the openers file")]
+    OpenerError(String),
+    #[error("Could not read petnames file so authors are shown by their agent id.")]
+    PetnamesFileReadError,
+    #[error("Petnames file not used as it could not be understood: {0}")]
+    PetnamesFileError(String),
+    #[error("Name not saved as could not write to petnames file.")]
+    PetnamesFileWriteError,
+    #[error("There is already a reader key, clear it first so it isn't replaced by mistake")]
+    ReaderKeyExists,
     #[error("The directory of boreds is currently empty")]
     DirectoryIsEmpty,
     #[error("The index: {0} is out of bounds of directory of len {1}")]
@@ -76,7 +178,74 @@ pub enum View {
     DraftView(DraftMode),
     CreateView(CreateMode),
     GoToView,
+    /// Typing text to search the current bored for
+    SearchView,
+    /// Typing a command after : with the vim keymap
+    CommandView,
+    /// Naming the current bored before adding it to the directory
+    BookmarkView,
+    /// Giving the author of the selected notice a name only the user sees
+    PetnameView,
     DirectoryView(usize),
+    /// Editing the listing at the index in the directory
+    ListingView {
+        directory_index: usize,
+        field: ListingField,
+    },
+    /// Typing the path of a file to import the directory from or export it to
+    DirectoryFileView(DirectoryFileAction),
+    DownloadsView(usize),
+    /// Picking the theme, with the index of the selected one
+    ThemeView(usize),
+    /// Saved drafts, with the index of the selected one
+    DraftsView(usize),
+    /// The reader key boreds can be encrypted for
+    KeyView(KeyMode),
+    /// Recent errors, with the index of the selected one
+    ErrorLogView(usize),
+    /// What has been published from here, with the index of the selected entry
+    LedgerView(usize),
+    /// The notices on the bored newest first, with the index of the selected row
+    NoticeListView(usize),
+    /// The address of the bored as a QR code to scan
+    QrView(String),
+    /// Searching the boreds found by crawling, with the index of the selected match
+    IndexView(usize),
+    /// The boreds found by crawling and the links between them, with the index of the
+    /// selected bored
+    GraphView(usize),
+    /// Picking a template to start the draft from, with the index of the selected one
+    TemplateView(usize),
+    /// Asking whether to open a web address or downloaded file with another program, opened
+    /// over another view
+    OpenView {
+        target: String,
+        scheme: String,
+        from: Box<View>,
+    },
+    OverviewView,
+    /// Keys for each view, scrolled down by a number of lines, opened over another view
+    HelpView {
+        scroll: u16,
+        from: Box<View>,
+    },
+    /// Files attached to notices on the current bored, with the addresses of those picked
+    /// for download
+    AttachmentsView {
+        index: usize,
+        selected: Vec<String>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum KeyMode {
+    Show,
+    /// Typing or pasting a secret key or seed phrase from elsewhere
+    Import,
+    /// Showing the secret key as a seed phrase to write down
+    SeedPhrase,
+    /// Asking whether to clear the key, as boreds encrypted for it can't be read without it
+    Clear,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -93,11 +262,66 @@ impl CreateMode {
     }
 }
 
+/// Whether a url name typed when creating a bored already has a bored, with the names close
+/// to it that were found to be free
+#[derive(Clone, Debug, PartialEq)]
+pub struct UrlNameCheck {
+    pub url_name: String,
+    pub taken: bool,
+    pub free: Vec<String>,
+}
+
+/// What checking the links on a bored found, which schemes they use and which go nowhere
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinkCheck {
+    /// Address of the bored whose links were checked
+    pub address: String,
+    /// How many different links use each scheme, most used first
+    pub schemes: Vec<(String, usize)>,
+    pub dead: HashSet<String>,
+}
+
+impl LinkCheck {
+    /// What was found, to tell the user
+    pub fn describe(&self) -> String {
+        let count: usize = self.schemes.iter().map(|(_, count)| count).sum();
+        let schemes = self
+            .schemes
+            .iter()
+            .map(|(scheme, count)| format!("{count} {scheme}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let dead = match self.dead.len() {
+            0 => "None of them go nowhere.".to_string(),
+            1 => "1 goes nowhere and is crossed out.".to_string(),
+            dead => format!("{dead} go nowhere and are crossed out."),
+        };
+        format!("Checked {count} links: {schemes}. {dead}")
+    }
+}
+
+/// Where a link found on a bored goes, for checking it
+#[derive(Debug, PartialEq)]
+enum LinkTarget {
+    /// A bored that can be looked for
+    Bored(BoredAddress),
+    /// Somewhere that isn't checked, the web or an app command
+    Unchecked,
+    /// Nowhere that can be followed
+    Dead,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum DraftMode {
     Content,
     Hyperlink(HyperlinkMode),
     Position,
+    /// Growing or shrinking the notice with the arrow keys
+    Resize,
+    /// Asking whether to post the notice, with what posting it would take
+    Confirm(PostingEstimate),
+    /// Typing when and where the event the notice is for happens
+    Event(EventField),
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -114,6 +338,62 @@ impl HyperlinkMode {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DirectoryFileAction {
+    Import,
+    Export,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ListingField {
+    Name,
+    Tags,
+    Note,
+}
+impl ListingField {
+    pub fn next(&self) -> ListingField {
+        match self {
+            ListingField::Name => ListingField::Tags,
+            ListingField::Tags => ListingField::Note,
+            ListingField::Note => ListingField::Name,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum EventField {
+    Title,
+    Start,
+    End,
+    Location,
+}
+impl EventField {
+    pub fn next(&self) -> EventField {
+        match self {
+            EventField::Title => EventField::Start,
+            EventField::Start => EventField::End,
+            EventField::End => EventField::Location,
+            EventField::Location => EventField::Title,
+        }
+    }
+}
+
+/// A bored that can be picked while typing an address in the goto view
+#[derive(Clone, Debug, PartialEq)]
+pub struct GoToSuggestion {
+    pub name: String,
+    pub address: String,
+}
+
+/// What a mouse drag that is under way is moving
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MouseDrag {
+    /// Panning the view, from where the mouse was last on screen
+    View { column: u16, row: u16 },
+    /// Moving the draft, held this far from its top left
+    Draft { offset: Coordinate },
+}
+
 #[derive(Debug)]
 pub enum NoticeSelection {
     Direction(Direction),
@@ -130,16 +410,207 @@ pub struct App {
     pub previous_view: View,
     pub interupted_view: View,
     pub selected_notice: Option<usize>,
+    /// Notice raised over those covering it by peeking, shown on top while it stays selected
+    peeked_notice: Option<usize>,
+    /// Cell of the bored last clicked, peeking looks under it
+    pub clicked_cell: Option<Coordinate>,
+    /// Theme things are drawn in, the picked theme in the current bored's colours
     pub theme: Theme,
+    /// Theme picked by the user
+    pub picked_theme: Theme,
+    /// Whether boreds are shown in the colours they ask for
+    pub bored_colours: bool,
+    /// Colours the terminal can show, themes are brought down to these
+    pub colour_depth: ColourDepth,
+    /// Built in themes and those from the themes file
+    pub themes: Vec<Theme>,
     pub bored_view_port: Option<BoredViewPort>,
     pub name_input: String,
     pub url_name_input: String,
+    /// What was found when the typed url name was last checked
+    pub url_name_check: Option<UrlNameCheck>,
+    /// What was found when the links on a bored were last checked
+    pub link_check: Option<LinkCheck>,
     pub content_input: String,
     pub link_text_input: String,
     pub link_url_input: String,
     pub goto_input: String,
+    /// Boreds the user knows of, gathered when the goto view is opened
+    pub goto_candidates: Vec<GoToSuggestion>,
+    /// Suggestion picked with the arrow keys, the typed address is used if there isn't one
+    pub goto_selected: Option<usize>,
+    pub bookmark_name_input: String,
+    pub petname_input: String,
+    pub search_input: String,
+    /// Text the boreds found by crawling are searched for in the index view
+    pub index_search: String,
+    /// Text being searched for on the current bored, its matches are highlighted
+    pub search: Option<String>,
+    /// Which match of the search was last jumped to
+    pub search_hit: Option<usize>,
+    pub directory_file_input: String,
+    pub listing_name_input: String,
+    pub listing_tags_input: String,
+    pub listing_note_input: String,
+    pub event_title_input: String,
+    pub event_start_input: String,
+    pub event_end_input: String,
+    pub event_location_input: String,
+    /// Only listings with this tag are shown in the directory view
+    pub directory_tag_filter: Option<String>,
+    /// What is being typed in the directory view search box, if it is open
+    pub directory_search: Option<String>,
     pub menu_visible: bool,
+    pub downloads: Downloads,
+    pub mouse_drag: Option<MouseDrag>,
+    /// The hyperlink on the bored under the mouse, if any
+    pub hovered_hyperlink: Option<Hyperlink>,
+    pub minimap_visible: bool,
+    /// Show boreds as a list of notices in reading order, for screen readers
+    pub linear_mode: bool,
+    pub overview: Option<Overview>,
+    pub history: History,
+    /// Opened the first time something is copied or pasted, kept open as on some systems what
+    /// was copied is lost when the clipboard is closed
+    pub clipboard: Option<Clipboard>,
+    /// Drafts saved as they are written
+    pub drafts: Drafts,
+    /// Where the draft being written is kept in the drafts, none until it has some text
+    pub draft_index: Option<usize>,
+    /// Move drafts from gridline to gridline while positioning them
+    pub grid_snap: bool,
+    /// The key to read boreds encrypted for the user, none until one is made or imported
+    pub reader_key: Option<ReaderKey>,
+    pub key_input: String,
+    /// The network operation being waited on, if any
+    pub tasks: Tasks,
+    /// Connecting to x0x in the background, boreds are read from the cache and network
+    /// operations wait until it is done
+    pub connecting: bool,
+    /// The daemon has gone and is being reconnected to
+    pub connection_lost: bool,
+    /// When the daemon was last checked, none to check it as soon as possible
+    last_connection_check: Option<Instant>,
+    checking_connection: bool,
+    /// Messages shown for a while in the corner
+    pub toasts: Toasts,
+    /// Errors shown this session, to look back at once they are dismissed
+    pub error_log: ErrorLog,
+    /// The client's ledger as it was when the ledger view was opened
+    pub ledger: Vec<LedgerEntry>,
+    /// Time between asking peers for changes to the bored, none to never ask
+    pub auto_refresh: Option<Duration>,
+    last_auto_refresh: Instant,
+    /// Ids of the notices that were on the bored when it was shown or that have been selected
+    /// since, the rest are new
+    seen_notices: HashSet<String>,
+    /// Checks the boreds watched in the directory for new notices
+    watcher: Watcher,
+    /// Boreds found by crawling out from the directory
+    pub index: Index,
+    /// The boreds in the index laid out with the links between them
+    pub graph: Graph,
+    /// Where the bored links that have been selected or hovered over go
+    previews: Previews,
+    /// The boreds notices on the current bored are embedded from
+    embeds: Embeds,
+    /// The notice picked to embed in the draft, with the address and name of its bored
+    embed_source: Option<(BoredAddress, String, Notice)>,
+    /// Schemes of links, and "file" for downloads, that are opened without asking
+    pub trusted_schemes: Vec<String>,
+    /// What to open links and downloads with in place of the system's programs
+    pub openers: OpenersFile,
+    /// Names given to the authors of notices
+    pub petnames: Petnames,
+    /// A command to open something with that needs the terminal, run once the key that asked
+    /// for it has been handled
+    terminal_command: Option<Vec<String>>,
+    /// The usual keys, or vim keys on top of them
+    pub keymap: Keymap,
+    pub vim_keys: VimKeys,
+    pub command_input: String,
+    /// A key to handle as if it were pressed, for the commands typed after :
+    pending_key: Option<KeyEvent>,
+    /// Whether the terminal was asked to send keys with all their modifiers
+    pub keyboard_enhanced: bool,
+    /// Started with --read-only, so no bored can be created or changed
+    pub read_only: bool,
+    /// Started with --count-views, so notices opened are counted for whoever posted them
+    pub count_views: bool,
+    /// Most proof of work to do to post, from --max-pow and --max-work
+    pub work_limit: WorkLimit,
+}
+/// The bored with hidden notices emptied for display, only copied if anything is hidden
+fn redact_hidden(bored: Arc<Bored>) -> Arc<Bored> {
+    if bored.get_hidden_notices().is_empty() {
+        bored
+    } else {
+        Arc::new(bored.redact_hidden())
+    }
+}
+
+/// The stack of notices at the first cell where the notice overlaps another, none if it
+/// overlaps nothing
+fn get_overlapping_stack(bored: &Bored, notice_index: usize) -> Option<Vec<usize>> {
+    let notice = bored.notices().get(notice_index)?;
+    let top_left = notice.get_top_left();
+    let bottom_right = top_left.add(&notice.get_dimensions());
+    (top_left.y..bottom_right.y)
+        .flat_map(|y| (top_left.x..bottom_right.x).map(move |x| Coordinate { x, y }))
+        .map(|cell| bored.get_notice_stack_at(cell))
+        .find(|stack| stack.len() > 1)
 }
+
+/// The scheme of a link and where it goes. Links with a scheme Surf Bored doesn't know are
+/// taken for bored names, so the scheme written is compared with the one they are read as.
+/// That makes ant:// links, left from boreds on Autonomi which isn't reached now they are on
+/// x0x, dead along with links that can't be read at all.
+fn classify_link(link: &str) -> (String, LinkTarget) {
+    let Ok(url) = URL::from_string(link.to_string()) else {
+        return ("unknown".to_string(), LinkTarget::Dead);
+    };
+    let scheme = url.get_scheme();
+    if let Some((written, _)) = link.trim().split_once("://")
+        && written != scheme
+    {
+        return (written.to_lowercase(), LinkTarget::Dead);
+    }
+    let target = match url {
+        URL::BoredNet(address) => LinkTarget::Bored(address),
+        URL::BoredShare(share_link) => LinkTarget::Bored(share_link.get_address().clone()),
+        URL::BoredApp(_) | URL::ClearNet(_) => LinkTarget::Unchecked,
+    };
+    (scheme.to_string(), target)
+}
+
+/// How many of the schemes there are of each, most first
+fn count_schemes(schemes: impl IntoIterator<Item = String>) -> Vec<(String, usize)> {
+    let mut counts: Vec<(String, usize)> = vec![];
+    for scheme in schemes {
+        match counts.iter_mut().find(|(counted, _)| *counted == scheme) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((scheme, 1)),
+        }
+    }
+    // stable so schemes as common as each other stay in the order they were found
+    counts.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    counts
+}
+
+/// File name for the events on a bored, its name with anything but letters and numbers made
+/// dashes
+fn get_events_filename(bored_name: &str) -> String {
+    let name: String = bored_name
+        .chars()
+        .map(|char| if char.is_alphanumeric() { char } else { '-' })
+        .collect();
+    let name = name.trim_matches('-');
+    if name.is_empty() {
+        return "events.ics".to_string();
+    }
+    format!("{name}-events.ics")
+}
+
 fn determine_directory_path() -> String {
     if let Some(standard_dir) = bored::x0x_client::get_we_are_bored_data_dir() {
         if std::fs::create_dir_all(&standard_dir).is_ok() {
@@ -160,6 +631,76 @@ fn determine_directory_path() -> String {
     "directory_of_boreds.toml".to_string()
 }
 
+/// Program and arguments of the editor to write drafts in, from $VISUAL then $EDITOR, falling
+/// back to one that comes with the system
+pub fn get_editor_command(visual: Option<String>, editor: Option<String>) -> Vec<String> {
+    let command = [visual, editor]
+        .into_iter()
+        .flatten()
+        .find(|command| !command.trim().is_empty())
+        .unwrap_or_else(|| {
+            if std::env::consts::OS == "windows" {
+                "notepad".to_string()
+            } else {
+                "vi".to_string()
+            }
+        });
+    command.split_whitespace().map(str::to_string).collect()
+}
+
+/// How far to move a draft from a position to reach the next gridline in a direction
+pub fn get_grid_step(position: u16, forward: bool) -> u16 {
+    let past_gridline = position % DRAFT_GRID_SPACING;
+    if forward {
+        DRAFT_GRID_SPACING - past_gridline
+    } else if past_gridline == 0 {
+        position.min(DRAFT_GRID_SPACING)
+    } else {
+        past_gridline
+    }
+}
+
+/// The gridline nearest a position, for dropping a dragged draft onto the grid
+pub fn snap_to_grid(position: u16) -> u16 {
+    let past_gridline = position % DRAFT_GRID_SPACING;
+    if past_gridline * 2 < DRAFT_GRID_SPACING {
+        position - past_gridline
+    } else {
+        position.saturating_add(DRAFT_GRID_SPACING - past_gridline)
+    }
+}
+
+/// Time between asking for changes to the bored from the SURF_BORED_REFRESH_SECONDS setting
+pub fn get_auto_refresh(setting: Option<String>) -> Option<Duration> {
+    match setting.map(|seconds| seconds.trim().parse::<u64>()) {
+        Some(Ok(0)) => None,
+        Some(Ok(seconds)) => Some(Duration::from_secs(seconds)),
+        // not set or not a number
+        _ => Some(DEFAULT_AUTO_REFRESH),
+    }
+}
+
+/// Schemes opened without asking from the SURF_BORED_TRUSTED_SCHEMES setting, none if it isn't
+/// set so everything is asked about
+pub fn get_trusted_schemes(setting: Option<String>) -> Vec<String> {
+    setting
+        .unwrap_or_default()
+        .split(',')
+        .map(|scheme| scheme.trim().to_lowercase())
+        .filter(|scheme| !scheme.is_empty())
+        .collect()
+}
+
+/// Go to the bored at a bored:// address or share link
+async fn go_to_url(client: &mut X0xBoredClient, url: URL) -> Result<(), SurfBoredError> {
+    match url {
+        URL::BoredNet(bored_address) => client.go_to_bored(&bored_address).await?,
+        URL::BoredShare(share_link) => client.go_to_share_link(&share_link).await?,
+        _ => return Err(SurfBoredError::Message("Not a bored".to_string())),
+    }
+    Ok(())
+}
+
 impl App {
     pub fn new() -> App {
         App {
@@ -170,20 +711,125 @@ impl App {
             previous_view: View::BoredView,
             interupted_view: View::BoredView,
             selected_notice: None,
+            peeked_notice: None,
+            clicked_cell: None,
             theme: Theme::surf_bored_synth_wave(),
+            picked_theme: Theme::surf_bored_synth_wave(),
+            bored_colours: true,
+            colour_depth: ColourDepth::detect(),
+            themes: Theme::built_in(),
             bored_view_port: None,
             name_input: String::new(),
             url_name_input: String::new(),
+            url_name_check: None,
+            link_check: None,
             content_input: String::new(),
             link_text_input: String::new(),
             link_url_input: String::new(),
             goto_input: String::new(),
+            goto_candidates: vec![],
+            goto_selected: None,
+            bookmark_name_input: String::new(),
+            petname_input: String::new(),
+            search_input: String::new(),
+            index_search: String::new(),
+            search: None,
+            search_hit: None,
+            directory_file_input: String::new(),
+            listing_name_input: String::new(),
+            listing_tags_input: String::new(),
+            listing_note_input: String::new(),
+            event_title_input: String::new(),
+            event_start_input: String::new(),
+            event_end_input: String::new(),
+            event_location_input: String::new(),
+            directory_tag_filter: None,
+            directory_search: None,
             menu_visible: false,
+            downloads: Downloads::new(),
+            mouse_drag: None,
+            hovered_hyperlink: None,
+            minimap_visible: false,
+            linear_mode: false,
+            overview: None,
+            history: History::new(),
+            clipboard: None,
+            drafts: Drafts::default(),
+            draft_index: None,
+            grid_snap: false,
+            reader_key: None,
+            key_input: String::new(),
+            tasks: Tasks::new(),
+            connecting: false,
+            connection_lost: false,
+            last_connection_check: Some(Instant::now()),
+            checking_connection: false,
+            toasts: Toasts::default(),
+            error_log: ErrorLog::default(),
+            ledger: vec![],
+            auto_refresh: get_auto_refresh(std::env::var(AUTO_REFRESH_VAR).ok()),
+            last_auto_refresh: Instant::now(),
+            seen_notices: HashSet::new(),
+            watcher: Watcher::new(),
+            index: Index::new(),
+            graph: Graph::default(),
+            previews: Previews::new(),
+            embeds: Embeds::new(),
+            embed_source: None,
+            trusted_schemes: get_trusted_schemes(std::env::var(TRUSTED_SCHEMES_VAR).ok()),
+            openers: OpenersFile::default(),
+            petnames: Petnames::default(),
+            terminal_command: None,
+            keymap: keymap::get_keymap(std::env::var(keymap::KEYMAP_VAR).ok()),
+            vim_keys: VimKeys::default(),
+            command_input: String::new(),
+            pending_key: None,
+            keyboard_enhanced: false,
+            read_only: false,
+            count_views: false,
+            work_limit: WorkLimit::default(),
         }
     }
 
-    pub async fn init_client(&mut self) -> Result<(), BoredError> {
-        self.client = Some(X0xBoredClient::init().await?);
+    /// Connect to the x0x daemon in the background so the ui comes up at once, boreds that have
+    /// been visited are read from the cache in the meantime
+    pub fn start_connecting(&mut self, data_dir: Option<PathBuf>, daemon: X0xDaemon) {
+        let data_dir = data_dir
+            .or_else(bored::x0x_client::get_we_are_bored_data_dir)
+            .unwrap_or_else(|| PathBuf::from("."));
+        let mut client = X0xBoredClient::offline(&data_dir, &daemon);
+        client.set_read_only(self.read_only);
+        client.set_count_views(self.count_views);
+        client.set_work_limit(self.work_limit);
+        client.set_reader_key(self.reader_key.clone());
+        self.client = Some(client);
+        self.connecting = true;
+        self.tasks.connect(Some(data_dir), daemon);
+    }
+
+    /// Take the client once it has connected, staying on the bored read from the cache, and
+    /// run the network operation that was waiting for it
+    pub fn finish_connecting(
+        &mut self,
+        client: Result<Box<X0xBoredClient>, BoredError>,
+    ) -> Result<(), BoredError> {
+        self.connecting = false;
+        let mut client = match client {
+            Ok(client) => *client,
+            Err(e) => {
+                self.tasks.clear_queued();
+                return Err(e);
+            }
+        };
+        client.set_read_only(self.read_only);
+        client.set_count_views(self.count_views);
+        client.set_work_limit(self.work_limit);
+        client.set_reader_key(self.reader_key.clone());
+        if let Some(bored_address) = self.get_current_address() {
+            let _ = client.open_cached(&bored_address);
+        }
+        self.client = Some(client.clone());
+        self.tasks.spawn_queued(client);
         Ok(())
     }
 
@@ -197,44 +843,581 @@ impl App {
         Ok(())
     }
 
+    /// Write the directory to a file in the format its extension is for, returns how many
+    /// boreds were exported
+    pub fn export_directory(&self, path: &str) -> Result<usize, SurfBoredError> {
+        let format = DirectoryFormat::from_path(Path::new(path))?;
+        let exported = self.directory.export(format)?;
+        std::fs::write(path, exported)
+            .map_err(|_| SurfBoredError::DirectoryExportError(path.to_string()))?;
+        Ok(self.directory.get_bored_addresses().len())
+    }
+
+    /// Merge the boreds in a file exported by surf-bored or a browser into the directory,
+    /// returns how many were new
+    pub fn import_directory(&mut self, path: &str) -> Result<usize, SurfBoredError> {
+        let format = DirectoryFormat::from_path(Path::new(path))?;
+        let text = std::fs::read_to_string(path)
+            .map_err(|_| SurfBoredError::DirectoryImportError(path.to_string()))?;
+        let imported = Directory::import(&text, format)?;
+        let listing_count = self.directory.get_bored_addresses().len();
+        self.directory.merge(&imported);
+        self.directory.save_file(&self.directory_path)?;
+        Ok(self
+            .directory
+            .get_bored_addresses()
+            .len()
+            .saturating_sub(listing_count))
+    }
+
+    /// Keep the directory in step with the copy at the path in SURF_BORED_DIRECTORY_SYNC, such
+    /// as a folder shared between machines, merging the two and saving the result to both
+    pub fn sync_directory(&mut self) -> Result<(), SurfBoredError> {
+        let Some(sync_path) = std::env::var_os(DIRECTORY_SYNC_VAR) else {
+            return Ok(());
+        };
+        let sync_path = sync_path.to_string_lossy().to_string();
+        if Path::new(&sync_path).exists() {
+            self.directory.merge(&Directory::load_file(&sync_path)?);
+        }
+        self.directory.save_file(&self.directory_path)?;
+        self.directory.save_file(&sync_path)
+    }
+
+    /// The session file is kept next to the directory of boreds
+    fn get_session_path(&self) -> PathBuf {
+        Path::new(&self.directory_path).with_file_name("session.toml")
+    }
+
+    /// Save the bored being viewed, where it is viewed from and the selected notice
+    pub fn save_session(&self) -> Result<(), SurfBoredError> {
+        let Some(visit) = self.history.get_current() else {
+            return Ok(());
+        };
+        let session = Session {
+            link: visit.link.clone(),
+            view_top_left: self
+                .bored_view_port
+                .as_ref()
+                .map(|bored_view_port| bored_view_port.get_view_top_left())
+                .unwrap_or(visit.view_top_left),
+            selected_notice: self.selected_notice,
+            linear_mode: self.linear_mode,
+        };
+        session.save_file(&self.get_session_path())
+    }
+
+    pub fn load_session(&self) -> Option<Session> {
+        Session::load_file(&self.get_session_path())
+    }
+
+    /// The themes file is kept next to the directory of boreds too
+    pub fn get_themes_path(&self) -> PathBuf {
+        Path::new(&self.directory_path).with_file_name("themes.toml")
+    }
+
+    /// Add the themes from the themes file and use its default theme
+    pub fn load_themes(&mut self) -> Result<(), SurfBoredError> {
+        let theme_file = ThemeFile::load_file(&self.get_themes_path())?;
+        self.themes = theme_file.get_themes()?;
+        self.bored_colours = theme_file.bored_colours;
+        if let Some(colour_depth) = theme_file.colour_depth {
+            self.colour_depth = colour_depth;
+        }
+        if let Some(name) = theme_file.default {
+            self.use_theme_named(&name)?;
+        }
+        Ok(())
+    }
+
+    /// Use the theme with the name from now on, without saving it as the default
+    pub fn use_theme_named(&mut self, name: &str) -> Result<(), SurfBoredError> {
+        let Some(theme) = self.themes.iter().find(|t| t.get_name() == name) else {
+            return Err(SurfBoredError::UnknownTheme(name.to_string()));
+        };
+        self.picked_theme = theme.clone();
+        self.theme = theme.clone();
+        Ok(())
+    }
+
+    /// The openers file is kept next to the directory of boreds too
+    pub fn get_openers_path(&self) -> PathBuf {
+        Path::new(&self.directory_path).with_file_name("openers.toml")
+    }
+
+    pub fn load_openers(&mut self) -> Result<(), SurfBoredError> {
+        self.openers = OpenersFile::load_file(&self.get_openers_path())?;
+        Ok(())
+    }
+
+    /// The petnames file is kept next to the directory of boreds too
+    pub fn get_petnames_path(&self) -> PathBuf {
+        Path::new(&self.directory_path).with_file_name("petnames.toml")
+    }
+
+    pub fn load_petnames(&mut self) -> Result<(), SurfBoredError> {
+        self.petnames = Petnames::load_file(&self.get_petnames_path())?;
+        Ok(())
+    }
+
+    /// Draw in the picked theme with the colours the bored asks for, unless the user has
+    /// turned bored colours off, in the colours the terminal can show
+    pub fn apply_palette(&mut self, palette: Option<&Palette>) {
+        let theme = match palette {
+            Some(palette) if self.bored_colours => self.picked_theme.with_palette(palette),
+            _ => self.picked_theme.clone(),
+        };
+        self.theme = theme.for_colour_depth(self.colour_depth);
+    }
+
+    /// Turn showing boreds in their own colours on or off and remember it for next time
+    pub fn toggle_bored_colours(&mut self) -> Result<(), SurfBoredError> {
+        self.bored_colours = !self.bored_colours;
+        ThemeFile::save_bored_colours(&self.get_themes_path(), self.bored_colours)
+    }
+
+    /// Open the ledger of everything published from here, newest first
+    pub fn open_ledger(&mut self) -> Result<(), SurfBoredError> {
+        let Some(client) = &self.client else {
+            return Err(SurfBoredError::BoredError(
+                BoredError::ClientConnectionError,
+            ));
+        };
+        self.ledger = client.get_ledger()?;
+        self.change_view(View::LedgerView(0));
+        Ok(())
+    }
+
+    /// Open the theme picker with the theme in use selected
+    pub fn open_themes(&mut self) {
+        let index = self
+            .themes
+            .iter()
+            .position(|t| t.get_name() == self.picked_theme.get_name())
+            .unwrap_or(0);
+        self.change_view(View::ThemeView(index));
+    }
+
+    /// Switch to the theme at the index and save it as the default for next time
+    pub fn set_theme(&mut self, theme_index: usize) -> Result<(), SurfBoredError> {
+        let Some(theme) = self.themes.get(theme_index) else {
+            return Ok(());
+        };
+        self.picked_theme = theme.clone();
+        self.theme = theme.clone();
+        ThemeFile::save_default(&self.get_themes_path(), theme.get_name())
+    }
+
+    /// Go back to where the user was when the session was saved, going home instead if the
+    /// bored can't be loaded
+    pub fn restore_session(&mut self, session: Session) -> Result<(), SurfBoredError> {
+        self.linear_mode = session.linear_mode;
+        let url = URL::from_string(session.link)?;
+        self.load_bored(url, move |app, result| {
+            if result.is_err() {
+                return app.start_at_home();
+            }
+            if let Some(bored_view_port) = app.bored_view_port.as_mut() {
+                bored_view_port.move_view(session.view_top_left);
+            }
+            let notice_count = app.get_current_bored().map_or(0, |b| b.notices().len());
+            app.selected_notice = session
+                .selected_notice
+                .filter(|index| *index < notice_count);
+            Ok(())
+        })
+    }
+
+    /// Go to the home bored, the welcome bored is made if it is home and isn't on x0x yet
+    /// Start at the bored at a bored:// address or share link given when surf bored was run
+    pub fn start_at(&mut self, link: &str) -> Result<(), SurfBoredError> {
+        let url = URL::from_string(link.to_string())?;
+        self.load_bored(url, |_, result| result)
+    }
+
+    pub fn start_at_home(&mut self) -> Result<(), SurfBoredError> {
+        let Some(home) = self.directory.get_home() else {
+            return Ok(());
+        };
+        let home_address = BoredAddress::from_string(home)?;
+        let is_welcome_bored = home_address.to_string() == "bored://welcome";
+        self.load_bored(
+            URL::BoredNet(home_address),
+            move |app, result| match result {
+                Err(_) if is_welcome_bored => app.create_welcome_bored(),
+                _ => result,
+            },
+        )
+    }
+
+    fn create_welcome_bored(&mut self) -> Result<(), SurfBoredError> {
+        self.spawn_client_task(
+            "Initializing Welcome board...",
+            |mut client| async move {
+                client
+                    .create_bored("Welcome", Coordinate { x: 120, y: 40 }, Some("welcome"))
+                    .await?;
+                client.create_draft(Coordinate { x: 55, y: 6 })?;
+                client.edit_draft("Welcome to the we are bored network\nrunning on the [x0x](https://x0x.md) network.")?;
+                client.position_draft(Coordinate { x: 32, y: 17 })?;
+                client.add_draft_to_bored().await?;
+                Ok(client)
+            },
+            |app, result| {
+                result?;
+                app.show_created_bored()?;
+                if app
+                    .get_current_bored()
+                    .is_some_and(|bored| !bored.notices().is_empty())
+                {
+                    app.selected_notice = Some(0);
+                }
+                Ok(())
+            },
+        )
+    }
+
     pub fn set_home(&mut self, directory_index: usize) -> Result<(), SurfBoredError> {
         self.directory.set_home(directory_index);
         self.directory.save_file(&self.directory_path)?;
         Ok(())
     }
 
-    pub fn next_directory_item(&mut self, directory_index: usize) -> Result<usize, SurfBoredError> {
-        let bored_addresses = self.directory.get_bored_addresses();
-        if bored_addresses.is_empty() {
+    /// Watch the bored on a row of the directory view for new notices or stop, returning
+    /// whether it is watched
+    pub fn toggle_watched(&mut self, directory_row: usize) -> Result<bool, SurfBoredError> {
+        let directory_index = self.get_directory_index(directory_row)?;
+        self.directory
+            .toggle_watched(directory_index, &self.directory_path)
+    }
+
+    /// Indexes in the directory of the rows shown in the directory view
+    fn get_directory_rows(&self) -> Vec<usize> {
+        self.directory.get_listing_indexes(
+            self.directory_tag_filter.as_deref(),
+            self.directory_search.as_deref().unwrap_or_default(),
+        )
+    }
+
+    /// Index in the directory of a row of the directory view, which may be filtered by tag
+    /// and search
+    pub fn get_directory_index(&self, directory_row: usize) -> Result<usize, SurfBoredError> {
+        let rows = self.get_directory_rows();
+        if rows.is_empty() {
             return Err(SurfBoredError::DirectoryIsEmpty);
-        } else if directory_index + 1 > bored_addresses.len() - 1 {
+        }
+        rows.get(directory_row)
+            .copied()
+            .ok_or(SurfBoredError::DirectoryOutOfBounds(
+                directory_row,
+                rows.len(),
+            ))
+    }
+
+    pub fn next_directory_item(&mut self, directory_row: usize) -> Result<usize, SurfBoredError> {
+        let rows = self.get_directory_rows();
+        if rows.is_empty() {
+            return Err(SurfBoredError::DirectoryIsEmpty);
+        } else if directory_row + 1 > rows.len() - 1 {
             return Ok(0);
         }
-        Ok(directory_index + 1)
+        Ok(directory_row + 1)
     }
 
     pub fn previous_directory_item(
         &mut self,
-        directory_index: usize,
+        directory_row: usize,
     ) -> Result<usize, SurfBoredError> {
-        let bored_addresses = self.directory.get_bored_addresses();
-        if bored_addresses.is_empty() {
+        let rows = self.get_directory_rows();
+        if rows.is_empty() {
             return Err(SurfBoredError::DirectoryIsEmpty);
-        } else if directory_index >= 1 {
-            return Ok(directory_index - 1);
+        } else if directory_row >= 1 {
+            return Ok(directory_row - 1);
+        }
+        Ok(rows.len() - 1)
+    }
+
+    /// Swap the listing on a row of the directory view with the one above or below it,
+    /// returns the row it ends up on
+    pub fn move_directory_item(
+        &mut self,
+        directory_row: usize,
+        up: bool,
+    ) -> Result<usize, SurfBoredError> {
+        let rows = self.get_directory_rows();
+        let other_row = if up {
+            directory_row.checked_sub(1)
+        } else {
+            Some(directory_row + 1).filter(|row| *row < rows.len())
+        };
+        let Some(other_row) = other_row else {
+            return Ok(directory_row);
+        };
+        let directory_index = self.get_directory_index(directory_row)?;
+        self.directory
+            .swap(directory_index, rows[other_row], &self.directory_path)?;
+        Ok(other_row)
+    }
+
+    /// Remove the listing on a row of the directory view, returns the row to select after
+    pub fn remove_directory_item(&mut self, directory_row: usize) -> Result<usize, SurfBoredError> {
+        let directory_index = self.get_directory_index(directory_row)?;
+        self.directory
+            .remove(directory_index, &self.directory_path)?;
+        if self.get_directory_rows().is_empty() {
+            self.directory_tag_filter = None;
+        }
+        Ok(directory_row.min(self.get_directory_rows().len().saturating_sub(1)))
+    }
+
+    /// Filter the directory view by the next tag, after the last tag the filter is removed
+    pub fn next_directory_tag_filter(&mut self) {
+        let tags = self.directory.get_tags();
+        self.directory_tag_filter = match &self.directory_tag_filter {
+            None => tags.first().cloned(),
+            Some(tag) => tags.iter().skip_while(|t| *t != tag).nth(1).cloned(),
+        };
+    }
+
+    pub fn edit_directory_item(&mut self, directory_row: usize) -> Result<(), SurfBoredError> {
+        let directory_index = self.get_directory_index(directory_row)?;
+        let listing = self.directory.get_bored_address(directory_index)?;
+        self.listing_name_input = listing.name.clone();
+        self.listing_tags_input = listing.get_tags_string();
+        self.listing_note_input = listing.note.clone().unwrap_or_default();
+        self.change_view(View::ListingView {
+            directory_index,
+            field: ListingField::Name,
+        });
+        Ok(())
+    }
+
+    pub fn get_listing_input_mut(&mut self, field: ListingField) -> &mut String {
+        match field {
+            ListingField::Name => &mut self.listing_name_input,
+            ListingField::Tags => &mut self.listing_tags_input,
+            ListingField::Note => &mut self.listing_note_input,
+        }
+    }
+
+    /// Save the edited listing and go back to the directory, a blank name keeps the old one
+    pub fn save_listing_edit(&mut self, directory_index: usize) -> Result<(), SurfBoredError> {
+        let mut listing = self.directory.get_bored_address(directory_index)?;
+        let name = self.listing_name_input.trim();
+        if !name.is_empty() {
+            listing.name = name.to_string();
+        }
+        listing.set_tags_from_str(&self.listing_tags_input);
+        let note = self.listing_note_input.trim();
+        listing.note = (!note.is_empty()).then(|| note.to_string());
+        self.directory
+            .update(directory_index, listing, &self.directory_path)?;
+        if let Some(tag) = &self.directory_tag_filter
+            && !self.directory.get_tags().contains(tag)
+        {
+            self.directory_tag_filter = None;
+        }
+        let directory_row = self
+            .get_directory_rows()
+            .iter()
+            .position(|i| *i == directory_index)
+            .unwrap_or(0);
+        self.current_view = View::DirectoryView(directory_row);
+        Ok(())
+    }
+
+    /// Show the help over the current view, which is returned to when it is closed
+    pub fn open_help(&mut self) {
+        let from = Box::new(self.current_view.clone());
+        self.current_view = View::HelpView { scroll: 0, from };
+        self.menu_visible = false;
+    }
+
+    pub fn close_help(&mut self) {
+        if let View::HelpView { from, .. } = &self.current_view {
+            self.current_view = *from.clone();
+        }
+    }
+
+    /// Scroll the help by a number of rows, stopping at the last row
+    pub fn scroll_help(&mut self, rows: i32) {
+        let last_row = help::get_row_count().saturating_sub(1) as i32;
+        if let View::HelpView { scroll, .. } = &mut self.current_view {
+            *scroll = (*scroll as i32).saturating_add(rows).clamp(0, last_row) as u16;
+        }
+    }
+
+    /// Open the goto view with the boreds in the directory, history and cache to suggest
+    pub fn open_goto(&mut self) {
+        let mut candidates: Vec<GoToSuggestion> = vec![];
+        let mut add = |name: &str, address: &str| {
+            let Ok(bored_address) = BoredAddress::from_string(address) else {
+                return;
+            };
+            // bored://welcome and bored://bored.welcome are the same bored
+            let topic = bored_address.get_topic();
+            if !candidates.iter().any(|c| {
+                BoredAddress::from_string(&c.address).is_ok_and(|a| a.get_topic() == topic)
+            }) {
+                candidates.push(GoToSuggestion {
+                    name: name.to_string(),
+                    address: address.to_string(),
+                });
+            }
+        };
+        for listing in self.directory.get_bored_addresses() {
+            add(&listing.name, &listing.bored_address);
+        }
+        let cached = self
+            .client
+            .as_ref()
+            .map(|client| client.get_cached_boreds())
+            .unwrap_or_default();
+        // most recent first, share links are left out as they aren't plain addresses
+        for visit in self.history.get_visits().iter().rev() {
+            if visit.link.starts_with("bored://")
+                && let Ok(address) = BoredAddress::from_string(&visit.link)
+            {
+                let name = cached
+                    .iter()
+                    .find(|(a, _)| a.get_topic() == address.get_topic())
+                    .map_or(visit.link.as_str(), |(_, name)| name.as_str());
+                add(name, &visit.link);
+            }
+        }
+        for (address, name) in &cached {
+            add(name, &address.to_string());
+        }
+        self.goto_candidates = candidates;
+        self.goto_selected = None;
+        self.change_view(View::GoToView);
+    }
+
+    /// Boreds whose name or address match what has been typed in the goto view
+    pub fn get_goto_suggestions(&self) -> Vec<&GoToSuggestion> {
+        let input = self.goto_input.trim();
+        self.goto_candidates
+            .iter()
+            .filter(|c| {
+                directory::fuzzy_match(input, &c.name).is_some()
+                    || directory::fuzzy_match(input, &c.address).is_some()
+            })
+            .take(MAX_GOTO_SUGGESTIONS)
+            .collect()
+    }
+
+    /// Move the goto view selection up or down the suggestions, moving up from the first goes
+    /// back to the typed address
+    pub fn select_goto_suggestion(&mut self, up: bool) {
+        let count = self.get_goto_suggestions().len();
+        self.goto_selected = match (self.goto_selected, up) {
+            (None, true) => None,
+            (None, false) => (count > 0).then_some(0),
+            (Some(0), true) => None,
+            (Some(i), true) => Some(i - 1),
+            (Some(i), false) => Some((i + 1).min(count.saturating_sub(1))),
+        };
+    }
+
+    /// The address to go to from the goto view, the selected suggestion or else what was typed
+    pub fn get_goto_address(&self) -> String {
+        self.goto_selected
+            .and_then(|i| {
+                self.get_goto_suggestions()
+                    .get(i)
+                    .map(|c| c.address.clone())
+            })
+            .unwrap_or_else(|| self.goto_input.clone())
+    }
+
+    /// Matches of the search on the current bored, none if nothing is being searched for
+    pub fn get_search_hits(&self) -> Vec<SearchHit> {
+        let (Some(search), Some(bored)) = (&self.search, self.get_current_bored()) else {
+            return vec![];
+        };
+        bored
+            .search(&SearchPattern::Text(search.clone()))
+            .unwrap_or_default()
+    }
+
+    /// Search the current bored for what was typed and jump to the first match
+    pub fn start_search(&mut self) -> Result<(), SurfBoredError> {
+        let search = self.search_input.trim().to_string();
+        self.search_hit = None;
+        self.search = (!search.is_empty()).then_some(search.clone());
+        self.revert_view();
+        if self.search.is_some() && !self.jump_to_search_hit(true) {
+            return Err(SurfBoredError::Message(format!(
+                "No matches for \"{search}\" on this board"
+            )));
+        }
+        Ok(())
+    }
+
+    pub fn clear_search(&mut self) {
+        self.search = None;
+        self.search_hit = None;
+    }
+
+    /// Select the notice of the next or previous match of the search and move the view to it,
+    /// going round from the last to the first. Returns false if there are no matches.
+    pub fn jump_to_search_hit(&mut self, forward: bool) -> bool {
+        let hits = self.get_search_hits();
+        if hits.is_empty() {
+            return false;
         }
-        Ok(bored_addresses.len() - 1)
+        let hit_index = match (self.search_hit, forward) {
+            (None, _) => 0,
+            (Some(i), true) => (i + 1) % hits.len(),
+            (Some(i), false) => (i + hits.len() - 1) % hits.len(),
+        };
+        self.search_hit = Some(hit_index);
+        let hit = &hits[hit_index];
+        self.selected_notice = Some(hit.notice_index);
+        if let (Some(bored_view_port), Some(first), Some(last)) = (
+            self.bored_view_port.as_mut(),
+            hit.coordinates.first(),
+            hit.coordinates.last(),
+        ) && !bored_view_port.in_view(*first, *last)
+        {
+            bored_view_port.centre_on(*first);
+        }
+        true
     }
 
     pub fn display_error(&mut self, surf_bored_error: SurfBoredError) {
         self.change_view(View::ErrorView(surf_bored_error));
     }
 
+    /// Say something in the corner for a while without stopping what the user is doing
+    pub fn show_toast(&mut self, kind: ToastKind, message: &str) {
+        self.toasts.push(kind, message);
+    }
+
     /// set previous view so can allways go back
     pub fn change_view(&mut self, view: View) {
+        if matches!(view, View::NoticeView { .. })
+            && !matches!(self.current_view, View::NoticeView { .. })
+        {
+            self.record_view();
+        }
         match view {
-            View::ErrorView(_) => self.interupted_view(self.current_view.clone()),
+            View::ErrorView(ref e) => {
+                self.error_log.push(e.clone());
+                self.interupted_view(self.current_view.clone())
+            }
             View::DirectoryView(_) => self.interupted_view(self.current_view.clone()),
+            View::DownloadsView(_) => self.interupted_view(self.current_view.clone()),
+            View::ThemeView(_) => self.interupted_view(self.current_view.clone()),
+            View::DraftsView(_) => self.interupted_view(self.current_view.clone()),
+            View::KeyView(_) => self.interupted_view(self.current_view.clone()),
+            View::ErrorLogView(_) => self.interupted_view(self.current_view.clone()),
+            View::LedgerView(_) => self.interupted_view(self.current_view.clone()),
+            View::NoticeListView(_) => self.interupted_view(self.current_view.clone()),
+            View::QrView(_) => self.interupted_view(self.current_view.clone()),
+            View::IndexView(_) => self.interupted_view(self.current_view.clone()),
+            View::GraphView(_) => self.interupted_view(self.current_view.clone()),
+            View::TemplateView(_) => self.interupted_view(self.current_view.clone()),
             _ => {
                 self.previous_view = self.current_view.clone();
             }
@@ -243,11 +1426,25 @@ impl App {
         self.menu_visible = false;
     }
 
-    /// only sets interupted view if it is not an error/diretory
+    /// only sets interupted view if it is not an error/diretory/listing/directory file/downloads/
+    /// theme/drafts/key/error log/ledger/notice list/qr code/index/graph/template
     fn interupted_view(&mut self, view: View) {
         match view {
             View::ErrorView(_) => (),
             View::DirectoryView(_) => (),
+            View::ListingView { .. } => (),
+            View::DirectoryFileView(_) => (),
+            View::DownloadsView(_) => (),
+            View::ThemeView(_) => (),
+            View::DraftsView(_) => (),
+            View::KeyView(_) => (),
+            View::ErrorLogView(_) => (),
+            View::LedgerView(_) => (),
+            View::NoticeListView(_) => (),
+            View::QrView(_) => (),
+            View::IndexView(_) => (),
+            View::GraphView(_) => (),
+            View::TemplateView(_) => (),
             _ => self.interupted_view = self.current_view.clone(),
         }
     }
@@ -257,43 +1454,544 @@ impl App {
         match self.current_view {
             View::ErrorView(_) => self.current_view = self.interupted_view.clone(),
             View::DirectoryView(_) => self.current_view = self.interupted_view.clone(),
+            View::DownloadsView(_) => self.current_view = self.interupted_view.clone(),
+            View::ThemeView(_) => self.current_view = self.interupted_view.clone(),
+            View::DraftsView(_) => self.current_view = self.interupted_view.clone(),
+            View::KeyView(_) => self.current_view = self.interupted_view.clone(),
+            View::ErrorLogView(_) => self.current_view = self.interupted_view.clone(),
+            View::LedgerView(_) => self.current_view = self.interupted_view.clone(),
+            View::NoticeListView(_) => self.current_view = self.interupted_view.clone(),
+            View::QrView(_) => self.current_view = self.interupted_view.clone(),
+            View::IndexView(_) => self.current_view = self.interupted_view.clone(),
+            View::GraphView(_) => self.current_view = self.interupted_view.clone(),
+            View::TemplateView(_) => self.current_view = self.interupted_view.clone(),
             _ => self.current_view = self.previous_view.clone(),
         }
         self.menu_visible = false;
     }
 
-    pub async fn goto_bored(&mut self, bored_address: BoredAddress) -> Result<(), SurfBoredError> {
-        let Some(ref mut client) = self.client else {
+    /// Run a network operation on a copy of the client in the background, the copy takes the
+    /// place of the app's client if the operation succeeds, then on_finished is run with the
+    /// result. While connecting the operation waits until the client has connected.
+    pub fn spawn_client_task<Fut>(
+        &mut self,
+        message: &str,
+        operation: impl FnOnce(X0xBoredClient) -> Fut + Send + 'static,
+        on_finished: impl FnOnce(&mut App, Result<(), SurfBoredError>) -> Result<(), SurfBoredError>
+        + Send
+        + 'static,
+    ) -> Result<(), SurfBoredError>
+    where
+        Fut: Future<Output = Result<X0xBoredClient, SurfBoredError>> + Send + 'static,
+    {
+        if self.connecting {
+            self.tasks.queue(
+                message,
+                Box::new(|client| Box::pin(operation(client))),
+                Box::new(on_finished),
+            );
+            self.show_toast(
+                ToastKind::Info,
+                "Still connecting to x0x, this carries on once connected",
+            );
+            return Ok(());
+        }
+        let Some(client) = self.client.clone() else {
             return Err(SurfBoredError::BoredError(
                 BoredError::ClientConnectionError,
             ));
         };
-        client.go_to_bored(&bored_address).await?;
-        self.selected_notice = None;
-        let bored = client.get_current_bored()?;
-        self.revert_view();
-        self.bored_view_port = Some(BoredViewPort::create(
-            &bored,
-            bored.get_dimensions(),
-            self.selected_notice,
-        ));
+        self.tasks
+            .spawn(message, operation(client), Box::new(on_finished));
         Ok(())
     }
 
-    pub fn get_current_bored(&self) -> Option<Bored> {
-        if let Some(client) = &self.client {
-            if let Ok(bored) = client.get_current_bored() {
-                return Some(bored);
-            }
+    /// Take the client back from a task that has finished and do what is left to do
+    pub fn finish_task(
+        &mut self,
+        client: Result<Box<X0xBoredClient>, SurfBoredError>,
+        on_finished: OnFinished,
+    ) {
+        if let Err(SurfBoredError::BoredError(
+            BoredError::ClientConnectionError | BoredError::NetworkError(_),
+        )) = client
+        {
+            // the daemon may have gone, check now rather than waiting
+            self.last_connection_check = None;
+        }
+        let result = client.map(|client| self.client = Some(*client));
+        if let Err(e) = on_finished(self, result) {
+            self.display_error(e);
         }
-        None
     }
 
-    pub fn get_current_address(&self) -> Option<BoredAddress> {
-        if let Some(client) = &self.client {
-            return client.get_bored_address().ok();
+    /// Give up on a task that has been waited on for too long
+    pub fn check_task_timeout(&mut self) {
+        if self
+            .tasks
+            .get_running()
+            .is_some_and(|running| running.get_elapsed() > TASK_TIMEOUT)
+        {
+            self.tasks.cancel();
+            self.display_error(SurfBoredError::StillWaiting);
         }
-        None
+    }
+
+    /// Go to a bored or share link in the background, once it has loaded it is shown and
+    /// visited in the history then on_loaded is run with the result
+    fn load_bored(
+        &mut self,
+        url: URL,
+        on_loaded: impl FnOnce(&mut App, Result<(), SurfBoredError>) -> Result<(), SurfBoredError>
+        + Send
+        + 'static,
+    ) -> Result<(), SurfBoredError> {
+        let link = match &url {
+            URL::BoredNet(bored_address) => bored_address.to_string(),
+            URL::BoredShare(share_link) => share_link.to_string(),
+            _ => return Err(SurfBoredError::Message("Not a bored".to_string())),
+        };
+        self.save_view_to_history();
+        // show what is in the cache while waiting to connect
+        if self.connecting
+            && let URL::BoredNet(bored_address) = &url
+            && let Some(client) = self.client.as_mut()
+            && client.open_cached(bored_address).is_ok()
+        {
+            self.show_new_bored()?;
+        }
+        self.spawn_client_task(
+            "Loading board from x0x...",
+            |mut client| async move {
+                go_to_url(&mut client, url).await?;
+                Ok(client)
+            },
+            move |app, result| {
+                let result = result.and_then(|_| {
+                    app.show_new_bored()?;
+                    app.history.visit(&link);
+                    // anyone who knows a bored can say it has moved so it is only followed if
+                    // the user chooses to
+                    let moved_to = app
+                        .client
+                        .as_ref()
+                        .and_then(|client| client.current_bored().ok()?.get_moved_to());
+                    if let Some(moved_to) = moved_to {
+                        app.show_toast(
+                            ToastKind::Info,
+                            &format!(
+                                "This bored says it has moved, type :e {moved_to} to go there"
+                            ),
+                        );
+                    }
+                    Ok(())
+                });
+                on_loaded(app, result)
+            },
+        )
+    }
+
+    pub fn goto_bored(&mut self, bored_address: BoredAddress) -> Result<(), SurfBoredError> {
+        self.load_bored(URL::BoredNet(bored_address), |app, result| {
+            result?;
+            app.goto_input = String::new();
+            Ok(())
+        })
+    }
+
+    /// Keep where the current bored is viewed from so going back through the history returns
+    /// to the same place
+    fn save_view_to_history(&mut self) {
+        if let Some(bored_view_port) = &self.bored_view_port {
+            self.history.set_view(bored_view_port.get_view_top_left());
+        }
+    }
+
+    /// Go back or forward to a bored in the history, viewing it from where it was left
+    pub fn surf_history(&mut self, direction: HistoryDirection) -> Result<(), SurfBoredError> {
+        let Some(visit) = self.history.get(direction).cloned() else {
+            return Ok(());
+        };
+        let url = URL::from_string(visit.link)?;
+        if !matches!(url, URL::BoredNet(_) | URL::BoredShare(_)) {
+            return Err(SurfBoredError::Message(
+                "Not a bored in the history".to_string(),
+            ));
+        }
+        self.save_view_to_history();
+        self.spawn_client_task(
+            "Loading board from x0x...",
+            |mut client| async move {
+                go_to_url(&mut client, url).await?;
+                Ok(client)
+            },
+            move |app, result| {
+                result?;
+                app.show_new_bored()?;
+                app.history.go(direction);
+                if let Some(bored_view_port) = app.bored_view_port.as_mut() {
+                    bored_view_port.move_view(visit.view_top_left);
+                }
+                Ok(())
+            },
+        )
+    }
+
+    /// Queue a download of the file attached to the selected notice
+    pub fn download_selected_attachment(&mut self) -> Result<(), SurfBoredError> {
+        let Some(attachment) = self
+            .get_selected_notice()
+            .and_then(|notice| notice.get_attachment().cloned())
+        else {
+            return Err(SurfBoredError::Message(
+                "This notice has no attachment to download".to_string(),
+            ));
+        };
+        self.download_attachments(vec![attachment])
+    }
+
+    /// Files attached to notices on the current bored
+    pub fn get_current_attachments(&self) -> Vec<Attachment> {
+        self.get_current_bored()
+            .map(|bored| bored.get_attachments())
+            .unwrap_or_default()
+    }
+
+    /// Queue downloads of files attached to notices on the current bored
+    pub fn download_attachments(
+        &mut self,
+        attachments: Vec<Attachment>,
+    ) -> Result<(), SurfBoredError> {
+        let Some(ref client) = self.client else {
+            return Err(SurfBoredError::BoredError(BoredError::ClientConnectionError));
+        };
+        for attachment in attachments {
+            self.downloads.queue(client, attachment);
+        }
+        Ok(())
+    }
+
+    /// Load the current bored again to see what has been added, saying how many notices are new
+    pub fn refresh_bored(&mut self) -> Result<(), SurfBoredError> {
+        let Some(bored_address) = self.get_current_address() else {
+            return Ok(());
+        };
+        let previous = self.get_current_bored();
+        self.load_bored(URL::BoredNet(bored_address), move |app, result| {
+            result?;
+            let new_notices = app.get_current_bored().map_or(0, |bored| {
+                bored
+                    .notices()
+                    .iter()
+                    .filter(|notice| {
+                        !previous
+                            .as_ref()
+                            .is_some_and(|previous| previous.notices().contains(notice))
+                    })
+                    .count()
+            });
+            let message = match new_notices {
+                0 => "Refreshed the bored, no new notices".to_string(),
+                1 => "Refreshed the bored, 1 new notice".to_string(),
+                _ => format!("Refreshed the bored, {new_notices} new notices"),
+            };
+            app.show_toast(ToastKind::Info, &message);
+            Ok(())
+        })
+    }
+
+    pub fn goto_share_link(&mut self, share_link: ShareLink) -> Result<(), SurfBoredError> {
+        self.load_bored(URL::BoredShare(share_link), |_, result| result)
+    }
+
+    /// Show the bored the client has just gone to, from the top with nothing selected
+    fn show_new_bored(&mut self) -> Result<(), SurfBoredError> {
+        let Some(ref client) = self.client else {
+            return Err(SurfBoredError::BoredError(
+                BoredError::ClientConnectionError,
+            ));
+        };
+        let bored = redact_hidden(client.current_bored()?);
+        self.selected_notice = None;
+        self.revert_view();
+        self.bored_view_port = Some(BoredViewPort::create(
+            bored.clone(),
+            bored.get_dimensions(),
+            self.selected_notice,
+        ));
+        self.mark_notices_seen();
+        Ok(())
+    }
+
+    /// Everything on the bored now counts as seen, so only notices that arrive later are new
+    pub fn mark_notices_seen(&mut self) {
+        self.seen_notices = self
+            .get_current_bored()
+            .map(|bored| {
+                bored
+                    .notices()
+                    .iter()
+                    .map(|notice| notice.get_notice_id().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+    }
+
+    /// The selected notice is no longer new
+    pub fn mark_selected_seen(&mut self) {
+        if let Some(notice) = self.get_selected_notice() {
+            self.seen_notices.insert(notice.get_notice_id().to_string());
+        }
+    }
+
+    /// Select and raise the next notice in the stack under the clicked cell, or under where the
+    /// selected notice overlaps another, so covered notices can be read without anyone posting
+    /// them again. After the bottom of the stack the bored shows as it is again.
+    pub fn peek_under(&mut self) -> Result<(), SurfBoredError> {
+        let (Some(bored), Some(selected_notice)) = (self.get_current_bored(), self.selected_notice)
+        else {
+            return Err(SurfBoredError::Message(
+                "Select a notice to peek under".to_string(),
+            ));
+        };
+        let stack = self
+            .clicked_cell
+            .map(|cell| bored.get_notice_stack_at(cell))
+            .filter(|stack| stack.len() > 1 && stack.contains(&selected_notice))
+            .or_else(|| get_overlapping_stack(&bored, selected_notice))
+            .ok_or(SurfBoredError::Message(
+                "Nothing is covered here".to_string(),
+            ))?;
+        let next = match self.get_peeked_notice() {
+            Some(peeked) => stack
+                .iter()
+                .position(|index| *index == peeked)
+                .map_or(1, |p| p + 1),
+            None => 1,
+        };
+        match stack.get(next) {
+            Some(&notice_index) => {
+                self.peeked_notice = Some(notice_index);
+                self.selected_notice = Some(notice_index);
+                self.show_toast(
+                    ToastKind::Info,
+                    &format!("Peeking at notice {} of {} here.", next + 1, stack.len()),
+                );
+            }
+            None => {
+                self.peeked_notice = None;
+                self.selected_notice = Some(stack[0]);
+            }
+        }
+        self.mark_selected_seen();
+        Ok(())
+    }
+
+    /// The notice raised by peeking, none once something else has been selected
+    pub fn get_peeked_notice(&self) -> Option<usize> {
+        self.peeked_notice
+            .filter(|peeked| self.selected_notice == Some(*peeked))
+    }
+
+    /// Indexes of the notices on the bored that have arrived since it was shown and haven't
+    /// been selected
+    pub fn get_new_notices(&self, bored: &Bored) -> Vec<usize> {
+        bored
+            .notices()
+            .iter()
+            .enumerate()
+            .filter(|(_, notice)| !self.seen_notices.contains(notice.get_notice_id()))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Tell the bored the selected notice was opened without waiting on it, the client only
+    /// does so when views are counted
+    fn record_view(&self) {
+        if let (Some(client), Some(notice)) = (
+            self.client.clone().filter(X0xBoredClient::is_available),
+            self.get_selected_notice(),
+        ) {
+            tokio::spawn(async move {
+                // a view that doesn't get through isn't worth troubling the reader with
+                let _ = client.record_view(&notice).await;
+            });
+        }
+    }
+
+    /// How many readers that count views have opened the notice, as far as this client heard
+    pub fn get_notice_views(&self, notice: &Notice) -> usize {
+        self.client
+            .as_ref()
+            .and_then(|client| client.get_notice_stats().ok())
+            .and_then(|stats| {
+                stats
+                    .into_iter()
+                    .find(|stats| stats.notice_id == notice.get_notice_id())
+            })
+            .map_or(0, |stats| stats.views)
+    }
+
+    /// Every so often ask peers for changes to the bored without waiting on them, what they
+    /// send reaches the cache in the background and is shown as it arrives
+    pub fn auto_refresh(&mut self) {
+        let Some(interval) = self.auto_refresh else {
+            return;
+        };
+        if self.last_auto_refresh.elapsed() < interval || self.tasks.is_running() {
+            return;
+        }
+        self.last_auto_refresh = Instant::now();
+        if let Some(client) = self.client.clone().filter(X0xBoredClient::is_available) {
+            tokio::spawn(async move {
+                // there is nothing to do if it fails, it is tried again next time
+                let _ = client.request_sync().await;
+            });
+        }
+    }
+
+    /// Every so often check the daemon is still there and reconnect if it has gone, so losing
+    /// it doesn't leave every network operation failing until surf bored is restarted
+    pub fn check_connection(&mut self) {
+        let interval = if self.connection_lost {
+            RECONNECT_INTERVAL
+        } else {
+            CONNECTION_CHECK_INTERVAL
+        };
+        if self.connecting
+            || self.checking_connection
+            || self
+                .last_connection_check
+                .is_some_and(|last_check| last_check.elapsed() < interval)
+        {
+            return;
+        }
+        let Some(client) = self.client.clone().filter(X0xBoredClient::is_available) else {
+            return;
+        };
+        self.last_connection_check = Some(Instant::now());
+        self.checking_connection = true;
+        self.tasks.check_connection(client);
+    }
+
+    /// Tell the user when the daemon is lost and when it is back
+    pub fn finish_connection_check(&mut self, check: ConnectionCheck) {
+        self.checking_connection = false;
+        match check {
+            ConnectionCheck::Healthy => self.connection_lost = false,
+            ConnectionCheck::Reconnected => {
+                self.connection_lost = false;
+                self.show_toast(ToastKind::Success, "Reconnected to x0x");
+            }
+            ConnectionCheck::Lost(_) => {
+                if !self.connection_lost {
+                    self.show_toast(
+                        ToastKind::Warning,
+                        "Lost the connection to x0x, trying to reconnect",
+                    );
+                }
+                self.connection_lost = true;
+            }
+        }
+    }
+
+    /// Send changes held back for being made too quickly, in the background as there is room
+    /// for them
+    pub fn publish_pending(&mut self) {
+        if let Some(client) = self
+            .client
+            .clone()
+            .filter(|client| client.is_available() && client.has_pending_updates())
+        {
+            tokio::spawn(async move {
+                // what couldn't be sent is kept to try again next time
+                let _ = client.publish_pending().await;
+            });
+        }
+    }
+
+    /// Check the watched boreds when they are due and tell the user about new notices, on the
+    /// desktop as well in case they are busy elsewhere
+    pub fn check_watched(&mut self) {
+        if let Some(client) = &self.client
+            && client.is_available()
+        {
+            self.watcher.poll(client, self.directory.get_watched());
+        }
+        let current_address = self.get_current_address();
+        for (name, count) in self.watcher.update(current_address.as_ref()) {
+            let message = if count == 1 {
+                format!("A new notice on {name}")
+            } else {
+                format!("{count} new notices on {name}")
+            };
+            watch::notify_desktop(&message);
+            self.show_toast(ToastKind::Info, &message);
+        }
+    }
+
+    /// Look up where the selected or hovered over link goes if it is to a bored
+    pub fn preview_links(&mut self) {
+        self.previews.update();
+        let link = self
+            .get_selected_hyperlink()
+            .or_else(|| self.hovered_hyperlink.clone());
+        if let Some(client) = &self.client
+            && client.is_available()
+            && let Some(hyperlink) = link
+            && let Ok(URL::BoredNet(address)) = URL::from_string(hyperlink.get_link())
+        {
+            self.previews.request(client, &address);
+        }
+    }
+
+    /// A line saying where a link goes, if it is to a bored that has been looked up
+    pub fn get_link_preview(&self, link: &str) -> Option<String> {
+        let Ok(URL::BoredNet(address)) = URL::from_string(link.to_string()) else {
+            return None;
+        };
+        self.previews
+            .get(&address)
+            .map(|preview| preview.describe())
+    }
+
+    /// Fetch the boreds notices on the current bored are embedded from
+    pub fn resolve_embeds(&mut self) {
+        self.embeds.update();
+        if let Some(client) = &self.client
+            && client.is_available()
+            && let Ok(bored) = client.current_bored()
+        {
+            for address in bored.get_embedded_addresses() {
+                self.embeds.request(client, &address);
+            }
+        }
+    }
+
+    /// The current bored as it should be shown, with notices hidden by moderation emptied
+    /// and embedded notices shown as what they embed. It is shared with the client rather than
+    /// copied, as it is looked at many times a frame, unless there are any of either.
+    pub fn get_current_bored(&self) -> Option<Arc<Bored>> {
+        let client = self.client.as_ref()?;
+        let bored = redact_hidden(client.current_bored().ok()?);
+        if bored.get_embedded_addresses().is_empty() {
+            return Some(bored);
+        }
+        let topic = client.get_bored_address().ok().map(|a| a.get_topic());
+        Some(Arc::new(bored.with_embeds(|address| {
+            if Some(address.get_topic()) == topic {
+                Some(bored.clone())
+            } else {
+                self.embeds.get(address)
+            }
+        })))
+    }
+
+    pub fn get_current_address(&self) -> Option<BoredAddress> {
+        if let Some(client) = &self.client {
+            return client.get_bored_address().ok();
+        }
+        None
     }
 
     pub fn has_local_connection(&self) -> bool {
@@ -303,104 +2001,801 @@ impl App {
         false
     }
 
-    pub async fn create_bored_on_network(
+    pub fn create_bored_on_network(
         &mut self,
         name: &str,
         dimensions: Coordinate,
         url_name: Option<&str>,
     ) -> Result<(), SurfBoredError> {
-        let Some(ref mut client) = self.client else {
+        self.save_view_to_history();
+        let name = name.to_string();
+        let url_name = url_name.map(str::to_string);
+        self.spawn_client_task(
+            "Creating board on x0x...",
+            move |mut client| async move {
+                client
+                    .create_bored(&name, dimensions, url_name.as_deref())
+                    .await?;
+                Ok(client)
+            },
+            |app, result| {
+                result?;
+                app.name_input = String::new();
+                app.url_name_input = String::new();
+                app.url_name_check = None;
+                app.show_created_bored()
+            },
+        )
+    }
+
+    /// Create a bored from what was typed in the create view. A url name is checked first and
+    /// the bored only created straight away if it is free, if it is taken names close to it are
+    /// offered and the bored is created with it anyway if asked again.
+    pub fn create_bored_from_input(&mut self) -> Result<(), SurfBoredError> {
+        let name = self.name_input.clone();
+        let url_name = self.url_name_input.trim().to_string();
+        let checked = self
+            .url_name_check
+            .as_ref()
+            .is_some_and(|check| check.url_name == url_name || check.free.contains(&url_name));
+        if url_name.is_empty() || checked {
+            let url_name = (!url_name.is_empty()).then_some(url_name.as_str());
+            return self.create_bored_on_network(&name, Coordinate { x: 120, y: 40 }, url_name);
+        }
+        let mut url_names = vec![url_name.clone()];
+        url_names.extend(suggest_url_names(&url_name));
+        let addresses = url_names
+            .iter()
+            .map(|url_name| BoredAddress::from_string(url_name))
+            .collect::<Result<Vec<_>, _>>()?;
+        let checked_addresses = addresses.clone();
+        self.spawn_client_task(
+            "Checking whether the URL name is taken...",
+            |client| async move {
+                // boreds that nobody has are left out of the cache, anything else is a failure
+                for result in client.fetch_boreds(&addresses).await {
+                    match result {
+                        Ok(_) | Err(BoredError::BoardDoesNotExist(_)) => {}
+                        Err(e) => return Err(SurfBoredError::BoredError(e)),
+                    }
+                }
+                Ok(client)
+            },
+            move |app, result| {
+                result?;
+                let is_taken = |address: &BoredAddress| {
+                    app.client
+                        .as_ref()
+                        .is_some_and(|client| client.get_cached_bored(address).is_ok())
+                };
+                let taken = is_taken(&checked_addresses[0]);
+                let free = url_names
+                    .iter()
+                    .zip(&checked_addresses)
+                    .skip(1)
+                    .filter(|(_, address)| !is_taken(address))
+                    .map(|(url_name, _)| url_name.clone())
+                    .collect();
+                app.url_name_check = Some(UrlNameCheck {
+                    url_name: url_name.clone(),
+                    taken,
+                    free,
+                });
+                if taken {
+                    Ok(())
+                } else {
+                    app.create_bored_on_network(
+                        &name,
+                        Coordinate { x: 120, y: 40 },
+                        Some(&url_name),
+                    )
+                }
+            },
+        )
+    }
+
+    /// Put the next or previous free url name found by the last check in place of the typed one
+    pub fn cycle_free_url_names(&mut self, forward: bool) {
+        let Some(check) = &self.url_name_check else {
+            return;
+        };
+        if check.free.is_empty() {
+            return;
+        }
+        let count = check.free.len();
+        let next = match check
+            .free
+            .iter()
+            .position(|free| *free == self.url_name_input)
+        {
+            Some(position) if forward => (position + 1) % count,
+            Some(position) => (position + count - 1) % count,
+            None if forward => 0,
+            None => count - 1,
+        };
+        self.url_name_input = check.free[next].clone();
+    }
+
+    /// Show the bored the client has just created and keep it in the directory
+    fn show_created_bored(&mut self) -> Result<(), SurfBoredError> {
+        let Some(ref client) = self.client else {
             return Err(SurfBoredError::BoredError(
                 BoredError::ClientConnectionError,
             ));
         };
-        client
-            .create_bored(name, dimensions, url_name)
-            .await?;
-        let bored = client.get_current_bored()?;
+        let bored = redact_hidden(client.current_bored()?);
         self.selected_notice = None;
         self.current_view = View::BoredView;
         self.bored_view_port = Some(BoredViewPort::create(
-            &bored,
+            bored.clone(),
             bored.get_dimensions(),
             self.selected_notice,
         ));
         self.directory.add(
-            Listing {
-                name: client.get_bored_name()?.to_string(),
-                bored_address: format!("{}", client.get_bored_address()?),
-            },
+            Listing::new(
+                client.get_bored_name()?,
+                &format!("{}", client.get_bored_address()?),
+            ),
             &self.directory_path,
         )?;
+        self.history.visit(&client.get_bored_address()?.to_string());
+        self.mark_notices_seen();
+        Ok(())
+    }
+
+    /// Listing for the current bored under its own name, if it isn't in the directory already
+    fn get_current_bored_listing(&self) -> Result<Listing, SurfBoredError> {
+        let Some(ref client) = self.client else {
+            return Err(SurfBoredError::BoredError(BoredError::ClientConnectionError));
+        };
+        let Ok(bored) = client.current_bored() else {
+            return Err(SurfBoredError::Message("No board is currently loaded.".to_string()));
+        };
+        let Ok(address) = client.get_bored_address() else {
+            return Err(SurfBoredError::Message("Current board has no valid address.".to_string()));
+        };
+        
+        let address_str = address.to_string();
+        if self.directory.get_bored_addresses().iter().any(|l| l.bored_address == address_str) {
+            return Err(SurfBoredError::Message("This board is already in your directory.".to_string()));
+        }
+
+        Ok(Listing::new(bored.get_name(), &address_str))
+    }
+
+    pub fn save_current_bored_to_directory(&mut self) -> Result<(), SurfBoredError> {
+        let listing = self.get_current_bored_listing()?;
+        self.directory.add(listing, &self.directory_path)?;
+        Ok(())
+    }
+
+    /// Ask for the name to add the current bored to the directory under, starting with its own
+    pub fn start_bookmark(&mut self) -> Result<(), SurfBoredError> {
+        let listing = self.get_current_bored_listing()?;
+        self.bookmark_name_input = listing.name;
+        self.change_view(View::BookmarkView);
         Ok(())
     }
 
-    pub fn save_current_bored_to_directory(&mut self) -> Result<(), SurfBoredError> {
-        let Some(ref client) = self.client else {
-            return Err(SurfBoredError::BoredError(BoredError::ClientConnectionError));
-        };
-        let Ok(bored) = client.get_current_bored() else {
-            return Err(SurfBoredError::Message("No board is currently loaded.".to_string()));
+    /// Add the current bored to the directory with the name typed, a blank name keeps its own
+    pub fn save_bookmark(&mut self) -> Result<(), SurfBoredError> {
+        let mut listing = self.get_current_bored_listing()?;
+        let name = self.bookmark_name_input.trim();
+        if !name.is_empty() {
+            listing.name = name.to_string();
+        }
+        self.directory.add(listing, &self.directory_path)?;
+        self.bookmark_name_input = String::new();
+        self.revert_view();
+        Ok(())
+    }
+
+    /// Ask for the name to show the author of the selected notice by, starting with the one
+    /// they have
+    pub fn start_naming_author(&mut self) -> Result<(), SurfBoredError> {
+        let author = self.get_selected_author()?;
+        self.petname_input = self.petnames.get(&author).unwrap_or_default().to_string();
+        self.change_view(View::PetnameView);
+        Ok(())
+    }
+
+    /// Show the author of the selected notice by the name typed from now on, a blank name goes
+    /// back to their agent id
+    pub fn save_petname(&mut self) -> Result<(), SurfBoredError> {
+        let author = self.get_selected_author()?;
+        self.petnames.set(&author, &self.petname_input);
+        self.petnames.save_file(&self.get_petnames_path())?;
+        self.petname_input = String::new();
+        self.revert_view();
+        Ok(())
+    }
+
+    fn get_selected_author(&self) -> Result<String, SurfBoredError> {
+        self.get_selected_notice()
+            .and_then(|notice| notice.get_author().map(str::to_string))
+            .ok_or(SurfBoredError::Message(
+                "Who posted this notice can't be told".to_string(),
+            ))
+    }
+
+    pub fn create_draft(&mut self, dimensions: Coordinate) -> Result<(), BoredError> {
+        let Some(ref mut client) = self.client else {
+            return Err(BoredError::ClientConnectionError);
+        };
+        client.create_draft(dimensions)?;
+        self.content_input = String::new();
+        self.draft_index = None;
+        self.change_view(View::DraftView(DraftMode::Content));
+        Ok(())
+    }
+
+    /// Start a notice in the next free space on the bored reading left to right then top to
+    /// bottom, so notices can follow on from each other like entries in a guestbook
+    pub fn start_guestbook_entry(&mut self, dimensions: Coordinate) -> Result<(), SurfBoredError> {
+        self.create_draft(dimensions)?;
+        if let Err(e) = self.place_draft_in_reading_order() {
+            self.revert_view();
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Move the draft to the next free space on the bored in reading order and the view to it
+    pub fn place_draft_in_reading_order(&mut self) -> Result<(), SurfBoredError> {
+        let Some(ref mut client) = self.client else {
+            return Err(SurfBoredError::BoredError(
+                BoredError::ClientConnectionError,
+            ));
+        };
+        client.place_draft_in_reading_order()?;
+        let _ = self.save_draft();
+        if let (Some(draft), Some(bored_view_port)) =
+            (self.get_draft(), self.bored_view_port.as_mut())
+            && !bored_view_port.in_view(
+                draft.get_top_left(),
+                draft.get_top_left().add(&draft.get_dimensions()),
+            )
+        {
+            let new_view_position = bored_view_port.get_view_for_notice(&draft);
+            bored_view_port.move_view(new_view_position);
+        }
+        Ok(())
+    }
+
+    /// Start the draft again from the template, at the template's size where the bored has room
+    /// for it and moved back onto the bored if it no longer fits where it was
+    pub fn use_template(&mut self, template_index: usize) -> Result<(), SurfBoredError> {
+        let (Some(template), Some(bored), Some(draft)) = (
+            TEMPLATES.get(template_index),
+            self.get_current_bored(),
+            self.get_draft(),
+        ) else {
+            return Ok(());
+        };
+        let Some(ref mut client) = self.client else {
+            return Err(SurfBoredError::BoredError(
+                BoredError::ClientConnectionError,
+            ));
+        };
+        let bored_dimensions = bored.get_dimensions();
+        let dimensions = Coordinate {
+            x: template.dimensions.x.min(bored_dimensions.x),
+            y: template.dimensions.y.min(bored_dimensions.y),
+        };
+        let top_left = draft.get_top_left();
+        let top_left = Coordinate {
+            x: top_left.x.min(bored_dimensions.x - dimensions.x),
+            y: top_left.y.min(bored_dimensions.y - dimensions.y),
+        };
+        client.create_draft(dimensions)?;
+        client.position_draft(top_left)?;
+        client.edit_draft(template.content)?;
+        self.content_input = template.content.to_string();
+        let _ = self.save_draft();
+        self.current_view = View::DraftView(DraftMode::Content);
+        self.fit_view_to_terminal();
+        Ok(())
+    }
+
+    /// Open the event details of the draft to be typed, filled in from what it already has
+    pub fn edit_draft_event(&mut self) {
+        let kind = self.get_draft().map(|draft| draft.get_kind().clone());
+        (
+            self.event_title_input,
+            self.event_start_input,
+            self.event_end_input,
+            self.event_location_input,
+        ) = match kind {
+            Some(NoticeKind::Event {
+                title,
+                start,
+                end,
+                location,
+            }) => (
+                title,
+                format_event_time(start, &Local),
+                end.map(|end| format_event_time(end, &Local))
+                    .unwrap_or_default(),
+                location.unwrap_or_default(),
+            ),
+            _ => Default::default(),
+        };
+        self.current_view = View::DraftView(DraftMode::Event(EventField::Title));
+    }
+
+    pub fn get_event_input_mut(&mut self, field: EventField) -> &mut String {
+        match field {
+            EventField::Title => &mut self.event_title_input,
+            EventField::Start => &mut self.event_start_input,
+            EventField::End => &mut self.event_end_input,
+            EventField::Location => &mut self.event_location_input,
+        }
+    }
+
+    /// Make the draft the event typed in and go back to its text, a blank title makes it a
+    /// plain notice again. An end without a date is on the day it starts.
+    pub fn set_draft_event(&mut self) -> Result<(), SurfBoredError> {
+        let title = self.event_title_input.trim();
+        let kind = if title.is_empty() {
+            NoticeKind::Text
+        } else {
+            let start = self.event_start_input.trim();
+            let end = self.event_end_input.trim();
+            let end = match (end.contains(' '), start.split_once(' ')) {
+                _ if end.is_empty() => None,
+                (false, Some((day, _))) => Some(parse_event_time(&format!("{day} {end}"), &Local)?),
+                _ => Some(parse_event_time(end, &Local)?),
+            };
+            let location = self.event_location_input.trim();
+            NoticeKind::Event {
+                title: title.to_string(),
+                start: parse_event_time(start, &Local)?,
+                end,
+                location: (!location.is_empty()).then(|| location.to_string()),
+            }
+        };
+        let Some(ref mut client) = self.client else {
+            return Err(SurfBoredError::BoredError(
+                BoredError::ClientConnectionError,
+            ));
+        };
+        client.set_draft_kind(kind)?;
+        let _ = self.save_draft();
+        self.current_view = View::DraftView(DraftMode::Content);
+        Ok(())
+    }
+
+    /// Save the events on the current bored to an iCalendar file in the downloads folder so
+    /// they can be added to a calendar, returning where it was saved
+    pub fn export_events(&self) -> Result<PathBuf, SurfBoredError> {
+        let (Some(bored), Some(address)) = (self.get_current_bored(), self.get_current_address())
+        else {
+            return Err(SurfBoredError::BoredError(
+                BoredError::ClientConnectionError,
+            ));
+        };
+        if bored
+            .notices()
+            .iter()
+            .all(|notice| notice.get_kind().is_text())
+        {
+            return Err(SurfBoredError::Message(
+                "There are no events on this bored".to_string(),
+            ));
+        }
+        let calendar = export::to_ical(&bored, &address)?;
+        let dir = self.downloads.get_dir();
+        let path = dir.join(get_events_filename(bored.get_name()));
+        fs::create_dir_all(dir)
+            .and_then(|_| fs::write(&path, calendar))
+            .map_err(|_| SurfBoredError::EventsExportError(path.display().to_string()))?;
+        Ok(path)
+    }
+
+    /// Pick the selected notice to embed in a notice on another bored, or on this one. A
+    /// notice that embeds another is picked as the one it embeds.
+    pub fn pick_notice_to_embed(&mut self) -> Result<(), SurfBoredError> {
+        let (Some(client), Some(notice_index)) = (&self.client, self.selected_notice) else {
+            return Ok(());
+        };
+        let bored = redact_hidden(client.current_bored()?);
+        let Some(notice) = bored.notices().get(notice_index) else {
+            return Ok(());
+        };
+        let (address, notice_id, source_bored) = match notice.get_kind() {
+            NoticeKind::Embed { address, notice_id } => (
+                address.clone(),
+                notice_id.as_str(),
+                self.embeds.get(address),
+            ),
+            _ => (
+                client.get_bored_address()?,
+                notice.get_notice_id(),
+                Some(bored.clone()),
+            ),
+        };
+        // checked now rather than when it is embedded
+        NoticeKind::Embed {
+            address: address.clone(),
+            notice_id: notice_id.to_string(),
+        }
+        .validate()?;
+        let Some((name, source)) = source_bored.and_then(|source_bored| {
+            let source = source_bored
+                .notices()
+                .iter()
+                .find(|source| source.get_notice_id() == notice_id)?
+                .clone();
+            Some((source_bored.get_name().to_string(), source))
+        }) else {
+            return Err(SurfBoredError::Message(
+                "The notice this one embeds hasn't been found yet".to_string(),
+            ));
+        };
+        self.embed_source = Some((address, name, source));
+        Ok(())
+    }
+
+    /// Make the draft embed the picked notice, with a copy of it as the draft's own text for
+    /// readers that can't find it
+    pub fn embed_picked_notice(&mut self) -> Result<(), SurfBoredError> {
+        let Some((address, name, source)) = self.embed_source.clone() else {
+            return Err(SurfBoredError::Message(
+                "Pick a notice to embed first, press (m) on it".to_string(),
+            ));
+        };
+        let Some(draft) = self.get_draft() else {
+            return Ok(());
+        };
+        let copy = draft.embed(&source, &address, &name);
+        let Some(ref mut client) = self.client else {
+            return Err(SurfBoredError::BoredError(
+                BoredError::ClientConnectionError,
+            ));
+        };
+        client.edit_draft(copy.get_content())?;
+        client.set_draft_kind(NoticeKind::Embed {
+            address,
+            notice_id: source.get_notice_id().to_string(),
+        })?;
+        self.content_input = copy.get_content().to_string();
+        let _ = self.save_draft();
+        Ok(())
+    }
+
+    pub fn get_draft(&self) -> Option<Notice> {
+        let Some(ref client) = self.client else {
+            return None;
+        };
+        client.get_draft()
+    }
+
+    /// Keep the view on the bored after the terminal is resized, with the draft or the selected
+    /// notice still in it
+    pub fn fit_view_to_terminal(&mut self) {
+        // the mouse was dragged over a layout that has gone
+        self.mouse_drag = None;
+        let focus = match self.current_view {
+            View::DraftView(_) => self.get_draft(),
+            _ => self.get_selected_notice(),
+        };
+        let Some(bored_view_port) = self.bored_view_port.as_mut() else {
+            return;
+        };
+        bored_view_port.keep_on_bored();
+        if let Some(notice) = focus
+            && !bored_view_port.in_view(
+                notice.get_top_left(),
+                notice.get_top_left().add(&notice.get_dimensions()),
+            )
+        {
+            let new_view_position = bored_view_port.get_view_for_notice(&notice);
+            bored_view_port.move_view(new_view_position);
+        }
+    }
+
+    /// Select the notice and move the view on the bored to it if it isn't in view
+    pub fn jump_to_notice(&mut self, notice_index: usize) {
+        self.selected_notice = Some(notice_index);
+        self.mark_selected_seen();
+        if let (Some(notice), Some(bored_view_port)) =
+            (self.get_selected_notice(), self.bored_view_port.as_mut())
+            && !bored_view_port.in_view(
+                notice.get_top_left(),
+                notice.get_top_left().add(&notice.get_dimensions()),
+            )
+        {
+            let new_view_position = bored_view_port.get_view_for_notice(&notice);
+            bored_view_port.move_view(new_view_position);
+        }
+    }
+
+    /// The notices the draft would cover where it is and how much of each
+    pub fn get_covered_by_draft(&self) -> Vec<(usize, Coverage)> {
+        let (Some(bored), Some(draft)) = (self.get_current_bored(), self.get_draft()) else {
+            return vec![];
+        };
+        WhatsOnTheBored::create(&bored).get_covered(draft.get_top_left(), draft.get_dimensions())
+    }
+
+    pub fn edit_draft(&mut self, content: &str) -> Result<(), BoredError> {
+        let Some(ref mut client) = self.client else {
+            return Err(BoredError::ClientConnectionError);
+        };
+        client.edit_draft(content)?;
+        // a draft that can't be saved can still be placed, so this doesn't stop the typing
+        let _ = self.save_draft();
+        Ok(())
+    }
+
+    /// What posting the draft would take, without posting it
+    pub fn estimate_posting(&self) -> Result<PostingEstimate, SurfBoredError> {
+        let (Some(client), Some(draft)) = (&self.client, self.get_draft()) else {
+            return Err(SurfBoredError::BoredError(
+                BoredError::ClientConnectionError,
+            ));
+        };
+        client
+            .estimate_posting(&draft)
+            .map_err(SurfBoredError::BoredError)
+    }
+
+    /// Hashes expected for the proof of work done so far this session
+    pub fn get_work_done(&self) -> u64 {
+        self.client
+            .as_ref()
+            .map(|client| client.get_work_done())
+            .unwrap_or(0)
+    }
+
+    /// Post the draft to the bored, going back to the bored once it is on it
+    pub fn add_draft_to_bored(&mut self) -> Result<(), SurfBoredError> {
+        self.spawn_client_task(
+            "Updating board on x0x...",
+            |mut client| async move {
+                client.add_draft_to_bored().await?;
+                Ok(client)
+            },
+            |app, result| {
+                result?;
+                app.change_view(View::BoredView);
+                app.content_input = String::new();
+                // the user's own notice isn't new to them
+                app.mark_notices_seen();
+                if app
+                    .client
+                    .as_ref()
+                    .is_some_and(X0xBoredClient::has_pending_updates)
+                {
+                    app.show_toast(
+                        ToastKind::Info,
+                        "Posting quickly, the notice will reach peers shortly",
+                    );
+                }
+                // it is on the bored now so no longer needs keeping
+                if let Some(draft_index) = app.draft_index.take() {
+                    app.discard_draft(draft_index)?;
+                }
+                Ok(())
+            },
+        )
+    }
+
+    fn get_drafts_path(&self) -> PathBuf {
+        Path::new(&self.directory_path).with_file_name("drafts.toml")
+    }
+
+    pub fn load_drafts(&mut self) {
+        self.drafts = Drafts::load_file(&self.get_drafts_path());
+    }
+
+    fn get_index_path(&self) -> PathBuf {
+        Path::new(&self.directory_path).with_file_name("index.json")
+    }
+
+    pub fn load_index(&mut self) {
+        self.index.load_file(&self.get_index_path());
+    }
+
+    /// Open the index of crawled boreds to search it, crawling first if nothing has been
+    /// crawled yet and x0x can be reached
+    pub fn open_index(&mut self) {
+        self.index_search = String::new();
+        self.change_view(View::IndexView(0));
+        if self.index.get().get_entries().is_empty()
+            && self
+                .client
+                .as_ref()
+                .is_some_and(X0xBoredClient::is_available)
+        {
+            self.crawl();
+        }
+    }
+
+    /// Show the boreds in the index and the links between them, crawling first like the index
+    /// view if nothing has been crawled yet
+    pub fn open_graph(&mut self) {
+        self.graph = Graph::create(self.index.get());
+        self.change_view(View::GraphView(0));
+        if self.index.get().get_entries().is_empty()
+            && self
+                .client
+                .as_ref()
+                .is_some_and(X0xBoredClient::is_available)
+        {
+            self.crawl();
+        }
+    }
+
+    /// Crawl out from the boreds in the directory and the current bored in the background,
+    /// following their links to other boreds
+    pub fn crawl(&mut self) {
+        let Some(client) = self.client.as_ref().filter(|client| client.is_available()) else {
+            self.display_error(SurfBoredError::BoredError(
+                BoredError::ClientConnectionError,
+            ));
+            return;
         };
-        let Ok(address) = client.get_bored_address() else {
-            return Err(SurfBoredError::Message("Current board has no valid address.".to_string()));
+        let mut seeds: Vec<BoredAddress> = self
+            .directory
+            .get_bored_addresses()
+            .iter()
+            .filter_map(|listing| BoredAddress::from_string(&listing.bored_address).ok())
+            .collect();
+        seeds.extend(self.get_current_address());
+        self.index.crawl(client, seeds);
+    }
+
+    /// Keep the index from a crawl that has finished and say how much it found
+    pub fn check_crawl(&mut self) {
+        let Some(index) = self.index.update() else {
+            return;
         };
-        
-        let address_str = address.to_string();
-        if self.directory.get_bored_addresses().iter().any(|l| l.bored_address == address_str) {
-            return Err(SurfBoredError::Message("This board is already in your directory.".to_string()));
+        let message = format!(
+            "Crawled {} boreds, {} linked to couldn't be fetched",
+            index.get_entries().len(),
+            index.get_failures().len()
+        );
+        self.graph = Graph::create(index);
+        match self.current_view {
+            View::IndexView(_) => self.current_view = View::IndexView(0),
+            View::GraphView(_) => self.current_view = View::GraphView(0),
+            _ => {}
+        }
+        self.show_toast(ToastKind::Info, &message);
+        if let Err(e) = self.index.save_file(&self.get_index_path()) {
+            self.display_error(e);
         }
+    }
 
-        self.directory.add(
-            Listing {
-                name: bored.get_name().to_string(),
-                bored_address: address_str,
-            },
-            &self.directory_path,
-        )?;
+    pub fn get_reader_key_path(&self) -> PathBuf {
+        Path::new(&self.directory_path).with_file_name("reader_key")
+    }
+
+    /// Load the reader key if one has been made or imported
+    pub fn load_reader_key(&mut self) {
+        let reader_key = fs::read_to_string(self.get_reader_key_path())
+            .ok()
+            .and_then(|secret| ReaderKey::from_secret_string(&secret).ok());
+        self.use_reader_key(reader_key);
+    }
+
+    /// Keep the reader key and give it to the client, which opens boreds encrypted for it as
+    /// they arrive
+    fn use_reader_key(&mut self, reader_key: Option<ReaderKey>) {
+        if let Some(client) = &mut self.client {
+            client.set_reader_key(reader_key.clone());
+        }
+        self.reader_key = reader_key;
+    }
+
+    /// Make a new reader key, one already kept isn't replaced as boreds encrypted for it
+    /// couldn't be read any more
+    pub fn create_reader_key(&mut self) -> Result<(), SurfBoredError> {
+        if self.reader_key.is_some() {
+            return Err(SurfBoredError::ReaderKeyExists);
+        }
+        let reader_key = ReaderKey::generate();
+        reader_key.save(&self.get_reader_key_path())?;
+        self.use_reader_key(Some(reader_key));
         Ok(())
     }
 
-    pub fn create_draft(&mut self, dimensions: Coordinate) -> Result<(), BoredError> {
-        let Some(ref mut client) = self.client else {
-            return Err(BoredError::ClientConnectionError);
-        };
-        client.create_draft(dimensions)?;
-        self.change_view(View::DraftView(DraftMode::Content));
+    /// Use the secret key or seed phrase typed or pasted in, e.g. to read the same boreds on
+    /// another device
+    pub fn import_reader_key(&mut self) -> Result<(), SurfBoredError> {
+        if self.reader_key.is_some() {
+            return Err(SurfBoredError::ReaderKeyExists);
+        }
+        let reader_key = ReaderKey::parse(self.key_input.trim())?;
+        reader_key.save(&self.get_reader_key_path())?;
+        self.use_reader_key(Some(reader_key));
+        self.key_input = String::new();
         Ok(())
     }
 
-    pub fn get_draft(&self) -> Option<Notice> {
-        let Some(ref client) = self.client else {
-            return None;
-        };
-        client.get_draft()
+    pub fn clear_reader_key(&mut self) -> Result<(), SurfBoredError> {
+        let path = self.get_reader_key_path();
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        self.use_reader_key(None);
+        Ok(())
     }
 
-    pub fn edit_draft(&mut self, content: &str) -> Result<(), BoredError> {
-        let Some(ref mut client) = self.client else {
-            return Err(BoredError::ClientConnectionError);
+    /// Copy the public half of the reader key to give to whoever encrypts boreds for the user,
+    /// returning it
+    pub fn copy_reader_public_key(&mut self) -> Result<String, SurfBoredError> {
+        let public_key = self
+            .reader_key
+            .as_ref()
+            .ok_or(SurfBoredError::Message(
+                "There is no reader key, press (n) to make one.".to_string(),
+            ))?
+            .public_key()
+            .to_string();
+        self.copy_to_clipboard(public_key.clone())?;
+        Ok(public_key)
+    }
+
+    /// Keep the draft being written in the drafts file, a draft without text isn't kept
+    pub fn save_draft(&mut self) -> Result<(), SurfBoredError> {
+        let (Some(notice), Some(visit)) = (self.get_draft(), self.history.get_current()) else {
+            return Ok(());
         };
-        client.edit_draft(content)?;
-        Ok(())
+        if notice.get_content().is_empty() {
+            return match self.draft_index.take() {
+                Some(draft_index) => self.discard_draft(draft_index),
+                None => Ok(()),
+            };
+        }
+        let draft = Draft {
+            link: visit.link.clone(),
+            content: notice.get_content().to_string(),
+            dimensions: notice.get_dimensions(),
+            top_left: notice.get_top_left(),
+            kind: notice.get_kind().clone(),
+        };
+        self.draft_index = Some(self.drafts.keep(self.draft_index, draft));
+        self.drafts.save_file(&self.get_drafts_path())
     }
 
-    pub async fn add_draft_to_bored(&mut self) -> Result<(), SurfBoredError> {
-        let Some(ref mut client) = self.client else {
-            return Err(SurfBoredError::BoredError(
-                BoredError::ClientConnectionError,
-            ));
+    pub fn discard_draft(&mut self, draft_index: usize) -> Result<(), SurfBoredError> {
+        self.drafts.remove(draft_index);
+        self.drafts.save_file(&self.get_drafts_path())
+    }
+
+    /// Carry on writing a saved draft, going to the bored it is for if needed
+    pub fn resume_draft(&mut self, draft_index: usize) -> Result<(), SurfBoredError> {
+        let Some(draft) = self.drafts.get_drafts().get(draft_index).cloned() else {
+            return Ok(());
         };
-        client
-            .add_draft_to_bored()
-            .await
-            .map_err(|e| SurfBoredError::BoredError(e))?;
+        self.revert_view();
+        if self.history.get_current().map(|visit| visit.link.as_str()) != Some(&draft.link) {
+            let url = URL::from_string(draft.link.clone())?;
+            return self.load_bored(url, move |app, result| {
+                result?;
+                app.open_draft(draft_index, draft)
+            });
+        }
+        self.open_draft(draft_index, draft)
+    }
+
+    fn open_draft(&mut self, draft_index: usize, draft: Draft) -> Result<(), SurfBoredError> {
+        self.create_draft(draft.dimensions)?;
+        self.draft_index = Some(draft_index);
+        self.edit_draft(&draft.content)?;
+        self.content_input = draft.content;
+        self.position_draft(draft.top_left)?;
+        if let Some(ref mut client) = self.client {
+            client.set_draft_kind(draft.kind)?;
+        }
         Ok(())
     }
 
     pub fn select_notice(&mut self, direction: Direction) {
         if let Some(bored) = self.get_current_bored() {
-            if !bored.get_notices().is_empty() {
+            if !bored.notices().is_empty() {
                 if self.selected_notice.is_none() {
                     self.selected_notice = bored.get_upper_left_most_notice();
                 } else {
@@ -418,18 +2813,38 @@ impl App {
         if let Some(notice_index) = self.selected_notice {
             return self
                 .get_current_bored()
-                .map(|b| b.get_notices()[notice_index].clone());
+                .and_then(|b| b.notices().get(notice_index).cloned());
         }
         None
     }
 
+    /// Select the notice a number of places on in reading order, for the linear view, the first
+    /// notice is selected if none is yet
+    pub fn select_in_reading_order(&mut self, step: i32) {
+        let Some(bored) = self.get_current_bored() else {
+            return;
+        };
+        let order = display_bored::get_reading_order(&bored);
+        let Some(last) = order.len().checked_sub(1) else {
+            return;
+        };
+        let position = match self
+            .selected_notice
+            .and_then(|selected| order.iter().position(|i| *i == selected))
+        {
+            Some(position) => (position as i64 + step as i64).clamp(0, last as i64) as usize,
+            None => 0,
+        };
+        self.selected_notice = Some(order[position]);
+    }
+
     pub fn increment_selected_notice(&mut self) {
         if let Some(bored) = self.get_current_bored() {
-            if self.selected_notice.is_none() && !bored.get_notices().is_empty() {
+            if self.selected_notice.is_none() && !bored.notices().is_empty() {
                 self.selected_notice = Some(0);
             } else {
                 if let Some(notices_index) = self.selected_notice {
-                    if notices_index >= bored.get_notices().len() - 1 {
+                    if notices_index >= bored.notices().len() - 1 {
                         self.selected_notice = Some(0);
                     } else {
                         self.selected_notice = Some(notices_index + 1);
@@ -443,7 +2858,7 @@ impl App {
         if let Some(notices_index) = self.selected_notice {
             if let Some(bored) = self.get_current_bored() {
                 if notices_index == 0 {
-                    self.selected_notice = Some(bored.get_notices().len() - 1);
+                    self.selected_notice = Some(bored.notices().len() - 1);
                 } else {
                     self.selected_notice = Some(notices_index - 1);
                 }
@@ -463,6 +2878,7 @@ impl App {
                     _ => return Err(bored_error),
                 },
                 Ok(_) => {
+                    let _ = self.save_draft();
                     if let Some(bored_view_port) = &self.bored_view_port {
                         return Ok(bored_view_port.in_view(new_top_left, new_bottom_right));
                     }
@@ -472,13 +2888,94 @@ impl App {
         Ok(true)
     }
 
+    /// Add the hyperlink being drafted to the end of the draft's text, which is where typing goes.
+    /// The url has to be one surf-bored can follow and the text can't hold square brackets as
+    /// they would end it early, if there is no text the url is shown.
+    pub fn insert_hyperlink(&mut self) -> Result<(), SurfBoredError> {
+        let url = self.link_url_input.trim().to_string();
+        if url.contains(char::is_whitespace) || url.contains(['(', ')']) {
+            return Err(SurfBoredError::Message(
+                "Hyperlink url can't have spaces or round brackets in it!".to_string(),
+            ));
+        }
+        if url.len() > MAX_URL_LENGTH {
+            return Err(SurfBoredError::BoredError(BoredError::URLTooLong));
+        }
+        URL::from_string(url.clone())?;
+        let text = self.link_text_input.replace(['[', ']'], "");
+        let text = if text.trim().is_empty() {
+            url.clone()
+        } else {
+            text
+        };
+        let content = format!("{}[{}]({})", self.content_input, text, url);
+        match self.edit_draft(&content) {
+            Ok(_) => {
+                self.content_input = content;
+                self.link_text_input = String::new();
+                self.link_url_input = String::new();
+                Ok(())
+            }
+            Err(BoredError::TooMuchText) => Err(SurfBoredError::Message(
+                "Hyperlink too big to fit on notice!".to_string(),
+            )),
+            Err(e) => Err(SurfBoredError::BoredError(e)),
+        }
+    }
+
+    /// Path of the file a draft is written in with an external editor
+    pub fn get_editor_draft_path() -> PathBuf {
+        std::env::temp_dir().join(format!("surf-bored-draft-{}.md", std::process::id()))
+    }
+
+    /// Replace the draft's text with what was written in an external editor, the trailing new
+    /// line editors add is left off. The draft is left as it was if the text doesn't fit.
+    pub fn apply_edited_draft(&mut self, text: &str) -> Result<(), SurfBoredError> {
+        let content = strip_disallowed_chars(text.trim_end_matches(['\n', '\r']));
+        match self.edit_draft(&content) {
+            Ok(_) => {
+                self.content_input = content;
+                Ok(())
+            }
+            Err(BoredError::TooMuchText) => Err(SurfBoredError::Message(
+                "Text from the editor too big to fit on notice, the draft was not changed!"
+                    .to_string(),
+            )),
+            Err(e) => Err(SurfBoredError::BoredError(e)),
+        }
+    }
+
+    /// Grow or shrink the draft by a number of columns and rows, sizes that wouldn't fit on the
+    /// bored or would be too small for the text are ignored
+    pub fn resize_draft(&mut self, change: (i32, i32)) -> Result<(), BoredError> {
+        let Some(draft) = self.get_draft() else {
+            return Ok(());
+        };
+        let Some(ref mut client) = self.client else {
+            return Err(BoredError::ClientConnectionError);
+        };
+        let new_dimensions = draft.get_dimensions().add_i32_tuple(change);
+        match client.resize_draft(new_dimensions) {
+            Err(
+                BoredError::NoticeOutOfBounds(..)
+                | BoredError::TooMuchText
+                | BoredError::InvalidDimensions(_),
+            ) => Ok(()),
+            Err(e) => Err(e),
+            Ok(_) => {
+                let _ = self.save_draft();
+                Ok(())
+            }
+        }
+    }
+
     pub fn next_hyperlink(&mut self) {
         if let View::NoticeView { hyperlinks_index } = self.current_view {
-            if let (Some(notices), Some(notice_index)) = (
-                self.get_current_bored().map(|b| b.get_notices()),
-                self.selected_notice,
-            ) {
-                if let Some(Ok(hyperlinks)) = notices
+            if let (Some(bored), Some(notice_index)) =
+                (self.get_current_bored(), self.selected_notice)
+            {
+                if let Some(Ok(hyperlinks)) = bored
+                    .notices()
                     .get(notice_index)
                     .map(|n| n.get_display().map(|d| d.get_hyperlink_locations()))
                 {
@@ -506,11 +3003,11 @@ impl App {
 
     pub fn previous_hyperlink(&mut self) {
         if let View::NoticeView { hyperlinks_index } = self.current_view {
-            if let (Some(notices), Some(notice_index)) = (
-                self.get_current_bored().map(|b| b.get_notices()),
-                self.selected_notice,
-            ) {
-                if let Some(Ok(hyperlinks)) = notices
+            if let (Some(bored), Some(notice_index)) =
+                (self.get_current_bored(), self.selected_notice)
+            {
+                if let Some(Ok(hyperlinks)) = bored
+                    .notices()
                     .get(notice_index)
                     .map(|n| n.get_display().map(|d| d.get_hyperlink_locations()))
                 {
@@ -551,65 +3048,363 @@ impl App {
         None
     }
 
-    pub async fn go_home(&mut self) -> Result<(), SurfBoredError> {
+    /// Select the hyperlink of the notice at the index and follow it, if there is one
+    pub fn activate_hyperlink(&mut self, hyperlinks_index: usize) -> Result<(), SurfBoredError> {
+        let Some(hyperlink) = self
+            .get_selected_notice()
+            .and_then(|notice| get_hyperlinks(notice.get_content()).ok())
+            .and_then(|hyperlinks| hyperlinks.get(hyperlinks_index).cloned())
+        else {
+            return Ok(());
+        };
+        self.current_view = View::NoticeView {
+            hyperlinks_index: Some(hyperlinks_index),
+        };
+        self.handle_hyperlink(hyperlink)
+    }
+
+    fn open_clipboard(&mut self) -> Result<&mut Clipboard, SurfBoredError> {
+        let clipboard = match self.clipboard.take() {
+            Some(clipboard) => clipboard,
+            None => Clipboard::new().map_err(|e| SurfBoredError::ClipboardError(e.to_string()))?,
+        };
+        Ok(self.clipboard.insert(clipboard))
+    }
+
+    pub fn copy_to_clipboard(&mut self, text: String) -> Result<(), SurfBoredError> {
+        self.open_clipboard()?
+            .set_text(text)
+            .map_err(|e| SurfBoredError::ClipboardError(e.to_string()))
+    }
+
+    /// Copy the address of the current bored, returning it
+    pub fn copy_current_address(&mut self) -> Result<String, SurfBoredError> {
+        let address = self
+            .get_current_address()
+            .ok_or(SurfBoredError::Message(
+                "No board is currently loaded.".to_string(),
+            ))?
+            .to_string();
+        self.copy_to_clipboard(address.clone())?;
+        Ok(address)
+    }
+
+    /// Show the address of the current bored as a QR code, to scan onto another device
+    pub fn open_qr(&mut self) -> Result<(), SurfBoredError> {
+        let address = self
+            .get_current_address()
+            .ok_or(SurfBoredError::Message(
+                "No board is currently loaded.".to_string(),
+            ))?
+            .to_string();
+        self.change_view(View::QrView(address));
+        Ok(())
+    }
+
+    /// Check where the links on the current bored go, looking for the boreds they link to in
+    /// the background, so the links that go nowhere can be crossed out. Web and app links
+    /// aren't checked.
+    pub fn check_links(&mut self) -> Result<(), SurfBoredError> {
+        let no_bored = || SurfBoredError::Message("No board is currently loaded.".to_string());
+        let bored = self.get_current_bored().ok_or_else(no_bored)?;
+        let address = self.get_current_address().ok_or_else(no_bored)?.to_string();
+        let mut links: Vec<String> = bored
+            .notices()
+            .iter()
+            .flat_map(|notice| get_hyperlinks(notice.get_content()).unwrap_or_default())
+            .map(|hyperlink| hyperlink.get_link())
+            .collect();
+        links.sort();
+        links.dedup();
+        if links.is_empty() {
+            self.show_toast(ToastKind::Info, "There are no links on the bored to check.");
+            return Ok(());
+        }
+        let mut schemes = vec![];
+        let mut dead = HashSet::new();
+        let mut linked_boreds = vec![];
+        for link in links {
+            let (scheme, target) = classify_link(&link);
+            schemes.push(scheme);
+            match target {
+                LinkTarget::Bored(address) => linked_boreds.push((link, address)),
+                LinkTarget::Unchecked => {}
+                LinkTarget::Dead => {
+                    dead.insert(link);
+                }
+            }
+        }
+        let addresses: Vec<BoredAddress> = linked_boreds
+            .iter()
+            .map(|(_, address)| address.clone())
+            .collect();
+        self.spawn_client_task(
+            "Checking the links on the bored...",
+            |client| async move {
+                // boreds that nobody has are left out of the cache, anything else is a failure
+                for result in client.fetch_boreds(&addresses).await {
+                    match result {
+                        Ok(_) | Err(BoredError::BoardDoesNotExist(_)) => {}
+                        Err(e) => return Err(SurfBoredError::BoredError(e)),
+                    }
+                }
+                Ok(client)
+            },
+            move |app, result| {
+                result?;
+                for (link, address) in linked_boreds {
+                    if app
+                        .client
+                        .as_ref()
+                        .is_none_or(|client| client.get_cached_bored(&address).is_err())
+                    {
+                        dead.insert(link);
+                    }
+                }
+                let link_check = LinkCheck {
+                    address,
+                    schemes: count_schemes(schemes),
+                    dead,
+                };
+                let kind = if link_check.dead.is_empty() {
+                    ToastKind::Success
+                } else {
+                    ToastKind::Warning
+                };
+                app.show_toast(kind, &link_check.describe());
+                app.link_check = Some(link_check);
+                Ok(())
+            },
+        )
+    }
+
+    /// Links on the current bored found to go nowhere when its links were last checked
+    pub fn get_dead_links(&self) -> HashSet<String> {
+        let current_address = self
+            .get_current_address()
+            .map(|address| address.to_string());
+        match &self.link_check {
+            Some(link_check) if Some(&link_check.address) == current_address.as_ref() => {
+                link_check.dead.clone()
+            }
+            _ => HashSet::new(),
+        }
+    }
+
+    /// Copy the url of the selected hyperlink, or the text of the selected notice if no
+    /// hyperlink is selected, returning what was copied
+    pub fn copy_selected_notice(&mut self) -> Result<String, SurfBoredError> {
+        let copied = if let Some(hyperlink) = self.get_selected_hyperlink() {
+            hyperlink.get_link()
+        } else {
+            self.get_selected_notice()
+                .ok_or(SurfBoredError::Message(
+                    "No notice is selected.".to_string(),
+                ))?
+                .get_display()?
+                .get_display_text()
+        };
+        self.copy_to_clipboard(copied.clone())?;
+        Ok(copied)
+    }
+
+    /// Whether the current view has a text input that can be pasted into
+    pub fn accepts_paste(&self) -> bool {
+        match self.current_view {
+            View::DirectoryView(_) => self.directory_search.is_some(),
+            _ => matches!(
+                self.current_view,
+                View::GoToView
+                    | View::CreateView(_)
+                    | View::DraftView(
+                        DraftMode::Content | DraftMode::Hyperlink(_) | DraftMode::Event(_)
+                    )
+                    | View::SearchView
+                    | View::IndexView(_)
+                    | View::CommandView
+                    | View::BookmarkView
+                    | View::PetnameView
+                    | View::DirectoryFileView(_)
+                    | View::ListingView { .. }
+                    | View::KeyView(KeyMode::Import)
+            ),
+        }
+    }
+
+    /// Paste text from the clipboard into the text input of the current view
+    pub fn paste_from_clipboard(&mut self) -> Result<(), SurfBoredError> {
+        let text = self
+            .open_clipboard()?
+            .get_text()
+            .map_err(|e| SurfBoredError::ClipboardError(e.to_string()))?;
+        self.paste(&text)
+    }
+
+    /// Add text to the text input of the current view all at once, whether from the clipboard
+    /// or pasted into the terminal. Only a notice being drafted keeps new lines, other inputs
+    /// are a single line. Nothing is pasted into the notice if the text doesn't fit.
+    pub fn paste(&mut self, text: &str) -> Result<(), SurfBoredError> {
+        let text = strip_disallowed_chars(text);
+        let line = text.lines().map(str::trim).collect::<Vec<&str>>().join(" ");
+        match &self.current_view {
+            View::SearchView => self.search_input.push_str(&line),
+            View::IndexView(_) => {
+                self.index_search.push_str(&line);
+                self.current_view = View::IndexView(0);
+            }
+            View::CommandView => self.command_input.push_str(&line),
+            View::BookmarkView => self.bookmark_name_input.push_str(&line),
+            View::PetnameView => self.petname_input.push_str(&line),
+            View::DirectoryFileView(_) => self.directory_file_input.push_str(&line),
+            View::ListingView { field, .. } => {
+                let field = *field;
+                self.get_listing_input_mut(field).push_str(&line)
+            }
+            View::DirectoryView(_) => {
+                if let Some(search) = self.directory_search.as_mut() {
+                    search.push_str(&line);
+                    self.current_view = View::DirectoryView(0);
+                }
+            }
+            View::GoToView => {
+                self.goto_input.push_str(&line);
+                self.goto_selected = None;
+            }
+            View::KeyView(KeyMode::Import) => self.key_input.push_str(&line),
+            View::CreateView(CreateMode::Name) => self.name_input.push_str(&line),
+            View::CreateView(CreateMode::URLName) => self.url_name_input.push_str(&line),
+            View::DraftView(DraftMode::Hyperlink(HyperlinkMode::Text)) => {
+                self.link_text_input.push_str(&line)
+            }
+            View::DraftView(DraftMode::Event(field)) => {
+                let field = *field;
+                self.get_event_input_mut(field).push_str(&line)
+            }
+            View::DraftView(DraftMode::Hyperlink(HyperlinkMode::URL)) => {
+                self.link_url_input.push_str(&line)
+            }
+            View::DraftView(DraftMode::Content) => {
+                let content = format!("{}{}", self.content_input, text);
+                match self.edit_draft(&content) {
+                    Ok(_) => self.content_input = content,
+                    Err(BoredError::TooMuchText) => {
+                        return Err(SurfBoredError::Message(
+                            "Pasted text too big to fit on notice!".to_string(),
+                        ));
+                    }
+                    Err(e) => return Err(SurfBoredError::BoredError(e)),
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn go_home(&mut self) -> Result<(), SurfBoredError> {
         if let Some(home) = self.directory.get_home() {
             let home_address = BoredAddress::from_string(home)?;
-            self.goto_bored(home_address).await?
+            self.goto_bored(home_address)?
         }
         Ok(())
     }
 
-    pub async fn handle_hyperlink<B: Backend>(
-        &mut self,
-        hyperlink: Hyperlink,
-        terminal: &mut Terminal<B>,
-        previous_buffer: Buffer,
-    ) -> Result<(), SurfBoredError> {
-        let theme = self.theme.clone();
+    pub fn handle_hyperlink(&mut self, hyperlink: Hyperlink) -> Result<(), SurfBoredError> {
         let url = URL::from_string(hyperlink.get_link())?;
+        let scheme = url.get_scheme();
         match url {
-            URL::BoredNet(bored_address) => {
-                let going_to_bored = self.goto_bored(bored_address);
-                match wait_pop_up(
-                    terminal,
-                    previous_buffer,
-                    going_to_bored,
-                    "Loading board from x0x...",
-                    theme,
-                )
-                .await
-                {
-                    Err(e) => self.display_error(e),
-                    _ => (),
-                }
-                return Ok(());
+            URL::BoredNet(bored_address) => self.goto_bored(bored_address),
+            URL::BoredShare(share_link) => self.goto_share_link(share_link),
+            URL::BoredApp(command) => self.hyperlink_command(&command),
+            URL::ClearNet(clear_net_url) => self.open_outside(&clear_net_url, scheme),
+        }
+    }
+
+    /// Open a web address or downloaded file with its opener, asking first unless its scheme
+    /// is trusted as anything could be behind it
+    pub fn open_outside(&mut self, target: &str, scheme: &str) -> Result<(), SurfBoredError> {
+        if self.trusted_schemes.iter().any(|trusted| trusted == scheme) {
+            return self.open_target(target, scheme);
+        }
+        let from = Box::new(self.current_view.clone());
+        self.current_view = View::OpenView {
+            target: target.to_string(),
+            scheme: scheme.to_string(),
+            from,
+        };
+        self.menu_visible = false;
+        Ok(())
+    }
+
+    /// Open what was asked about and go back to the view it was asked from
+    pub fn confirm_open(&mut self) -> Result<(), SurfBoredError> {
+        if let View::OpenView {
+            target,
+            scheme,
+            from,
+        } = self.current_view.clone()
+        {
+            self.current_view = *from;
+            return self.open_target(&target, &scheme);
+        }
+        Ok(())
+    }
+
+    /// Open a web address or file with the opener the user set for it, or the system's
+    fn open_target(&mut self, target: &str, scheme: &str) -> Result<(), SurfBoredError> {
+        match self.openers.get_opener(target, scheme) {
+            Opener::System => open::that(target)
+                .map_err(|_| SurfBoredError::Message(format!("Could not open {target}"))),
+            Opener::Copy => {
+                self.copy_to_clipboard(target.to_string())?;
+                self.show_toast(ToastKind::Success, "Copied to the clipboard.");
+                Ok(())
             }
-            URL::BoredApp(command) => {
-                let executing_command = self.hyperlink_command(&command);
-                let message = if command == "home" {
-                    "Loading home board from x0x"
-                } else {
-                    ""
-                };
-                match wait_pop_up(terminal, previous_buffer, executing_command, message, theme)
-                    .await
-                {
-                    Err(e) => Ok(self.display_error(e)),
-                    _ => Ok(()),
-                }
+            Opener::Command(command) => {
+                self.terminal_command = Some(openers::get_command_args(&command, target));
+                Ok(())
             }
-            URL::ClearNet(clear_net_url) => {
-                if let Err(_) = open::that(clear_net_url) {
-                    return Err(SurfBoredError::Message(
-                        "Could not open old fashioned (https/http) link".to_string(),
-                    ));
-                };
-                return Ok(());
+        }
+    }
+
+    /// The command waiting to be given the terminal, if any
+    pub fn take_terminal_command(&mut self) -> Option<Vec<String>> {
+        self.terminal_command.take()
+    }
+
+    /// Start typing a command after :
+    pub fn open_command(&mut self) {
+        self.command_input.clear();
+        self.change_view(View::CommandView);
+    }
+
+    /// Run the command typed after : from the view it was typed over, by handing the key it
+    /// stands for to the main loop
+    pub fn run_command(&mut self) -> Result<(), SurfBoredError> {
+        self.revert_view();
+        match keymap::parse_command(&self.command_input)? {
+            VimCommand::Key(key) => self.pending_key = Some(key),
+            VimCommand::GoTo(address) => {
+                self.open_goto();
+                self.goto_input = address;
+                self.pending_key = Some(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
             }
         }
+        Ok(())
+    }
+
+    /// The key a command stands for, handled as if it were pressed
+    pub fn take_pending_key(&mut self) -> Option<KeyEvent> {
+        self.pending_key.take()
+    }
+
+    /// Go back to the view the open was asked from without opening anything
+    pub fn cancel_open(&mut self) {
+        if let View::OpenView { from, .. } = &self.current_view {
+            self.current_view = *from.clone();
+        }
     }
 
-    pub async fn hyperlink_command(&mut self, command: &str) -> Result<(), SurfBoredError> {
+    pub fn hyperlink_command(&mut self, command: &str) -> Result<(), SurfBoredError> {
         if command == "about" {
             let Some(ref mut client) = self.client else {
                 return Err(SurfBoredError::BoredError(
@@ -621,15 +3416,14 @@ impl App {
             self.menu_visible = false;
             let about = directory::about_bored();
             self.bored_view_port = Some(BoredViewPort::create(
-                &about,
+                about.clone(),
                 about.get_dimensions(),
                 self.selected_notice,
             ));
             client.load_app_bored(about);
             Ok(())
         } else if command == "home" {
-            self.go_home().await?;
-            Ok(())
+            self.go_home()
         } else {
             return Err(SurfBoredError::LinkCommandUnknown(command.to_string()));
         }
@@ -639,6 +3433,19 @@ impl App {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tasks::{self, AppEvent};
+    use bored::url::Capability;
+
+    async fn wait_for_task(app: &mut App) {
+        if let AppEvent::TaskFinished {
+            client,
+            on_finished,
+            ..
+        } = app.tasks.recv().await
+        {
+            app.finish_task(client, on_finished);
+        }
+    }
 
     #[tokio::test]
     #[ignore]
@@ -647,30 +3454,393 @@ mod tests {
         {
             let mut app = App::new();
             app.directory_path = "test_directory.toml".to_string();
-            app.init_client().await?;
-            app.create_bored_on_network("I am bored", Coordinate { x: 120, y: 40 }, None)
-                .await?;
+            let client = tasks::connect(None, &X0xDaemon::Standard).await;
+            app.finish_connecting(client.map(Box::new))?;
+            app.create_bored_on_network("I am bored", Coordinate { x: 120, y: 40 }, None)?;
+            wait_for_task(&mut app).await;
             directory = app.directory.clone();
         }
         {
             let mut app = App::new();
             app.directory_path = "test_directory.toml".to_string();
-            app.init_client().await?;
+            let client = tasks::connect(None, &X0xDaemon::Standard).await;
+            app.finish_connecting(client.map(Box::new))?;
             app.load_directory()?;
             assert_eq!(directory, app.directory);
             app.create_bored_on_network(
                 "We are bored",
                 Coordinate { x: 120, y: 40 },
                 Some("bored.of.domains"),
-            )
-            .await?;
+            )?;
+            wait_for_task(&mut app).await;
             directory = app.directory.clone();
         }
         let mut app = App::new();
         app.directory_path = "test_directory.toml".to_string();
-        app.init_client().await?;
+        let client = tasks::connect(None, &X0xDaemon::Standard).await;
+        app.finish_connecting(client.map(Box::new))?;
         app.load_directory()?;
         assert_eq!(directory, app.directory);
         Ok(())
     }
+
+    #[test]
+    fn test_cycle_free_url_names() {
+        let mut app = App::new();
+        app.url_name_input = "cats".to_string();
+        app.cycle_free_url_names(true);
+        assert_eq!(app.url_name_input, "cats");
+        app.url_name_check = Some(UrlNameCheck {
+            url_name: "cats".to_string(),
+            taken: true,
+            free: vec!["cats-3".to_string(), "the-cats".to_string()],
+        });
+        app.cycle_free_url_names(true);
+        assert_eq!(app.url_name_input, "cats-3");
+        app.cycle_free_url_names(true);
+        assert_eq!(app.url_name_input, "the-cats");
+        app.cycle_free_url_names(true);
+        assert_eq!(app.url_name_input, "cats-3");
+        app.cycle_free_url_names(false);
+        assert_eq!(app.url_name_input, "the-cats");
+    }
+
+    #[test]
+    fn test_overlapping_stack() -> Result<(), BoredError> {
+        let mut bored = Bored::create("Test", Coordinate { x: 40, y: 20 });
+        for (x, y) in [(0, 0), (5, 2), (25, 10)] {
+            bored.add(
+                Notice::create(Coordinate { x: 10, y: 5 }),
+                Coordinate { x, y },
+            )?;
+        }
+        assert_eq!(get_overlapping_stack(&bored, 0), Some(vec![1, 0]));
+        assert_eq!(get_overlapping_stack(&bored, 1), Some(vec![1, 0]));
+        assert_eq!(get_overlapping_stack(&bored, 2), None);
+        assert_eq!(get_overlapping_stack(&bored, 3), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_classify_link() -> Result<(), BoredError> {
+        let bored = |address| LinkTarget::Bored(BoredAddress::from_string(address).unwrap());
+        assert_eq!(
+            classify_link("bored://cats"),
+            ("bored".to_string(), bored("cats"))
+        );
+        assert_eq!(classify_link("cats"), ("bored".to_string(), bored("cats")));
+        assert_eq!(
+            classify_link("https://example.com"),
+            ("https".to_string(), LinkTarget::Unchecked)
+        );
+        assert_eq!(
+            classify_link("app://help"),
+            ("app".to_string(), LinkTarget::Unchecked)
+        );
+        assert_eq!(
+            classify_link("ant://cats"),
+            ("ant".to_string(), LinkTarget::Dead)
+        );
+        assert_eq!(classify_link(""), ("unknown".to_string(), LinkTarget::Dead));
+        let share_link = ShareLink::create(
+            BoredAddress::from_string("cats")?,
+            Capability::ReadOnly,
+            None,
+        );
+        let (_, target) = classify_link(&share_link.to_string());
+        assert_eq!(target, bored("cats"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_events_filename() {
+        assert_eq!(get_events_filename("Bored club"), "Bored-club-events.ics");
+        assert_eq!(get_events_filename("../.."), "events.ics");
+    }
+
+    #[test]
+    fn test_link_check_describe() {
+        let schemes = ["https", "bored", "ant", "bored"].map(String::from);
+        assert_eq!(
+            count_schemes(schemes),
+            vec![
+                ("bored".to_string(), 2),
+                ("https".to_string(), 1),
+                ("ant".to_string(), 1)
+            ]
+        );
+        let mut link_check = LinkCheck {
+            address: "bored://cats".to_string(),
+            schemes: count_schemes(["bored", "https", "bored"].map(String::from)),
+            dead: HashSet::new(),
+        };
+        assert_eq!(
+            link_check.describe(),
+            "Checked 3 links: 2 bored, 1 https. None of them go nowhere."
+        );
+        link_check.dead.insert("bored://gone".to_string());
+        assert_eq!(
+            link_check.describe(),
+            "Checked 3 links: 2 bored, 1 https. 1 goes nowhere and is crossed out."
+        );
+    }
+
+    #[test]
+    fn test_trusted_schemes_setting() {
+        assert!(get_trusted_schemes(None).is_empty());
+        assert_eq!(
+            get_trusted_schemes(Some(" HTTPS, ,file".to_string())),
+            vec!["https", "file"]
+        );
+    }
+
+    #[test]
+    fn test_open_outside() -> Result<(), SurfBoredError> {
+        let mut app = App::new();
+        app.trusted_schemes = vec![];
+        app.current_view = View::DownloadsView(0);
+        app.open_outside("https://autonomi.com", "https")?;
+        assert_eq!(
+            app.current_view,
+            View::OpenView {
+                target: "https://autonomi.com".to_string(),
+                scheme: "https".to_string(),
+                from: Box::new(View::DownloadsView(0)),
+            }
+        );
+        app.cancel_open();
+        assert_eq!(app.current_view, View::DownloadsView(0));
+        // an opener that needs the terminal is left for the main loop to run
+        app.openers = toml::from_str("https = \"w3m\"").unwrap();
+        app.trusted_schemes = vec!["https".to_string()];
+        app.open_outside("https://autonomi.com", "https")?;
+        assert_eq!(app.current_view, View::DownloadsView(0));
+        assert_eq!(
+            app.take_terminal_command(),
+            Some(vec!["w3m".to_string(), "https://autonomi.com".to_string()])
+        );
+        assert_eq!(app.take_terminal_command(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_auto_refresh_setting() {
+        assert_eq!(get_auto_refresh(None), Some(DEFAULT_AUTO_REFRESH));
+        assert_eq!(
+            get_auto_refresh(Some("15".to_string())),
+            Some(Duration::from_secs(15))
+        );
+        assert_eq!(get_auto_refresh(Some("0".to_string())), None);
+        assert_eq!(
+            get_auto_refresh(Some("soon".to_string())),
+            Some(DEFAULT_AUTO_REFRESH)
+        );
+    }
+
+    #[test]
+    fn test_connection_check() {
+        let mut app = App::new();
+        app.finish_connection_check(ConnectionCheck::Healthy);
+        assert!(!app.connection_lost);
+        assert!(app.toasts.get_current().is_none());
+        app.finish_connection_check(ConnectionCheck::Lost(BoredError::ClientConnectionError));
+        assert!(app.connection_lost);
+        assert_eq!(
+            app.toasts.get_current().map(|toast| toast.get_kind()),
+            Some(ToastKind::Warning)
+        );
+        app.finish_connection_check(ConnectionCheck::Reconnected);
+        assert!(!app.connection_lost);
+        // a failed network operation has the connection checked straight away
+        app.finish_task(
+            Err(SurfBoredError::BoredError(BoredError::ClientConnectionError)),
+            Box::new(|_, _| Ok(())),
+        );
+        assert_eq!(app.last_connection_check, None);
+    }
+
+    #[test]
+    fn test_new_notices() -> Result<(), BoredError> {
+        let mut bored = Bored::create("New", Coordinate { x: 40, y: 20 });
+        let mut notice = Notice::create(Coordinate { x: 10, y: 5 });
+        notice.set_notice_id("notice:1:first".to_string());
+        bored.add(notice, Coordinate { x: 0, y: 0 })?;
+        let mut app = App::new();
+        assert_eq!(app.get_new_notices(&bored), vec![0]);
+        app.seen_notices = bored
+            .notices()
+            .iter()
+            .map(|notice| notice.get_notice_id().to_string())
+            .collect();
+        assert!(app.get_new_notices(&bored).is_empty());
+        // one arrives from a peer
+        let mut notice = Notice::create(Coordinate { x: 10, y: 5 });
+        notice.set_notice_id("notice:2:second".to_string());
+        bored.add(notice, Coordinate { x: 20, y: 10 })?;
+        assert_eq!(app.get_new_notices(&bored), vec![1]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_goto_suggestions() -> Result<(), SurfBoredError> {
+        let path = std::env::temp_dir()
+            .join(format!("surf-bored-goto-{}.toml", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        let mut app = App::new();
+        app.directory = Directory::default();
+        app.directory
+            .add(Listing::new("Games", "bored://bored.games"), &path)?;
+        app.history.visit("bored://news");
+        app.history.visit("bored+ro://bored.secret");
+        // the same bored as the directory listing
+        app.history.visit("bored://games");
+        app.open_goto();
+        let addresses: Vec<&str> = app
+            .get_goto_suggestions()
+            .iter()
+            .map(|c| c.address.as_str())
+            .collect();
+        assert_eq!(
+            addresses,
+            vec!["bored://welcome", "bored://bored.games", "bored://news"]
+        );
+
+        app.goto_input = "gms".to_string();
+        assert_eq!(app.get_goto_address(), "gms");
+        app.select_goto_suggestion(false);
+        app.select_goto_suggestion(false);
+        assert_eq!(app.goto_selected, Some(0));
+        assert_eq!(app.get_goto_address(), "bored://bored.games");
+        app.select_goto_suggestion(true);
+        assert_eq!(app.get_goto_address(), "gms");
+        let _ = std::fs::remove_file(path);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_editor_command() {
+        assert_eq!(
+            get_editor_command(Some("code --wait".to_string()), Some("nano".to_string())),
+            vec!["code", "--wait"]
+        );
+        assert_eq!(
+            get_editor_command(Some(" ".to_string()), Some("nano".to_string())),
+            vec!["nano"]
+        );
+        assert!(!get_editor_command(None, None).is_empty());
+    }
+
+    #[test]
+    fn test_reader_key() -> Result<(), SurfBoredError> {
+        let dir = std::env::temp_dir().join(format!("surf-bored-key-{}", std::process::id()));
+        let mut app = App::new();
+        let directory_path = dir.join("directory_of_boreds.toml");
+        app.directory_path = directory_path.to_string_lossy().to_string();
+        app.load_reader_key();
+        assert!(app.reader_key.is_none());
+        app.create_reader_key()?;
+        let public_key = |app: &App| app.reader_key.as_ref().map(ReaderKey::public_key);
+        let created = public_key(&app);
+        let exists = Err(SurfBoredError::ReaderKeyExists);
+        assert_eq!(app.create_reader_key(), exists);
+        app.load_reader_key();
+        assert_eq!(public_key(&app), created);
+        let secret = app.reader_key.as_ref().map(ReaderKey::to_secret_string);
+        let seed_phrase = app.reader_key.as_ref().map(ReaderKey::to_seed_phrase);
+        app.clear_reader_key()?;
+        app.load_reader_key();
+        assert!(app.reader_key.is_none());
+        app.key_input = "not a key".to_string();
+        assert!(app.import_reader_key().is_err());
+        app.key_input = format!(" {} ", secret.unwrap_or_default());
+        app.import_reader_key()?;
+        assert_eq!(public_key(&app), created);
+        assert!(app.key_input.is_empty());
+        // the seed phrase imports the same key
+        app.clear_reader_key()?;
+        app.key_input = seed_phrase.unwrap_or_default();
+        app.import_reader_key()?;
+        assert_eq!(public_key(&app), created);
+        let _ = fs::remove_dir_all(dir);
+        Ok(())
+    }
+
+    #[test]
+    fn test_grid() {
+        assert_eq!(get_grid_step(0, true), 10);
+        assert_eq!(get_grid_step(13, true), 7);
+        assert_eq!(get_grid_step(13, false), 3);
+        assert_eq!(get_grid_step(20, false), 10);
+        assert_eq!(get_grid_step(0, false), 0);
+        assert_eq!(snap_to_grid(14), 10);
+        assert_eq!(snap_to_grid(15), 20);
+        assert_eq!(snap_to_grid(30), 30);
+    }
+
+    #[test]
+    fn test_insert_hyperlink() {
+        let mut app = App::new();
+        app.link_text_input = "games".to_string();
+        assert_eq!(
+            app.insert_hyperlink(),
+            Err(SurfBoredError::BoredError(BoredError::UnknownURLType(
+                String::new()
+            )))
+        );
+        app.link_url_input = "https://games.com/a (b)".to_string();
+        assert!(matches!(
+            app.insert_hyperlink(),
+            Err(SurfBoredError::Message(_))
+        ));
+        // a valid hyperlink gets as far as needing a draft to go on
+        app.link_url_input = " bored://games ".to_string();
+        assert_eq!(
+            app.insert_hyperlink(),
+            Err(SurfBoredError::BoredError(
+                BoredError::ClientConnectionError
+            ))
+        );
+        assert_eq!(app.link_text_input, "games");
+    }
+
+    #[test]
+    fn test_paste() -> Result<(), SurfBoredError> {
+        let mut app = App::new();
+        app.paste("ignored")?;
+        assert!(!app.accepts_paste());
+        app.current_view = View::GoToView;
+        app.goto_input = "bored://".to_string();
+        app.goto_selected = Some(0);
+        app.paste("games\n")?;
+        assert_eq!(app.goto_input, "bored://games");
+        assert_eq!(app.goto_selected, None);
+        // other inputs are a single line and can't take control characters
+        app.current_view = View::CreateView(CreateMode::Name);
+        app.paste("We are\r\n bored\x1b")?;
+        assert_eq!(app.name_input, "We are bored");
+        app.current_view = View::ListingView {
+            directory_index: 0,
+            field: ListingField::Tags,
+        };
+        app.paste("games, fun")?;
+        assert_eq!(app.listing_tags_input, "games, fun");
+        // the directory only takes a paste while searching it
+        app.current_view = View::DirectoryView(3);
+        assert!(!app.accepts_paste());
+        app.directory_search = Some("ga".to_string());
+        app.paste("mes")?;
+        assert_eq!(app.directory_search.as_deref(), Some("games"));
+        assert_eq!(app.current_view, View::DirectoryView(0));
+        app.current_view = View::DraftView(DraftMode::Content);
+        assert!(app.accepts_paste());
+        assert_eq!(
+            app.paste("a\nb"),
+            Err(SurfBoredError::BoredError(
+                BoredError::ClientConnectionError
+            ))
+        );
+        assert_eq!(app.content_input, "");
+        Ok(())
+    }
 }
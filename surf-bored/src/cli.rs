@@ -0,0 +1,179 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use bored::url::URL;
+use bored::notice::MAX_POW_DIFFICULTY;
+use bored::x0x_client::{WorkLimit, X0xDaemon};
+use clap::Parser;
+use std::path::PathBuf;
+
+/// Folder in the current directory everything is kept in with --local
+pub const LOCAL_DATA_DIR: &str = "we-are-bored";
+
+/// Surf Bored, a terminal browser for boreds, the notice boards shared over x0x
+#[derive(Debug, Default, Parser)]
+#[command(version, about)]
+pub struct Cli {
+    /// Keep the directory, settings and cache in a we-are-bored folder in the current directory,
+    /// away from your own
+    #[arg(long)]
+    pub local: bool,
+    /// Open the bored at this bored:// address or share link rather than where you left off
+    #[arg(long, value_name = "BORED_URL", value_parser = parse_bored_url)]
+    pub address: Option<String>,
+    /// Directory of boreds to use, the other settings files are kept next to it
+    #[arg(long, value_name = "PATH")]
+    pub directory: Option<PathBuf>,
+    /// Theme to use, by its name
+    #[arg(long, value_name = "NAME")]
+    pub theme: Option<String>,
+    /// Read boreds without creating or changing any
+    #[arg(long)]
+    pub read_only: bool,
+    /// Let whoever posts a notice know you opened it, counted by an id made from the notice so
+    /// it can't be told which other notices you opened
+    #[arg(long)]
+    pub count_views: bool,
+    /// Add errors to the end of this file as they happen
+    #[arg(long, value_name = "PATH")]
+    pub log_file: Option<PathBuf>,
+    /// Connect to the x0x instance started with `x0x start --name NAME`, such as one on a test
+    /// network, its boreds are cached apart from the others
+    #[arg(long, value_name = "NAME", conflicts_with = "x0x_api")]
+    pub x0x_name: Option<String>,
+    /// Connect to the x0x daemon's api at this address, a port on this machine or host:port,
+    /// for a daemon set up with its own bootstrap peers
+    #[arg(long, value_name = "ADDRESS")]
+    pub x0x_api: Option<String>,
+    /// Refuse to post on boreds asking for proof of work above this difficulty, each step up
+    /// doubles the hashing it takes
+    #[arg(long, value_name = "DIFFICULTY", value_parser = clap::value_parser!(u8).range(0..=MAX_POW_DIFFICULTY as i64))]
+    pub max_pow: Option<u8>,
+    /// Refuse to post once the proof of work done this session would go over this many million
+    /// hashes
+    #[arg(long, value_name = "MILLION_HASHES")]
+    pub max_work: Option<u64>,
+}
+
+impl Cli {
+    /// Where the client keeps its cache, none for the standard place
+    pub fn get_data_dir(&self) -> Option<PathBuf> {
+        self.local.then(|| PathBuf::from(LOCAL_DATA_DIR))
+    }
+
+    /// The x0x daemon to connect to
+    pub fn get_daemon(&self) -> X0xDaemon {
+        match (&self.x0x_name, &self.x0x_api) {
+            (Some(name), _) => X0xDaemon::Named(name.clone()),
+            (None, Some(address)) => X0xDaemon::Custom(address.clone()),
+            (None, None) => X0xDaemon::Standard,
+        }
+    }
+
+    /// Most proof of work to do to post
+    pub fn get_work_limit(&self) -> WorkLimit {
+        WorkLimit {
+            per_notice: self.max_pow,
+            per_session: self.max_work.map(|million| million.saturating_mul(1_000_000)),
+        }
+    }
+
+    /// The directory of boreds file to use, none for the standard one
+    pub fn get_directory_path(&self) -> Option<PathBuf> {
+        self.directory.clone().or_else(|| {
+            self.get_data_dir()
+                .map(|data_dir| data_dir.join("directory_of_boreds.toml"))
+        })
+    }
+}
+
+/// Only take addresses that can be opened as a bored
+fn parse_bored_url(link: &str) -> Result<String, String> {
+    match URL::from_string(link.to_string()) {
+        Ok(URL::BoredNet(_) | URL::BoredShare(_)) => Ok(link.to_string()),
+        Ok(_) => Err("not a bored:// address or share link".to_string()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli() {
+        let cli = Cli::try_parse_from(["surf-bored"]).unwrap();
+        assert!(!cli.local && !cli.read_only && !cli.count_views);
+        assert_eq!(cli.get_directory_path(), None);
+        let cli = Cli::try_parse_from([
+            "surf-bored",
+            "--local",
+            "--read-only",
+            "--count-views",
+            "--address",
+            "bored://welcome",
+            "--theme",
+            "Synth",
+        ])
+        .unwrap();
+        assert_eq!(cli.address.as_deref(), Some("bored://welcome"));
+        assert!(cli.count_views);
+        assert_eq!(cli.get_data_dir(), Some(PathBuf::from(LOCAL_DATA_DIR)));
+        assert_eq!(
+            cli.get_directory_path(),
+            Some(PathBuf::from(LOCAL_DATA_DIR).join("directory_of_boreds.toml"))
+        );
+        // a directory given is used even with --local
+        let cli =
+            Cli::try_parse_from(["surf-bored", "--local", "--directory", "boreds.toml"]).unwrap();
+        assert_eq!(cli.get_directory_path(), Some(PathBuf::from("boreds.toml")));
+        assert!(Cli::try_parse_from(["surf-bored", "--address", "https://example.com"]).is_err());
+        assert!(Cli::try_parse_from(["surf-bored", "local"]).is_err());
+    }
+
+    #[test]
+    fn test_cli_daemon() {
+        let cli = Cli::try_parse_from(["surf-bored"]).unwrap();
+        assert_eq!(cli.get_daemon(), X0xDaemon::Standard);
+        let cli = Cli::try_parse_from(["surf-bored", "--x0x-name", "testnet"]).unwrap();
+        assert_eq!(cli.get_daemon(), X0xDaemon::Named("testnet".to_string()));
+        let cli = Cli::try_parse_from(["surf-bored", "--x0x-api", "10.0.0.2:12700"]).unwrap();
+        assert_eq!(
+            cli.get_daemon(),
+            X0xDaemon::Custom("10.0.0.2:12700".to_string())
+        );
+        assert!(
+            Cli::try_parse_from(["surf-bored", "--x0x-name", "testnet", "--x0x-api", "12701"])
+                .is_err()
+        );
+    }
+    #[test]
+    fn test_cli_work_limit() {
+        let cli = Cli::try_parse_from(["surf-bored"]).unwrap();
+        assert_eq!(cli.get_work_limit(), WorkLimit::default());
+        let cli =
+            Cli::try_parse_from(["surf-bored", "--max-pow", "16", "--max-work", "50"]).unwrap();
+        assert_eq!(
+            cli.get_work_limit(),
+            WorkLimit {
+                per_notice: Some(16),
+                per_session: Some(50_000_000),
+            }
+        );
+        assert!(Cli::try_parse_from(["surf-bored", "--max-pow", "99"]).is_err());
+    }
+}
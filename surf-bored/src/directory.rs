@@ -19,6 +19,31 @@ use crate::app::SurfBoredError;
 use bored::{Bored, Coordinate, notice::Notice};
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::path::Path;
+
+/// Formats the directory can be exported to and imported from to share it with other people
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DirectoryFormat {
+    /// The Netscape bookmark file most web browsers read and write
+    Bookmarks,
+    Json,
+}
+impl DirectoryFormat {
+    /// The format for a file, going by its extension
+    pub fn from_path(path: &Path) -> Result<DirectoryFormat, SurfBoredError> {
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        match extension.as_str() {
+            "html" | "htm" => Ok(DirectoryFormat::Bookmarks),
+            "json" => Ok(DirectoryFormat::Json),
+            _ => Err(SurfBoredError::UnknownDirectoryFormat(
+                path.display().to_string(),
+            )),
+        }
+    }
+}
 
 /// The directory of boreds...list of bored the user has saved for future reference
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -109,15 +134,199 @@ impl Directory {
         Ok(self.bored_addresses[directory_index].clone())
     }
 
-    pub fn as_table(&self) -> Vec<[String; 2]> {
+    /// Bring in the listings from another copy of the directory, such as one kept for another
+    /// machine. Listings only in the other copy are added to the end, ones in both keep their
+    /// name here and gain the other's tags, and its note if there isn't one here. A copy that
+    /// is still the default takes the other's listings and home as they are. Listings removed
+    /// from one copy come back from the other as there is no record of removals.
+    pub fn merge(&mut self, other: &Directory) {
+        if *self == Directory::default() {
+            *self = other.clone();
+            return;
+        }
+        for other_listing in &other.bored_addresses {
+            match self
+                .bored_addresses
+                .iter_mut()
+                .find(|listing| listing.bored_address == other_listing.bored_address)
+            {
+                Some(listing) => {
+                    for tag in &other_listing.tags {
+                        if !listing.has_tag(tag) {
+                            listing.tags.push(tag.clone());
+                        }
+                    }
+                    if listing.note.is_none() {
+                        listing.note = other_listing.note.clone();
+                    }
+                    listing.watched |= other_listing.watched;
+                }
+                None => self.bored_addresses.push(other_listing.clone()),
+            }
+        }
+    }
+
+    pub fn export(&self, format: DirectoryFormat) -> Result<String, SurfBoredError> {
+        match format {
+            DirectoryFormat::Json => serde_json::to_string_pretty(self)
+                .map_err(|_| SurfBoredError::DirectorySerialzationError),
+            DirectoryFormat::Bookmarks => {
+                let mut html = String::from(
+                    "<!DOCTYPE NETSCAPE-Bookmark-file-1>\n\
+                     <META HTTP-EQUIV=\"Content-Type\" CONTENT=\"text/html; charset=UTF-8\">\n\
+                     <TITLE>Directory of boreds</TITLE>\n\
+                     <H1>Directory of boreds</H1>\n\
+                     <DL><p>\n",
+                );
+                for listing in &self.bored_addresses {
+                    html.push_str(&format!(
+                        "    <DT><A HREF=\"{}\"",
+                        escape_html(&listing.bored_address)
+                    ));
+                    if !listing.tags.is_empty() {
+                        html.push_str(&format!(
+                            " TAGS=\"{}\"",
+                            escape_html(&listing.tags.join(","))
+                        ));
+                    }
+                    html.push_str(&format!(">{}</A>\n", escape_html(&listing.name)));
+                    if let Some(note) = &listing.note {
+                        html.push_str(&format!("    <DD>{}\n", escape_html(note)));
+                    }
+                }
+                html.push_str("</DL><p>\n");
+                Ok(html)
+            }
+        }
+    }
+
+    /// Read a directory exported by surf-bored or a browser, links that aren't to boreds are
+    /// left out
+    pub fn import(text: &str, format: DirectoryFormat) -> Result<Directory, SurfBoredError> {
+        match format {
+            DirectoryFormat::Json => {
+                serde_json::from_str(text).map_err(|_| SurfBoredError::DirectoryDeserialzationError)
+            }
+            DirectoryFormat::Bookmarks => Ok(parse_bookmarks(text)),
+        }
+    }
+
+    /// Indexes of the listings with the tag, or all of them if there is no tag, that match
+    /// the search, in order
+    pub fn get_listing_indexes(&self, tag: Option<&str>, search: &str) -> Vec<usize> {
+        self.bored_addresses
+            .iter()
+            .enumerate()
+            .filter(|(_, listing)| tag.is_none_or(|tag| listing.has_tag(tag)))
+            .filter(|(_, listing)| listing.matches(search))
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Every tag used in the directory, sorted
+    pub fn get_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .bored_addresses
+            .iter()
+            .flat_map(|listing| listing.tags.clone())
+            .collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    pub fn update(
+        &mut self,
+        directory_index: usize,
+        listing: Listing,
+        path: &str,
+    ) -> Result<(), SurfBoredError> {
+        let len = self.bored_addresses.len();
+        let Some(existing) = self.bored_addresses.get_mut(directory_index) else {
+            return Err(SurfBoredError::DirectoryOutOfBounds(directory_index, len));
+        };
+        *existing = listing;
+        self.save_file(path)
+    }
+
+    pub fn remove(&mut self, directory_index: usize, path: &str) -> Result<(), SurfBoredError> {
+        if directory_index >= self.bored_addresses.len() {
+            return Err(SurfBoredError::DirectoryOutOfBounds(
+                directory_index,
+                self.bored_addresses.len(),
+            ));
+        }
+        self.bored_addresses.remove(directory_index);
+        // keep pointing at the same home, the first listing becomes home if it was removed
+        if directory_index < self.home_bored {
+            self.home_bored -= 1;
+        } else if directory_index == self.home_bored {
+            self.home_bored = 0;
+        }
+        self.save_file(path)
+    }
+
+    /// Watch the bored at the index for new notices or stop, returning whether it is watched
+    pub fn toggle_watched(
+        &mut self,
+        directory_index: usize,
+        path: &str,
+    ) -> Result<bool, SurfBoredError> {
+        let len = self.bored_addresses.len();
+        let Some(listing) = self.bored_addresses.get_mut(directory_index) else {
+            return Err(SurfBoredError::DirectoryOutOfBounds(directory_index, len));
+        };
+        listing.watched = !listing.watched;
+        let watched = listing.watched;
+        self.save_file(path)?;
+        Ok(watched)
+    }
+
+    pub fn get_watched(&self) -> Vec<Listing> {
+        self.bored_addresses
+            .iter()
+            .filter(|listing| listing.watched)
+            .cloned()
+            .collect()
+    }
+
+    /// Swap the order of two listings, for moving listings up and down the directory
+    pub fn swap(&mut self, a: usize, b: usize, path: &str) -> Result<(), SurfBoredError> {
+        let len = self.bored_addresses.len();
+        if let Some(out_of_bounds) = [a, b].into_iter().find(|i| *i >= len) {
+            return Err(SurfBoredError::DirectoryOutOfBounds(out_of_bounds, len));
+        }
+        self.bored_addresses.swap(a, b);
+        if self.home_bored == a {
+            self.home_bored = b;
+        } else if self.home_bored == b {
+            self.home_bored = a;
+        }
+        self.save_file(path)
+    }
+
+    /// Rows of name, tags, home marker and watched marker for the listings with the tag, or
+    /// all of them, that match the search
+    pub fn as_table(&self, tag: Option<&str>, search: &str) -> Vec<[String; 4]> {
         let mut v = vec![];
-        for (i, listing) in self.bored_addresses.iter().enumerate() {
+        for i in self.get_listing_indexes(tag, search) {
+            let listing = &self.bored_addresses[i];
             let home = if i == self.home_bored {
                 "*".to_string()
             } else {
                 String::new()
             };
-            v.push([listing.name.clone(), home]);
+            let watched = if listing.watched {
+                "*".to_string()
+            } else {
+                String::new()
+            };
+            v.push([
+                listing.name.clone(),
+                listing.get_tags_string(),
+                home,
+                watched,
+            ]);
         }
         v
     }
@@ -148,30 +357,420 @@ pub fn about_bored() -> Bored {
     about
 }
 
+/// Most boreds kept in the history, the oldest are forgotten first
+const MAX_HISTORY: usize = 100;
+
+/// A bored that was surfed to and where it was being viewed from when it was left
+#[derive(Clone, Debug, PartialEq)]
+pub struct Visit {
+    /// The bored:// address or share link the bored was gone to with
+    pub link: String,
+    pub view_top_left: Coordinate,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum HistoryDirection {
+    Back,
+    Forward,
+}
+
 /// History of boreds surfed in current session
-// pub struct History {
-//     boreds: Vec<Bored>,
-//     current_position: usize,
-// }
-// impl History {
-//     pub fn new() -> History {
-//         History {
-//             boreds: vec![],
-//             current_position: 0,
-//         }
-//     }
-// }
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    visits: Vec<Visit>,
+    current_position: usize,
+}
+impl History {
+    pub fn new() -> History {
+        History {
+            visits: vec![],
+            current_position: 0,
+        }
+    }
+
+    /// Record going to a bored, anything that was forward of the current bored is forgotten
+    pub fn visit(&mut self, link: &str) {
+        if self.get_current().is_some_and(|visit| visit.link == link) {
+            return;
+        }
+        self.visits.truncate(self.current_position + 1);
+        self.visits.push(Visit {
+            link: link.to_string(),
+            view_top_left: Coordinate { x: 0, y: 0 },
+        });
+        if self.visits.len() > MAX_HISTORY {
+            self.visits.remove(0);
+        }
+        self.current_position = self.visits.len() - 1;
+    }
+
+    /// Remember where the current bored is being viewed from so it is returned to
+    pub fn set_view(&mut self, view_top_left: Coordinate) {
+        if let Some(visit) = self.visits.get_mut(self.current_position) {
+            visit.view_top_left = view_top_left;
+        }
+    }
+
+    pub fn get_current(&self) -> Option<&Visit> {
+        self.visits.get(self.current_position)
+    }
+
+    pub fn get_visits(&self) -> &Vec<Visit> {
+        &self.visits
+    }
+
+    /// The visit that going in direction would return to, none if at that end of the history
+    pub fn get(&self, direction: HistoryDirection) -> Option<&Visit> {
+        match direction {
+            HistoryDirection::Back => self
+                .current_position
+                .checked_sub(1)
+                .and_then(|position| self.visits.get(position)),
+            HistoryDirection::Forward => self.visits.get(self.current_position + 1),
+        }
+    }
+
+    /// Move through the history, call once the bored returned by get has been gone to
+    pub fn go(&mut self, direction: HistoryDirection) {
+        if self.get(direction).is_none() {
+            return;
+        }
+        match direction {
+            HistoryDirection::Back => self.current_position -= 1,
+            HistoryDirection::Forward => self.current_position += 1,
+        }
+    }
+}
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct Listing {
     pub name: String,
     pub bored_address: String,
+    /// Words to group listings by and filter the directory with
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Tell the user when notices are added to the bored, even while they are on another
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub watched: bool,
 }
 impl Listing {
     pub fn new(name: &str, bored_address: &str) -> Listing {
         Listing {
             name: name.to_string(),
             bored_address: bored_address.to_string(),
+            tags: vec![],
+            note: None,
+            watched: false,
+        }
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// Tags separated by commas, as they are typed in
+    pub fn get_tags_string(&self) -> String {
+        self.tags.join(", ")
+    }
+
+    /// If the search fuzzy matches the name, address or one of the tags
+    pub fn matches(&self, search: &str) -> bool {
+        fuzzy_match(search, &self.name).is_some()
+            || fuzzy_match(search, &self.bored_address).is_some()
+            || self
+                .tags
+                .iter()
+                .any(|tag| fuzzy_match(search, tag).is_some())
+    }
+
+    /// Set the tags from a comma separated list, ignoring blanks and repeats
+    pub fn set_tags_from_str(&mut self, tags: &str) {
+        self.tags = vec![];
+        for tag in tags.split(',').map(str::trim) {
+            if !tag.is_empty() && !self.has_tag(tag) {
+                self.tags.push(tag.to_string());
+            }
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn unescape_html(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Byte position of the needle in the haystack ignoring the case of ascii letters
+fn find_ignore_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack.to_ascii_lowercase().find(needle)
+}
+
+/// Value of a quoted attribute in the inside of an html tag
+fn get_attribute(attributes: &str, name: &str) -> Option<String> {
+    let attributes = format!(" {attributes}");
+    let start = find_ignore_case(&attributes, &format!(" {name}=\""))? + name.len() + 3;
+    let length = attributes[start..].find('"')?;
+    Some(unescape_html(&attributes[start..start + length]))
+}
+
+/// Listings from the links in a Netscape bookmark file, with the tags and description
+/// browsers keep as tags and note
+fn parse_bookmarks(html: &str) -> Directory {
+    let mut directory = Directory::new();
+    let mut rest = html;
+    while let Some(start) = find_ignore_case(rest, "<a ") {
+        rest = &rest[start + 3..];
+        let Some(tag_end) = rest.find('>') else {
+            break;
+        };
+        let attributes = &rest[..tag_end];
+        rest = &rest[tag_end + 1..];
+        let Some(text_end) = find_ignore_case(rest, "</a>") else {
+            break;
+        };
+        let name = unescape_html(rest[..text_end].trim());
+        rest = &rest[text_end + 4..];
+        let Some(address) = get_attribute(attributes, "href").filter(|a| a.starts_with("bored://"))
+        else {
+            continue;
+        };
+        if directory
+            .bored_addresses
+            .iter()
+            .any(|listing| listing.bored_address == address)
+        {
+            continue;
+        }
+        let name = if name.is_empty() { &address } else { &name };
+        let mut listing = Listing::new(name, &address);
+        if let Some(tags) = get_attribute(attributes, "tags") {
+            listing.set_tags_from_str(&tags);
+        }
+        // a description follows the link it belongs to before the next one
+        let next_link = find_ignore_case(rest, "<a ").unwrap_or(rest.len());
+        if let Some(description) = find_ignore_case(&rest[..next_link], "<dd>") {
+            let note = rest[description + 4..]
+                .split(['<', '\n'])
+                .next()
+                .unwrap_or_default()
+                .trim();
+            listing.note = (!note.is_empty()).then(|| unescape_html(note));
+        }
+        directory.bored_addresses.push(listing);
+    }
+    directory
+}
+
+/// Positions of the characters of the text that the search matches, in order and ignoring
+/// case and spaces in the search, or none if it doesn't match
+pub fn fuzzy_match(search: &str, text: &str) -> Option<Vec<usize>> {
+    let mut positions = vec![];
+    let mut text_chars = text.chars().enumerate();
+    for search_char in search.chars().filter(|c| !c.is_whitespace()) {
+        let search_char = search_char.to_lowercase().to_string();
+        let (position, _) = text_chars
+            .by_ref()
+            .find(|(_, c)| c.to_lowercase().to_string() == search_char)?;
+        positions.push(position);
+    }
+    Some(positions)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directory_editing() -> Result<(), SurfBoredError> {
+        let path = std::env::temp_dir()
+            .join(format!("surf-bored-directory-{}.toml", std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        let mut directory = Directory::default();
+        let mut listing = Listing::new("Games", "bored://games");
+        listing.set_tags_from_str(" fun, , games,fun ");
+        assert_eq!(listing.tags, vec!["fun", "games"]);
+        assert_eq!(listing.get_tags_string(), "fun, games");
+        directory.add(listing, &path)?;
+        let mut listing = Listing::new("News", "bored://news");
+        listing.set_tags_from_str("news");
+        listing.note = Some("daily".to_string());
+        directory.add(listing, &path)?;
+        assert_eq!(directory.get_tags(), vec!["fun", "games", "news"]);
+        assert_eq!(directory.get_listing_indexes(Some("games"), ""), vec![1]);
+        assert_eq!(directory.get_listing_indexes(None, ""), vec![0, 1, 2]);
+        assert_eq!(Directory::load_file(&path)?, directory);
+
+        // home follows the listing when it is moved
+        directory.set_home(0);
+        directory.swap(0, 2, &path)?;
+        assert_eq!(directory.get_home(), Some("bored://welcome"));
+        assert_eq!(directory.as_table(Some("news"), "")[0][0], "News");
+        assert!(directory.toggle_watched(0, &path)?);
+        assert_eq!(directory.as_table(Some("news"), "")[0][3], "*");
+        assert_eq!(directory.get_watched()[0].name, "News");
+        assert_eq!(Directory::load_file(&path)?, directory);
+        assert!(!directory.toggle_watched(0, &path)?);
+        assert!(directory.get_watched().is_empty());
+        directory.remove(1, &path)?;
+        assert_eq!(directory.get_home(), Some("bored://welcome"));
+        directory.remove(1, &path)?;
+        assert_eq!(directory.get_home(), Some("bored://news"));
+        assert_eq!(
+            directory.remove(1, &path),
+            Err(SurfBoredError::DirectoryOutOfBounds(1, 1))
+        );
+        let _ = fs::remove_file(path);
+        Ok(())
+    }
+
+    #[test]
+    fn test_export_import() -> Result<(), SurfBoredError> {
+        let mut directory = Directory::default();
+        let mut listing = Listing::new("Fish & <chips>", "bored://chips");
+        listing.set_tags_from_str("food, \"quoted\"");
+        listing.note = Some("Fridays".to_string());
+        directory.bored_addresses.push(listing);
+        for format in [DirectoryFormat::Bookmarks, DirectoryFormat::Json] {
+            let exported = directory.export(format)?;
+            let mut imported = Directory::import(&exported, format)?;
+            imported.home_bored = directory.home_bored;
+            assert_eq!(imported, directory);
+        }
+
+        // as a browser would write it, with links to the old fashioned internet left out
+        let browser = r#"<!DOCTYPE NETSCAPE-Bookmark-file-1>
+<DL><p>
+    <DT><H3>Boreds</H3>
+    <DL><p>
+        <DT><a href="https://example.com" add_date="1">Example</a>
+        <DT><a href="bored://games" add_date="1" tags="fun,games">Games</a>
+        <DD>Best &amp; brightest
+        <DT><A HREF="bored://news"></A>
+    </DL><p>
+</DL><p>"#;
+        let imported = Directory::import(browser, DirectoryFormat::Bookmarks)?;
+        assert_eq!(imported.bored_addresses.len(), 2);
+        assert_eq!(imported.bored_addresses[0].tags, vec!["fun", "games"]);
+        assert_eq!(
+            imported.bored_addresses[0].note.as_deref(),
+            Some("Best & brightest")
+        );
+        assert_eq!(imported.bored_addresses[1].name, "bored://news");
+        assert_eq!(imported.bored_addresses[1].note, None);
+        assert_eq!(
+            DirectoryFormat::from_path(Path::new("friends.HTM")),
+            Ok(DirectoryFormat::Bookmarks)
+        );
+        assert!(DirectoryFormat::from_path(Path::new("friends.txt")).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut remote = Directory::new();
+        let mut games = Listing::new("Games", "bored://games");
+        games.set_tags_from_str("fun");
+        games.note = Some("weekly".to_string());
+        remote.bored_addresses.push(games);
+        remote
+            .bored_addresses
+            .push(Listing::new("News", "bored://news"));
+        remote.home_bored = 1;
+
+        // a fresh directory takes on everything
+        let mut fresh = Directory::default();
+        fresh.merge(&remote);
+        assert_eq!(fresh, remote);
+
+        let mut local = Directory::default();
+        let mut games = Listing::new("My games", "bored://games");
+        games.set_tags_from_str("games");
+        local.bored_addresses.push(games);
+        local.merge(&remote);
+        let names: Vec<&str> = local
+            .bored_addresses
+            .iter()
+            .map(|l| l.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Welcome", "My games", "News"]);
+        assert_eq!(local.bored_addresses[1].tags, vec!["games", "fun"]);
+        assert_eq!(local.bored_addresses[1].note.as_deref(), Some("weekly"));
+        assert_eq!(local.get_home(), Some("bored://welcome"));
+
+        // merging again changes nothing
+        let merged = local.clone();
+        local.merge(&remote);
+        assert_eq!(local, merged);
+    }
+
+    #[test]
+    fn test_fuzzy_match() {
+        assert_eq!(fuzzy_match("", "Games"), Some(vec![]));
+        assert_eq!(fuzzy_match("gms", "Games"), Some(vec![0, 2, 4]));
+        assert_eq!(fuzzy_match("G M", "games"), Some(vec![0, 2]));
+        assert_eq!(fuzzy_match("smg", "Games"), None);
+        let mut listing = Listing::new("News", "bored://daily");
+        listing.set_tags_from_str("world");
+        assert!(listing.matches("nws"));
+        assert!(listing.matches("daily"));
+        assert!(listing.matches("wrld"));
+        assert!(!listing.matches("games"));
+        let mut directory = Directory::default();
+        directory.bored_addresses.push(listing);
+        assert_eq!(directory.get_listing_indexes(None, "bored"), vec![0, 1]);
+        assert_eq!(directory.get_listing_indexes(None, "wld"), vec![1]);
+    }
+
+    #[test]
+    fn test_history() {
+        let mut history = History::new();
+        assert_eq!(history.get(HistoryDirection::Back), None);
+        history.visit("bored://one");
+        history.set_view(Coordinate { x: 5, y: 2 });
+        history.visit("bored://two");
+        // refreshing doesn't add a visit
+        history.visit("bored://two");
+        history.visit("bored://three");
+        let back = history.get(HistoryDirection::Back).cloned();
+        assert_eq!(back.map(|v| v.link), Some("bored://two".to_string()));
+        history.go(HistoryDirection::Back);
+        history.go(HistoryDirection::Back);
+        let current = history.get_current().cloned();
+        assert_eq!(
+            current,
+            Some(Visit {
+                link: "bored://one".to_string(),
+                view_top_left: Coordinate { x: 5, y: 2 },
+            })
+        );
+        assert_eq!(history.get(HistoryDirection::Back), None);
+        history.go(HistoryDirection::Back);
+        history.go(HistoryDirection::Forward);
+        // going somewhere new from two forgets three
+        history.visit("bored://four");
+        assert_eq!(history.get(HistoryDirection::Forward), None);
+        let back = history.get(HistoryDirection::Back).cloned();
+        assert_eq!(back.map(|v| v.link), Some("bored://two".to_string()));
+        for i in 0..MAX_HISTORY * 2 {
+            history.visit(&format!("bored://{i}"));
         }
+        assert_eq!(history.visits.len(), MAX_HISTORY);
+        let current = history.get_current().cloned();
+        assert_eq!(
+            current.map(|v| v.link),
+            Some(format!("bored://{}", MAX_HISTORY * 2 - 1))
+        );
     }
 }
@@ -15,17 +15,26 @@ You should have received a copy of the GNU Affero General Public License
 along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
-use bored::notice::{Notice, NoticeHyperlinkMap, get_display, get_hyperlinks};
-use bored::{Bored, BoredError, BoredHyperlinkMap, Coordinate};
+use bored::notice::{
+    Notice, NoticeHyperlinkMap, NoticeKind, get_display, get_hyperlinks, layout_text,
+};
+use bored::{
+    Background, Bored, BoredError, BoredHyperlinkMap, Coordinate, Coverage, FrameStyle,
+    WhatsOnTheBored,
+};
+use chrono::Local;
 use ratatui::buffer::Buffer;
 use ratatui::{
-    layout::Rect,
-    style::Style,
+    layout::{Position, Rect},
+    style::{Color, Modifier, Style, Stylize},
     text::{Line, Span, Text},
     widgets::{Block, BorderType, Borders, Clear, Paragraph, Widget},
 };
 use std::cmp::min;
+use std::collections::HashSet;
+use std::sync::Arc;
 
+use crate::petnames::Petnames;
 use crate::theme::Theme;
 use crate::ui::safe_subtract_u16;
 
@@ -38,7 +47,7 @@ struct BoredOfRects {
 impl BoredOfRects {
     fn create(bored: &Bored, y_offset: u16) -> BoredOfRects {
         let mut notice_rects = vec![];
-        for notice in bored.get_notices() {
+        for notice in bored.notices() {
             let notice_rect = Rect::new(
                 notice.get_top_left().x,
                 notice.get_top_left().y + y_offset,
@@ -55,8 +64,8 @@ impl BoredOfRects {
     fn get_display_notices(&self, bored: &Bored) -> Result<Vec<(Paragraph, Rect)>, BoredError> {
         let mut display_notices = vec![];
         let notices = bored
-            .get_notices()
-            .into_iter()
+            .notices()
+            .iter()
             .zip(self.notice_rects.clone());
         for (notice, notice_rect) in notices {
             let display = get_display(notice.get_content(), get_hyperlinks(notice.get_content())?);
@@ -69,27 +78,42 @@ impl BoredOfRects {
 }
 
 /// widget that can render the entirety of a bored
-pub struct DisplayBored {
-    bored: Bored,
+pub struct DisplayBored<'a> {
+    bored: &'a Bored,
     theme: Theme,
     selected_notice: Option<usize>,
 }
-impl Widget for DisplayBored {
+impl Widget for DisplayBored<'_> {
     fn render(self, _: Rect, buffer: &mut Buffer) {
         // Render background of bored
+        let background = self.bored.get_background();
+        let (borders, bored_border_type) = match background.get_frame() {
+            FrameStyle::Rounded => (Borders::ALL, BorderType::Rounded),
+            FrameStyle::Plain => (Borders::ALL, BorderType::Plain),
+            FrameStyle::Double => (Borders::ALL, BorderType::Double),
+            FrameStyle::Thick => (Borders::ALL, BorderType::Thick),
+            FrameStyle::None => (Borders::NONE, BorderType::Plain),
+        };
         let bored_block = Block::default()
-            .borders(Borders::ALL)
+            .borders(borders)
             .style(self.theme.text_style())
-            .border_type(BorderType::Rounded);
+            .border_type(bored_border_type);
+        let pattern_area = bored_block.inner(buffer.area);
         bored_block.render(buffer.area, buffer);
-        let bored_of_rects = BoredOfRects::create(&self.bored, 0);
+        render_background_pattern(
+            background,
+            pattern_area,
+            buffer,
+            self.theme.dimmed_text_style(),
+        );
+        let bored_of_rects = BoredOfRects::create(self.bored, 0);
         // uglify for windows terminals that don't support some charaters
         let border_type = if std::env::consts::OS == "windows" {
             BorderType::Thick
         } else {
             BorderType::QuadrantOutside
         };
-        if let Ok(display_notices) = bored_of_rects.get_display_notices(&self.bored) {
+        if let Ok(display_notices) = bored_of_rects.get_display_notices(self.bored) {
             for (notice_index, (display_notice, notice_rect)) in display_notices.iter().enumerate()
             {
                 let (style, border_type) = if Some(notice_index) == self.selected_notice {
@@ -101,54 +125,319 @@ impl Widget for DisplayBored {
                 let block = Block::default()
                     .borders(Borders::ALL)
                     .border_type(border_type);
+                let block = add_event_titles(block, &self.bored.notices()[notice_index]);
                 let display_notice = display_notice.clone().style(style).block(block);
                 Clear.render(*notice_rect, buffer);
                 display_notice.render(*notice_rect, buffer);
             }
             // style hyperlinks
-            style_bored_hyperlinks(&self.bored, buffer, self.theme.hyperlink_style());
+            style_bored_hyperlinks(self.bored, buffer, &self.theme);
         }
     }
 }
 
-impl DisplayBored {
-    pub fn create(bored: &Bored, theme: Theme, selected_notice: Option<usize>) -> DisplayBored {
+/// The notice's block with the title of its event along the top and when and where it is
+/// along the bottom, unchanged if it isn't an event
+pub fn add_event_titles<'a>(block: Block<'a>, notice: &Notice) -> Block<'a> {
+    let (NoticeKind::Event { title, .. }, Some(when)) =
+        (notice.get_kind(), notice.get_kind().describe_event(&Local))
+    else {
+        return block;
+    };
+    block
+        .title_top(Line::from(format!(" {title} ")).bold())
+        .title_bottom(format!(" {when} "))
+}
+
+impl DisplayBored<'_> {
+    pub fn create(bored: &Bored, theme: Theme, selected_notice: Option<usize>) -> DisplayBored<'_> {
         DisplayBored {
-            bored: bored.clone(),
+            bored,
             theme,
             selected_notice,
         }
     }
 }
 
+/// Width and height of the square of a bored each cell of a scaled down map covers so the map
+/// fits within max_dimensions
+fn scale_to_fit(bored_dimensions: Coordinate, max_dimensions: Coordinate) -> u16 {
+    bored_dimensions
+        .x
+        .div_ceil(max_dimensions.x.max(1))
+        .max(bored_dimensions.y.div_ceil(max_dimensions.y.max(1)))
+        .max(1)
+}
+
+/// Widget showing the whole bored scaled down so users can see where they are on big boreds.
+/// Each cell of the map covers a square of the bored, shown as the selected notice if it is in
+/// the square, otherwise as a notice if there is any and dotted if it is in the view.
+pub struct MiniMap {
+    whats_on_the_bored: WhatsOnTheBored,
+    bored_dimensions: Coordinate,
+    view: Rect,
+    selected_notice: Option<usize>,
+    theme: Theme,
+    /// Width and height of the square of the bored each cell of the map covers
+    scale: u16,
+}
+
+impl MiniMap {
+    /// Map of the bored small enough to fit within max_dimensions
+    pub fn create(
+        bored: &Bored,
+        view: Rect,
+        selected_notice: Option<usize>,
+        theme: Theme,
+        max_dimensions: Coordinate,
+    ) -> MiniMap {
+        let bored_dimensions = bored.get_dimensions();
+        let scale = scale_to_fit(bored_dimensions, max_dimensions);
+        MiniMap {
+            whats_on_the_bored: WhatsOnTheBored::create(bored),
+            bored_dimensions,
+            view,
+            selected_notice,
+            theme,
+            scale,
+        }
+    }
+
+    /// Size of the map in cells
+    pub fn get_dimensions(&self) -> Coordinate {
+        Coordinate {
+            x: self.bored_dimensions.x.div_ceil(self.scale),
+            y: self.bored_dimensions.y.div_ceil(self.scale),
+        }
+    }
+
+    fn get_cell(&self, map_x: u16, map_y: u16) -> (char, Style) {
+        let square = Rect::new(map_x * self.scale, map_y * self.scale, self.scale, self.scale);
+        let mut has_notice = false;
+        for y in square.top()..square.bottom() {
+            for x in square.left()..square.right() {
+                match self.whats_on_the_bored.get_at(Coordinate { x, y }) {
+                    Some(notice_index) if Some(notice_index) == self.selected_notice => {
+                        return ('█', self.theme.text_style());
+                    }
+                    Some(_) => has_notice = true,
+                    None => (),
+                }
+            }
+        }
+        let in_view = square.intersects(self.view);
+        match (has_notice, in_view) {
+            (true, true) => ('▒', self.theme.text_style()),
+            (true, false) => ('▒', self.theme.dimmed_text_style()),
+            (false, true) => ('·', self.theme.text_style()),
+            (false, false) => (' ', self.theme.dimmed_text_style()),
+        }
+    }
+}
+
+impl Widget for MiniMap {
+    fn render(self, area: Rect, buffer: &mut Buffer) {
+        let dimensions = self.get_dimensions();
+        for map_y in 0..min(dimensions.y, area.height) {
+            for map_x in 0..min(dimensions.x, area.width) {
+                let (char, style) = self.get_cell(map_x, map_y);
+                if let Some(cell) = buffer.cell_mut((area.x + map_x, area.y + map_y)) {
+                    cell.set_char(char).set_style(style);
+                }
+            }
+        }
+    }
+}
+
+/// Colours notices are drawn in on the overview, repeated when there are more notices
+const OVERVIEW_COLOURS: [Color; 6] = [
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+];
+
+/// Widget showing the entire bored shrunk to fit with each notice as a block of colour, keeps
+/// where it was rendered so clicks can be mapped back to notices
+pub struct Overview {
+    whats_on_the_bored: WhatsOnTheBored,
+    bored_dimensions: Coordinate,
+    selected_notice: Option<usize>,
+    theme: Theme,
+    /// Width and height of the square of the bored each cell of the overview covers
+    scale: u16,
+    screen_area: Rect,
+}
+
+impl Overview {
+    /// Overview of the bored small enough to fit within max_dimensions
+    pub fn create(
+        bored: &Bored,
+        selected_notice: Option<usize>,
+        theme: Theme,
+        max_dimensions: Coordinate,
+    ) -> Overview {
+        let bored_dimensions = bored.get_dimensions();
+        Overview {
+            whats_on_the_bored: WhatsOnTheBored::create(bored),
+            bored_dimensions,
+            selected_notice,
+            theme,
+            scale: scale_to_fit(bored_dimensions, max_dimensions),
+            screen_area: Rect::default(),
+        }
+    }
+
+    /// Size of the overview in cells
+    pub fn get_dimensions(&self) -> Coordinate {
+        Coordinate {
+            x: self.bored_dimensions.x.div_ceil(self.scale),
+            y: self.bored_dimensions.y.div_ceil(self.scale),
+        }
+    }
+
+    pub fn get_scale(&self) -> u16 {
+        self.scale
+    }
+
+    /// Remember where on screen the overview was rendered
+    pub fn set_screen_area(&mut self, screen_area: Rect) {
+        self.screen_area = screen_area;
+    }
+
+    /// The notice shown at a column and row of the screen
+    pub fn get_notice_at(&self, column: u16, row: u16) -> Option<usize> {
+        if !self.screen_area.contains(Position::new(column, row)) {
+            return None;
+        }
+        self.get_notice_in_square(column - self.screen_area.x, row - self.screen_area.y)
+    }
+
+    /// The newest notice in the square of the bored a cell covers, as that is the one most
+    /// likely to be showing
+    fn get_notice_in_square(&self, map_x: u16, map_y: u16) -> Option<usize> {
+        let square = Rect::new(map_x * self.scale, map_y * self.scale, self.scale, self.scale);
+        let mut newest = None;
+        for y in square.top()..square.bottom() {
+            for x in square.left()..square.right() {
+                newest = newest.max(self.whats_on_the_bored.get_at(Coordinate { x, y }));
+            }
+        }
+        newest
+    }
+}
+
+impl Widget for &Overview {
+    fn render(self, area: Rect, buffer: &mut Buffer) {
+        let dimensions = self.get_dimensions();
+        for map_y in 0..min(dimensions.y, area.height) {
+            for map_x in 0..min(dimensions.x, area.width) {
+                let (char, style) = match self.get_notice_in_square(map_x, map_y) {
+                    Some(notice_index) if Some(notice_index) == self.selected_notice => {
+                        ('█', self.theme.text_style())
+                    }
+                    Some(notice_index) => (
+                        '█',
+                        self.theme
+                            .text_style()
+                            .fg(OVERVIEW_COLOURS[notice_index % OVERVIEW_COLOURS.len()]),
+                    ),
+                    None => ('·', self.theme.dimmed_text_style()),
+                };
+                if let Some(cell) = buffer.cell_mut((area.x + map_x, area.y + map_y)) {
+                    cell.set_char(char).set_style(style);
+                }
+            }
+        }
+    }
+}
+
 /// Widget to display a part of the bored that can fit in the ui depending on the terminal size
 /// with methods to move the view about the bored if it can't all be seen at once
 #[derive(Debug)]
 pub struct BoredViewPort {
-    bored: Bored,
+    /// Shared with the client rather than copied
+    bored: Arc<Bored>,
     bored_rect: Rect,
     // bored_dimensions: Coordinate,
     view_top_left: Coordinate,
     view_dimensions: Coordinate,
     buffer: Buffer,
     selected_notice: Option<usize>,
+    /// Theme the whole bored in the buffer was rendered with, none if it needs rendering again
+    rendered_theme: Option<Theme>,
+    /// Notice drawn over the ones covering it, for peeking under them
+    raised_notice: Option<usize>,
+    /// Links found to go nowhere when the links on the bored were checked, crossed out
+    dead_links: HashSet<String>,
+    /// Where on screen the view was last rendered, for finding what was clicked
+    screen_area: Rect,
 }
 
 impl BoredViewPort {
     pub fn create(
-        bored: &Bored,
+        bored: impl Into<Arc<Bored>>,
         view_dimensions: Coordinate,
         selected_notice: Option<usize>,
     ) -> BoredViewPort {
+        let bored = bored.into();
         let bored_rect = Rect::new(0, 0, bored.get_dimensions().x, bored.get_dimensions().y);
         BoredViewPort {
-            bored: bored.clone(),
+            bored,
             bored_rect,
             // bored_dimensions: bored.get_dimensions(),
             view_top_left: Coordinate { x: 0, y: 0 },
             view_dimensions,
             buffer: Buffer::empty(bored_rect),
             selected_notice,
+            rendered_theme: None,
+            raised_notice: None,
+            dead_links: HashSet::new(),
+            screen_area: Rect::default(),
+        }
+    }
+
+    /// Show the bored with the notice selected in a view of the dimensions, keeping where the
+    /// view is. The whole bored is only rendered again if the bored or selection has changed,
+    /// so frames where only the view moves just copy what is in view.
+    pub fn update(
+        &mut self,
+        bored: &Arc<Bored>,
+        view_dimensions: Coordinate,
+        selected_notice: Option<usize>,
+    ) {
+        // the client hands out the same bored until it changes so most frames stop at ptr_eq
+        if !Arc::ptr_eq(bored, &self.bored) && **bored != *self.bored {
+            self.bored_rect = Rect::new(0, 0, bored.get_dimensions().x, bored.get_dimensions().y);
+            if self.buffer.area != self.bored_rect {
+                self.buffer = Buffer::empty(self.bored_rect);
+            }
+            self.rendered_theme = None;
+        }
+        self.bored = bored.clone();
+        if selected_notice != self.selected_notice {
+            self.selected_notice = selected_notice;
+            self.rendered_theme = None;
+        }
+        self.view_dimensions = view_dimensions;
+    }
+
+    /// Draw the notice at the index on top of the others without changing the bored, none to
+    /// show the bored as it is
+    pub fn raise_notice(&mut self, raised_notice: Option<usize>) {
+        if raised_notice != self.raised_notice {
+            self.raised_notice = raised_notice;
+            self.rendered_theme = None;
+        }
+    }
+
+    /// Cross out the links on the bored that go nowhere
+    pub fn mark_dead_links(&mut self, dead_links: HashSet<String>) {
+        if dead_links != self.dead_links {
+            self.dead_links = dead_links;
+            self.rendered_theme = None;
         }
     }
 
@@ -168,10 +457,159 @@ impl BoredViewPort {
         }
     }
 
+    /// Moves the view by an offset, stopping at the edges of the bored
+    pub fn scroll(&mut self, offset: (i32, i32)) {
+        let max_top_left = Coordinate {
+            x: safe_subtract_u16(self.bored_rect.width, self.view_dimensions.x),
+            y: safe_subtract_u16(self.bored_rect.height, self.view_dimensions.y),
+        };
+        let x = (self.view_top_left.x as i32).saturating_add(offset.0);
+        let y = (self.view_top_left.y as i32).saturating_add(offset.1);
+        self.move_view(Coordinate {
+            x: x.clamp(0, max_top_left.x as i32) as u16,
+            y: y.clamp(0, max_top_left.y as i32) as u16,
+        });
+    }
+
+    /// The coordinate on the bored shown at a column and row of the screen, none if that isn't
+    /// part of the bored
+    pub fn get_bored_coordinate(&self, column: u16, row: u16) -> Option<Coordinate> {
+        if !self.screen_area.contains(Position::new(column, row)) {
+            return None;
+        }
+        let coordinate = Coordinate {
+            x: column - self.screen_area.x + self.view_top_left.x,
+            y: row - self.screen_area.y + self.view_top_left.y,
+        };
+        if coordinate.x < self.bored_rect.width && coordinate.y < self.bored_rect.height {
+            Some(coordinate)
+        } else {
+            None
+        }
+    }
+
     pub fn get_view_top_left(&self) -> Coordinate {
         self.view_top_left
     }
 
+    /// Move the view so the coordinate is in the middle of it, as near as the edges allow
+    pub fn centre_on(&mut self, coordinate: Coordinate) {
+        let middle = self.view_top_left.add(&Coordinate {
+            x: self.view_dimensions.x / 2,
+            y: self.view_dimensions.y / 2,
+        });
+        self.scroll((
+            coordinate.x as i32 - middle.x as i32,
+            coordinate.y as i32 - middle.y as i32,
+        ));
+    }
+
+    /// Move the view back within the edges of the bored, as after the terminal grows it can
+    /// show past them
+    pub fn keep_on_bored(&mut self) {
+        self.scroll((0, 0));
+    }
+
+    /// Restyle the characters at the coordinates on the bored that are in view, after the view
+    /// has been rendered to the buffer
+    pub fn highlight(&self, buffer: &mut Buffer, coordinates: &[Coordinate], style: Style) {
+        for coordinate in coordinates {
+            if !self
+                .get_view()
+                .contains(Position::new(coordinate.x, coordinate.y))
+            {
+                continue;
+            }
+            let column = coordinate.x - self.view_top_left.x + self.screen_area.x;
+            let row = coordinate.y - self.view_top_left.y + self.screen_area.y;
+            if self.screen_area.contains(Position::new(column, row))
+                && let Some(cell) = buffer.cell_mut((column, row))
+            {
+                cell.set_style(style);
+            }
+        }
+    }
+
+    /// Mark every so many columns and rows of the bored in view with faint dots where no
+    /// notice covers them, after the view has been rendered to the buffer
+    pub fn render_grid(&self, buffer: &mut Buffer, spacing: u16, style: Style) {
+        let view = self.get_view().intersection(self.bored_rect);
+        let notice_rects: Vec<Rect> = self
+            .bored
+            .notices()
+            .iter()
+            .map(|notice| {
+                let top_left = notice.get_top_left();
+                let dimensions = notice.get_dimensions();
+                Rect::new(top_left.x, top_left.y, dimensions.x, dimensions.y)
+            })
+            .collect();
+        for position in view.positions() {
+            if position.x % spacing != 0 && position.y % spacing != 0 {
+                continue;
+            }
+            if notice_rects.iter().any(|rect| rect.contains(position)) {
+                continue;
+            }
+            let column = position.x - self.view_top_left.x + self.screen_area.x;
+            let row = position.y - self.view_top_left.y + self.screen_area.y;
+            if self.screen_area.contains(Position::new(column, row))
+                && let Some(cell) = buffer.cell_mut((column, row))
+            {
+                cell.set_symbol("·").set_style(style);
+            }
+        }
+    }
+
+    /// Restyle what shows of the covered notices in view, fully covered ones in their own style
+    pub fn shade_covered(
+        &self,
+        buffer: &mut Buffer,
+        covered: &[(usize, Coverage)],
+        partial_style: Style,
+        full_style: Style,
+    ) {
+        let whats_on_the_bored = WhatsOnTheBored::create(&self.bored);
+        for position in self.get_view().intersection(self.bored_rect).positions() {
+            let coordinate = Coordinate {
+                x: position.x,
+                y: position.y,
+            };
+            let Some(notice_index) = whats_on_the_bored.get_at(coordinate) else {
+                continue;
+            };
+            let Some((_, coverage)) = covered.iter().find(|(index, _)| *index == notice_index)
+            else {
+                continue;
+            };
+            let style = match coverage {
+                Coverage::Partial => partial_style,
+                Coverage::Full => full_style,
+            };
+            self.highlight(buffer, &[coordinate], style);
+        }
+    }
+
+    /// Restyle what shows of the notices at the indexes that are in view
+    pub fn shade_notices(&self, buffer: &mut Buffer, notice_indexes: &[usize], style: Style) {
+        if notice_indexes.is_empty() {
+            return;
+        }
+        let whats_on_the_bored = WhatsOnTheBored::create(&self.bored);
+        for position in self.get_view().intersection(self.bored_rect).positions() {
+            let coordinate = Coordinate {
+                x: position.x,
+                y: position.y,
+            };
+            if whats_on_the_bored
+                .get_at(coordinate)
+                .is_some_and(|notice_index| notice_indexes.contains(&notice_index))
+            {
+                self.highlight(buffer, &[coordinate], style);
+            }
+        }
+    }
+
     /// Get rect that is position and size of view
     pub fn get_view(&self) -> Rect {
         Rect::new(
@@ -191,6 +629,7 @@ impl BoredViewPort {
     pub fn render_view(&mut self, buffer: &mut Buffer, theme: Theme) {
         let view_rect = self.get_view();
         let buffer_rect = buffer.area().clone();
+        self.screen_area = buffer_rect;
         let x_limit = view_rect.x
             + min(
                 view_rect.width,
@@ -201,9 +640,35 @@ impl BoredViewPort {
                 view_rect.height,
                 min(buffer_rect.height, self.bored_rect.height - view_rect.y),
             );
-        let display_bored = DisplayBored::create(&self.bored, theme.clone(), self.selected_notice);
-        display_bored.render(self.bored_rect, &mut self.buffer);
-        let bored_content = self.buffer.content.clone();
+        if self.rendered_theme.as_ref() != Some(&theme) {
+            self.buffer.reset();
+            let rendered = match self.raised_notice {
+                Some(raised_notice) => {
+                    let bored = self.bored.with_notice_raised(raised_notice);
+                    // the raised notice is now last and those after it have moved down one
+                    let selected_notice =
+                        self.selected_notice
+                            .map(|selected| match selected.cmp(&raised_notice) {
+                                std::cmp::Ordering::Equal => bored.notices().len() - 1,
+                                std::cmp::Ordering::Greater => selected - 1,
+                                std::cmp::Ordering::Less => selected,
+                            });
+                    DisplayBored::create(&bored, theme.clone(), selected_notice)
+                        .render(self.bored_rect, &mut self.buffer);
+                    Arc::new(bored)
+                }
+                None => {
+                    DisplayBored::create(&self.bored, theme.clone(), self.selected_notice)
+                        .render(self.bored_rect, &mut self.buffer);
+                    self.bored.clone()
+                }
+            };
+            if !self.dead_links.is_empty() {
+                cross_out_bored_hyperlinks(&rendered, &mut self.buffer, &self.dead_links);
+            }
+            self.rendered_theme = Some(theme);
+        }
+        let bored_content = &self.buffer.content;
         for x in view_rect.x..x_limit {
             let buffer_x = x - view_rect.x + buffer_rect.x;
             for y in view_rect.y..y_limit {
@@ -239,30 +704,50 @@ impl BoredViewPort {
     }
 }
 
-/// Wrap text on a character basis so word can be on mutiple lines using ratatui text hierachy
+/// Fill the area of the buffer with the repeating background pattern of a bored
+fn render_background_pattern(
+    background: &Background,
+    area: Rect,
+    buffer: &mut Buffer,
+    style: Style,
+) {
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            if let Some(char) = background.get_char_at(Coordinate { x, y })
+                && let Some(cell) = buffer.cell_mut((x, y))
+            {
+                cell.set_char(char).set_style(style);
+            }
+        }
+    }
+}
+
+/// Wrap text on a character basis so word can be on mutiple lines using ratatui text hierachy,
+/// laid out by display width the same as the hyperlink maps so wide characters line up
 pub fn character_wrap(display_text: String, line_width: u16) -> Text<'static> {
-    let mut lines = vec![];
-    let mut line = Line::raw("");
-    let mut line_char_index = 0;
-    for char in display_text.chars() {
-        // if line_char % line_width as usize == 0 && char_index > 0 {
-        if char == '\n' {
-            lines.push(line);
-            line = Line::raw("");
-            line_char_index = 0;
-        } else if line_char_index < line_width {
-            line.push_span(Span::raw(char.to_string()));
-            line_char_index += 1;
-        } else {
-            lines.push(line);
-            line = Line::raw("");
-            line_char_index = 0;
-            line.push_span(Span::raw(char.to_string()));
-            line_char_index += 1;
+    let mut lines = vec![String::new()];
+    for (char, position) in display_text
+        .chars()
+        .zip(layout_text(&display_text, line_width))
+    {
+        if let Some(position) = position {
+            while lines.len() <= position.y as usize {
+                lines.push(String::new());
+            }
+            // one string per line so combining marks join the character before them
+            lines[position.y as usize].push(char);
         }
     }
-    lines.push(line);
-    Text::from_iter(lines)
+    Text::from_iter(lines.into_iter().map(Line::raw))
+}
+
+/// Style of each hyperlink of a notice by where it goes
+fn get_hyperlink_styles(notice: &Notice, theme: &Theme) -> Vec<Style> {
+    get_hyperlinks(notice.get_content())
+        .unwrap_or_default()
+        .iter()
+        .map(|hyperlink| theme.get_hyperlink_style(&hyperlink.get_link()))
+        .collect()
 }
 
 /// Add hyperlink format to the buffer of notice
@@ -270,16 +755,18 @@ pub fn style_notice_hyperlinks(
     notice: &Notice,
     buffer: &mut Buffer,
     offset: Coordinate,
-    hyperlink_style: Style,
+    theme: &Theme,
 ) {
+    let hyperlink_styles = get_hyperlink_styles(notice, theme);
     if let Ok(notice_hyperlink_map) = NoticeHyperlinkMap::create(&notice) {
         for (mut y, row) in notice_hyperlink_map.get_map().iter().enumerate() {
             y = y + offset.y as usize + 1; // + 1 as the buffer will have a border
             for (mut x, char) in row.iter().enumerate() {
                 x = x + offset.x as usize + 1; // as the buffer will have a border
-                if char.is_some() {
+                if let Some(hyperlink_index) = char {
                     if let Some(cell) = buffer.cell_mut((x as u16, y as u16)) {
-                        cell.set_style(hyperlink_style);
+                        let hyperlink_style = hyperlink_styles.get(*hyperlink_index);
+                        cell.set_style(*hyperlink_style.unwrap_or(&theme.hyperlink_style()));
                     }
                 }
             }
@@ -287,16 +774,160 @@ pub fn style_notice_hyperlinks(
     }
 }
 
+/// Label the first nine hyperlinks of the buffer of a notice with the number that opens them,
+/// put in the cell before each so the text of the hyperlink is left whole
+pub fn label_notice_hyperlinks(
+    notice: &Notice,
+    buffer: &mut Buffer,
+    offset: Coordinate,
+    label_style: Style,
+) {
+    let Ok(notice_hyperlink_map) = NoticeHyperlinkMap::create(notice) else {
+        return;
+    };
+    let mut labelled = HashSet::new();
+    for (y, row) in notice_hyperlink_map.get_map().iter().enumerate() {
+        let y = y + offset.y as usize + 1; // + 1 as the buffer will have a border
+        for (x, hyperlink_index) in row.iter().enumerate() {
+            // the cell before the first character, the border if it starts a line
+            let x = x + offset.x as usize;
+            if let Some(hyperlink_index) = hyperlink_index
+                && *hyperlink_index < 9
+                && labelled.insert(*hyperlink_index)
+                && let Some(cell) = buffer.cell_mut((x as u16, y as u16))
+            {
+                cell.set_symbol(&(hyperlink_index + 1).to_string())
+                    .set_style(label_style);
+            }
+        }
+    }
+}
+
+/// Indexes of the notices of a bored in reading order, top to bottom then left to right
+pub fn get_reading_order(bored: &Bored) -> Vec<usize> {
+    let notices = bored.notices();
+    let mut order: Vec<usize> = (0..notices.len()).collect();
+    order.sort_by_key(|i| {
+        let top_left = notices[*i].get_top_left();
+        (top_left.y, top_left.x)
+    });
+    order
+}
+
+/// Indexes of the notices of a bored newest first, by when they were posted and then by the
+/// order they were added
+pub fn get_recency_order(bored: &Bored) -> Vec<usize> {
+    let notices = bored.notices();
+    let mut order: Vec<usize> = (0..notices.len()).collect();
+    order.sort_by_key(|i| std::cmp::Reverse((notices[*i].get_timestamp(), *i)));
+    order
+}
+
+/// How much of each notice of a bored shows from under those over it, as a percentage
+pub fn get_percent_showing(bored: &Bored) -> Vec<u8> {
+    let mut showing = vec![0usize; bored.notices().len()];
+    let whats_on_the_bored = WhatsOnTheBored::create(bored);
+    for notice_index in whats_on_the_bored.get_1d().into_iter().flatten() {
+        showing[notice_index] += 1;
+    }
+    bored
+        .notices()
+        .iter()
+        .zip(showing)
+        .map(|(notice, showing)| {
+            let dimensions = notice.get_dimensions();
+            let area = dimensions.x as usize * dimensions.y as usize;
+            (showing * 100).checked_div(area).unwrap_or(0) as u8
+        })
+        .collect()
+}
+
+/// The notices of a bored as plain lines for screen readers, in reading order starting from
+/// the selected notice. Each notice is a title row, naming who posted it by the name they were
+/// given, its text and then its hyperlinks, without any box drawing so only the content is
+/// read out.
+pub fn get_linear_lines(
+    bored: &Bored,
+    selected_notice: Option<usize>,
+    theme: &Theme,
+    petnames: &Petnames,
+) -> Result<Vec<Line<'static>>, BoredError> {
+    let order = get_reading_order(bored);
+    let notices = bored.notices();
+    let start = selected_notice
+        .and_then(|selected| order.iter().position(|i| *i == selected))
+        .unwrap_or(0);
+    let mut lines = vec![];
+    for (position, notice_index) in order.iter().enumerate().skip(start) {
+        let notice = &notices[*notice_index];
+        let top_left = notice.get_top_left();
+        let mut title = format!(
+            "Notice {} of {}, {} across and {} down",
+            position + 1,
+            order.len(),
+            top_left.x,
+            top_left.y
+        );
+        if let Some(attachment) = notice.get_attachment() {
+            title.push_str(&format!(", with the file {}", attachment.get_filename()));
+        }
+        if let Some(author) = petnames.describe_author(notice) {
+            title.push_str(&format!(", posted by {author}"));
+        }
+        if let (NoticeKind::Event { title: event, .. }, Some(when)) =
+            (notice.get_kind(), notice.get_kind().describe_event(&Local))
+        {
+            title.push_str(&format!(", the event {event} on {when}"));
+        }
+        let title_style = if Some(*notice_index) == selected_notice {
+            theme.inverted_text_style()
+        } else {
+            theme.text_style()
+        };
+        lines.push(Line::styled(title, title_style.bold()));
+        let hyperlinks = get_hyperlinks(notice.get_content())?;
+        let display_text = get_display(notice.get_content(), hyperlinks.clone()).get_display_text();
+        if display_text.trim().is_empty() {
+            lines.push(Line::styled("No text", theme.dimmed_text_style()));
+        }
+        for line in display_text.lines() {
+            lines.push(Line::styled(line.to_string(), theme.text_style()));
+        }
+        for (i, hyperlink) in hyperlinks.iter().enumerate() {
+            lines.push(Line::from(vec![
+                Span::styled(format!("Link {}: ", i + 1), theme.text_style()),
+                Span::styled(
+                    hyperlink.get_text(),
+                    theme
+                        .text_style()
+                        .patch(theme.get_hyperlink_style(&hyperlink.get_link())),
+                ),
+                Span::styled(format!(", {}", hyperlink.get_link()), theme.text_style()),
+            ]));
+        }
+        lines.push(Line::default());
+    }
+    Ok(lines)
+}
+
 /// Add notice hyperlinks to buffer of bored
-pub fn style_bored_hyperlinks(bored: &Bored, buffer: &mut Buffer, hyperlink_style: Style) {
+pub fn style_bored_hyperlinks(bored: &Bored, buffer: &mut Buffer, theme: &Theme) {
+    let hyperlink_styles: Vec<Vec<Style>> = bored
+        .notices()
+        .iter()
+        .map(|notice| get_hyperlink_styles(notice, theme))
+        .collect();
     if let Ok(bored_hyperlink_map) = BoredHyperlinkMap::create(&bored) {
         for (y, row) in bored_hyperlink_map.get_map().iter().enumerate() {
             // y += 1;
             for (x, char) in row.iter().enumerate() {
                 // x += 1;
-                if char.is_some() {
+                if let Some((notice_index, hyperlink_index)) = char {
                     if let Some(cell) = buffer.cell_mut((x as u16, y as u16)) {
-                        cell.set_style(hyperlink_style);
+                        let hyperlink_style = hyperlink_styles
+                            .get(*notice_index)
+                            .and_then(|styles| styles.get(*hyperlink_index));
+                        cell.set_style(*hyperlink_style.unwrap_or(&theme.hyperlink_style()));
                     }
                 }
             }
@@ -304,12 +935,44 @@ pub fn style_bored_hyperlinks(bored: &Bored, buffer: &mut Buffer, hyperlink_styl
     }
 }
 
+/// Cross out the hyperlinks of the bored in the buffer that go to any of the links
+pub fn cross_out_bored_hyperlinks(bored: &Bored, buffer: &mut Buffer, links: &HashSet<String>) {
+    let crossed_out: Vec<Vec<bool>> = bored
+        .notices()
+        .iter()
+        .map(|notice| {
+            get_hyperlinks(notice.get_content())
+                .unwrap_or_default()
+                .iter()
+                .map(|hyperlink| links.contains(&hyperlink.get_link()))
+                .collect()
+        })
+        .collect();
+    let Ok(bored_hyperlink_map) = BoredHyperlinkMap::create(bored) else {
+        return;
+    };
+    for (y, row) in bored_hyperlink_map.get_map().iter().enumerate() {
+        for (x, char) in row.iter().enumerate() {
+            if let Some((notice_index, hyperlink_index)) = char
+                && crossed_out
+                    .get(*notice_index)
+                    .and_then(|links| links.get(*hyperlink_index))
+                    .is_some_and(|crossed_out| *crossed_out)
+                && let Some(cell) = buffer.cell_mut((x as u16, y as u16))
+            {
+                cell.modifier.insert(Modifier::CROSSED_OUT);
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 
 mod tests {
 
     use bored::notice::Notice;
     use ratatui::prelude::Stylize;
+    use ratatui::style::Modifier;
 
     use crate::app::SurfBoredError;
 
@@ -403,12 +1066,14 @@ mod tests {
 }"#;
         assert_eq!(expected_output, format!("{:?}", buffer));
         // just test view port with 100% view so should be the same as above
-        let mut bored_view_port = BoredViewPort::create(&bored, Coordinate { x: 60, y: 20 }, None);
+        let mut bored_view_port =
+            BoredViewPort::create(bored.clone(), Coordinate { x: 60, y: 20 }, None);
         let bored_rect = Rect::new(0, 0, bored.get_dimensions().x, bored.get_dimensions().y);
         buffer = Buffer::empty(bored_rect);
         bored_view_port.render_view(&mut buffer, theme.clone());
         assert_eq!(expected_output, format!("{:?}", buffer));
-        let mut bored_view_port = BoredViewPort::create(&bored, Coordinate { x: 40, y: 15 }, None);
+        let mut bored_view_port =
+            BoredViewPort::create(bored.clone(), Coordinate { x: 40, y: 15 }, None);
         bored_view_port.move_view(Coordinate { x: 5, y: 5 });
         let bored_rect = Rect::new(5, 5, 40, 15);
         buffer = Buffer::empty(bored_rect);
@@ -464,9 +1129,23 @@ line"#;
         eprintln!("\n{}", text);
     }
 
+    #[test]
+    fn test_wide_character_wrap() {
+        // the emoji doesn't fit after the e so starts the next line
+        let text = character_wrap("日本abcde😀e\u{301}".to_string(), 5);
+        assert_eq!("日本a\nbcde\n😀e\u{301}", format!("{}", text));
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 5, 3));
+        Paragraph::new(text).render(buffer.area, &mut buffer);
+        assert_eq!(buffer.cell((4, 0)).map(|cell| cell.symbol()), Some("a"));
+        assert_eq!(buffer.cell((0, 2)).map(|cell| cell.symbol()), Some("😀"));
+        assert_eq!(
+            buffer.cell((2, 2)).map(|cell| cell.symbol()),
+            Some("e\u{301}")
+        );
+    }
+
     #[test]
     fn test_style_notice_hyperlinks() -> Result<(), SurfBoredError> {
-        let hyperlink_style = Style::new().underlined();
         let mut notice = Notice::create(Coordinate { x: 30, y: 9 });
         notice.write(
             "We are [link](url) [bored](url).\nYou are [link](url) bored.\nI am [boooo\nooored](url).\nHello\nWorld",
@@ -484,7 +1163,7 @@ line"#;
             &notice,
             &mut notice_buffer,
             Coordinate { x: 0, y: 0 },
-            hyperlink_style,
+            &Theme::default(),
         );
         let expected_output = r#"Buffer {
     area: Rect { x: 0, y: 0, width: 30, height: 9 },
@@ -518,6 +1197,64 @@ line"#;
         Ok(())
     }
 
+    #[test]
+    fn test_label_notice_hyperlinks() -> Result<(), SurfBoredError> {
+        let mut notice = Notice::create(Coordinate { x: 30, y: 6 });
+        notice.write("We are [link](url) [bored](url).\n[boooo\nooored](url)")?;
+        let notice_rect = Rect::new(0, 0, 30, 6);
+        let notice_text = Paragraph::new(character_wrap(
+            notice.get_display()?.get_display_text(),
+            notice.get_text_width(),
+        ))
+        .block(Block::default().borders(Borders::ALL));
+        let mut notice_buffer = Buffer::empty(notice_rect);
+        notice_text.render(notice_rect, &mut notice_buffer);
+        label_notice_hyperlinks(
+            &notice,
+            &mut notice_buffer,
+            Coordinate { x: 0, y: 0 },
+            Style::new(),
+        );
+        let rows: Vec<String> = (1..4)
+            .map(|y| {
+                (0..30)
+                    .map(|x| notice_buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect();
+        // a hyperlink that wraps is only labelled where it starts
+        assert_eq!(
+            rows,
+            vec![
+                "│We are1link2bored.          │",
+                "3boooo                       │",
+                "│ooored                      │",
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_cross_out_bored_hyperlinks() -> Result<(), SurfBoredError> {
+        let mut bored = Bored::create("Links", Coordinate { x: 40, y: 10 });
+        let bored_rect = Rect::new(0, 0, bored.get_dimensions().x, bored.get_dimensions().y);
+        let mut notice = Notice::create(Coordinate { x: 30, y: 5 });
+        notice.write("[dead](bored://gone) [live](https://example.com)")?;
+        bored.add(notice, Coordinate { x: 5, y: 3 })?;
+        let mut bored_buffer = Buffer::empty(bored_rect);
+        DisplayBored::create(&bored, Theme::default(), None).render(bored_rect, &mut bored_buffer);
+        let dead_links = HashSet::from(["bored://gone".to_string()]);
+        cross_out_bored_hyperlinks(&bored, &mut bored_buffer, &dead_links);
+        for x in 6..10 {
+            let modifier = bored_buffer[(x, 4)].modifier;
+            assert!(modifier.contains(Modifier::CROSSED_OUT | Modifier::UNDERLINED));
+        }
+        for x in 10..15 {
+            assert!(!bored_buffer[(x, 4)].modifier.contains(Modifier::CROSSED_OUT));
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_style_bored_hyperlinks() -> Result<(), SurfBoredError> {
         let theme = Theme::default();
@@ -607,4 +1344,335 @@ line"#;
         assert_eq!(expected_output, format!("{:?}", bored_buffer));
         Ok(())
     }
+
+    #[test]
+    fn test_display_bored_background() -> Result<(), BoredError> {
+        let mut bored = Bored::create("Hello", Coordinate { x: 12, y: 6 });
+        bored.set_background(Background::create(".:", FrameStyle::Double)?);
+        let notice = Notice::create(Coordinate { x: 5, y: 3 });
+        bored.add(notice, Coordinate { x: 5, y: 2 })?;
+        let bored_rect = Rect::new(0, 0, bored.get_dimensions().x, bored.get_dimensions().y);
+        let mut buffer = Buffer::empty(bored_rect);
+        DisplayBored::create(&bored, Theme::default(), None).render(bored_rect, &mut buffer);
+        let expected_output = Buffer::with_lines(vec![
+            "╔══════════╗",
+            "║.:.:.:.:.:║",
+            "║:.:.┏━━━┓.║",
+            "║.:.:┃   ┃:║",
+            "║:.:.┗━━━┛.║",
+            "╚══════════╝",
+        ]);
+        assert_eq!(
+            format!("{:?}", expected_output.content),
+            format!("{:?}", buffer.content)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_view_port_mouse_coordinates() {
+        let bored = Bored::create("Hello", Coordinate { x: 60, y: 20 });
+        let mut view_port = BoredViewPort::create(bored.clone(), Coordinate { x: 20, y: 10 }, None);
+        // nothing has been rendered so nothing on screen is part of the bored
+        assert_eq!(view_port.get_bored_coordinate(0, 0), None);
+        let mut buffer = Buffer::empty(Rect::new(0, 4, 20, 10));
+        view_port.render_view(&mut buffer, Theme::default());
+        view_port.scroll((5, 3));
+        assert_eq!(view_port.get_view_top_left(), Coordinate { x: 5, y: 3 });
+        assert_eq!(
+            view_port.get_bored_coordinate(2, 6),
+            Some(Coordinate { x: 7, y: 5 })
+        );
+        assert_eq!(view_port.get_bored_coordinate(2, 3), None);
+        // can't scroll past the edges of the bored
+        view_port.scroll((100, 100));
+        assert_eq!(view_port.get_view_top_left(), Coordinate { x: 40, y: 10 });
+        view_port.scroll((-100, -100));
+        assert_eq!(view_port.get_view_top_left(), Coordinate { x: 0, y: 0 });
+        view_port.scroll((i32::MAX, i32::MAX));
+        assert_eq!(view_port.get_view_top_left(), Coordinate { x: 40, y: 10 });
+    }
+
+    #[test]
+    fn test_view_port_search_highlight() {
+        let bored = Bored::create("Hello", Coordinate { x: 60, y: 20 });
+        let mut view_port = BoredViewPort::create(bored.clone(), Coordinate { x: 20, y: 10 }, None);
+        view_port.centre_on(Coordinate { x: 30, y: 10 });
+        assert_eq!(view_port.get_view_top_left(), Coordinate { x: 20, y: 5 });
+        // stops at the edges of the bored
+        view_port.centre_on(Coordinate { x: 2, y: 19 });
+        assert_eq!(view_port.get_view_top_left(), Coordinate { x: 0, y: 10 });
+
+        let mut buffer = Buffer::empty(Rect::new(0, 4, 20, 10));
+        view_port.render_view(&mut buffer, Theme::default());
+        let style = Style::new().underlined();
+        let coordinates = [
+            Coordinate { x: 3, y: 12 },
+            // out of view
+            Coordinate { x: 3, y: 2 },
+            Coordinate { x: 25, y: 12 },
+        ];
+        view_port.highlight(&mut buffer, &coordinates, style);
+        let highlighted: Vec<(u16, u16)> = buffer
+            .area
+            .positions()
+            .filter(|p| buffer[*p].modifier.contains(Modifier::UNDERLINED))
+            .map(|p| (p.x, p.y))
+            .collect();
+        assert_eq!(highlighted, vec![(3, 6)]);
+    }
+
+    #[test]
+    fn test_view_port_keep_on_bored() {
+        let bored = Bored::create("Hello", Coordinate { x: 60, y: 30 });
+        // scrolled to the bottom right then the terminal got bigger
+        let mut view_port = BoredViewPort::create(bored.clone(), Coordinate { x: 40, y: 20 }, None);
+        view_port.move_view(Coordinate { x: 40, y: 20 });
+        view_port.keep_on_bored();
+        assert_eq!(view_port.get_view_top_left(), Coordinate { x: 20, y: 10 });
+        // bigger than the bored so it all fits
+        let mut view_port = BoredViewPort::create(bored.clone(), Coordinate { x: 80, y: 40 }, None);
+        view_port.move_view(Coordinate { x: 10, y: 5 });
+        view_port.keep_on_bored();
+        assert_eq!(view_port.get_view_top_left(), Coordinate { x: 0, y: 0 });
+    }
+
+    #[test]
+    fn test_view_port_renders_bored_again_on_change() -> Result<(), BoredError> {
+        let mut bored = Bored::create("Hello", Coordinate { x: 60, y: 20 });
+        bored.add(
+            Notice::create(Coordinate { x: 8, y: 4 }),
+            Coordinate { x: 2, y: 2 },
+        )?;
+        let view_dimensions = Coordinate { x: 20, y: 10 };
+        let shared = Arc::new(bored.clone());
+        let mut view_port = BoredViewPort::create(shared.clone(), view_dimensions, None);
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 20, 10));
+        view_port.render_view(&mut buffer, Theme::default());
+        let rendered = buffer.clone();
+        // mark the kept render so it shows whether it was rendered again
+        view_port.buffer[(0, 0)].set_symbol("X");
+
+        // the same bored is copied from what was kept, even after moving the view
+        view_port.update(&shared, view_dimensions, None);
+        view_port.scroll((1, 0));
+        view_port.scroll((-1, 0));
+        view_port.render_view(&mut buffer, Theme::default());
+        assert_eq!(buffer[(0, 0)].symbol(), "X");
+
+        // as is a copy of the same bored
+        view_port.update(&Arc::new(bored.clone()), view_dimensions, None);
+        view_port.render_view(&mut buffer, Theme::default());
+        assert_eq!(buffer[(0, 0)].symbol(), "X");
+
+        // selecting a notice renders it again
+        view_port.update(&shared, view_dimensions, Some(0));
+        view_port.render_view(&mut buffer, Theme::default());
+        assert_ne!(buffer[(0, 0)].symbol(), "X");
+        assert_ne!(buffer, rendered);
+        view_port.buffer[(0, 0)].set_symbol("X");
+
+        // as does a change to the bored, keeping the view where it was
+        view_port.scroll((5, 0));
+        bored.add(
+            Notice::create(Coordinate { x: 8, y: 4 }),
+            Coordinate { x: 30, y: 10 },
+        )?;
+        view_port.update(&Arc::new(bored), view_dimensions, Some(0));
+        assert_eq!(view_port.get_view_top_left(), Coordinate { x: 5, y: 0 });
+        view_port.render_view(&mut buffer, Theme::default());
+        assert_ne!(view_port.buffer[(0, 0)].symbol(), "X");
+        Ok(())
+    }
+
+    #[test]
+    fn test_view_port_raises_notice() -> Result<(), BoredError> {
+        let mut bored = Bored::create("Hello", Coordinate { x: 40, y: 20 });
+        for (content, x) in [("aaaaaa", 2), ("bbbbbb", 4)] {
+            let mut notice = Notice::create(Coordinate { x: 8, y: 3 });
+            notice.write(content)?;
+            bored.add(notice, Coordinate { x, y: 2 })?;
+        }
+        let view_dimensions = Coordinate { x: 40, y: 20 };
+        let mut view_port = BoredViewPort::create(bored, view_dimensions, Some(0));
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 40, 20));
+        view_port.render_view(&mut buffer, Theme::default());
+        // the second notice covers the first where they overlap
+        assert_eq!(buffer[(5, 3)].symbol(), "b");
+        view_port.raise_notice(Some(0));
+        view_port.render_view(&mut buffer, Theme::default());
+        assert_eq!(buffer[(5, 3)].symbol(), "a");
+        view_port.raise_notice(None);
+        view_port.render_view(&mut buffer, Theme::default());
+        assert_eq!(buffer[(5, 3)].symbol(), "b");
+        Ok(())
+    }
+
+    #[test]
+    fn test_mini_map() -> Result<(), BoredError> {
+        let mut bored = Bored::create("Hello", Coordinate { x: 40, y: 12 });
+        bored.add(
+            Notice::create(Coordinate { x: 8, y: 4 }),
+            Coordinate { x: 0, y: 0 },
+        )?;
+        bored.add(
+            Notice::create(Coordinate { x: 8, y: 4 }),
+            Coordinate { x: 30, y: 8 },
+        )?;
+        let view = Rect::new(20, 0, 20, 6);
+        let mini_map = MiniMap::create(
+            &bored,
+            view,
+            Some(1),
+            Theme::default(),
+            Coordinate { x: 10, y: 10 },
+        );
+        // the width needs a scale of 4 to fit
+        assert_eq!(mini_map.get_dimensions(), Coordinate { x: 10, y: 3 });
+        let area = Rect::new(0, 0, 10, 3);
+        let mut buffer = Buffer::empty(area);
+        mini_map.render(area, &mut buffer);
+        let expected_output = Buffer::with_lines(vec![
+            "▒▒   ·····",
+            "     ·····",
+            "       ███",
+        ]);
+        assert_eq!(
+            format!("{:?}", expected_output.content),
+            format!("{:?}", buffer.content)
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_overview() -> Result<(), BoredError> {
+        let mut bored = Bored::create("Hello", Coordinate { x: 20, y: 6 });
+        bored.add(
+            Notice::create(Coordinate { x: 6, y: 4 }),
+            Coordinate { x: 0, y: 0 },
+        )?;
+        bored.add(
+            Notice::create(Coordinate { x: 6, y: 4 }),
+            Coordinate { x: 4, y: 2 },
+        )?;
+        let mut overview =
+            Overview::create(&bored, Some(0), Theme::default(), Coordinate { x: 10, y: 10 });
+        assert_eq!(overview.get_scale(), 2);
+        assert_eq!(overview.get_dimensions(), Coordinate { x: 10, y: 3 });
+        let area = Rect::new(3, 1, 10, 3);
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 13, 4));
+        (&overview).render(area, &mut buffer);
+        overview.set_screen_area(area);
+        let symbols: String = buffer.content.iter().map(|cell| cell.symbol()).collect();
+        assert_eq!(
+            symbols,
+            "             ".to_string() + "   ███·······" + "   █████·····" + "   ··███·····"
+        );
+        // where notices overlap the newest is shown and picked
+        assert_eq!(overview.get_notice_at(3, 1), Some(0));
+        assert_eq!(overview.get_notice_at(5, 1), Some(0));
+        assert_eq!(overview.get_notice_at(5, 2), Some(1));
+        assert_eq!(overview.get_notice_at(12, 3), None);
+        assert_eq!(overview.get_notice_at(0, 0), None);
+        assert_eq!(buffer[(5, 2)].fg, OVERVIEW_COLOURS[1]);
+        Ok(())
+    }
+    #[test]
+    fn test_notice_list_order() -> Result<(), BoredError> {
+        let mut bored = Bored::create("list", Coordinate { x: 40, y: 20 });
+        for (notice_id, x) in [("notice:200:a", 0), ("notice:100:b", 5), ("", 30)] {
+            let mut notice = Notice::create(Coordinate { x: 10, y: 4 });
+            notice.set_notice_id(notice_id.to_string());
+            bored.add(notice, Coordinate { x, y: 0 })?;
+        }
+        // posting times come first, the order added after that
+        assert_eq!(get_recency_order(&bored), vec![0, 1, 2]);
+        // the second notice covers half of the first
+        assert_eq!(get_percent_showing(&bored), vec![50, 100, 100]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_linear_lines() -> Result<(), SurfBoredError> {
+        let mut bored = Bored::create("linear", Coordinate { x: 40, y: 20 });
+        let mut notice = Notice::create(Coordinate { x: 16, y: 5 });
+        notice.write("Lower\n[link](bored://abc)")?;
+        bored.add(notice, Coordinate { x: 2, y: 10 })?;
+        let mut notice = Notice::create(Coordinate { x: 16, y: 5 });
+        notice.write("Upper right")?;
+        notice.set_notice_id("notice:1779796800000:abc123ef".to_string());
+        bored.add(notice, Coordinate { x: 20, y: 1 })?;
+        bored.add(
+            Notice::create(Coordinate { x: 10, y: 4 }),
+            Coordinate { x: 1, y: 1 },
+        )?;
+        assert_eq!(get_reading_order(&bored), vec![2, 1, 0]);
+
+        let theme = Theme::default();
+        let text = |lines: &Vec<Line>| -> Vec<String> {
+            lines.iter().map(|line| line.to_string()).collect()
+        };
+        let mut petnames = Petnames::default();
+        petnames.set("abc123ef", "Sam");
+        let lines = get_linear_lines(&bored, None, &theme, &petnames)?;
+        assert_eq!(
+            text(&lines),
+            vec![
+                "Notice 1 of 3, 1 across and 1 down",
+                "No text",
+                "",
+                "Notice 2 of 3, 20 across and 1 down, posted by Sam",
+                "Upper right",
+                "",
+                "Notice 3 of 3, 2 across and 10 down",
+                "Lower",
+                "link",
+                "Link 1: link, bored://abc",
+                "",
+            ]
+        );
+        // starts from the selected notice which stands out
+        let lines = get_linear_lines(&bored, Some(0), &theme, &petnames)?;
+        assert_eq!(lines[0].to_string(), "Notice 3 of 3, 2 across and 10 down");
+        assert_eq!(lines[0].style, theme.inverted_text_style().bold());
+        assert_eq!(lines.len(), 5);
+        Ok(())
+    }
+
+    /// Frames that only move the view copy it out of the bored rendered on the first frame, so
+    /// they stay quick however big the bored is. Timing needs an optimised build, run it with
+    /// cargo test --release -p surf-bored -- --ignored --nocapture frames_that_only_move
+    #[test]
+    #[ignore]
+    fn frames_that_only_move_the_view_are_quick() -> Result<(), BoredError> {
+        let mut bored = Bored::create("Big", Coordinate { x: 400, y: 200 });
+        for y in (0..200).step_by(10) {
+            for x in (0..400).step_by(20) {
+                let mut notice = Notice::create(Coordinate { x: 20, y: 10 });
+                notice.write(&format!("Notice at {x}, {y} with a [link](bored://big)"))?;
+                bored.add(notice, Coordinate { x, y })?;
+            }
+        }
+        let bored = Arc::new(bored);
+        let view = Coordinate { x: 200, y: 50 };
+        let mut bored_view_port = BoredViewPort::create(bored.clone(), view, None);
+        let mut buffer = Buffer::empty(Rect::new(0, 0, view.x, view.y));
+        let theme = Theme::default();
+
+        let started = std::time::Instant::now();
+        bored_view_port.update(&bored, view, None);
+        bored_view_port.render_view(&mut buffer, theme.clone());
+        let first_frame = started.elapsed();
+        let frames = 200;
+        let started = std::time::Instant::now();
+        for frame in 0..frames {
+            bored_view_port.update(&bored, view, None);
+            bored_view_port.scroll(if frame < frames / 2 { (1, 1) } else { (-1, -1) });
+            bored_view_port.render_view(&mut buffer, theme.clone());
+        }
+        let per_frame = started.elapsed() / frames;
+        println!("first frame {first_frame:?}, then {per_frame:?} a frame");
+        assert!(per_frame < std::time::Duration::from_millis(1));
+        Ok(())
+    }
 }
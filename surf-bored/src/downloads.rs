@@ -0,0 +1,262 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use bored::notice::Attachment;
+use bored::x0x_client::{DownloadProgress, X0xBoredClient, get_we_are_bored_data_dir};
+use std::path::PathBuf;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+
+/// How many files are downloaded at once, the rest wait in the queue
+const MAX_RUNNING_DOWNLOADS: usize = 2;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum DownloadStatus {
+    Queued,
+    Downloading,
+    Done(PathBuf),
+    Failed(String),
+}
+
+/// A file attached to a notice being downloaded in the background
+pub struct Download {
+    id: usize,
+    attachment: Attachment,
+    status: DownloadStatus,
+    progress: Option<DownloadProgress>,
+    /// Client to download with once it leaves the queue, it was cloned while the bored the
+    /// attachment is on was current so requests go to that bored's peers
+    client: Option<X0xBoredClient>,
+}
+
+impl Download {
+    pub fn get_attachment(&self) -> &Attachment {
+        &self.attachment
+    }
+
+    pub fn get_status(&self) -> &DownloadStatus {
+        &self.status
+    }
+
+    /// Percentage of the file received so far
+    pub fn get_percentage(&self) -> u8 {
+        match (&self.status, self.progress) {
+            (DownloadStatus::Done(_), _) => 100,
+            (_, Some(progress)) if progress.total > 0 => {
+                (progress.received.min(progress.total) * 100 / progress.total) as u8
+            }
+            _ => 0,
+        }
+    }
+}
+
+enum DownloadUpdate {
+    Progress(DownloadProgress),
+    Finished(Result<PathBuf, String>),
+}
+
+/// Queue of downloads run as background tasks so the ui keeps responding while files transfer
+pub struct Downloads {
+    downloads: Vec<Download>,
+    next_id: usize,
+    dir: PathBuf,
+    /// Updates from running downloads, by download id
+    sender: UnboundedSender<(usize, DownloadUpdate)>,
+    receiver: UnboundedReceiver<(usize, DownloadUpdate)>,
+}
+
+impl Downloads {
+    pub fn new() -> Downloads {
+        let (sender, receiver) = unbounded_channel();
+        Downloads {
+            downloads: vec![],
+            next_id: 0,
+            dir: default_download_dir(),
+            sender,
+            receiver,
+        }
+    }
+
+    pub fn get_downloads(&self) -> &[Download] {
+        &self.downloads
+    }
+
+    pub fn get_dir(&self) -> &PathBuf {
+        &self.dir
+    }
+
+    /// Add a download to the queue, it starts on the next update if there is room
+    pub fn queue(&mut self, client: &X0xBoredClient, attachment: Attachment) {
+        self.downloads.push(Download {
+            id: self.next_id,
+            attachment,
+            status: DownloadStatus::Queued,
+            progress: None,
+            client: Some(client.clone()),
+        });
+        self.next_id += 1;
+    }
+
+    /// Take in progress from running downloads and start queued ones, called every time the
+    /// ui is drawn
+    pub fn update(&mut self) {
+        while let Ok((id, update)) = self.receiver.try_recv() {
+            let Some(download) = self.downloads.iter_mut().find(|d| d.id == id) else {
+                continue;
+            };
+            match update {
+                DownloadUpdate::Progress(progress) => download.progress = Some(progress),
+                DownloadUpdate::Finished(Ok(path)) => download.status = DownloadStatus::Done(path),
+                DownloadUpdate::Finished(Err(e)) => download.status = DownloadStatus::Failed(e),
+            }
+        }
+        let running = self
+            .downloads
+            .iter()
+            .filter(|d| d.status == DownloadStatus::Downloading)
+            .count();
+        for _ in running..MAX_RUNNING_DOWNLOADS {
+            let Some(index) = self
+                .downloads
+                .iter()
+                .position(|d| d.status == DownloadStatus::Queued)
+            else {
+                break;
+            };
+            self.start(index);
+        }
+    }
+
+    fn start(&mut self, index: usize) {
+        let download = &mut self.downloads[index];
+        let id = download.id;
+        let Some(client) = download.client.take() else {
+            download.status = DownloadStatus::Failed("Not connected to x0x".to_string());
+            return;
+        };
+        download.status = DownloadStatus::Downloading;
+        let attachment = download.attachment.clone();
+        let dir = self.dir.clone();
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            let (progress_sender, mut progress_receiver) = unbounded_channel();
+            let progress_updates = sender.clone();
+            tokio::spawn(async move {
+                while let Some(progress) = progress_receiver.recv().await {
+                    let _ = progress_updates.send((id, DownloadUpdate::Progress(progress)));
+                }
+            });
+            let result = client
+                .download_file_with_progress(&attachment, &dir, Some(progress_sender))
+                .await
+                .map_err(|e| e.to_string());
+            let _ = sender.send((id, DownloadUpdate::Finished(result)));
+        });
+    }
+
+    pub fn has_downloads(&self) -> bool {
+        !self.downloads.is_empty()
+    }
+
+    pub fn next_download(&self, index: usize) -> usize {
+        if index + 1 >= self.downloads.len() {
+            0
+        } else {
+            index + 1
+        }
+    }
+
+    pub fn previous_download(&self, index: usize) -> usize {
+        if index == 0 {
+            self.downloads.len().saturating_sub(1)
+        } else {
+            index - 1
+        }
+    }
+
+    /// Drop finished and failed downloads from the list
+    pub fn clear_finished(&mut self) {
+        self.downloads.retain(|d| {
+            matches!(
+                d.status,
+                DownloadStatus::Queued | DownloadStatus::Downloading
+            )
+        });
+    }
+}
+
+/// The user's Downloads folder if they have one, otherwise a folder in the app's data
+fn default_download_dir() -> PathBuf {
+    let home = std::env::var("HOME").or_else(|_| std::env::var("USERPROFILE"));
+    if let Ok(home) = home {
+        let downloads = PathBuf::from(home).join("Downloads");
+        if downloads.is_dir() {
+            return downloads;
+        }
+    }
+    get_we_are_bored_data_dir()
+        .map(|dir| dir.join("downloads"))
+        .unwrap_or_else(|| PathBuf::from("downloads"))
+}
+
+/// Size in bytes written for people, e.g. 1.5 MiB
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{size:.1} {}", UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(16 * 1024 * 1024), "16.0 MiB");
+    }
+
+    #[test]
+    fn test_percentage() {
+        let attachment = Attachment::create(b"hello", "hello.txt").unwrap();
+        let mut download = Download {
+            id: 0,
+            attachment,
+            status: DownloadStatus::Downloading,
+            progress: None,
+            client: None,
+        };
+        assert_eq!(download.get_percentage(), 0);
+        download.progress = Some(DownloadProgress {
+            received: 2,
+            total: 5,
+        });
+        assert_eq!(download.get_percentage(), 40);
+        download.status = DownloadStatus::Done(PathBuf::from("hello.txt"));
+        assert_eq!(download.get_percentage(), 100);
+    }
+}
@@ -0,0 +1,148 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::app::SurfBoredError;
+use bored::Coordinate;
+use bored::notice::NoticeKind;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// A notice that was being written, kept so it isn't lost if it can't be placed straight away
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Draft {
+    /// The bored:// address or share link of the bored the notice is for
+    pub link: String,
+    pub content: String,
+    pub dimensions: Coordinate,
+    pub top_left: Coordinate,
+    /// The event the notice is for, if it is one
+    #[serde(default, skip_serializing_if = "NoticeKind::is_text")]
+    pub kind: NoticeKind,
+}
+
+impl Draft {
+    /// First line of the draft's text to tell it apart from the others
+    pub fn get_summary(&self) -> &str {
+        self.content
+            .lines()
+            .find(|line| !line.trim().is_empty())
+            .unwrap_or_default()
+    }
+}
+
+/// Drafts saved as they are written, kept in a file next to the directory
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct Drafts {
+    #[serde(default)]
+    drafts: Vec<Draft>,
+}
+
+impl Drafts {
+    /// The saved drafts, none if the file isn't there or can't be read
+    pub fn load_file(path: &Path) -> Drafts {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|drafts_string| toml::from_str(&drafts_string).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save_file(&self, path: &Path) -> Result<(), SurfBoredError> {
+        let Ok(drafts_string) = toml::to_string(&self) else {
+            return Err(SurfBoredError::DraftsSerialzationError);
+        };
+        let Ok(()) = fs::write(path, drafts_string) else {
+            return Err(SurfBoredError::DraftsFileWriteError);
+        };
+        Ok(())
+    }
+
+    pub fn get_drafts(&self) -> &Vec<Draft> {
+        &self.drafts
+    }
+
+    /// Keep a draft in place of the one at the index, or as a new draft if there isn't one,
+    /// returning where it is kept
+    pub fn keep(&mut self, index: Option<usize>, draft: Draft) -> usize {
+        match index.and_then(|index| self.drafts.get_mut(index).map(|kept| (index, kept))) {
+            Some((index, kept)) => {
+                *kept = draft;
+                index
+            }
+            None => {
+                self.drafts.push(draft);
+                self.drafts.len() - 1
+            }
+        }
+    }
+
+    pub fn remove(&mut self, index: usize) -> Option<Draft> {
+        if index < self.drafts.len() {
+            return Some(self.drafts.remove(index));
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drafts_file() -> Result<(), SurfBoredError> {
+        let path =
+            std::env::temp_dir().join(format!("surf-bored-drafts-{}.toml", std::process::id()));
+        assert_eq!(Drafts::load_file(&path), Drafts::default());
+        let draft = Draft {
+            link: "bored://welcome".to_string(),
+            content: "\nWe are\nbored".to_string(),
+            dimensions: Coordinate { x: 20, y: 6 },
+            top_left: Coordinate { x: 3, y: 4 },
+            kind: NoticeKind::Text,
+        };
+        assert_eq!(draft.get_summary(), "We are");
+        let mut drafts = Drafts::default();
+        assert_eq!(drafts.keep(None, draft.clone()), 0);
+        let other = Draft {
+            content: "so bored".to_string(),
+            kind: NoticeKind::Event {
+                title: "Bored meetup".to_string(),
+                start: 1_781_290_800_000,
+                end: None,
+                location: Some("The village hall".to_string()),
+            },
+            ..draft.clone()
+        };
+        assert_eq!(drafts.keep(None, other.clone()), 1);
+        // keeping a draft again replaces it
+        let edited = Draft {
+            content: "We are bored".to_string(),
+            ..draft
+        };
+        assert_eq!(drafts.keep(Some(0), edited.clone()), 0);
+        assert_eq!(drafts.keep(Some(5), edited.clone()), 2);
+        assert_eq!(drafts.remove(2), Some(edited.clone()));
+        assert_eq!(drafts.remove(2), None);
+        drafts.save_file(&path)?;
+        let loaded = Drafts::load_file(&path);
+        assert_eq!(loaded.get_drafts(), &vec![edited, other]);
+        fs::write(&path, "not drafts")?;
+        assert_eq!(Drafts::load_file(&path), Drafts::default());
+        let _ = fs::remove_file(path);
+        Ok(())
+    }
+}
@@ -0,0 +1,114 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use bored::Bored;
+use bored::url::BoredAddress;
+use bored::x0x_client::X0xBoredClient;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+
+/// How long fetching a bored notices are embedded from is waited on before it is given up on
+pub const EMBED_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long a bored notices are embedded from is kept before it is fetched again, so the
+/// embedded notices keep up with it
+pub const EMBED_LIFETIME: Duration = Duration::from_secs(5 * 60);
+
+/// What fetching a bored notices are embedded from found
+#[derive(Clone, Debug)]
+enum Source {
+    /// With notices hidden by moderation emptied, so they aren't shown elsewhere
+    Found(Arc<Bored>),
+    NotFound,
+}
+
+/// Fetches the boreds notices on the current bored are embedded from in the background, so the
+/// notices can be shown in place of the ones embedding them
+pub struct Embeds {
+    /// What was fetched for each address and when
+    found: HashMap<String, (Source, Instant)>,
+    /// Addresses being fetched, what was found before is still shown meanwhile
+    loading: HashSet<String>,
+    sender: UnboundedSender<(String, Source)>,
+    receiver: UnboundedReceiver<(String, Source)>,
+}
+
+impl Embeds {
+    pub fn new() -> Embeds {
+        let (sender, receiver) = unbounded_channel();
+        Embeds {
+            found: HashMap::new(),
+            loading: HashSet::new(),
+            sender,
+            receiver,
+        }
+    }
+
+    /// Fetch the bored at the address on a copy of the client, unless it is already being
+    /// fetched or was recently
+    pub fn request(&mut self, client: &X0xBoredClient, address: &BoredAddress) {
+        let key = address.get_topic();
+        if self.loading.contains(&key)
+            || self
+                .found
+                .get(&key)
+                .is_some_and(|(_, at)| at.elapsed() < EMBED_LIFETIME)
+        {
+            return;
+        }
+        self.loading.insert(key.clone());
+        let client = client.clone();
+        let address = address.clone();
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            let fetched = tokio::time::timeout(
+                EMBED_TIMEOUT,
+                client.fetch_boreds(std::slice::from_ref(&address)),
+            )
+            .await;
+            let source = match fetched.ok().and_then(|mut boreds| boreds.pop()) {
+                Some(Ok(bored)) => Source::Found(Arc::new(bored.redact_hidden())),
+                _ => Source::NotFound,
+            };
+            let _ = sender.send((key, source));
+        });
+    }
+
+    /// Take in what has been fetched since last time
+    pub fn update(&mut self) {
+        while let Ok((key, source)) = self.receiver.try_recv() {
+            self.loading.remove(&key);
+            let source = match (source, self.found.remove(&key)) {
+                // a bored that can't be fetched again is still shown as it was
+                (Source::NotFound, Some((found @ Source::Found(_), _))) => found,
+                (source, _) => source,
+            };
+            self.found.insert(key, (source, Instant::now()));
+        }
+    }
+
+    /// The bored at the address if it has been fetched, kept after it goes out of date until
+    /// it has been fetched again
+    pub fn get(&self, address: &BoredAddress) -> Option<Arc<Bored>> {
+        match self.found.get(&address.get_topic()) {
+            Some((Source::Found(bored), _)) => Some(bored.clone()),
+            _ => None,
+        }
+    }
+}
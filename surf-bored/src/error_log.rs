@@ -0,0 +1,164 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::app::SurfBoredError;
+use std::collections::VecDeque;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Most errors kept, older ones are forgotten as more happen
+const MAX_LOGGED_ERRORS: usize = 100;
+
+/// An error that was shown to the user and when
+#[derive(Debug)]
+pub struct LoggedError {
+    error: SurfBoredError,
+    logged: Instant,
+}
+
+impl LoggedError {
+    pub fn get_error(&self) -> &SurfBoredError {
+        &self.error
+    }
+
+    /// How long ago the error happened
+    pub fn get_age(&self) -> Duration {
+        self.logged.elapsed()
+    }
+}
+
+/// Recent errors, newest first, so ones dismissed before they were read can be looked at later
+#[derive(Debug, Default)]
+pub struct ErrorLog {
+    errors: VecDeque<LoggedError>,
+    /// File each error is added to as well, so they outlast the session
+    file: Option<PathBuf>,
+}
+
+impl ErrorLog {
+    pub fn with_file(file: PathBuf) -> ErrorLog {
+        ErrorLog {
+            file: Some(file),
+            ..ErrorLog::default()
+        }
+    }
+
+    pub fn push(&mut self, error: SurfBoredError) {
+        if let Some(file) = &self.file {
+            // there is nowhere left to show an error writing the log, so it is dropped
+            let _ = append_to_file(file, &error);
+        }
+        self.errors.push_front(LoggedError {
+            error,
+            logged: Instant::now(),
+        });
+        self.errors.truncate(MAX_LOGGED_ERRORS);
+    }
+
+    pub fn get_errors(&self) -> &VecDeque<LoggedError> {
+        &self.errors
+    }
+
+    pub fn clear(&mut self) {
+        self.errors.clear();
+    }
+}
+
+/// Add the error to the end of the file on one line, after the seconds since the unix epoch
+fn append_to_file(file: &Path, error: &SurfBoredError) -> std::io::Result<()> {
+    let seconds = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let message = error.to_string().replace('\n', " ");
+    let mut file = OpenOptions::new().create(true).append(true).open(file)?;
+    writeln!(file, "{seconds} {message}")
+}
+
+/// How long ago something happened, in the largest whole unit
+pub fn format_age(age: Duration) -> String {
+    let seconds = age.as_secs();
+    if seconds < 60 {
+        format!("{seconds}s ago")
+    } else if seconds < 60 * 60 {
+        format!("{}m ago", seconds / 60)
+    } else if seconds < 24 * 60 * 60 {
+        format!("{}h ago", seconds / (60 * 60))
+    } else {
+        format!("{}d ago", seconds / (24 * 60 * 60))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_log() {
+        let mut error_log = ErrorLog::default();
+        error_log.push(SurfBoredError::StillWaiting);
+        error_log.push(SurfBoredError::Message("Not a bored".to_string()));
+        let errors: Vec<&SurfBoredError> = error_log
+            .get_errors()
+            .iter()
+            .map(LoggedError::get_error)
+            .collect();
+        assert_eq!(
+            errors,
+            vec![
+                &SurfBoredError::Message("Not a bored".to_string()),
+                &SurfBoredError::StillWaiting
+            ]
+        );
+        for _ in 0..MAX_LOGGED_ERRORS {
+            error_log.push(SurfBoredError::DirectoryIsEmpty);
+        }
+        assert_eq!(error_log.get_errors().len(), MAX_LOGGED_ERRORS);
+        error_log.clear();
+        assert!(error_log.get_errors().is_empty());
+    }
+
+    #[test]
+    fn test_error_log_file() {
+        let path =
+            std::env::temp_dir().join(format!("surf-bored-errors-{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut error_log = ErrorLog::with_file(path.clone());
+        error_log.push(SurfBoredError::Message("Not\na bored".to_string()));
+        error_log.push(SurfBoredError::StillWaiting);
+        let logged = std::fs::read_to_string(&path).unwrap_or_default();
+        let messages: Vec<&str> = logged
+            .lines()
+            .filter_map(|line| line.split_once(' ').map(|(_, message)| message))
+            .collect();
+        assert_eq!(
+            messages,
+            vec!["Not a bored", "Daemon call timed out as never returned"]
+        );
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[test]
+    fn test_format_age() {
+        assert_eq!(format_age(Duration::from_secs(5)), "5s ago");
+        assert_eq!(format_age(Duration::from_secs(125)), "2m ago");
+        assert_eq!(format_age(Duration::from_secs(3 * 60 * 60)), "3h ago");
+        assert_eq!(format_age(Duration::from_secs(50 * 60 * 60)), "2d ago");
+    }
+}
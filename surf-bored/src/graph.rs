@@ -0,0 +1,341 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use bored::Coordinate;
+use bored::crawler::CrawlIndex;
+use bored::url::BoredAddress;
+
+/// Widest a bored's name is drawn, with the brackets around it
+pub const NODE_WIDTH: u16 = 18;
+
+/// Space between columns for the links to be drawn in
+const COLUMN_GAP: u16 = 6;
+
+/// Which ways a cell's line goes out of it
+const UP: u8 = 1;
+const DOWN: u8 = 2;
+const LEFT: u8 = 4;
+const RIGHT: u8 = 8;
+
+/// A bored drawn in the graph
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphNode {
+    pub address: BoredAddress,
+    pub name: String,
+    /// Where its label is drawn
+    pub top_left: Coordinate,
+    /// The bored's name in brackets, cut to fit NODE_WIDTH
+    pub label: String,
+}
+
+/// Which way to move the selection through the graph
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GraphStep {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// The boreds in a crawl index laid out as a diagram, a column for each number of links from
+/// the seeds with lines from each bored to those it links to in the next column. Links back to
+/// boreds found earlier aren't drawn, as they would cross the rest, but are still known.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Graph {
+    nodes: Vec<GraphNode>,
+    /// Links between the boreds by their index in the nodes
+    edges: Vec<(usize, usize)>,
+}
+
+impl Graph {
+    pub fn create(index: &CrawlIndex) -> Graph {
+        let mut rows_used: Vec<u16> = vec![];
+        let nodes: Vec<GraphNode> = index
+            .get_entries()
+            .iter()
+            .map(|entry| {
+                if rows_used.len() <= entry.depth {
+                    rows_used.resize(entry.depth + 1, 0);
+                }
+                let row = rows_used[entry.depth];
+                rows_used[entry.depth] += 1;
+                GraphNode {
+                    address: entry.address.clone(),
+                    name: entry.name.clone(),
+                    top_left: Coordinate {
+                        x: get_column_x(entry.depth),
+                        y: row * 2,
+                    },
+                    label: get_label(&entry.name),
+                }
+            })
+            .collect();
+        let mut edges = vec![];
+        for (from, entry) in index.get_entries().iter().enumerate() {
+            for link in &entry.links {
+                if let Some(to) = nodes
+                    .iter()
+                    .position(|node| node.address.get_topic() == link.get_topic())
+                {
+                    edges.push((from, to));
+                }
+            }
+        }
+        Graph { nodes, edges }
+    }
+
+    pub fn get_nodes(&self) -> &Vec<GraphNode> {
+        &self.nodes
+    }
+
+    /// Nodes the node links to
+    pub fn get_links_from(&self, node: usize) -> Vec<usize> {
+        self.edges
+            .iter()
+            .filter(|(from, _)| *from == node)
+            .map(|(_, to)| *to)
+            .collect()
+    }
+
+    /// Nodes that link to the node
+    pub fn get_links_to(&self, node: usize) -> Vec<usize> {
+        self.edges
+            .iter()
+            .filter(|(_, to)| *to == node)
+            .map(|(from, _)| *from)
+            .collect()
+    }
+
+    /// Width and height of the whole diagram
+    pub fn get_size(&self) -> Coordinate {
+        self.nodes
+            .iter()
+            .fold(Coordinate { x: 0, y: 0 }, |size, node| Coordinate {
+                x: size
+                    .x
+                    .max(node.top_left.x + node.label.chars().count() as u16),
+                y: size.y.max(node.top_left.y + 1),
+            })
+    }
+
+    /// The diagram as rows of characters, boreds as their labels and links as lines drawn
+    /// with box characters ending in an arrow at the bored linked to
+    pub fn render(&self) -> Vec<Vec<char>> {
+        let size = self.get_size();
+        let mut ways = vec![vec![0u8; size.x as usize]; size.y as usize];
+        let mut arrows = vec![];
+        for &(from, to) in &self.edges {
+            let (from, to) = (&self.nodes[from], &self.nodes[to]);
+            // only links into the next column are drawn
+            if to.top_left.x != from.top_left.x + NODE_WIDTH + COLUMN_GAP {
+                continue;
+            }
+            let start = from.top_left.x + from.label.chars().count() as u16;
+            let middle = to.top_left.x - COLUMN_GAP / 2;
+            let end = to.top_left.x - 2;
+            draw_across(&mut ways, from.top_left.y, start, middle);
+            draw_down(&mut ways, middle, from.top_left.y, to.top_left.y);
+            draw_across(&mut ways, to.top_left.y, middle, end);
+            arrows.push(Coordinate {
+                x: end + 1,
+                y: to.top_left.y,
+            });
+        }
+        let mut lines: Vec<Vec<char>> = ways
+            .iter()
+            .map(|row| row.iter().map(|ways| get_line_char(*ways)).collect())
+            .collect();
+        for arrow in arrows {
+            lines[arrow.y as usize][arrow.x as usize] = '▶';
+        }
+        for node in &self.nodes {
+            for (i, char) in node.label.chars().enumerate() {
+                lines[node.top_left.y as usize][node.top_left.x as usize + i] = char;
+            }
+        }
+        lines
+    }
+
+    /// The node to select after moving from one, up and down the column it is in or across to
+    /// the nearest in the column next to it, staying put if there is nothing that way
+    pub fn step(&self, node: usize, step: GraphStep) -> usize {
+        let Some(current) = self.nodes.get(node).map(|node| node.top_left) else {
+            return 0;
+        };
+        let column_x = match step {
+            GraphStep::Up | GraphStep::Down => current.x,
+            GraphStep::Left if current.x == 0 => return node,
+            GraphStep::Left => current.x - NODE_WIDTH - COLUMN_GAP,
+            GraphStep::Right => current.x + NODE_WIDTH + COLUMN_GAP,
+        };
+        let candidates = self
+            .nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, other)| other.top_left.x == column_x);
+        let found = match step {
+            GraphStep::Up => candidates
+                .filter(|(_, other)| other.top_left.y < current.y)
+                .max_by_key(|(_, other)| other.top_left.y),
+            GraphStep::Down => candidates
+                .filter(|(_, other)| other.top_left.y > current.y)
+                .min_by_key(|(_, other)| other.top_left.y),
+            GraphStep::Left | GraphStep::Right => {
+                candidates.min_by_key(|(_, other)| other.top_left.y.abs_diff(current.y))
+            }
+        };
+        found.map_or(node, |(index, _)| index)
+    }
+}
+
+fn get_column_x(depth: usize) -> u16 {
+    depth as u16 * (NODE_WIDTH + COLUMN_GAP)
+}
+
+/// The name in brackets, cut short with an ellipsis if it is too wide
+fn get_label(name: &str) -> String {
+    let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+    let room = NODE_WIDTH as usize - 2;
+    if name.chars().count() > room {
+        let cut: String = name.chars().take(room - 1).collect();
+        format!("[{cut}…]")
+    } else {
+        format!("[{name}]")
+    }
+}
+
+/// A line across a row between two columns, both included
+fn draw_across(ways: &mut [Vec<u8>], y: u16, from_x: u16, to_x: u16) {
+    let row = &mut ways[y as usize];
+    for x in from_x..=to_x {
+        if x > from_x {
+            row[x as usize] |= LEFT;
+        }
+        if x < to_x {
+            row[x as usize] |= RIGHT;
+        }
+    }
+    if from_x == to_x {
+        row[from_x as usize] |= LEFT | RIGHT;
+    }
+}
+
+/// A line down a column between two rows, both included, in whichever order they come
+fn draw_down(ways: &mut [Vec<u8>], x: u16, from_y: u16, to_y: u16) {
+    let (top, bottom) = (from_y.min(to_y), from_y.max(to_y));
+    for y in top..=bottom {
+        if y > top {
+            ways[y as usize][x as usize] |= UP;
+        }
+        if y < bottom {
+            ways[y as usize][x as usize] |= DOWN;
+        }
+    }
+}
+
+/// The box drawing character joining the ways a line goes out of a cell
+fn get_line_char(ways: u8) -> char {
+    match ways {
+        0 => ' ',
+        w if w == UP | DOWN | LEFT | RIGHT => '┼',
+        w if w == UP | DOWN | RIGHT => '├',
+        w if w == UP | DOWN | LEFT => '┤',
+        w if w == LEFT | RIGHT | DOWN => '┬',
+        w if w == LEFT | RIGHT | UP => '┴',
+        w if w == DOWN | RIGHT => '┌',
+        w if w == DOWN | LEFT => '┐',
+        w if w == UP | RIGHT => '└',
+        w if w == UP | LEFT => '┘',
+        w if w & (UP | DOWN) != 0 => '│',
+        _ => '─',
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bored::notice::Notice;
+    use bored::{Bored, BoredError};
+
+    fn bored_linking_to(name: &str, links: &[&str]) -> Result<Bored, BoredError> {
+        let mut bored = Bored::create(name, Coordinate { x: 60, y: 10 });
+        let mut notice = Notice::create(Coordinate { x: 60, y: 10 });
+        let text: Vec<String> = links
+            .iter()
+            .map(|link| format!("[{link}](bored://{link})"))
+            .collect();
+        notice.write(&text.join(" "))?;
+        bored.add(notice, Coordinate { x: 0, y: 0 })?;
+        Ok(bored)
+    }
+
+    /// Games links to chess and go, which links back to games
+    fn test_graph() -> Result<Graph, BoredError> {
+        let mut index = CrawlIndex::default();
+        for (name, links, depth) in [
+            ("games", vec!["chess", "go"], 0),
+            ("chess", vec![], 1),
+            ("go", vec!["games"], 1),
+        ] {
+            let address = BoredAddress::from_string(name)?;
+            index.add_bored(address, &bored_linking_to(name, &links)?, depth);
+        }
+        Ok(Graph::create(&index))
+    }
+
+    #[test]
+    fn test_render() -> Result<(), BoredError> {
+        let graph = test_graph()?;
+        let lines: Vec<String> = graph
+            .render()
+            .iter()
+            .map(|line| line.iter().collect::<String>().trim_end().to_string())
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                format!("[games]{}┬─▶[chess]", "─".repeat(14)),
+                format!("{}│", " ".repeat(21)),
+                format!("{}└─▶[go]", " ".repeat(21)),
+            ]
+        );
+        assert_eq!(graph.get_links_from(0), vec![1, 2]);
+        assert_eq!(graph.get_links_to(0), vec![2]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_step() -> Result<(), BoredError> {
+        let graph = test_graph()?;
+        assert_eq!(graph.step(0, GraphStep::Right), 1);
+        assert_eq!(graph.step(1, GraphStep::Down), 2);
+        assert_eq!(graph.step(2, GraphStep::Down), 2);
+        assert_eq!(graph.step(2, GraphStep::Up), 1);
+        assert_eq!(graph.step(2, GraphStep::Left), 0);
+        assert_eq!(graph.step(0, GraphStep::Left), 0);
+        assert_eq!(graph.step(0, GraphStep::Up), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_label() {
+        assert_eq!(get_label("Chess  club"), "[Chess club]");
+        let label = get_label("A bored with a very long name");
+        assert_eq!(label, "[A bored with a …]");
+        assert_eq!(label.chars().count(), NODE_WIDTH as usize);
+    }
+}
@@ -0,0 +1,428 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::app::View;
+
+/// What boreds are, shown above the keys in the help view
+pub const CONCEPTS: &str = "A bored is a shared notice board on the x0x network that anyone with its \
+address can read and pin notices to. Notices are placed on top of older ones, so the newest are \
+uppermost and old notices are covered up over time. Notices can hold hyperlinks to other boreds \
+(bored://) or to websites (https://) and can have files attached. The directory keeps the boreds \
+you want to come back to, one of which is your home bored.";
+
+/// A key or keys and what they do
+#[derive(Debug, PartialEq)]
+pub struct KeyBinding {
+    pub keys: &'static str,
+    pub action: &'static str,
+}
+
+/// The keys for one view
+#[derive(Debug, PartialEq)]
+pub struct HelpSection {
+    pub title: &'static str,
+    pub bindings: &'static [KeyBinding],
+}
+
+const fn bind(keys: &'static str, action: &'static str) -> KeyBinding {
+    KeyBinding { keys, action }
+}
+
+pub const HELP_SECTIONS: &[HelpSection] = &[
+    HelpSection {
+        title: "Bored",
+        bindings: &[
+            bind("arrow keys", "Select the notice in that direction"),
+            bind("tab / shift + tab", "Select the next or previous notice"),
+            bind("enter", "View the selected notice"),
+            bind("n", "New notice, or next match while searching"),
+            bind(
+                "p",
+                "New notice in the next free space, reading left to right then down",
+            ),
+            bind("N", "Previous match while searching"),
+            bind("/", "Search the text of the notices"),
+            bind("ctrl + arrow keys", "Move the view"),
+            bind("page up / page down", "Move the view a screen up or down"),
+            bind(
+                "home / end",
+                "Move the view to the top left or bottom right",
+            ),
+            bind("backspace / alt + left", "Go back to the previous bored"),
+            bind("alt + right", "Go forward to the next bored"),
+            bind("r / F5", "Refresh the bored"),
+            bind("c", "Create a bored"),
+            bind("g", "Go to a bored by its address"),
+            bind("y", "Copy the bored's address"),
+            bind("Q", "Show the bored's address as a QR code"),
+            bind("d", "Open the directory of boreds"),
+            bind("s", "Save the bored to the directory"),
+            bind("b", "Save the bored to the directory under a name"),
+            bind("f", "Files attached to notices"),
+            bind("l", "List downloads"),
+            bind("m", "Show or hide the map"),
+            bind("z", "Zoomed out overview of the bored"),
+            bind(
+                "x",
+                "Peek at the notices covered where the selected notice overlaps or was clicked",
+            ),
+            bind("t", "Pick a theme"),
+            bind("e", "Saved drafts"),
+            bind("k", "Your reader key for encrypted boreds"),
+            bind("E", "Errors shown recently"),
+            bind("L", "Ledger of what you have published"),
+            bind("w", "List the notices on the bored, newest first"),
+            bind(
+                "C",
+                "Check where the links on the bored go and cross out those that go nowhere",
+            ),
+            bind(
+                "I",
+                "Search the boreds found by following links out from the directory",
+            ),
+            bind("W", "Graph of the boreds found and the links between them"),
+            bind(
+                "v",
+                "Show the notices as a list for screen readers, or the bored again",
+            ),
+            bind("a", "About Surf Bored"),
+            bind("space", "Show or hide the menu"),
+            bind("? / F1", "This help"),
+            bind("q / ctrl + c", "Quit"),
+        ],
+    },
+    HelpSection {
+        title: "Notice",
+        bindings: &[
+            bind("tab / shift + tab", "Select the next or previous hyperlink"),
+            bind("enter", "Follow the selected hyperlink"),
+            bind("1 - 9", "Follow the hyperlink with that number"),
+            bind(
+                "y",
+                "Copy the selected hyperlink's address, or the notice's text",
+            ),
+            bind("d", "Download the selected attachment"),
+            bind("c", "Save the events on the bored to add to a calendar"),
+            bind("m", "Pick the notice to embed in a notice on any bored"),
+            bind("p", "Give the notice's author a name only you see"),
+            bind("esc / backspace", "Back to the bored"),
+        ],
+    },
+    HelpSection {
+        title: "Drafting a notice",
+        bindings: &[
+            bind("type", "Write the notice"),
+            bind("ctrl + h", "Add a hyperlink"),
+            bind("ctrl + d", "Pick a hyperlink's address from the directory"),
+            bind("ctrl + u", "Clear the notice"),
+            bind("ctrl + e", "Write the notice in $VISUAL or $EDITOR"),
+            bind("ctrl + t", "Start again from a template"),
+            bind(
+                "ctrl + w",
+                "Make the notice an event, tab moves between its details",
+            ),
+            bind(
+                "ctrl + o",
+                "Embed the notice picked with m, showing it as it is on its bored",
+            ),
+            bind("ctrl + v", "Paste"),
+            bind("ctrl + p", "Position the notice"),
+            bind(
+                "ctrl + g",
+                "Post the notice in the next free space without positioning it",
+            ),
+            bind("ctrl + r", "Resize the notice"),
+            bind(
+                "shift + arrow keys",
+                "Grow or shrink the notice while resizing",
+            ),
+            bind("arrow keys / drag", "Move the notice while positioning"),
+            bind(
+                "g",
+                "Snap the notice to the grid while positioning, or stop",
+            ),
+            bind(
+                "enter",
+                "Place the notice while positioning, after saying what it will take",
+            ),
+            bind("y / n", "Post the notice or not when asked"),
+            bind(
+                "y",
+                "Place the notice even if it would hide others entirely",
+            ),
+            bind("esc", "Back to writing, or stop drafting"),
+        ],
+    },
+    HelpSection {
+        title: "Directory",
+        bindings: &[
+            bind("up / down", "Select a bored"),
+            bind("enter", "Go to the selected bored"),
+            bind("/", "Search names, addresses and tags"),
+            bind("t", "Filter by the next tag"),
+            bind("e", "Edit the name, tags and note"),
+            bind(
+                "w",
+                "Watch the bored for new notices while elsewhere, or stop",
+            ),
+            bind("shift + up / down", "Move the bored up or down"),
+            bind("delete", "Remove the bored"),
+            bind("ctrl + h", "Make the bored your home"),
+            bind("i / x", "Import or export the directory"),
+            bind("esc", "Close the search or the directory"),
+        ],
+    },
+    HelpSection {
+        title: "Go to",
+        bindings: &[
+            bind("type", "Enter an address"),
+            bind("up / down", "Pick a suggestion"),
+            bind("tab", "Complete the address from a suggestion"),
+            bind("ctrl + v", "Paste"),
+            bind("enter", "Go to the address"),
+        ],
+    },
+    HelpSection {
+        title: "Overview",
+        bindings: &[
+            bind("arrow keys / tab", "Select a notice"),
+            bind("enter / click", "Back to the bored at the selected notice"),
+            bind("esc / z", "Back to the bored"),
+        ],
+    },
+    HelpSection {
+        title: "Attached files",
+        bindings: &[
+            bind("up / down", "Select a file"),
+            bind("space", "Pick the file to download"),
+            bind("a", "Pick all or none of the files"),
+            bind("enter", "Download the picked files"),
+        ],
+    },
+    HelpSection {
+        title: "Downloads",
+        bindings: &[
+            bind("up / down", "Select a download"),
+            bind("enter", "Open the downloaded file"),
+            bind("c", "Clear finished downloads"),
+        ],
+    },
+    HelpSection {
+        title: "Drafts",
+        bindings: &[
+            bind("up / down", "Select a draft"),
+            bind("enter", "Carry on writing the draft"),
+            bind("delete", "Discard the draft"),
+            bind("esc", "Close the drafts"),
+        ],
+    },
+    HelpSection {
+        title: "Templates",
+        bindings: &[
+            bind("up / down", "Select a template"),
+            bind("enter", "Start the notice from the template"),
+            bind("esc", "Back to the notice"),
+        ],
+    },
+    HelpSection {
+        title: "Reader key",
+        bindings: &[
+            bind("n", "Make a new key"),
+            bind("i", "Import a secret key or seed phrase from another device"),
+            bind("y", "Copy your public key"),
+            bind("w", "Show the key as a seed phrase to write down"),
+            bind("delete", "Clear the key"),
+            bind("esc", "Close the reader key"),
+        ],
+    },
+    HelpSection {
+        title: "Error log",
+        bindings: &[
+            bind("up / down", "Select an error"),
+            bind("c", "Clear the log"),
+            bind("esc", "Close the error log"),
+        ],
+    },
+    HelpSection {
+        title: "Ledger",
+        bindings: &[
+            bind("up / down", "Select an entry"),
+            bind("esc", "Close the ledger"),
+        ],
+    },
+    HelpSection {
+        title: "Notice list",
+        bindings: &[
+            bind("up / down", "Select a notice"),
+            bind("home / end", "Newest or oldest notice"),
+            bind("enter", "Show the notice on the bored"),
+            bind("esc", "Close the list"),
+        ],
+    },
+    HelpSection {
+        title: "QR code",
+        bindings: &[
+            bind("y", "Copy the address"),
+            bind("esc", "Close the QR code"),
+        ],
+    },
+    HelpSection {
+        title: "Index",
+        bindings: &[
+            bind("type", "Search the names and notices of the boreds crawled"),
+            bind("up / down", "Select a match"),
+            bind("home / end", "First or last match"),
+            bind("enter", "Go to the bored of the match"),
+            bind("ctrl + r", "Crawl again from the boreds in the directory"),
+            bind("esc", "Close the index"),
+        ],
+    },
+    HelpSection {
+        title: "Graph",
+        bindings: &[
+            bind("up / down", "Select a bored in the same column"),
+            bind(
+                "left / right",
+                "Select the nearest bored in the next column",
+            ),
+            bind("home", "Select the first bored"),
+            bind("enter", "Go to the selected bored"),
+            bind("ctrl + r", "Crawl again from the boreds in the directory"),
+            bind("esc", "Close the graph"),
+        ],
+    },
+    HelpSection {
+        title: "Opening outside Surf Bored",
+        bindings: &[
+            bind("y / enter", "Open the web address or file"),
+            bind("n / esc", "Don't open it"),
+        ],
+    },
+    HelpSection {
+        title: "Themes",
+        bindings: &[
+            bind("up / down", "Select a theme"),
+            bind("enter", "Use the selected theme from now on"),
+            bind("b", "Turn showing boreds in their own colours on or off"),
+            bind("esc", "Keep the theme in use"),
+        ],
+    },
+    HelpSection {
+        title: "Vim keys",
+        bindings: &[
+            bind(
+                "h / j / k / l",
+                "Left, down, up and right, where the arrow keys move",
+            ),
+            bind("gg / G", "Home and end, to the top or bottom"),
+            bind("i", "New notice"),
+            bind(":", "Type a command on the bored"),
+            bind(":e / :goto address", "Go to the bored at the address"),
+            bind(":dir / :w / :q", "The directory, save or quit"),
+            bind(
+                ":new / :create / :r",
+                "New notice, create a bored or refresh",
+            ),
+            bind(":yank / :files", "Copy the address or files attached"),
+            bind(":downloads / :drafts", "Downloads or saved drafts"),
+            bind(":map / :overview", "Show or hide the map, or the overview"),
+            bind(":themes / :key", "Themes or the reader key"),
+            bind(":errors / :linear", "The error log or the linear view"),
+            bind(":post", "New notice in the next free space"),
+            bind(":ledger", "The ledger of what you have published"),
+            bind(":notices", "List the notices, newest first"),
+            bind(":qr", "The bored's address as a QR code"),
+            bind(":peek", "Peek at the notices covered here"),
+            bind(":links", "Check the links on the bored"),
+            bind(":index", "Search the boreds crawled from the directory"),
+            bind(":graph", "The boreds crawled and the links between them"),
+            bind(":about / :help", "About Surf Bored or this help"),
+        ],
+    },
+    HelpSection {
+        title: "Help",
+        bindings: &[
+            bind("up / down", "Scroll"),
+            bind("page up / page down", "Scroll a screen at a time"),
+            bind("esc / ? / F1", "Close the help"),
+        ],
+    },
+];
+
+/// Title of the help section for the keys of a view
+fn get_section_title(view: &View) -> &'static str {
+    match view {
+        View::NoticeView { .. } => "Notice",
+        View::DraftView(_) => "Drafting a notice",
+        View::DirectoryView(_) | View::ListingView { .. } | View::DirectoryFileView(_) => {
+            "Directory"
+        }
+        View::GoToView => "Go to",
+        View::OverviewView => "Overview",
+        View::AttachmentsView { .. } => "Attached files",
+        View::DownloadsView(_) => "Downloads",
+        View::DraftsView(_) => "Drafts",
+        View::TemplateView(_) => "Templates",
+        View::KeyView(_) => "Reader key",
+        View::ErrorLogView(_) => "Error log",
+        View::LedgerView(_) => "Ledger",
+        View::NoticeListView(_) => "Notice list",
+        View::QrView(_) => "QR code",
+        View::IndexView(_) => "Index",
+        View::GraphView(_) => "Graph",
+        View::OpenView { .. } => "Opening outside Surf Bored",
+        View::ThemeView(_) => "Themes",
+        View::CommandView => "Vim keys",
+        View::HelpView { .. } => "Help",
+        _ => "Bored",
+    }
+}
+
+/// Every help section with the one for the view first, as it is what the user wants to know
+pub fn get_help_sections(view: &View) -> Vec<&'static HelpSection> {
+    let title = get_section_title(view);
+    let mut sections: Vec<&HelpSection> = HELP_SECTIONS.iter().collect();
+    sections.sort_by_key(|section| section.title != title);
+    sections
+}
+
+/// Rows the help takes, a title, a row for each binding and a blank row for every section
+pub fn get_row_count() -> usize {
+    HELP_SECTIONS
+        .iter()
+        .map(|section| section.bindings.len() + 2)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::DraftMode;
+
+    #[test]
+    fn test_get_help_sections() {
+        let sections = get_help_sections(&View::DirectoryView(0));
+        assert_eq!(sections.len(), HELP_SECTIONS.len());
+        assert_eq!(sections[0].title, "Directory");
+        // the rest keep their order
+        assert_eq!(sections[1].title, "Bored");
+        assert_eq!(sections[2].title, "Notice");
+        let sections = get_help_sections(&View::DraftView(DraftMode::Position));
+        assert_eq!(sections[0].title, "Drafting a notice");
+        assert_eq!(get_help_sections(&View::BoredView)[0].title, "Bored");
+    }
+}
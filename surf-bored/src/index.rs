@@ -0,0 +1,125 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::app::SurfBoredError;
+use bored::crawler::{CrawlIndex, DEFAULT_CRAWL_DEPTH};
+use bored::url::BoredAddress;
+use bored::x0x_client::X0xBoredClient;
+use std::fs;
+use std::path::Path;
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+
+/// The boreds found by crawling out from the directory, kept in a file next to it so they can
+/// be searched without crawling again. Crawls run in the background on a copy of the client.
+pub struct Index {
+    index: CrawlIndex,
+    crawling: bool,
+    sender: UnboundedSender<CrawlIndex>,
+    receiver: UnboundedReceiver<CrawlIndex>,
+}
+
+impl Index {
+    pub fn new() -> Index {
+        let (sender, receiver) = unbounded_channel();
+        Index {
+            index: CrawlIndex::default(),
+            crawling: false,
+            sender,
+            receiver,
+        }
+    }
+
+    /// The index kept in the file, empty if the file isn't there or can't be read
+    pub fn load_file(&mut self, path: &Path) {
+        self.index = fs::read_to_string(path)
+            .ok()
+            .and_then(|index_string| serde_json::from_str(&index_string).ok())
+            .unwrap_or_default();
+    }
+
+    pub fn save_file(&self, path: &Path) -> Result<(), SurfBoredError> {
+        let Ok(index_string) = serde_json::to_string(&self.index) else {
+            return Err(SurfBoredError::IndexSerialzationError);
+        };
+        let Ok(()) = fs::write(path, index_string) else {
+            return Err(SurfBoredError::IndexFileWriteError);
+        };
+        Ok(())
+    }
+
+    pub fn get(&self) -> &CrawlIndex {
+        &self.index
+    }
+
+    pub fn is_crawling(&self) -> bool {
+        self.crawling
+    }
+
+    /// Crawl out from the seeds, unless a crawl is already going
+    pub fn crawl(&mut self, client: &X0xBoredClient, seeds: Vec<BoredAddress>) {
+        if self.crawling {
+            return;
+        }
+        self.crawling = true;
+        let client = client.clone();
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            let index = CrawlIndex::crawl(&client, &seeds, DEFAULT_CRAWL_DEPTH).await;
+            let _ = sender.send(index);
+        });
+    }
+
+    /// Take in the index from a crawl that has finished since last time, returning it in
+    /// place of what was there before
+    pub fn update(&mut self) -> Option<&CrawlIndex> {
+        let mut finished = false;
+        while let Ok(index) = self.receiver.try_recv() {
+            self.index = index;
+            self.crawling = false;
+            finished = true;
+        }
+        finished.then_some(&self.index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bored::{Bored, Coordinate};
+
+    #[test]
+    fn test_index_file() -> Result<(), SurfBoredError> {
+        let path =
+            std::env::temp_dir().join(format!("surf-bored-index-{}.json", std::process::id()));
+        let mut index = Index::new();
+        index.load_file(&path);
+        assert!(index.get().get_entries().is_empty());
+        let address = BoredAddress::from_string("bored://chess")?;
+        index.index.add_bored(
+            address.clone(),
+            &Bored::create("Chess", Coordinate { x: 40, y: 10 }),
+            0,
+        );
+        index.save_file(&path)?;
+        let mut loaded = Index::new();
+        loaded.load_file(&path);
+        assert_eq!(loaded.get(), index.get());
+        assert!(loaded.get().get_entry(&address).is_some());
+        let _ = fs::remove_file(path);
+        Ok(())
+    }
+}
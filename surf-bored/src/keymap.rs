@@ -0,0 +1,235 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::app::{SurfBoredError, View};
+use ratatui::crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+/// Environment variable holding the keymap to use, "vim" for vim keys on top of the usual ones
+pub const KEYMAP_VAR: &str = "SURF_BORED_KEYMAP";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Keymap {
+    #[default]
+    Default,
+    /// hjkl to move, gg and G for the top and bottom, i to write a notice and : for commands
+    Vim,
+}
+
+/// The keymap from the SURF_BORED_KEYMAP setting, the usual keys if it isn't set or isn't known
+pub fn get_keymap(setting: Option<String>) -> Keymap {
+    match setting
+        .map(|keymap| keymap.trim().to_lowercase())
+        .as_deref()
+    {
+        Some("vim") => Keymap::Vim,
+        _ => Keymap::Default,
+    }
+}
+
+/// What a command typed after : does
+#[derive(Debug, PartialEq)]
+pub enum VimCommand {
+    /// Do what the key does on the bored
+    Key(KeyEvent),
+    /// Go to the bored at the address
+    GoTo(String),
+}
+
+/// Turns vim keys into the keys the views already handle, so there is only one place each
+/// action is done
+#[derive(Debug, Default)]
+pub struct VimKeys {
+    /// The first g of gg has been pressed
+    pending_g: bool,
+}
+
+impl VimKeys {
+    /// The key to handle in place of the one pressed, none while waiting for the second g of gg.
+    /// Keys are left alone while typing and in views that aren't moved around in.
+    pub fn translate(&mut self, key: KeyEvent, view: &View, typing: bool) -> Option<KeyEvent> {
+        let pending_g = std::mem::take(&mut self.pending_g);
+        if typing || !moves_through(view) || !(key.modifiers - KeyModifiers::SHIFT).is_empty() {
+            return Some(key);
+        }
+        let code = match key.code {
+            KeyCode::Char('h') => KeyCode::Left,
+            KeyCode::Char('j') => KeyCode::Down,
+            KeyCode::Char('k') => KeyCode::Up,
+            KeyCode::Char('l') => KeyCode::Right,
+            KeyCode::Char('G') => KeyCode::End,
+            KeyCode::Char('g') if pending_g => KeyCode::Home,
+            KeyCode::Char('g') => {
+                self.pending_g = true;
+                return None;
+            }
+            KeyCode::Char('i') if *view == View::BoredView => KeyCode::Char('n'),
+            _ => return Some(key),
+        };
+        Some(KeyEvent::new(code, KeyModifiers::NONE))
+    }
+}
+
+/// Views with a selection or a view to move, where hjkl and gg/G move
+fn moves_through(view: &View) -> bool {
+    matches!(
+        view,
+        View::BoredView
+            | View::DirectoryView(_)
+            | View::DownloadsView(_)
+            | View::DraftsView(_)
+            | View::ThemeView(_)
+            | View::ErrorLogView(_)
+            | View::LedgerView(_)
+            | View::NoticeListView(_)
+            | View::GraphView(_)
+            | View::TemplateView(_)
+            | View::OverviewView
+            | View::AttachmentsView { .. }
+            | View::HelpView { .. }
+    )
+}
+
+/// The command typed after :, most are named after what they open and do what its key does on
+/// the bored
+pub fn parse_command(command: &str) -> Result<VimCommand, SurfBoredError> {
+    let mut words = command.split_whitespace();
+    let name = words.next().unwrap_or_default();
+    let argument = words.next();
+    let key = match (name, argument) {
+        ("e" | "goto", Some(address)) => return Ok(VimCommand::GoTo(address.to_string())),
+        ("e" | "goto", None) => 'g',
+        ("q" | "quit", None) => 'q',
+        ("new", None) => 'n',
+        ("post", None) => 'p',
+        ("r" | "refresh", None) => 'r',
+        ("create", None) => 'c',
+        ("dir" | "directory", None) => 'd',
+        ("w" | "save", None) => 's',
+        ("yank", None) => 'y',
+        ("files", None) => 'f',
+        ("downloads", None) => 'l',
+        ("map", None) => 'm',
+        ("overview", None) => 'z',
+        ("themes", None) => 't',
+        ("drafts", None) => 'e',
+        ("key", None) => 'k',
+        ("errors", None) => 'E',
+        ("ledger", None) => 'L',
+        ("notices", None) => 'w',
+        ("qr", None) => 'Q',
+        ("peek", None) => 'x',
+        ("links", None) => 'C',
+        ("index", None) => 'I',
+        ("graph", None) => 'W',
+        ("linear", None) => 'v',
+        ("about", None) => 'a',
+        ("h" | "help", None) => '?',
+        _ => {
+            return Err(SurfBoredError::Message(format!(
+                "Not a command: {}",
+                command.trim()
+            )));
+        }
+    };
+    Ok(VimCommand::Key(KeyEvent::new(
+        KeyCode::Char(key),
+        KeyModifiers::NONE,
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_get_keymap() {
+        assert_eq!(get_keymap(None), Keymap::Default);
+        assert_eq!(get_keymap(Some(" Vim".to_string())), Keymap::Vim);
+        assert_eq!(get_keymap(Some("emacs".to_string())), Keymap::Default);
+    }
+
+    #[test]
+    fn test_translate() {
+        let mut vim_keys = VimKeys::default();
+        let bored = View::BoredView;
+        assert_eq!(
+            vim_keys.translate(press(KeyCode::Char('j')), &bored, false),
+            Some(press(KeyCode::Down))
+        );
+        assert_eq!(
+            vim_keys.translate(press(KeyCode::Char('i')), &bored, false),
+            Some(press(KeyCode::Char('n')))
+        );
+        // gg waits for the second g
+        assert_eq!(
+            vim_keys.translate(press(KeyCode::Char('g')), &bored, false),
+            None
+        );
+        assert_eq!(
+            vim_keys.translate(press(KeyCode::Char('g')), &bored, false),
+            Some(press(KeyCode::Home))
+        );
+        let shift_g = KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT);
+        assert_eq!(
+            vim_keys.translate(shift_g, &bored, false),
+            Some(press(KeyCode::End))
+        );
+        // a g followed by anything else is forgotten
+        assert_eq!(
+            vim_keys.translate(press(KeyCode::Char('g')), &bored, false),
+            None
+        );
+        assert_eq!(
+            vim_keys.translate(press(KeyCode::Char('k')), &bored, false),
+            Some(press(KeyCode::Up))
+        );
+        // typed text and control keys are left alone
+        let h = press(KeyCode::Char('h'));
+        assert_eq!(
+            vim_keys.translate(h, &View::DirectoryView(0), true),
+            Some(h)
+        );
+        assert_eq!(vim_keys.translate(h, &View::GoToView, false), Some(h));
+        let ctrl_h = KeyEvent::new(KeyCode::Char('h'), KeyModifiers::CONTROL);
+        assert_eq!(
+            vim_keys.translate(ctrl_h, &View::DirectoryView(0), false),
+            Some(ctrl_h)
+        );
+    }
+
+    #[test]
+    fn test_parse_command() {
+        assert_eq!(
+            parse_command(" e bored://welcome "),
+            Ok(VimCommand::GoTo("bored://welcome".to_string()))
+        );
+        assert_eq!(
+            parse_command("q"),
+            Ok(VimCommand::Key(press(KeyCode::Char('q'))))
+        );
+        assert_eq!(
+            parse_command("dir"),
+            Ok(VimCommand::Key(press(KeyCode::Char('d'))))
+        );
+        assert!(parse_command("").is_err());
+        assert!(parse_command("q now").is_err());
+    }
+}
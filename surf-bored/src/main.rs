@@ -15,181 +15,132 @@ You should have received a copy of the GNU Affero General Public License
 along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
-use app::{NoticeSelection, SurfBoredError};
-use bored::{BoredError, Coordinate, url::BoredAddress};
-use directory::Directory;
+use app::{MouseDrag, NoticeSelection, SurfBoredError};
+use bored::notice::{Attachment, get_hyperlinks};
+use bored::templates::TEMPLATES;
+use bored::{
+    BoredError, BoredHyperlinkMap, Coordinate, Coverage, WhatsOnTheBored, url::BoredAddress,
+};
+use clap::Parser;
+use cli::Cli;
+use directory::{Directory, HistoryDirection};
+use error_log::ErrorLog;
+use futures_core::Stream;
+use keymap::Keymap;
 use ratatui::{
     Terminal,
     backend::{Backend, CrosstermBackend},
     crossterm::{
-        event::{self, DisableMouseCapture, Event, KeyCode, KeyModifiers},
+        event::{
+            self, DisableBracketedPaste, DisableMouseCapture, EnableBracketedPaste,
+            EnableMouseCapture, Event, EventStream, KeyCode, KeyModifiers,
+            KeyboardEnhancementFlags, MouseButton, MouseEvent, MouseEventKind,
+            PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+        },
         execute,
-        terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
+        terminal::{
+            EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+            supports_keyboard_enhancement,
+        },
     },
-    layout::{Rect, Size},
+    layout::{Position, Rect, Size},
 };
 use std::{
     cmp::{max, min},
     error::Error,
-    fs, io,
+    env, fs, io,
+    pin::Pin,
 };
 
 mod app;
+mod cli;
 mod directory;
 mod display_bored;
+mod downloads;
+mod drafts;
+mod embeds;
+mod error_log;
+mod graph;
+mod help;
+mod index;
+mod keymap;
+mod openers;
+mod petnames;
+mod previews;
+mod qr;
+mod session;
+mod tasks;
 mod theme;
+mod toasts;
 mod ui;
-use crate::app::{App, CreateMode, DraftMode, HyperlinkMode, View};
-use crate::ui::{safe_subtract_u16, ui, wait_pop_up};
+mod watch;
+use crate::app::{App, CreateMode, DirectoryFileAction, DraftMode, HyperlinkMode, KeyMode, View};
+use crate::downloads::DownloadStatus;
+use crate::graph::GraphStep;
+use crate::tasks::AppEvent;
+use crate::toasts::ToastKind;
+use crate::ui::{safe_subtract_u16, ui};
+
+/// How many rows or columns the view moves for each step of the scroll wheel
+const SCROLL_STEP: i32 = 3;
+/// How often the screen is redrawn when no keys are pressed
+const REDRAW_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
+    let cli = Cli::parse();
     let mut app = App::new();
-    println!("Trying to connect to x0x daemon...");
-    if let Err(e) = app.init_client().await {
-        eprintln!("\nError: {}", e);
-        
-        match e {
-            BoredError::ClientConnectionError => {
-                let check_cmd = if cfg!(target_os = "windows") { "x0x.exe" } else { "x0x" };
-                let is_installed = std::process::Command::new(check_cmd)
-                    .arg("--version")
-                    .output()
-                    .map(|out| out.status.success())
-                    .unwrap_or(false);
-
-                let is_termux = std::env::var("TERMUX_VERSION").is_ok() || std::path::Path::new("/data/data/com.termux").exists();
-
-                if is_installed {
-                    eprintln!("\nThe x0x daemon is installed but currently stopped.");
-                    eprintln!("It must be running in the background for this application to work.");
-                    eprint!("\nWould you like me to attempt to start the x0x daemon for you now? (y/N): ");
-                } else {
-                    eprintln!("\nPlease ensure the x0x daemon is installed and running on your system.");
-                    eprintln!("For documentation and configuration details, visit: https://x0x.md");
-                    eprintln!("GitHub repository: https://github.com/saorsa-labs/x0x");
-                    
-                    if cfg!(target_os = "windows") {
-                        eprintln!("\nTo install on Windows:");
-                        eprintln!("1. Download the latest Windows zip from: https://github.com/saorsa-labs/x0x/releases");
-                        eprintln!("2. Extract x0xd.exe and x0x.exe, and place them in your PATH.");
-                        eprintln!("3. Run: x0x start");
-                        std::process::exit(1);
-                    } else if is_termux {
-                        eprintln!("\nTo install on Android (Termux):");
-                        eprintln!("1. Clone the x0x repository: git clone https://github.com/saorsa-labs/x0x.git");
-                        eprintln!("2. Build from source: cargo build --release --bin x0xd --bin x0x");
-                        eprintln!("3. Copy target/release/x0x and x0xd to your path (e.g. $PREFIX/bin/).");
-                        eprintln!("4. Run: x0x start");
-                        std::process::exit(1);
-                    } else {
-                        eprint!("\nWould you like me to attempt to install and start the x0x daemon for you now? (y/N): ");
-                    }
-                }
-
-                use std::io::Write;
-                let _ = std::io::stderr().flush();
-                
-                let mut input = String::new();
-                if std::io::stdin().read_line(&mut input).is_ok() {
-                    let choice = input.trim().to_lowercase();
-                    if choice == "y" || choice == "yes" {
-                        let status = if is_installed {
-                            eprintln!("\nStarting x0x daemon...");
-                            let start_cmd = if cfg!(target_os = "windows") { "x0x.exe" } else { "x0x" };
-                            eprintln!("Running: {} start", start_cmd);
-                            std::process::Command::new(start_cmd)
-                                .arg("start")
-                                .status()
-                        } else {
-                            eprintln!("\nInstalling and starting x0x daemon via standard installer...");
-                            eprintln!("Running: curl -sfL https://x0x.md | sh -s -- --start");
-                            std::process::Command::new("sh")
-                                .arg("-c")
-                                .arg("curl -sfL https://x0x.md | sh -s -- --start")
-                                .status()
-                        };
-                            
-                        match status {
-                            Ok(s) if s.success() => {
-                                eprintln!("\nAction completed successfully! Waiting 3 seconds for the daemon to spin up...");
-                                std::thread::sleep(std::time::Duration::from_secs(3));
-                                
-                                eprintln!("Re-connecting to x0x daemon...");
-                                match app.init_client().await {
-                                    Ok(_) => {
-                                        eprintln!("Connected successfully! Booting We Are Bored TUI...");
-                                        std::thread::sleep(std::time::Duration::from_secs(1));
-                                    }
-                                    Err(e2) => {
-                                        eprintln!("Failed to connect even after successful action: {}", e2);
-                                        std::process::exit(1);
-                                    }
-                                }
-                            }
-                            _ => {
-                                if is_installed {
-                                    let start_cmd = if cfg!(target_os = "windows") { "x0x.exe" } else { "x0x" };
-                                    eprintln!("\nFailed to start x0x daemon automatically.");
-                                    eprintln!("Please try running it manually in your shell:");
-                                    eprintln!("  {} start", start_cmd);
-                                } else {
-                                    eprintln!("\nFailed to execute installer automatically.");
-                                    eprintln!("Please try running the installer command manually in your shell:");
-                                    eprintln!("  curl -sfL https://x0x.md | sh -s -- --start");
-                                }
-                                std::process::exit(1);
-                            }
-                        }
-                    } else {
-                        std::process::exit(1);
-                    }
-                } else {
-                    std::process::exit(1);
-                }
-            }
-            _ => {
-                eprintln!("\n⚠️  The x0x daemon is running on this machine, but access was denied.");
-                eprintln!("\nThis usually happens when there are multiple users on the same computer.");
-                eprintln!("Because x0x uses port 12700 for local communication, if another user");
-                eprintln!("(or a background service) is already running the daemon, your app will");
-                eprintln!("connect to their daemon but get rejected due to a token mismatch.");
-
-                let token_path = if cfg!(target_os = "windows") {
-                    "%APPDATA%\\x0x\\api-token"
-                } else if cfg!(target_os = "macos") {
-                    "~/Library/Application Support/x0x/api-token"
-                } else {
-                    "~/.local/share/x0x/api-token"
-                };
-
-                eprintln!("\nTo fix this, you can either:");
-                eprintln!("1. Stop the daemon running on the other user account.");
-                eprintln!("2. Share the API token ({}) between users.", token_path);
-                eprintln!("3. Run your daemon on a different port using `x0xd --api-port <PORT>`.");
-                std::process::exit(1);
-            }
-        }
+    app.read_only = cli.read_only;
+    app.count_views = cli.count_views;
+    app.work_limit = cli.get_work_limit();
+    if let Some(data_dir) = cli.get_data_dir() {
+        fs::create_dir_all(data_dir)?;
+    }
+    if let Some(directory_path) = cli.get_directory_path() {
+        app.directory_path = directory_path.to_string_lossy().to_string();
+    }
+    if let Some(log_file) = &cli.log_file {
+        app.error_log = ErrorLog::with_file(log_file.clone());
     }
-    
+    // connect in the background so the ui comes up straight away
+    app.start_connecting(cli.get_data_dir(), cli.get_daemon());
+
     // setup terminal
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen,)?;
+    // pasted text arrives as one event rather than a key press for each character
+    execute!(
+        stdout,
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
+    app.keyboard_enhanced = enable_keyboard_enhancement()?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // run the app
-    let _res = run_app(&mut terminal, &mut app).await?;
+    let _res = run_app(&mut terminal, &mut app, &cli).await?;
+    // keep where the user was so the next session starts there
+    let saved_session = app.save_session();
+    let synced_directory = app.sync_directory();
 
     // restore terminal
+    disable_keyboard_enhancement(app.keyboard_enhanced)?;
     disable_raw_mode()?;
     execute!(
         terminal.backend_mut(),
         LeaveAlternateScreen,
         DisableMouseCapture,
+        DisableBracketedPaste,
     )?;
     terminal.show_cursor()?;
+    if let Err(e) = saved_session {
+        eprintln!("{e}");
+    }
+    if let Err(e) = synced_directory {
+        eprintln!("Directory not synced: {e}");
+    }
 
     Ok(())
 }
@@ -197,76 +148,192 @@ async fn main() -> Result<(), Box<dyn Error>> {
 async fn run_app<B: Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
+    cli: &Cli,
 ) -> Result<(), Box<dyn Error>> {
-    let previous_buffer = terminal.draw(|f| ui(f, app))?.buffer.clone();
     if let Err(_) = app.load_directory() {
         app.directory = Directory::default();
         let _ = app.save_directory();
     }
+    if let Err(e) = app.load_themes() {
+        app.display_error(e);
+    }
+    if let Some(theme) = &cli.theme
+        && let Err(e) = app.use_theme_named(theme)
+    {
+        app.display_error(e);
+    }
+    if let Err(e) = app.load_openers() {
+        app.display_error(e);
+    }
+    if let Err(e) = app.load_petnames() {
+        app.display_error(e);
+    }
+    app.load_drafts();
+    app.load_index();
+    app.load_reader_key();
+    // pick up boreds saved on other machines
+    if let Err(e) = app.sync_directory() {
+        app.display_error(e);
+    }
 
-    if let Some(home_address) = app.directory.get_home() {
-        match BoredAddress::from_string(home_address) {
-            Ok(home_address) => {
-                let theme = app.theme.clone();
-                let going_to_bored = app.goto_bored(home_address.clone());
-                let res = wait_pop_up(
-                    terminal,
-                    previous_buffer.clone(),
-                    going_to_bored,
-                    "Loading board from x0x...",
-                    theme.clone(),
-                )
-                .await;
-
-                if let Err(err) = res {
-                    // If welcome board does not exist on network, create it!
-                    let is_welcome_board = home_address.to_string() == "bored://welcome";
-                    if is_welcome_board {
-                        // Define future to create the board and add the welcome notice
-                        let create_and_init = async {
-                            app.create_bored_on_network("Welcome", Coordinate { x: 120, y: 40 }, Some("welcome")).await?;
-                            app.create_draft(Coordinate { x: 55, y: 6 })?;
-                            app.edit_draft("Welcome to the we are bored network\nrunning on the [x0x](https://x0x.md) network.")?;
-                            app.position_draft(Coordinate { x: 32, y: 17 })?;
-                            app.add_draft_to_bored().await?;
-                            app.change_view(View::BoredView);
-                            app.content_input = String::new();
-                            if let Some(bored) = app.get_current_bored() {
-                                if !bored.get_notices().is_empty() {
-                                    app.selected_notice = Some(0);
-                                }
-                            }
-                            Ok(())
-                        };
-                        match wait_pop_up(
-                            terminal,
-                            previous_buffer,
-                            create_and_init,
-                            "Initializing Welcome board...",
-                            theme,
-                        )
-                        .await {
-                            Err(e) => app.display_error(e),
-                            _ => (),
-                        }
+    // start at the bored asked for, or carry on from where the last session ended, otherwise
+    // start at home
+    let started = match (&cli.address, app.load_session()) {
+        (Some(address), _) => app.start_at(address),
+        (None, Some(session)) => app
+            .restore_session(session)
+            .or_else(|_| app.start_at_home()),
+        (None, None) => app.start_at_home(),
+    };
+    if let Err(e) = started {
+        app.display_error(e);
+    }
+
+    let mut events = EventStream::new();
+    loop {
+        if let Some(command) = app.take_terminal_command() {
+            // the opener reads the terminal itself, so stop reading events until it is done
+            drop(events);
+            let opened = run_opener(terminal, app.keyboard_enhanced, &command);
+            events = EventStream::new();
+            if let Err(e) = opened {
+                app.display_error(e);
+            }
+        }
+        app.downloads.update();
+        app.toasts.update();
+        terminal.draw(|f| ui(f, app))?;
+        // a key stood for by a command is handled as it is, without the keymap changing it
+        let pending_key = app.take_pending_key();
+        let pressed = pending_key.is_none();
+        let app_event = match pending_key {
+            Some(key) => AppEvent::Terminal(Event::Key(key)),
+            // wake up regularly so download progress and the waiting animation are redrawn
+            // without waiting for a key press
+            None => tokio::select! {
+                event = next_event(&mut events) => AppEvent::Terminal(event?),
+                app_event = app.tasks.recv() => app_event,
+                _ = tokio::time::sleep(REDRAW_INTERVAL) => AppEvent::Tick,
+            },
+        };
+        let event = match app_event {
+            AppEvent::Terminal(event) => event,
+            AppEvent::Tick => {
+                app.check_task_timeout();
+                app.auto_refresh();
+                app.check_watched();
+                app.preview_links();
+                app.resolve_embeds();
+                app.check_crawl();
+                app.check_connection();
+                app.publish_pending();
+                continue;
+            }
+            AppEvent::TaskFinished {
+                client,
+                on_finished,
+                ..
+            } => {
+                app.finish_task(client, on_finished);
+                continue;
+            }
+            AppEvent::ConnectionChecked(check) => {
+                app.finish_connection_check(check);
+                continue;
+            }
+            AppEvent::Connected(client) => {
+                if let Err(e) = app.finish_connecting(client) {
+                    let started = run_outside_terminal(terminal, app.keyboard_enhanced, || {
+                        offer_to_start_daemon(&e)
+                    })?;
+                    if started {
+                        app.start_connecting(cli.get_data_dir(), cli.get_daemon());
                     } else {
-                        app.display_error(err);
+                        app.display_error(e.into());
                     }
                 }
+                continue;
             }
-            Err(e) => app.display_error(app::SurfBoredError::BoredError(e)),
         };
-    }
-
-    loop {
-        let previous_buffer = terminal.draw(|f| ui(f, app))?.buffer.clone();
-        if let Event::Key(key) = event::read()? {
+        if let Event::Resize(..) = event {
+            // draw at the new size first so the view knows how much of the bored fits in it
+            terminal.draw(|f| ui(f, app))?;
+            app.fit_view_to_terminal();
+            continue;
+        }
+        if app.tasks.is_running() {
+            // while waiting on x0x keys only stop the wait or quit, anything else could change
+            // what the task is working on
+            if let Event::Key(key) = event
+                && key.kind != event::KeyEventKind::Release
+            {
+                if key.code == KeyCode::Esc {
+                    app.tasks.cancel();
+                } else if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
+                    break;
+                }
+            }
+            continue;
+        }
+        if let Event::Mouse(mouse) = event {
+            match app.current_view {
+                View::BoredView => handle_mouse(app, mouse),
+                View::DraftView(DraftMode::Position) => handle_draft_mouse(app, mouse),
+                View::OverviewView => {
+                    if let MouseEventKind::Down(MouseButton::Left) = mouse.kind
+                        && let Some(notice_index) = app
+                            .overview
+                            .as_ref()
+                            .and_then(|overview| overview.get_notice_at(mouse.column, mouse.row))
+                    {
+                        app.selected_notice = Some(notice_index);
+                        app.revert_view();
+                        try_select_notice(app, NoticeSelection::Current);
+                    }
+                }
+                _ => app.mouse_drag = None,
+            }
+            continue;
+        }
+        if let Event::Paste(text) = event {
+            if app.accepts_paste()
+                && let Err(e) = app.paste(&text)
+            {
+                app.display_error(e);
+            }
+            continue;
+        }
+        if let Event::Key(key) = event {
             if key.kind == event::KeyEventKind::Release {
                 // Skip events that are not KeyEvenKind::Press
                 continue;
             }
+            let key = match app.keymap {
+                Keymap::Vim if pressed => {
+                    let typing = app.accepts_paste();
+                    match app.vim_keys.translate(key, &app.current_view, typing) {
+                        Some(key) => key,
+                        // waiting for the rest of the key
+                        None => continue,
+                    }
+                }
+                _ => key,
+            };
             if key.code == KeyCode::Char('c') && key.modifiers == KeyModifiers::CONTROL {
                 break;
+            } else if key.code == KeyCode::Char('v')
+                && key.modifiers == KeyModifiers::CONTROL
+                && app.accepts_paste()
+            {
+                if let Err(e) = app.paste_from_clipboard() {
+                    app.display_error(e);
+                }
+            } else if key.code == KeyCode::F(1) {
+                if let View::HelpView { .. } = app.current_view {
+                    app.close_help();
+                } else {
+                    app.open_help();
+                }
             } else {
                 match &app.current_view {
                     View::ErrorView(_) => match key.code {
@@ -275,11 +342,56 @@ async fn run_app<B: Backend>(
                         KeyCode::Char('q') => break,
                         _ => {}
                     },
+                    View::OpenView { .. } => match key.code {
+                        KeyCode::Char('y') | KeyCode::Enter => {
+                            if let Err(e) = app.confirm_open() {
+                                app.show_toast(ToastKind::Warning, &e.to_string());
+                            }
+                        }
+                        KeyCode::Char('n') | KeyCode::Esc => app.cancel_open(),
+                        KeyCode::Char('?') => app.open_help(),
+                        _ => {}
+                    },
+                    View::HelpView { .. } => match key.code {
+                        KeyCode::Esc | KeyCode::Char('?') | KeyCode::Char('q') => app.close_help(),
+                        KeyCode::Up => app.scroll_help(-1),
+                        KeyCode::Down => app.scroll_help(1),
+                        KeyCode::PageUp => {
+                            let page = view_height(app);
+                            app.scroll_help(-page)
+                        }
+                        KeyCode::PageDown => {
+                            let page = view_height(app);
+                            app.scroll_help(page)
+                        }
+                        KeyCode::Home => app.scroll_help(i32::MIN),
+                        KeyCode::End => app.scroll_help(i32::MAX),
+                        _ => {}
+                    },
                     View::BoredView => match key.code {
                         KeyCode::Char('q') => break,
+                        KeyCode::Char('?') => app.open_help(),
                         KeyCode::Tab => try_select_notice(app, NoticeSelection::Next),
                         KeyCode::BackTab => try_select_notice(app, NoticeSelection::Previous),
-                        KeyCode::Esc => app.menu_visible = false,
+                        KeyCode::Esc => {
+                            if app.menu_visible {
+                                app.menu_visible = false;
+                            } else {
+                                app.clear_search();
+                            }
+                        }
+                        KeyCode::Char('/') => {
+                            app.search_input = app.search.clone().unwrap_or_default();
+                            app.change_view(View::SearchView);
+                        }
+                        KeyCode::Char(':') if app.keymap == Keymap::Vim => app.open_command(),
+                        // next and previous match while searching, otherwise n is a new notice
+                        KeyCode::Char('n') if app.search.is_some() => {
+                            app.jump_to_search_hit(true);
+                        }
+                        KeyCode::Char('N') if app.search.is_some() => {
+                            app.jump_to_search_hit(false);
+                        }
                         KeyCode::Char(' ') => {
                             if app.menu_visible {
                                 app.menu_visible = false;
@@ -287,6 +399,44 @@ async fn run_app<B: Backend>(
                                 app.menu_visible = true;
                             }
                         }
+                        KeyCode::Left if key.modifiers == KeyModifiers::ALT => {
+                            surf_history(app, HistoryDirection::Back)
+                        }
+                        KeyCode::Right if key.modifiers == KeyModifiers::ALT => {
+                            surf_history(app, HistoryDirection::Forward)
+                        }
+                        // ctrl + arrows, page up/down and home/end move the view on their own
+                        KeyCode::Up if key.modifiers == KeyModifiers::CONTROL => {
+                            pan_view(app, (0, -SCROLL_STEP))
+                        }
+                        KeyCode::Down if key.modifiers == KeyModifiers::CONTROL => {
+                            pan_view(app, (0, SCROLL_STEP))
+                        }
+                        KeyCode::Left if key.modifiers == KeyModifiers::CONTROL => {
+                            pan_view(app, (-SCROLL_STEP, 0))
+                        }
+                        KeyCode::Right if key.modifiers == KeyModifiers::CONTROL => {
+                            pan_view(app, (SCROLL_STEP, 0))
+                        }
+                        KeyCode::PageUp => {
+                            let page = view_height(app);
+                            pan_view(app, (0, -page))
+                        }
+                        KeyCode::PageDown => {
+                            let page = view_height(app);
+                            pan_view(app, (0, page))
+                        }
+                        // the linear view moves through notices in reading order instead
+                        KeyCode::Up | KeyCode::Left if app.linear_mode => {
+                            app.select_in_reading_order(-1)
+                        }
+                        KeyCode::Down | KeyCode::Right if app.linear_mode => {
+                            app.select_in_reading_order(1)
+                        }
+                        KeyCode::Home if app.linear_mode => app.select_in_reading_order(i32::MIN),
+                        KeyCode::End if app.linear_mode => app.select_in_reading_order(i32::MAX),
+                        KeyCode::Home => pan_view(app, (i32::MIN, i32::MIN)),
+                        KeyCode::End => pan_view(app, (i32::MAX, i32::MAX)),
                         KeyCode::Up => {
                             try_select_notice(app, NoticeSelection::Direction(bored::Direction::Up))
                         }
@@ -348,36 +498,82 @@ async fn run_app<B: Backend>(
                                 app.revert_view();
                             }
                         }
-                        KeyCode::Char('g') => app.change_view(View::GoToView),
-                        KeyCode::Char('d') => app.change_view(View::DirectoryView(0)),
-                        KeyCode::Char('r') | KeyCode::F(5) => {
-                            if let Some(bored_address) = app.get_current_address() {
-                                let theme = app.theme.clone();
-                                let going_to_bored = app.goto_bored(bored_address);
-                                match wait_pop_up(
-                                    terminal,
-                                    previous_buffer,
-                                    going_to_bored,
-                                    "Loading board from x0x...",
-                                    theme,
-                                )
-                                .await
-                                {
-                                    Err(e) => app.display_error(e),
-                                    _ => app.goto_input = String::new(),
+                        KeyCode::Char('p') => {
+                            if let Some(bored) = app.get_current_bored() {
+                                let draft_dimensions =
+                                    generate_notice_size(terminal.size()?, bored.get_dimensions());
+                                if let Err(e) = app.start_guestbook_entry(draft_dimensions) {
+                                    app.display_error(e);
                                 }
                             }
                         }
-                        KeyCode::Char('a') => match app.hyperlink_command("about").await {
+                        KeyCode::Char('g') => app.open_goto(),
+                        KeyCode::Char('d') => app.change_view(View::DirectoryView(0)),
+                        KeyCode::Char('l') => app.change_view(View::DownloadsView(0)),
+                        KeyCode::Char('m') => app.minimap_visible = !app.minimap_visible,
+                        KeyCode::Char('z') => app.change_view(View::OverviewView),
+                        KeyCode::Char('t') => app.open_themes(),
+                        KeyCode::Char('e') => app.change_view(View::DraftsView(0)),
+                        KeyCode::Char('k') => app.change_view(View::KeyView(KeyMode::Show)),
+                        KeyCode::Char('E') => app.change_view(View::ErrorLogView(0)),
+                        KeyCode::Char('L') => {
+                            if let Err(e) = app.open_ledger() {
+                                app.display_error(e);
+                            }
+                        }
+                        KeyCode::Char('w') => app.change_view(View::NoticeListView(0)),
+                        KeyCode::Char('Q') => {
+                            if let Err(e) = app.open_qr() {
+                                app.display_error(e);
+                            }
+                        }
+                        KeyCode::Char('x') => {
+                            if let Err(e) = app.peek_under() {
+                                app.display_error(e);
+                            }
+                        }
+                        KeyCode::Char('C') => {
+                            if let Err(e) = app.check_links() {
+                                app.display_error(e);
+                            }
+                        }
+                        KeyCode::Char('I') => app.open_index(),
+                        KeyCode::Char('W') => app.open_graph(),
+                        KeyCode::Char('v') => app.linear_mode = !app.linear_mode,
+                        KeyCode::Char('y') => match app.copy_current_address() {
                             Err(e) => app.display_error(e),
-                            _ => (),
+                            Ok(address) => app.show_toast(
+                                ToastKind::Success,
+                                &format!("Copied {} to the clipboard.", address),
+                            ),
                         },
-                        KeyCode::Char('s') => {
-                            match app.save_current_bored_to_directory() {
-                                Err(e) => app.display_error(e),
-                                Ok(_) => app.display_error(app::SurfBoredError::Message("Successfully added board to directory!".to_string())),
+                        KeyCode::Backspace => surf_history(app, HistoryDirection::Back),
+                        KeyCode::Char('f') => app.change_view(View::AttachmentsView {
+                            index: 0,
+                            selected: vec![],
+                        }),
+                        KeyCode::Char('r') | KeyCode::F(5) => {
+                            if let Err(e) = app.refresh_bored() {
+                                app.display_error(e);
+                            }
+                        }
+                        KeyCode::Char('a') => {
+                            if let Err(e) = app.hyperlink_command("about") {
+                                app.display_error(e);
+                            }
+                        }
+                        KeyCode::Char('b') => {
+                            if let Err(e) = app.start_bookmark() {
+                                app.display_error(e);
                             }
                         }
+                        KeyCode::Char('s') => match app.save_current_bored_to_directory() {
+                            Err(e) => app.display_error(e),
+                            Ok(_) => app.show_toast(
+                                ToastKind::Success,
+                                "Added the bored to the directory.",
+                            ),
+                        },
                         _ => {}
                     },
                     View::NoticeView { .. } => match key.code {
@@ -386,14 +582,18 @@ async fn run_app<B: Backend>(
                             app.revert_view();
                         }
                         KeyCode::Char('q') => break,
+                        KeyCode::Char('?') => app.open_help(),
                         KeyCode::Tab => app.next_hyperlink(),
                         KeyCode::BackTab => app.previous_hyperlink(),
+                        KeyCode::Char(number @ '1'..='9') => {
+                            let hyperlinks_index = number as usize - '1' as usize;
+                            if let Err(e) = app.activate_hyperlink(hyperlinks_index) {
+                                app.display_error(e);
+                            }
+                        }
                         KeyCode::Enter => {
                             if let Some(hyperlink) = app.get_selected_hyperlink() {
-                                if let Err(e) = app
-                                    .handle_hyperlink(hyperlink, terminal, previous_buffer)
-                                    .await
-                                {
+                                if let Err(e) = app.handle_hyperlink(hyperlink) {
                                     app.display_error(e);
                                 }
                             }
@@ -401,30 +601,125 @@ async fn run_app<B: Backend>(
                         KeyCode::Char('o') => {
                             fs::write("notice", format!("{:?}", app.get_selected_notice()))?;
                         }
+                        KeyCode::Char('y') => {
+                            let copying_link = app.get_selected_hyperlink().is_some();
+                            match app.copy_selected_notice() {
+                                Err(e) => app.display_error(e),
+                                Ok(_) if copying_link => app.show_toast(
+                                    ToastKind::Success,
+                                    "Copied the link to the clipboard.",
+                                ),
+                                Ok(_) => app.show_toast(
+                                    ToastKind::Success,
+                                    "Copied the notice text to the clipboard.",
+                                ),
+                            }
+                        }
+                        KeyCode::Char('m') => match app.pick_notice_to_embed() {
+                            Err(e) => app.display_error(e),
+                            Ok(_) => app.show_toast(
+                                ToastKind::Success,
+                                "Picked the notice, press (ctrl + o) while writing a notice to embed it.",
+                            ),
+                        },
+                        KeyCode::Char('p') => {
+                            if let Err(e) = app.start_naming_author() {
+                                app.display_error(e);
+                            }
+                        }
+                        KeyCode::Char('c') => match app.export_events() {
+                            Err(e) => app.display_error(e),
+                            Ok(path) => app.show_toast(
+                                ToastKind::Success,
+                                &format!("Saved the events to {}", path.display()),
+                            ),
+                        },
+                        KeyCode::Char('d') => match app.download_selected_attachment() {
+                            Err(e) => app.display_error(e),
+                            _ => app.change_view(View::DownloadsView(
+                                app.downloads.get_downloads().len().saturating_sub(1),
+                            )),
+                        },
+                        _ => {}
+                    },
+                    View::BookmarkView => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Backspace => {
+                            app.bookmark_name_input.pop();
+                        }
+                        KeyCode::Char(value) => app.bookmark_name_input.push(value),
+                        KeyCode::Enter => match app.save_bookmark() {
+                            Err(e) => app.display_error(e),
+                            Ok(_) => app.show_toast(
+                                ToastKind::Success,
+                                "Added the bored to the directory.",
+                            ),
+                        },
+                        _ => {}
+                    },
+                    View::PetnameView => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Backspace => {
+                            app.petname_input.pop();
+                        }
+                        KeyCode::Char(value) => app.petname_input.push(value),
+                        KeyCode::Enter => match app.save_petname() {
+                            Err(e) => app.display_error(e),
+                            Ok(_) => app.show_toast(ToastKind::Success, "Saved the name."),
+                        },
+                        _ => {}
+                    },
+                    View::SearchView => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Backspace => {
+                            app.search_input.pop();
+                        }
+                        KeyCode::Char(value) => app.search_input.push(value),
+                        KeyCode::Enter => {
+                            if let Err(e) = app.start_search() {
+                                app.display_error(e);
+                            }
+                        }
+                        _ => {}
+                    },
+                    View::CommandView => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Backspace => {
+                            app.command_input.pop();
+                        }
+                        KeyCode::Char(value) => app.command_input.push(value),
+                        KeyCode::Enter => {
+                            if let Err(e) = app.run_command() {
+                                app.display_error(e);
+                            }
+                        }
                         _ => {}
                     },
                     View::GoToView => match key.code {
                         KeyCode::Esc => app.revert_view(),
                         KeyCode::Backspace => {
                             app.goto_input.pop();
+                            app.goto_selected = None;
+                        }
+                        KeyCode::Char(value) => {
+                            app.goto_input.push(value);
+                            app.goto_selected = None;
+                        }
+                        KeyCode::Up => app.select_goto_suggestion(true),
+                        KeyCode::Down => app.select_goto_suggestion(false),
+                        // fill in the address of the selected or first suggestion to edit it
+                        KeyCode::Tab => {
+                            if app.goto_selected.is_none() {
+                                app.select_goto_suggestion(false);
+                            }
+                            app.goto_input = app.get_goto_address();
+                            app.goto_selected = None;
                         }
-                        KeyCode::Char(value) => app.goto_input.push(value),
                         KeyCode::Enter => {
-                            match BoredAddress::from_string(&app.goto_input) {
+                            match BoredAddress::from_string(&app.get_goto_address()) {
                                 Ok(address) => {
-                                    let theme = app.theme.clone();
-                                    let going_to_bored = app.goto_bored(address);
-                                    match wait_pop_up(
-                                        terminal,
-                                        previous_buffer,
-                                        going_to_bored,
-                                        "Loading board from x0x...",
-                                        theme,
-                                    )
-                                    .await
-                                    {
-                                        Err(e) => app.display_error(e),
-                                        _ => app.goto_input = String::new(),
+                                    if let Err(e) = app.goto_bored(address) {
+                                        app.display_error(e);
                                     }
                                 }
                                 Err(e) => app.display_error(app::SurfBoredError::BoredError(e)),
@@ -432,41 +727,92 @@ async fn run_app<B: Backend>(
                         }
                         _ => {}
                     },
-                    &View::DirectoryView(directory_index) => match key.code {
+                    &View::DirectoryView(directory_row) => match key.code {
+                        // typing in the search box filters the directory as you go
+                        KeyCode::Char(c)
+                            if key.modifiers != KeyModifiers::CONTROL
+                                && app.directory_search.is_some() =>
+                        {
+                            app.directory_search.get_or_insert_default().push(c);
+                            app.current_view = View::DirectoryView(0);
+                        }
+                        KeyCode::Backspace if app.directory_search.is_some() => {
+                            app.directory_search.get_or_insert_default().pop();
+                            app.current_view = View::DirectoryView(0);
+                        }
+                        KeyCode::Char('/') => {
+                            app.directory_search = Some(String::new());
+                            app.current_view = View::DirectoryView(0);
+                        }
+                        KeyCode::Esc if app.directory_search.is_some() => {
+                            app.directory_search = None;
+                            app.current_view = View::DirectoryView(0);
+                        }
                         KeyCode::Esc => app.revert_view(),
+                        KeyCode::Char('?') => app.open_help(),
+                        KeyCode::Up | KeyCode::Down if key.modifiers == KeyModifiers::SHIFT => {
+                            match app.move_directory_item(directory_row, key.code == KeyCode::Up) {
+                                Ok(new_directory_row) => {
+                                    app.current_view = View::DirectoryView(new_directory_row)
+                                }
+                                Err(e) => app.display_error(e),
+                            }
+                        }
+                        KeyCode::Delete => match app.remove_directory_item(directory_row) {
+                            Ok(new_directory_row) => {
+                                app.current_view = View::DirectoryView(new_directory_row)
+                            }
+                            Err(e) => app.display_error(e),
+                        },
+                        KeyCode::Char('e') => {
+                            if let Err(e) = app.edit_directory_item(directory_row) {
+                                app.display_error(e);
+                            }
+                        }
+                        KeyCode::Char('i') => {
+                            app.directory_file_input = String::new();
+                            app.change_view(View::DirectoryFileView(DirectoryFileAction::Import));
+                        }
+                        KeyCode::Char('x') => {
+                            app.directory_file_input = String::from("directory_of_boreds.html");
+                            app.change_view(View::DirectoryFileView(DirectoryFileAction::Export));
+                        }
+                        KeyCode::Char('w') => match app.toggle_watched(directory_row) {
+                            Ok(true) => app.show_toast(
+                                ToastKind::Success,
+                                "Watching the bored for new notices",
+                            ),
+                            Ok(false) => {
+                                app.show_toast(ToastKind::Success, "Stopped watching the bored")
+                            }
+                            Err(e) => app.display_error(e),
+                        },
+                        KeyCode::Char('t') => {
+                            app.next_directory_tag_filter();
+                            app.current_view = View::DirectoryView(0);
+                        }
                         KeyCode::Up => {
-                            let new_directroy_index =
-                                app.previous_directory_item(directory_index)?;
+                            let new_directroy_index = app.previous_directory_item(directory_row)?;
                             app.change_view(View::DirectoryView(new_directroy_index));
                         }
                         KeyCode::Down => {
-                            let new_directroy_index = app.next_directory_item(directory_index)?;
+                            let new_directroy_index = app.next_directory_item(directory_row)?;
                             app.change_view(View::DirectoryView(new_directroy_index));
                         }
                         KeyCode::Char('h') => {
                             if key.modifiers == KeyModifiers::CONTROL {
-                                app.set_home(directory_index)?;
+                                app.set_home(app.get_directory_index(directory_row)?)?;
                             }
                         }
                         KeyCode::Enter => {
+                            let directory_index = app.get_directory_index(directory_row)?;
                             let bored_address = app.directory.get_bored_address(directory_index)?;
                             match &app.interupted_view {
                                 View::BoredView => {
                                     match BoredAddress::from_string(&bored_address.bored_address) {
                                         Ok(address) => {
-                                            let theme = app.theme.clone();
-                                            let going_to_bored = app.goto_bored(address);
-                                            match wait_pop_up(
-                                                terminal,
-                                                previous_buffer,
-                                                going_to_bored,
-                                                "Loading board from x0x...",
-                                                theme,
-                                            )
-                                            .await
-                                            {
-                                                Err(e) => app.display_error(e),
-                                                _ => app.goto_input = String::new(),
+                                            if let Err(e) = app.goto_bored(address) {
+                                                app.display_error(e);
                                             }
                                         }
                                         Err(e) => {
@@ -488,93 +834,556 @@ async fn run_app<B: Backend>(
                         }
                         _ => {}
                     },
-                    View::CreateView(create_view) => match key.code {
-                        KeyCode::Tab => app.current_view = View::CreateView(create_view.toggle()),
-                        KeyCode::Esc => app.revert_view(),
-                        KeyCode::Backspace => match create_view {
-                            CreateMode::Name => {
-                                app.name_input.pop();
-                            }
-                            CreateMode::URLName => {
-                                app.url_name_input.pop();
-                            }
-                        },
-                        KeyCode::Char(value) => match create_view {
-                            CreateMode::Name => app.name_input.push(value),
-                            CreateMode::URLName => app.url_name_input.push(value),
-                        },
-                        KeyCode::Enter => match create_view {
-                            CreateMode::Name => {
-                                app.current_view = View::CreateView(CreateMode::URLName)
+                    View::AttachmentsView { index, selected } => {
+                        let attachments = app.get_current_attachments();
+                        let (index, mut selected) = (*index, selected.clone());
+                        match key.code {
+                            KeyCode::Esc => app.revert_view(),
+                            KeyCode::Char('q') => break,
+                            KeyCode::Char('?') => app.open_help(),
+                            KeyCode::Up => {
+                                let index = if index == 0 {
+                                    attachments.len().saturating_sub(1)
+                                } else {
+                                    index - 1
+                                };
+                                app.current_view = View::AttachmentsView { index, selected };
                             }
-                            CreateMode::URLName => {
-                                let name_input = app.name_input.clone();
-                                let url_name_input = if app.url_name_input.is_empty() {
-                                    None
+                            KeyCode::Down => {
+                                let index = if index + 1 >= attachments.len() {
+                                    0
                                 } else {
-                                    Some(app.url_name_input.clone())
+                                    index + 1
                                 };
-                                let theme = app.theme.clone();
-                                let creating_bored = app.create_bored_on_network(
-                                    &name_input,
-                                    Coordinate { x: 120, y: 40 },
-                                    url_name_input.as_deref(),
-                                );
-                                match wait_pop_up(
-                                    terminal,
-                                    previous_buffer,
-                                    creating_bored,
-                                    "Creating board on x0x...",
-                                    theme,
-                                )
-                                .await
-                                {
-                                    Err(e) => app.display_error(e),
-                                    _ => {
-                                        app.name_input = String::new();
-                                        app.url_name_input = String::new();
-                                    }
-                                }
+                                app.current_view = View::AttachmentsView { index, selected };
                             }
-                        },
-                        _ => {}
-                    },
-                    View::DraftView(draft_mode) => match draft_mode {
-                        DraftMode::Content => match key.code {
-                            KeyCode::Esc => app.revert_view(),
-                            KeyCode::Backspace => {
-                                if let Some(mut draft) = app.get_draft() {
-                                    if Ok(true) == draft.remove_tail_link() {
-                                        app.content_input = draft.get_content().to_string();
+                            KeyCode::Char(' ') => {
+                                if let Some(attachment) = attachments.get(index) {
+                                    let address = attachment.get_address().to_string();
+                                    if selected.contains(&address) {
+                                        selected.retain(|a| *a != address);
                                     } else {
-                                        app.content_input.pop();
-                                    }
-                                    match app.edit_draft(&app.content_input.clone()) {
-                                        Err(e) => {
-                                            app.change_view(View::ErrorView(
-                                                SurfBoredError::BoredError(e),
-                                            ));
-                                        }
-                                        _ => (),
+                                        selected.push(address);
                                     }
                                 }
+                                app.current_view = View::AttachmentsView { index, selected };
                             }
-                            KeyCode::Enter => {
-                                app.content_input.push('\n');
-                                try_edit(app);
+                            KeyCode::Char('a') => {
+                                selected = if selected.len() == attachments.len() {
+                                    vec![]
+                                } else {
+                                    attachments
+                                        .iter()
+                                        .map(|a| a.get_address().to_string())
+                                        .collect()
+                                };
+                                app.current_view = View::AttachmentsView { index, selected };
                             }
-                            KeyCode::Char(value) => {
-                                if key.modifiers == KeyModifiers::CONTROL {
-                                    if value == 'h' {
-                                        app.current_view = View::DraftView(DraftMode::Hyperlink(
-                                            HyperlinkMode::Text,
-                                        ));
-                                    }
-                                    if value == 'p' {
-                                        app.current_view = View::DraftView(DraftMode::Position);
+                            KeyCode::Enter => {
+                                // download the highlighted file if none are picked
+                                let picked: Vec<Attachment> = if selected.is_empty() {
+                                    attachments.get(index).cloned().into_iter().collect()
+                                } else {
+                                    attachments
+                                        .into_iter()
+                                        .filter(|a| selected.contains(&a.get_address().to_string()))
+                                        .collect()
+                                };
+                                if !picked.is_empty() {
+                                    match app.download_attachments(picked) {
+                                        Err(e) => app.display_error(e),
+                                        _ => app.change_view(View::DownloadsView(
+                                            app.downloads.get_downloads().len().saturating_sub(1),
+                                        )),
                                     }
-                                    if value == 'u' {
-                                        app.content_input = String::new();
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    View::OverviewView => match key.code {
+                        KeyCode::Esc | KeyCode::Char('z') => app.revert_view(),
+                        KeyCode::Char('q') => break,
+                        KeyCode::Char('?') => app.open_help(),
+                        KeyCode::Tab => try_select_notice(app, NoticeSelection::Next),
+                        KeyCode::BackTab => try_select_notice(app, NoticeSelection::Previous),
+                        KeyCode::Up => {
+                            try_select_notice(app, NoticeSelection::Direction(bored::Direction::Up))
+                        }
+                        KeyCode::Left => try_select_notice(
+                            app,
+                            NoticeSelection::Direction(bored::Direction::Left),
+                        ),
+                        KeyCode::Down => try_select_notice(
+                            app,
+                            NoticeSelection::Direction(bored::Direction::Down),
+                        ),
+                        KeyCode::Right => try_select_notice(
+                            app,
+                            NoticeSelection::Direction(bored::Direction::Right),
+                        ),
+                        // back to the bored at the selected notice
+                        KeyCode::Enter => {
+                            app.revert_view();
+                            try_select_notice(app, NoticeSelection::Current);
+                        }
+                        _ => {}
+                    },
+                    &View::DirectoryFileView(action) => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Backspace => {
+                            app.directory_file_input.pop();
+                        }
+                        KeyCode::Char(value) => app.directory_file_input.push(value),
+                        KeyCode::Enter => {
+                            let path = app.directory_file_input.trim().to_string();
+                            let done = match action {
+                                DirectoryFileAction::Import => {
+                                    app.import_directory(&path).map(|count| {
+                                        format!("Imported {count} new boreds from {path}")
+                                    })
+                                }
+                                DirectoryFileAction::Export => app
+                                    .export_directory(&path)
+                                    .map(|count| format!("Exported {count} boreds to {path}")),
+                            };
+                            match done {
+                                Ok(message) => {
+                                    app.current_view = View::DirectoryView(0);
+                                    app.show_toast(ToastKind::Success, &message);
+                                }
+                                Err(e) => app.display_error(e),
+                            }
+                        }
+                        _ => {}
+                    },
+                    &View::ListingView {
+                        directory_index,
+                        field,
+                    } => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Tab => {
+                            app.current_view = View::ListingView {
+                                directory_index,
+                                field: field.next(),
+                            }
+                        }
+                        KeyCode::Backspace => {
+                            app.get_listing_input_mut(field).pop();
+                        }
+                        KeyCode::Char(c) => app.get_listing_input_mut(field).push(c),
+                        KeyCode::Enter => {
+                            if let Err(e) = app.save_listing_edit(directory_index) {
+                                app.display_error(e);
+                            }
+                        }
+                        _ => {}
+                    },
+                    &View::ErrorLogView(error_index) => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Char('q') => break,
+                        KeyCode::Char('?') => app.open_help(),
+                        KeyCode::Up => {
+                            app.current_view = View::ErrorLogView(error_index.saturating_sub(1))
+                        }
+                        KeyCode::Down => {
+                            let last = app.error_log.get_errors().len().saturating_sub(1);
+                            app.current_view = View::ErrorLogView(min(error_index + 1, last))
+                        }
+                        KeyCode::Char('c') => {
+                            app.error_log.clear();
+                            app.current_view = View::ErrorLogView(0);
+                        }
+                        _ => {}
+                    },
+                    &View::LedgerView(entry_index) => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Char('q') => break,
+                        KeyCode::Char('?') => app.open_help(),
+                        KeyCode::Up => {
+                            app.current_view = View::LedgerView(entry_index.saturating_sub(1))
+                        }
+                        KeyCode::Down => {
+                            let last = app.ledger.len().saturating_sub(1);
+                            app.current_view = View::LedgerView(min(entry_index + 1, last))
+                        }
+                        _ => {}
+                    },
+                    View::QrView(_) => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Char('q') => break,
+                        KeyCode::Char('?') => app.open_help(),
+                        KeyCode::Char('y') => match app.copy_current_address() {
+                            Err(e) => app.display_error(e),
+                            Ok(address) => app.show_toast(
+                                ToastKind::Success,
+                                &format!("Copied {} to the clipboard.", address),
+                            ),
+                        },
+                        _ => {}
+                    },
+                    &View::NoticeListView(row) => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Char('q') => break,
+                        KeyCode::Char('?') => app.open_help(),
+                        KeyCode::Up => {
+                            app.current_view = View::NoticeListView(row.saturating_sub(1))
+                        }
+                        KeyCode::Down | KeyCode::End => {
+                            let last = app
+                                .get_current_bored()
+                                .map_or(0, |bored| bored.notices().len().saturating_sub(1));
+                            let row = match key.code {
+                                KeyCode::End => last,
+                                _ => min(row + 1, last),
+                            };
+                            app.current_view = View::NoticeListView(row)
+                        }
+                        KeyCode::Home => app.current_view = View::NoticeListView(0),
+                        KeyCode::Enter => {
+                            let notice_index = app.get_current_bored().and_then(|bored| {
+                                display_bored::get_recency_order(&bored).get(row).copied()
+                            });
+                            if let Some(notice_index) = notice_index {
+                                app.revert_view();
+                                app.jump_to_notice(notice_index);
+                            }
+                        }
+                        _ => {}
+                    },
+                    &View::IndexView(row) => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Char('r') if key.modifiers == KeyModifiers::CONTROL => app.crawl(),
+                        // typing searches the index as you go
+                        KeyCode::Char(c) => {
+                            app.index_search.push(c);
+                            app.current_view = View::IndexView(0);
+                        }
+                        KeyCode::Backspace => {
+                            app.index_search.pop();
+                            app.current_view = View::IndexView(0);
+                        }
+                        KeyCode::Up => app.current_view = View::IndexView(row.saturating_sub(1)),
+                        KeyCode::Down | KeyCode::End => {
+                            let last = app
+                                .index
+                                .get()
+                                .search(&app.index_search)
+                                .len()
+                                .saturating_sub(1);
+                            let row = match key.code {
+                                KeyCode::End => last,
+                                _ => min(row + 1, last),
+                            };
+                            app.current_view = View::IndexView(row)
+                        }
+                        KeyCode::Home => app.current_view = View::IndexView(0),
+                        KeyCode::Enter => {
+                            let address = app
+                                .index
+                                .get()
+                                .search(&app.index_search)
+                                .get(row)
+                                .map(|hit| hit.entry.address.clone());
+                            if let Some(address) = address {
+                                app.revert_view();
+                                if let Err(e) = app.goto_bored(address) {
+                                    app.display_error(e);
+                                }
+                            }
+                        }
+                        _ => {}
+                    },
+                    &View::GraphView(node) => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Char('q') => break,
+                        KeyCode::Char('?') => app.open_help(),
+                        KeyCode::Char('r') if key.modifiers == KeyModifiers::CONTROL => app.crawl(),
+                        KeyCode::Up | KeyCode::Down | KeyCode::Left | KeyCode::Right => {
+                            let step = match key.code {
+                                KeyCode::Up => GraphStep::Up,
+                                KeyCode::Down => GraphStep::Down,
+                                KeyCode::Left => GraphStep::Left,
+                                _ => GraphStep::Right,
+                            };
+                            app.current_view = View::GraphView(app.graph.step(node, step));
+                        }
+                        KeyCode::Home => app.current_view = View::GraphView(0),
+                        KeyCode::Enter => {
+                            if let Some(graph_node) = app.graph.get_nodes().get(node) {
+                                let address = graph_node.address.clone();
+                                app.revert_view();
+                                if let Err(e) = app.goto_bored(address) {
+                                    app.display_error(e);
+                                }
+                            }
+                        }
+                        _ => {}
+                    },
+                    &View::DraftsView(draft_index) => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Char('q') => break,
+                        KeyCode::Char('?') => app.open_help(),
+                        KeyCode::Up => {
+                            app.current_view = View::DraftsView(draft_index.saturating_sub(1))
+                        }
+                        KeyCode::Down => {
+                            let last = app.drafts.get_drafts().len().saturating_sub(1);
+                            app.current_view = View::DraftsView(min(draft_index + 1, last))
+                        }
+                        KeyCode::Delete => {
+                            if let Err(e) = app.discard_draft(draft_index) {
+                                app.display_error(e);
+                            } else {
+                                let last = app.drafts.get_drafts().len().saturating_sub(1);
+                                app.current_view = View::DraftsView(min(draft_index, last));
+                            }
+                        }
+                        KeyCode::Enter if draft_index < app.drafts.get_drafts().len() => {
+                            if let Err(e) = app.resume_draft(draft_index) {
+                                app.display_error(e);
+                            }
+                        }
+                        _ => {}
+                    },
+                    &View::TemplateView(template_index) => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Char('?') => app.open_help(),
+                        KeyCode::Up => {
+                            app.current_view = View::TemplateView(template_index.saturating_sub(1))
+                        }
+                        KeyCode::Down => {
+                            let last = TEMPLATES.len().saturating_sub(1);
+                            app.current_view = View::TemplateView(min(template_index + 1, last))
+                        }
+                        KeyCode::Enter => {
+                            if let Err(e) = app.use_template(template_index) {
+                                app.display_error(e);
+                            }
+                        }
+                        _ => {}
+                    },
+                    View::KeyView(key_mode) => match key_mode {
+                        KeyMode::Show => match key.code {
+                            KeyCode::Esc => app.revert_view(),
+                            KeyCode::Char('q') => break,
+                            KeyCode::Char('?') => app.open_help(),
+                            KeyCode::Char('n') => {
+                                if let Err(e) = app.create_reader_key() {
+                                    app.display_error(e);
+                                }
+                            }
+                            KeyCode::Char('i') if app.reader_key.is_none() => {
+                                app.current_view = View::KeyView(KeyMode::Import)
+                            }
+                            KeyCode::Char('y') => match app.copy_reader_public_key() {
+                                Err(e) => app.display_error(e),
+                                Ok(public_key) => app.show_toast(
+                                    ToastKind::Success,
+                                    &format!("Copied {} to the clipboard.", public_key),
+                                ),
+                            },
+                            KeyCode::Char('w') if app.reader_key.is_some() => {
+                                app.current_view = View::KeyView(KeyMode::SeedPhrase)
+                            }
+                            KeyCode::Delete if app.reader_key.is_some() => {
+                                app.current_view = View::KeyView(KeyMode::Clear)
+                            }
+                            _ => {}
+                        },
+                        KeyMode::SeedPhrase => match key.code {
+                            KeyCode::Esc | KeyCode::Enter => {
+                                app.current_view = View::KeyView(KeyMode::Show)
+                            }
+                            _ => {}
+                        },
+                        KeyMode::Import => match key.code {
+                            KeyCode::Esc => {
+                                app.key_input = String::new();
+                                app.current_view = View::KeyView(KeyMode::Show);
+                            }
+                            KeyCode::Backspace => {
+                                app.key_input.pop();
+                            }
+                            KeyCode::Enter => match app.import_reader_key() {
+                                Ok(_) => app.current_view = View::KeyView(KeyMode::Show),
+                                Err(e) => app.display_error(e),
+                            },
+                            KeyCode::Char(value) => app.key_input.push(value),
+                            _ => {}
+                        },
+                        KeyMode::Clear => match key.code {
+                            KeyCode::Char('y') => match app.clear_reader_key() {
+                                Ok(_) => app.current_view = View::KeyView(KeyMode::Show),
+                                Err(e) => app.display_error(e),
+                            },
+                            KeyCode::Char('n') | KeyCode::Esc => {
+                                app.current_view = View::KeyView(KeyMode::Show)
+                            }
+                            _ => {}
+                        },
+                    },
+                    &View::DownloadsView(download_index) => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Char('q') => break,
+                        KeyCode::Char('?') => app.open_help(),
+                        KeyCode::Up => {
+                            app.current_view =
+                                View::DownloadsView(app.downloads.previous_download(download_index))
+                        }
+                        KeyCode::Down => {
+                            app.current_view =
+                                View::DownloadsView(app.downloads.next_download(download_index))
+                        }
+                        KeyCode::Char('c') => {
+                            app.downloads.clear_finished();
+                            app.current_view = View::DownloadsView(0);
+                        }
+                        KeyCode::Enter => {
+                            if let Some(DownloadStatus::Done(path)) = app
+                                .downloads
+                                .get_downloads()
+                                .get(download_index)
+                                .map(|d| d.get_status())
+                                && let Err(e) =
+                                    app.open_outside(&path.display().to_string(), "file")
+                            {
+                                app.show_toast(ToastKind::Warning, &e.to_string());
+                            }
+                        }
+                        _ => {}
+                    },
+                    &View::ThemeView(theme_index) => match key.code {
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Char('q') => break,
+                        KeyCode::Char('?') => app.open_help(),
+                        KeyCode::Up => {
+                            app.current_view = View::ThemeView(theme_index.saturating_sub(1))
+                        }
+                        KeyCode::Down => {
+                            let last_index = app.themes.len().saturating_sub(1);
+                            app.current_view = View::ThemeView(min(theme_index + 1, last_index))
+                        }
+                        KeyCode::Char('b') => {
+                            if let Err(e) = app.toggle_bored_colours() {
+                                app.display_error(e);
+                            }
+                        }
+                        KeyCode::Enter => {
+                            app.revert_view();
+                            if let Err(e) = app.set_theme(theme_index) {
+                                app.display_error(e);
+                            }
+                        }
+                        _ => {}
+                    },
+                    View::CreateView(create_view) => match key.code {
+                        KeyCode::Tab => app.current_view = View::CreateView(create_view.toggle()),
+                        KeyCode::Esc => app.revert_view(),
+                        KeyCode::Backspace => match create_view {
+                            CreateMode::Name => {
+                                app.name_input.pop();
+                            }
+                            CreateMode::URLName => {
+                                app.url_name_input.pop();
+                            }
+                        },
+                        KeyCode::Char(value) => match create_view {
+                            CreateMode::Name => app.name_input.push(value),
+                            CreateMode::URLName => app.url_name_input.push(value),
+                        },
+                        KeyCode::Enter => match create_view {
+                            CreateMode::Name => {
+                                app.current_view = View::CreateView(CreateMode::URLName)
+                            }
+                            CreateMode::URLName => {
+                                if let Err(e) = app.create_bored_from_input() {
+                                    app.display_error(e);
+                                }
+                            }
+                        },
+                        KeyCode::Down if *create_view == CreateMode::URLName => {
+                            app.cycle_free_url_names(true)
+                        }
+                        KeyCode::Up if *create_view == CreateMode::URLName => {
+                            app.cycle_free_url_names(false)
+                        }
+                        _ => {}
+                    },
+                    View::DraftView(draft_mode) => match draft_mode {
+                        DraftMode::Content => match key.code {
+                            KeyCode::Esc => app.revert_view(),
+                            KeyCode::Backspace => {
+                                if let Some(mut draft) = app.get_draft() {
+                                    if Ok(true) == draft.remove_tail_link() {
+                                        app.content_input = draft.get_content().to_string();
+                                    } else {
+                                        app.content_input.pop();
+                                    }
+                                    match app.edit_draft(&app.content_input.clone()) {
+                                        Err(e) => {
+                                            app.change_view(View::ErrorView(
+                                                SurfBoredError::BoredError(e),
+                                            ));
+                                        }
+                                        _ => (),
+                                    }
+                                }
+                            }
+                            KeyCode::Enter => {
+                                app.content_input.push('\n');
+                                try_edit(app);
+                            }
+                            KeyCode::Char(value) => {
+                                if key.modifiers == KeyModifiers::CONTROL {
+                                    if value == 'h' {
+                                        app.current_view = View::DraftView(DraftMode::Hyperlink(
+                                            HyperlinkMode::Text,
+                                        ));
+                                    }
+                                    if value == 'p' {
+                                        app.current_view = View::DraftView(DraftMode::Position);
+                                    }
+                                    if value == 'r' {
+                                        app.current_view = View::DraftView(DraftMode::Resize);
+                                    }
+                                    if value == 'e' {
+                                        // the editor reads the terminal itself, so stop
+                                        // reading events until it is done
+                                        drop(events);
+                                        let composed = compose_in_editor(app, terminal);
+                                        events = EventStream::new();
+                                        if let Err(e) = composed {
+                                            app.display_error(e);
+                                        }
+                                        continue;
+                                    }
+                                    if value == 'u' {
+                                        app.content_input = String::new();
+                                    }
+                                    if value == 't' {
+                                        app.change_view(View::TemplateView(0));
+                                    }
+                                    if value == 'w' {
+                                        app.edit_draft_event();
+                                    }
+                                    if value == 'o'
+                                        && let Err(e) = app.embed_picked_notice()
+                                    {
+                                        app.display_error(e);
+                                    }
+                                    // post in the next free space without positioning it
+                                    if value == 'g' {
+                                        match app
+                                            .place_draft_in_reading_order()
+                                            .and_then(|_| app.estimate_posting())
+                                        {
+                                            Ok(estimate) => {
+                                                app.current_view =
+                                                    View::DraftView(DraftMode::Confirm(estimate))
+                                            }
+                                            Err(e) => app.display_error(e),
+                                        }
                                     }
                                 }
                                 if app.current_view == View::DraftView(DraftMode::Content) {
@@ -584,6 +1393,23 @@ async fn run_app<B: Backend>(
                             }
                             _ => {}
                         },
+                        &DraftMode::Event(field) => match key.code {
+                            KeyCode::Esc => app.current_view = View::DraftView(DraftMode::Content),
+                            KeyCode::Tab => {
+                                app.current_view = View::DraftView(DraftMode::Event(field.next()));
+                            }
+                            KeyCode::Backspace => {
+                                app.get_event_input_mut(field).pop();
+                            }
+                            KeyCode::Char(c) => app.get_event_input_mut(field).push(c),
+                            // an error leaves the details open to be put right
+                            KeyCode::Enter => {
+                                if let Err(e) = app.set_draft_event() {
+                                    app.display_error(e);
+                                }
+                            }
+                            _ => (),
+                        },
                         DraftMode::Hyperlink(hyperlink_mode) => match key.code {
                             KeyCode::Esc => app.current_view = View::DraftView(DraftMode::Content),
                             KeyCode::Tab => {
@@ -613,88 +1439,114 @@ async fn run_app<B: Backend>(
                                     app.current_view =
                                         View::DraftView(DraftMode::Hyperlink(HyperlinkMode::URL));
                                 }
-                                HyperlinkMode::URL => {
-                                    let content_with_hyperlink = format!(
-                                        "{}[{}]({})",
-                                        app.content_input, app.link_text_input, app.link_url_input
-                                    );
-                                    match app.edit_draft(&content_with_hyperlink) {
-                                        Err(e) => {
-                                            match e {
-                                                BoredError::TooMuchText => {
-                                                    app.change_view(View::ErrorView(
-                                                        SurfBoredError::Message(
-                                                            "Hyperlink too big to fit on notice!"
-                                                                .to_string(),
-                                                        ),
-                                                    ));
-                                                }
-                                                _ => {
-                                                    app.change_view(View::ErrorView(
-                                                        SurfBoredError::BoredError(e),
-                                                    ));
-                                                }
-                                            };
-                                        }
-                                        Ok(_) => {
-                                            app.content_input = content_with_hyperlink;
-                                            app.link_text_input = String::new();
-                                            app.link_url_input = String::new();
-                                        }
-                                    }
-                                    app.current_view = View::DraftView(DraftMode::Content);
-                                }
+                                // an error leaves the hyperlink open to be put right
+                                HyperlinkMode::URL => match app.insert_hyperlink() {
+                                    Err(e) => app.display_error(e),
+                                    Ok(_) => app.current_view = View::DraftView(DraftMode::Content),
+                                },
                             },
                             _ => (),
                         },
+                        // with or without shift, right and down grow the notice, left and up shrink it
+                        DraftMode::Resize => {
+                            let change = match key.code {
+                                KeyCode::Up => Some((0, -1)),
+                                KeyCode::Down => Some((0, 1)),
+                                KeyCode::Left => Some((-1, 0)),
+                                KeyCode::Right => Some((1, 0)),
+                                _ => None,
+                            };
+                            if let Some(change) = change {
+                                if let Err(e) = app.resize_draft(change) {
+                                    app.display_error(SurfBoredError::BoredError(e));
+                                }
+                            } else if key.code == KeyCode::Esc || key.code == KeyCode::Enter {
+                                app.current_view = View::DraftView(DraftMode::Content);
+                            }
+                        }
                         DraftMode::Position => {
                             if key.code == KeyCode::Esc {
                                 app.current_view = View::DraftView(DraftMode::Content);
                             }
                             if let Some(draft) = app.get_draft() {
                                 let position = draft.get_top_left();
+                                let grid_snap = app.grid_snap;
+                                let step = |position: u16, forward: bool| {
+                                    if grid_snap {
+                                        app::get_grid_step(position, forward)
+                                    } else {
+                                        1
+                                    }
+                                };
                                 match key.code {
-                                    KeyCode::Up => try_move(
-                                        app,
-                                        position.subtact(&Coordinate { x: 0, y: 1 }),
-                                        (0, -1),
-                                    ),
-                                    KeyCode::Down => try_move(
-                                        app,
-                                        position.add(&Coordinate { x: 0, y: 1 }),
-                                        (0, 1),
-                                    ),
-                                    KeyCode::Left => try_move(
-                                        app,
-                                        position.subtact(&Coordinate { x: 1, y: 0 }),
-                                        (-1, 0),
-                                    ),
-                                    KeyCode::Right => try_move(
-                                        app,
-                                        position.add(&Coordinate { x: 1, y: 0 }),
-                                        (1, 0),
-                                    ),
-                                    KeyCode::Enter => {
-                                        let theme = app.theme.clone();
-                                        let going_onto_bored = app.add_draft_to_bored();
-                                        match wait_pop_up(
-                                            terminal,
-                                            previous_buffer,
-                                            going_onto_bored,
-                                            "Updating board on x0x...",
-                                            theme,
+                                    KeyCode::Up => {
+                                        let y = step(position.y, false);
+                                        try_move(
+                                            app,
+                                            position.subtact(&Coordinate { x: 0, y }),
+                                            (0, -(y as i32)),
                                         )
-                                        .await
-                                        {
+                                    }
+                                    KeyCode::Down => {
+                                        let y = step(position.y, true);
+                                        try_move(
+                                            app,
+                                            position.add(&Coordinate { x: 0, y }),
+                                            (0, y as i32),
+                                        )
+                                    }
+                                    KeyCode::Left => {
+                                        let x = step(position.x, false);
+                                        try_move(
+                                            app,
+                                            position.subtact(&Coordinate { x, y: 0 }),
+                                            (-(x as i32), 0),
+                                        )
+                                    }
+                                    KeyCode::Right => {
+                                        let x = step(position.x, true);
+                                        try_move(
+                                            app,
+                                            position.add(&Coordinate { x, y: 0 }),
+                                            (x as i32, 0),
+                                        )
+                                    }
+                                    KeyCode::Char('g') => app.grid_snap = !app.grid_snap,
+                                    KeyCode::Enter
+                                        if app
+                                            .get_covered_by_draft()
+                                            .iter()
+                                            .any(|(_, coverage)| *coverage == Coverage::Full) =>
+                                    {
+                                        app.show_toast(
+                                            ToastKind::Warning,
+                                            "Placing the notice here would hide other notices entirely, press (y) to place it anyway or move it.",
+                                        );
+                                    }
+                                    KeyCode::Enter | KeyCode::Char('y') => {
+                                        match app.estimate_posting() {
+                                            Ok(estimate) => {
+                                                app.current_view =
+                                                    View::DraftView(DraftMode::Confirm(estimate))
+                                            }
                                             Err(e) => app.display_error(e),
-                                            _ => app.change_view(View::BoredView),
                                         }
-                                        app.content_input = String::new();
                                     }
                                     _ => {}
                                 }
                             }
                         }
+                        DraftMode::Confirm(_) => match key.code {
+                            KeyCode::Char('y') => {
+                                if let Err(e) = app.add_draft_to_bored() {
+                                    app.display_error(e);
+                                }
+                            }
+                            KeyCode::Char('n') | KeyCode::Esc => {
+                                app.current_view = View::DraftView(DraftMode::Position)
+                            }
+                            _ => {}
+                        },
                     },
                 }
             }
@@ -710,6 +1562,7 @@ fn try_select_notice(app: &mut App, notice_selection: NoticeSelection) {
         NoticeSelection::Previous => app.decrement_selected_notice(),
         NoticeSelection::Current => (),
     }
+    app.mark_selected_seen();
     if let Some(notice) = app.get_selected_notice() {
         let bored_view_port = app
             .bored_view_port
@@ -725,6 +1578,181 @@ fn try_select_notice(app: &mut App, notice_selection: NoticeSelection) {
     }
 }
 
+/// The next key press, mouse event, paste or resize from the terminal
+async fn next_event(events: &mut EventStream) -> io::Result<Event> {
+    match std::future::poll_fn(|cx| Pin::new(&mut *events).poll_next(cx)).await {
+        Some(event) => event,
+        // the terminal has gone, there is nothing more to wait for
+        None => std::future::pending().await,
+    }
+}
+
+/// Go back or forward through the boreds surfed this session
+fn surf_history(app: &mut App, direction: HistoryDirection) {
+    if let Err(e) = app.surf_history(direction) {
+        app.display_error(e);
+    }
+}
+
+/// Move the view of the bored without changing which notice is selected
+fn pan_view(app: &mut App, offset: (i32, i32)) {
+    if let Some(bored_view_port) = app.bored_view_port.as_mut() {
+        bored_view_port.scroll(offset);
+    }
+}
+
+/// Rows of the bored that fit in the view, how far a page moves
+fn view_height(app: &App) -> i32 {
+    app.bored_view_port
+        .as_ref()
+        .map(|bored_view_port| bored_view_port.get_view().height as i32)
+        .unwrap_or(0)
+}
+
+/// Scroll the view with the wheel, holding shift to scroll sideways, click to select notices
+/// or follow hyperlinks and drag empty space to pan
+fn handle_mouse(app: &mut App, mouse: MouseEvent) {
+    let Some(bored_view_port) = app.bored_view_port.as_mut() else {
+        return;
+    };
+    let sideways = mouse.modifiers.contains(KeyModifiers::SHIFT);
+    // anything but moving may change what is under the mouse, it is found again on the next move
+    app.hovered_hyperlink = None;
+    match mouse.kind {
+        MouseEventKind::ScrollUp if sideways => bored_view_port.scroll((-SCROLL_STEP, 0)),
+        MouseEventKind::ScrollDown if sideways => bored_view_port.scroll((SCROLL_STEP, 0)),
+        MouseEventKind::ScrollUp => bored_view_port.scroll((0, -SCROLL_STEP)),
+        MouseEventKind::ScrollDown => bored_view_port.scroll((0, SCROLL_STEP)),
+        MouseEventKind::ScrollLeft => bored_view_port.scroll((-SCROLL_STEP, 0)),
+        MouseEventKind::ScrollRight => bored_view_port.scroll((SCROLL_STEP, 0)),
+        MouseEventKind::Down(MouseButton::Left) => {
+            if let Some(coordinate) = bored_view_port.get_bored_coordinate(mouse.column, mouse.row)
+            {
+                click_bored(app, coordinate);
+                if app.current_view == View::BoredView
+                    && app
+                        .get_current_bored()
+                        .is_some_and(|b| WhatsOnTheBored::create(&b).get_at(coordinate).is_none())
+                {
+                    app.mouse_drag = Some(MouseDrag::View {
+                        column: mouse.column,
+                        row: mouse.row,
+                    });
+                }
+            }
+        }
+        MouseEventKind::Drag(MouseButton::Left) => {
+            if let Some(MouseDrag::View { column, row }) = app.mouse_drag {
+                // the bored moves with the mouse so the view moves the other way
+                bored_view_port.scroll((
+                    column as i32 - mouse.column as i32,
+                    row as i32 - mouse.row as i32,
+                ));
+                app.mouse_drag = Some(MouseDrag::View {
+                    column: mouse.column,
+                    row: mouse.row,
+                });
+            }
+        }
+        MouseEventKind::Up(MouseButton::Left) => app.mouse_drag = None,
+        MouseEventKind::Moved => {
+            if let Some(coordinate) = bored_view_port.get_bored_coordinate(mouse.column, mouse.row)
+                && let Some(bored) = app.get_current_bored()
+                && let Ok(bored_hyperlink_map) = BoredHyperlinkMap::create(&bored)
+                && let Some((notice_index, hyperlink_index)) =
+                    bored_hyperlink_map.get_at(coordinate)
+            {
+                app.hovered_hyperlink =
+                    get_hyperlinks(bored.notices()[notice_index].get_content())
+                        .ok()
+                        .and_then(|hyperlinks| hyperlinks.get(hyperlink_index).cloned());
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Drag the draft to place it while positioning it
+fn handle_draft_mouse(app: &mut App, mouse: MouseEvent) {
+    let (Some(draft), Some(bored_view_port)) = (app.get_draft(), app.bored_view_port.as_ref())
+    else {
+        return;
+    };
+    let Some(coordinate) = bored_view_port.get_bored_coordinate(mouse.column, mouse.row) else {
+        // dropped off the bored so leave the draft where it was last dragged to
+        if let MouseEventKind::Up(_) = mouse.kind {
+            app.mouse_drag = None;
+        }
+        return;
+    };
+    let top_left = draft.get_top_left();
+    match (mouse.kind, app.mouse_drag) {
+        (MouseEventKind::Down(MouseButton::Left), _) => {
+            let dimensions = draft.get_dimensions();
+            let draft_rect = Rect::new(top_left.x, top_left.y, dimensions.x, dimensions.y);
+            if draft_rect.contains(Position::new(coordinate.x, coordinate.y)) {
+                app.mouse_drag = Some(MouseDrag::Draft {
+                    offset: coordinate.subtact(&top_left),
+                });
+            }
+        }
+        (
+            MouseEventKind::Drag(MouseButton::Left) | MouseEventKind::Up(MouseButton::Left),
+            Some(MouseDrag::Draft { offset }),
+        ) => {
+            let mut new_top_left =
+                coordinate.add_i32_tuple((-(offset.x as i32), -(offset.y as i32)));
+            if app.grid_snap {
+                new_top_left = Coordinate {
+                    x: app::snap_to_grid(new_top_left.x),
+                    y: app::snap_to_grid(new_top_left.y),
+                };
+            }
+            if let Err(e) = app.position_draft(new_top_left) {
+                app.display_error(SurfBoredError::BoredError(e));
+            }
+            if let MouseEventKind::Up(_) = mouse.kind {
+                app.mouse_drag = None;
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Follow a hyperlink if one was clicked, otherwise select the clicked notice or view it if it
+/// was already selected
+fn click_bored(app: &mut App, coordinate: Coordinate) {
+    let Some(bored) = app.get_current_bored() else {
+        return;
+    };
+    app.menu_visible = false;
+    app.clicked_cell = Some(coordinate);
+    if let Ok(bored_hyperlink_map) = BoredHyperlinkMap::create(&bored)
+        && let Some((notice_index, hyperlink_index)) = bored_hyperlink_map.get_at(coordinate)
+        && let Some(hyperlink) = get_hyperlinks(bored.notices()[notice_index].get_content())
+            .ok()
+            .and_then(|hyperlinks| hyperlinks.get(hyperlink_index).cloned())
+    {
+        app.selected_notice = Some(notice_index);
+        if let Err(e) = app.handle_hyperlink(hyperlink) {
+            app.display_error(e);
+        }
+        return;
+    }
+    match WhatsOnTheBored::create(&bored).get_at(coordinate) {
+        Some(notice_index) if app.selected_notice == Some(notice_index) => {
+            app.change_view(View::NoticeView {
+                hyperlinks_index: None,
+            })
+        }
+        Some(notice_index) => {
+            app.selected_notice = Some(notice_index);
+            app.mark_selected_seen();
+        }
+        None => {}
+    }
+}
+
 fn try_move(app: &mut App, new_position: Coordinate, scroll_offset: (i32, i32)) {
     match app.position_draft(new_position) {
         Ok(in_view) => {
@@ -740,6 +1768,244 @@ fn try_move(app: &mut App, new_position: Coordinate, scroll_offset: (i32, i32))
     }
 }
 
+/// Hand the terminal over to the user's editor to write the draft in, then take it back and
+/// use what was written
+fn compose_in_editor<B: Backend>(
+    app: &mut App,
+    terminal: &mut Terminal<B>,
+) -> Result<(), SurfBoredError> {
+    let path = App::get_editor_draft_path();
+    fs::write(&path, &app.content_input)?;
+    let command = app::get_editor_command(env::var("VISUAL").ok(), env::var("EDITOR").ok());
+    let status = run_in_terminal(
+        terminal,
+        app.keyboard_enhanced,
+        std::process::Command::new(&command[0])
+            .args(&command[1..])
+            .arg(&path),
+    )?;
+    let written = fs::read_to_string(&path);
+    let _ = fs::remove_file(&path);
+    match status {
+        Ok(status) if status.success() => app.apply_edited_draft(&written?),
+        // the editor exiting with an error is taken as the user giving up on their changes
+        Ok(_) => Ok(()),
+        Err(_) => Err(SurfBoredError::EditorError(command.join(" "))),
+    }
+}
+
+/// Open a web address or file with the user's opener for it, which has the terminal until it
+/// exits
+fn run_opener<B: Backend>(
+    terminal: &mut Terminal<B>,
+    keyboard_enhanced: bool,
+    command: &[String],
+) -> Result<(), SurfBoredError> {
+    let status = run_in_terminal(
+        terminal,
+        keyboard_enhanced,
+        std::process::Command::new(&command[0]).args(&command[1..]),
+    )?;
+    match status {
+        // the opener exiting with an error has said why itself
+        Ok(_) => Ok(()),
+        Err(_) => Err(SurfBoredError::OpenerError(command[0].clone())),
+    }
+}
+
+/// Hand the terminal over to a command until it exits, then take it back
+fn run_in_terminal<B: Backend>(
+    terminal: &mut Terminal<B>,
+    keyboard_enhanced: bool,
+    command: &mut std::process::Command,
+) -> Result<io::Result<std::process::ExitStatus>, SurfBoredError> {
+    run_outside_terminal(terminal, keyboard_enhanced, || command.status())
+}
+
+/// Hand the terminal back to the command line while something is run, then take it back
+fn run_outside_terminal<B: Backend, T>(
+    terminal: &mut Terminal<B>,
+    keyboard_enhanced: bool,
+    run: impl FnOnce() -> T,
+) -> Result<T, SurfBoredError> {
+    // what is run gets the keys as the terminal usually sends them
+    disable_keyboard_enhancement(keyboard_enhanced)?;
+    disable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        LeaveAlternateScreen,
+        DisableMouseCapture,
+        DisableBracketedPaste
+    )?;
+    let result = run();
+    enable_raw_mode()?;
+    execute!(
+        io::stdout(),
+        EnterAlternateScreen,
+        EnableMouseCapture,
+        EnableBracketedPaste
+    )?;
+    if keyboard_enhanced {
+        enable_keyboard_enhancement()?;
+    }
+    terminal.clear()?;
+    Ok(result)
+}
+
+/// Say why x0x couldn't be connected to and offer to start or install the daemon, returning
+/// whether it was started so connecting can be tried again. Run with the terminal handed back
+/// as it asks on the command line.
+fn offer_to_start_daemon(e: &BoredError) -> bool {
+    use std::io::Write;
+    eprintln!("\nError: {}", e);
+    match e {
+        BoredError::ClientConnectionError => {
+            let check_cmd = if cfg!(target_os = "windows") { "x0x.exe" } else { "x0x" };
+            let is_installed = std::process::Command::new(check_cmd)
+                .arg("--version")
+                .output()
+                .map(|out| out.status.success())
+                .unwrap_or(false);
+
+            let is_termux = std::env::var("TERMUX_VERSION").is_ok() || std::path::Path::new("/data/data/com.termux").exists();
+
+            if is_installed {
+                eprintln!("\nThe x0x daemon is installed but currently stopped.");
+                eprintln!("It must be running in the background for this application to work.");
+                eprint!("\nWould you like me to attempt to start the x0x daemon for you now? (y/N): ");
+            } else {
+                eprintln!("\nPlease ensure the x0x daemon is installed and running on your system.");
+                eprintln!("For documentation and configuration details, visit: https://x0x.md");
+                eprintln!("GitHub repository: https://github.com/saorsa-labs/x0x");
+
+                if cfg!(target_os = "windows") {
+                    eprintln!("\nTo install on Windows:");
+                    eprintln!("1. Download the latest Windows zip from: https://github.com/saorsa-labs/x0x/releases");
+                    eprintln!("2. Extract x0xd.exe and x0x.exe, and place them in your PATH.");
+                    eprintln!("3. Run: x0x start");
+                    return wait_for_enter();
+                } else if is_termux {
+                    eprintln!("\nTo install on Android (Termux):");
+                    eprintln!("1. Clone the x0x repository: git clone https://github.com/saorsa-labs/x0x.git");
+                    eprintln!("2. Build from source: cargo build --release --bin x0xd --bin x0x");
+                    eprintln!("3. Copy target/release/x0x and x0xd to your path (e.g. $PREFIX/bin/).");
+                    eprintln!("4. Run: x0x start");
+                    return wait_for_enter();
+                } else {
+                    eprint!("\nWould you like me to attempt to install and start the x0x daemon for you now? (y/N): ");
+                }
+            }
+
+            let _ = std::io::stderr().flush();
+
+            let mut input = String::new();
+            if std::io::stdin().read_line(&mut input).is_ok() {
+                let choice = input.trim().to_lowercase();
+                if choice == "y" || choice == "yes" {
+                    let status = if is_installed {
+                        eprintln!("\nStarting x0x daemon...");
+                        let start_cmd = if cfg!(target_os = "windows") { "x0x.exe" } else { "x0x" };
+                        eprintln!("Running: {} start", start_cmd);
+                        std::process::Command::new(start_cmd)
+                            .arg("start")
+                            .status()
+                    } else {
+                        eprintln!("\nInstalling and starting x0x daemon via standard installer...");
+                        eprintln!("Running: curl -sfL https://x0x.md | sh -s -- --start");
+                        std::process::Command::new("sh")
+                            .arg("-c")
+                            .arg("curl -sfL https://x0x.md | sh -s -- --start")
+                            .status()
+                    };
+
+                    match status {
+                        Ok(s) if s.success() => {
+                            eprintln!("\nAction completed successfully! Waiting 3 seconds for the daemon to spin up...");
+                            std::thread::sleep(std::time::Duration::from_secs(3));
+                            eprintln!("Re-connecting to x0x daemon...");
+                            true
+                        }
+                        _ => {
+                            if is_installed {
+                                let start_cmd = if cfg!(target_os = "windows") { "x0x.exe" } else { "x0x" };
+                                eprintln!("\nFailed to start x0x daemon automatically.");
+                                eprintln!("Please try running it manually in your shell:");
+                                eprintln!("  {} start", start_cmd);
+                            } else {
+                                eprintln!("\nFailed to execute installer automatically.");
+                                eprintln!("Please try running the installer command manually in your shell:");
+                                eprintln!("  curl -sfL https://x0x.md | sh -s -- --start");
+                            }
+                            wait_for_enter()
+                        }
+                    }
+                } else {
+                    false
+                }
+            } else {
+                false
+            }
+        }
+        _ => {
+            eprintln!("\n⚠️  The x0x daemon is running on this machine, but access was denied.");
+            eprintln!("\nThis usually happens when there are multiple users on the same computer.");
+            eprintln!("Because x0x uses port 12700 for local communication, if another user");
+            eprintln!("(or a background service) is already running the daemon, your app will");
+            eprintln!("connect to their daemon but get rejected due to a token mismatch.");
+
+            let token_path = if cfg!(target_os = "windows") {
+                "%APPDATA%\\x0x\\api-token"
+            } else if cfg!(target_os = "macos") {
+                "~/Library/Application Support/x0x/api-token"
+            } else {
+                "~/.local/share/x0x/api-token"
+            };
+
+            eprintln!("\nTo fix this, you can either:");
+            eprintln!("1. Stop the daemon running on the other user account.");
+            eprintln!("2. Share the API token ({}) between users.", token_path);
+            eprintln!("3. Run your daemon on a different port using `x0xd --api-port <PORT>`.");
+            wait_for_enter()
+        }
+    }
+}
+
+/// Leave what was printed up until enter is pressed, then carry on without x0x
+fn wait_for_enter() -> bool {
+    use std::io::Write;
+    eprint!("\nPress enter to carry on with boreds saved on this computer: ");
+    let _ = std::io::stderr().flush();
+    let _ = std::io::stdin().read_line(&mut String::new());
+    false
+}
+
+/// Ask the terminal to send keys with all their modifiers using the kitty keyboard protocol, so
+/// combinations like shift + arrow keys and ctrl + enter can be told apart, returning whether it
+/// was asked. Terminals without the protocol carry on sending keys as they always have.
+fn enable_keyboard_enhancement() -> io::Result<bool> {
+    // the terminal has to be in raw mode to answer whether it supports the protocol
+    if !supports_keyboard_enhancement().unwrap_or(false) {
+        return Ok(false);
+    }
+    execute!(
+        io::stdout(),
+        PushKeyboardEnhancementFlags(
+            KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                | KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS
+        )
+    )?;
+    Ok(true)
+}
+
+/// Put the terminal back to sending keys the usual way if it was asked to use the kitty keyboard
+/// protocol
+fn disable_keyboard_enhancement(keyboard_enhanced: bool) -> io::Result<()> {
+    if keyboard_enhanced {
+        execute!(io::stdout(), PopKeyboardEnhancementFlags)?;
+    }
+    Ok(())
+}
+
 fn try_edit(app: &mut App) {
     if let Err(e) = app.edit_draft(&app.content_input.clone()) {
         match e {
@@ -0,0 +1,159 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::app::SurfBoredError;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// How to open a web address or downloaded file
+#[derive(Clone, Debug, PartialEq)]
+pub enum Opener {
+    /// The program the system uses for it
+    System,
+    /// Copy it to the clipboard instead of opening it
+    Copy,
+    /// A command run in the terminal in place of Surf Bored until it exits, given the address
+    /// or path in place of {} or after its other arguments
+    Command(Vec<String>),
+}
+
+impl Opener {
+    /// What will be done with the target, to show before it is opened
+    pub fn describe(&self, target: &str) -> String {
+        match self {
+            Opener::System => format!("will be opened with {}", get_system_opener(target)),
+            Opener::Copy => "will be copied to the clipboard".to_string(),
+            Opener::Command(command) => format!("will be opened with {}", command.join(" ")),
+        }
+    }
+}
+
+/// The user's openers file, which maps url schemes (https, http) and the file extensions of
+/// downloads (pdf, txt or file for any) to commands, "copy" or "system", e.g. https = "w3m {}"
+#[derive(Debug, Default, Deserialize, PartialEq)]
+pub struct OpenersFile {
+    #[serde(flatten)]
+    openers: HashMap<String, String>,
+}
+
+impl OpenersFile {
+    /// The openers file at the path, an empty one if there is no file yet
+    pub fn load_file(path: &Path) -> Result<OpenersFile, SurfBoredError> {
+        if !path.exists() {
+            return Ok(OpenersFile::default());
+        }
+        let Ok(openers_string) = fs::read_to_string(path) else {
+            return Err(SurfBoredError::OpenersFileReadError);
+        };
+        toml::from_str(&openers_string)
+            .map_err(|e| SurfBoredError::OpenersFileError(e.message().to_string()))
+    }
+
+    /// The opener for a target with the scheme, downloads have the scheme file and are looked
+    /// up by their extension first
+    pub fn get_opener(&self, target: &str, scheme: &str) -> Opener {
+        let mut keys = vec![];
+        if scheme == "file"
+            && let Some(extension) = Path::new(target).extension()
+        {
+            keys.push(extension.to_string_lossy().to_lowercase());
+        }
+        keys.push(scheme.to_lowercase());
+        let command = keys.iter().find_map(|key| {
+            self.openers
+                .iter()
+                .find(|(scheme, _)| scheme.to_lowercase() == *key)
+                .map(|(_, command)| command.trim())
+        });
+        match command {
+            None | Some("") | Some("system") => Opener::System,
+            Some("copy") => Opener::Copy,
+            Some(command) => {
+                Opener::Command(command.split_whitespace().map(str::to_string).collect())
+            }
+        }
+    }
+}
+
+/// The program the system would open a web address or file with, as far as can be told
+pub fn get_system_opener(target: &str) -> String {
+    open::commands(target)
+        .first()
+        .map(|command| command.get_program().to_string_lossy().to_string())
+        .unwrap_or("the system's default program".to_string())
+}
+
+/// The arguments to run a command with to open a target, in place of {} or after the others
+pub fn get_command_args(command: &[String], target: &str) -> Vec<String> {
+    if command.iter().any(|arg| arg.contains("{}")) {
+        return command
+            .iter()
+            .map(|arg| arg.replace("{}", target))
+            .collect();
+    }
+    let mut args = command.to_vec();
+    args.push(target.to_string());
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_opener() -> Result<(), toml::de::Error> {
+        let openers_file: OpenersFile = toml::from_str(
+            "https = \"w3m -o {}\"\nPDF = \"copy\"\nfile = \"less\"\nhttp = \"system\"",
+        )?;
+        assert_eq!(
+            openers_file.get_opener("https://autonomi.com", "https"),
+            Opener::Command(vec!["w3m".to_string(), "-o".to_string(), "{}".to_string()])
+        );
+        assert_eq!(
+            openers_file.get_opener("http://autonomi.com", "http"),
+            Opener::System
+        );
+        assert_eq!(
+            openers_file.get_opener("/tmp/report.pdf", "file"),
+            Opener::Copy
+        );
+        assert_eq!(
+            openers_file.get_opener("/tmp/notes.txt", "file"),
+            Opener::Command(vec!["less".to_string()])
+        );
+        assert_eq!(
+            OpenersFile::default().get_opener("/tmp/notes.txt", "file"),
+            Opener::System
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_command_args() {
+        let command = vec!["w3m".to_string(), "-o".to_string(), "{}".to_string()];
+        assert_eq!(
+            get_command_args(&command, "https://autonomi.com"),
+            vec!["w3m", "-o", "https://autonomi.com"]
+        );
+        assert_eq!(
+            get_command_args(&["less".to_string()], "/tmp/notes.txt"),
+            vec!["less", "/tmp/notes.txt"]
+        );
+    }
+}
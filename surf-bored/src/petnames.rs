@@ -0,0 +1,117 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::app::SurfBoredError;
+use bored::notice::Notice;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Longest name that can be given to an author
+pub const MAX_PETNAME_LENGTH: usize = 40;
+
+/// The user's own names for the authors of notices, by the start of their agent id, e.g.
+/// abc123ef = "Sam from the allotment". Only kept here, nobody else sees them.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Petnames {
+    #[serde(flatten)]
+    names: BTreeMap<String, String>,
+}
+
+impl Petnames {
+    /// The petnames file at the path, none named if there is no file yet
+    pub fn load_file(path: &Path) -> Result<Petnames, SurfBoredError> {
+        if !path.exists() {
+            return Ok(Petnames::default());
+        }
+        let Ok(petnames_string) = fs::read_to_string(path) else {
+            return Err(SurfBoredError::PetnamesFileReadError);
+        };
+        toml::from_str(&petnames_string)
+            .map_err(|e| SurfBoredError::PetnamesFileError(e.message().to_string()))
+    }
+
+    pub fn save_file(&self, path: &Path) -> Result<(), SurfBoredError> {
+        let Ok(petnames_string) = toml::to_string(self) else {
+            return Err(SurfBoredError::PetnamesFileWriteError);
+        };
+        let Ok(()) = fs::write(path, petnames_string) else {
+            return Err(SurfBoredError::PetnamesFileWriteError);
+        };
+        Ok(())
+    }
+
+    pub fn get(&self, author: &str) -> Option<&str> {
+        self.names.get(author).map(String::as_str)
+    }
+
+    /// Name the author, a blank name forgets the one they had
+    pub fn set(&mut self, author: &str, name: &str) {
+        let name: String = name
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(MAX_PETNAME_LENGTH)
+            .collect();
+        if name.is_empty() {
+            self.names.remove(author);
+        } else {
+            self.names.insert(author.to_string(), name);
+        }
+    }
+
+    /// Who posted the notice, by the name given to them or else by their agent id, none if it
+    /// can't be told
+    pub fn describe_author(&self, notice: &Notice) -> Option<String> {
+        let author = notice.get_author()?;
+        Some(self.get(author).unwrap_or(author).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_petnames_file() -> Result<(), SurfBoredError> {
+        let path =
+            std::env::temp_dir().join(format!("surf-bored-petnames-{}.toml", std::process::id()));
+        let mut petnames = Petnames::load_file(&path)?;
+        assert_eq!(petnames, Petnames::default());
+        petnames.set("abc123ef", "  Sam   from the allotment ");
+        petnames.set("0123abcd", "Jo");
+        petnames.set("0123abcd", " ");
+        petnames.save_file(&path)?;
+        let loaded = Petnames::load_file(&path)?;
+        assert_eq!(loaded.get("abc123ef"), Some("Sam from the allotment"));
+        assert_eq!(loaded.get("0123abcd"), None);
+
+        let mut notice = Notice::new();
+        assert_eq!(loaded.describe_author(&notice), None);
+        notice.set_notice_id("notice:1779796800000:abc123ef".to_string());
+        assert_eq!(
+            loaded.describe_author(&notice).as_deref(),
+            Some("Sam from the allotment")
+        );
+        notice.set_notice_id("notice:1779796800000:0123abcd".to_string());
+        assert_eq!(loaded.describe_author(&notice).as_deref(), Some("0123abcd"));
+        let _ = fs::remove_file(path);
+        Ok(())
+    }
+}
@@ -0,0 +1,133 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use bored::url::BoredAddress;
+use bored::x0x_client::X0xBoredClient;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+
+/// How long looking up where a link goes is waited on before it is given up on
+pub const PREVIEW_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long what was found for a link is kept before it is looked up again
+pub const PREVIEW_LIFETIME: Duration = Duration::from_secs(5 * 60);
+
+/// What is known about the bored a link goes to
+#[derive(Clone, Debug, PartialEq)]
+pub enum Preview {
+    Loading,
+    Found { name: String, notice_count: usize },
+    NotFound,
+}
+
+impl Preview {
+    /// A line saying where the link goes, to show under the link
+    pub fn describe(&self) -> String {
+        match self {
+            Preview::Loading => "Looking up where it goes...".to_string(),
+            Preview::Found { name, notice_count } if *notice_count == 1 => {
+                format!("Goes to \"{name}\", 1 notice")
+            }
+            Preview::Found { name, notice_count } => {
+                format!("Goes to \"{name}\", {notice_count} notices")
+            }
+            Preview::NotFound => "No bored found there".to_string(),
+        }
+    }
+}
+
+/// Looks up the boreds that selected links go to in the background, so where a link goes can
+/// be seen before following it
+pub struct Previews {
+    /// What was found for each address and when
+    found: HashMap<String, (Preview, Instant)>,
+    sender: UnboundedSender<(String, Preview)>,
+    receiver: UnboundedReceiver<(String, Preview)>,
+}
+
+impl Previews {
+    pub fn new() -> Previews {
+        let (sender, receiver) = unbounded_channel();
+        Previews {
+            found: HashMap::new(),
+            sender,
+            receiver,
+        }
+    }
+
+    /// Look up the bored at the address on a copy of the client, unless it is already being
+    /// looked up or was recently
+    pub fn request(&mut self, client: &X0xBoredClient, address: &BoredAddress) {
+        let key = address.to_string();
+        if self.found.get(&key).is_some_and(|(preview, at)| {
+            *preview == Preview::Loading || at.elapsed() < PREVIEW_LIFETIME
+        }) {
+            return;
+        }
+        self.found
+            .insert(key.clone(), (Preview::Loading, Instant::now()));
+        let client = client.clone();
+        let address = address.clone();
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            let fetched = tokio::time::timeout(
+                PREVIEW_TIMEOUT,
+                client.fetch_boreds(std::slice::from_ref(&address)),
+            )
+            .await;
+            let preview = match fetched.ok().and_then(|mut boreds| boreds.pop()) {
+                Some(Ok(bored)) => Preview::Found {
+                    name: bored.get_name().to_string(),
+                    notice_count: bored.notices().len(),
+                },
+                _ => Preview::NotFound,
+            };
+            let _ = sender.send((key, preview));
+        });
+    }
+
+    /// Take in what has been found since last time
+    pub fn update(&mut self) {
+        while let Ok((key, preview)) = self.receiver.try_recv() {
+            self.found.insert(key, (preview, Instant::now()));
+        }
+    }
+
+    pub fn get(&self, address: &BoredAddress) -> Option<&Preview> {
+        self.found
+            .get(&address.to_string())
+            .map(|(preview, _)| preview)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(Preview::Loading.describe(), "Looking up where it goes...");
+        let found = |notice_count| Preview::Found {
+            name: "Games".to_string(),
+            notice_count,
+        };
+        assert_eq!(found(1).describe(), "Goes to \"Games\", 1 notice");
+        assert_eq!(found(12).describe(), "Goes to \"Games\", 12 notices");
+        assert_eq!(Preview::NotFound.describe(), "No bored found there");
+    }
+}
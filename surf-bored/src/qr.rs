@@ -0,0 +1,70 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::app::SurfBoredError;
+use qrcode::{Color, QrCode};
+
+/// Light modules left around the code so scanners can find its edges
+const QUIET_ZONE: usize = 2;
+
+/// The text as a QR code drawn with half blocks, two rows of modules to each line of
+/// characters. Dark modules are the foreground so it should be drawn dark on light.
+pub fn get_qr_lines(text: &str) -> Result<Vec<String>, SurfBoredError> {
+    let code = QrCode::new(text.as_bytes())
+        .map_err(|e| SurfBoredError::Message(format!("Could not make a QR code: {e}")))?;
+    let width = code.width();
+    let colours = code.to_colors();
+    let size = width + QUIET_ZONE * 2;
+    let is_dark = |x: usize, y: usize| {
+        let (Some(x), Some(y)) = (x.checked_sub(QUIET_ZONE), y.checked_sub(QUIET_ZONE)) else {
+            return false;
+        };
+        x < width && y < width && colours[y * width + x] == Color::Dark
+    };
+    let lines = (0..size)
+        .step_by(2)
+        .map(|y| {
+            (0..size)
+                .map(|x| match (is_dark(x, y), is_dark(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                })
+                .collect()
+        })
+        .collect();
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_qr_lines() -> Result<(), SurfBoredError> {
+        let lines = get_qr_lines("bored://example")?;
+        let size = QrCode::new("bored://example").unwrap().width() + QUIET_ZONE * 2;
+        assert_eq!(lines.len(), size.div_ceil(2));
+        assert!(lines.iter().all(|line| line.chars().count() == size));
+        // the quiet zone is left light and the finder pattern starts in the corner after it
+        assert!(lines[0].trim().is_empty());
+        assert_eq!(lines[1].chars().nth(QUIET_ZONE), Some('█'));
+        assert!(get_qr_lines(&"x".repeat(8000)).is_err());
+        Ok(())
+    }
+}
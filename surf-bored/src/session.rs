@@ -0,0 +1,84 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::app::SurfBoredError;
+use bored::Coordinate;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Where the user was when surf-bored was closed so they can carry on from there next time
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Session {
+    /// The bored:// address or share link of the bored being viewed
+    pub link: String,
+    pub view_top_left: Coordinate,
+    #[serde(default)]
+    pub selected_notice: Option<usize>,
+    /// Whether boreds were shown as a linear list of notices for screen readers
+    #[serde(default)]
+    pub linear_mode: bool,
+}
+
+impl Session {
+    /// The saved session, none if there isn't one or it can't be read in which case surf-bored
+    /// starts at the home bored
+    pub fn load_file(path: &Path) -> Option<Session> {
+        toml::from_str(&fs::read_to_string(path).ok()?).ok()
+    }
+
+    pub fn save_file(&self, path: &Path) -> Result<(), SurfBoredError> {
+        let Ok(session_string) = toml::to_string(&self) else {
+            return Err(SurfBoredError::SessionSerialzationError);
+        };
+        let Ok(()) = fs::write(path, session_string) else {
+            return Err(SurfBoredError::SessionFileWriteError);
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_file() -> Result<(), SurfBoredError> {
+        let path =
+            std::env::temp_dir().join(format!("surf-bored-session-{}.toml", std::process::id()));
+        assert_eq!(Session::load_file(&path), None);
+        let session = Session {
+            link: "bored+ro://welcome".to_string(),
+            view_top_left: Coordinate { x: 12, y: 3 },
+            selected_notice: Some(2),
+            linear_mode: true,
+        };
+        session.save_file(&path)?;
+        assert_eq!(Session::load_file(&path), Some(session.clone()));
+        let session = Session {
+            selected_notice: None,
+            linear_mode: false,
+            ..session
+        };
+        session.save_file(&path)?;
+        assert_eq!(Session::load_file(&path), Some(session));
+        fs::write(&path, "not a session")?;
+        assert_eq!(Session::load_file(&path), None);
+        let _ = fs::remove_file(path);
+        Ok(())
+    }
+}
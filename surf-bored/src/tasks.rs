@@ -0,0 +1,323 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::app::{App, SurfBoredError};
+use bored::BoredError;
+use bored::x0x_client::{X0xBoredClient, X0xDaemon};
+use ratatui::crossterm::event::Event;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+use tokio::task::JoinHandle;
+
+/// How long a task is waited on before it is given up on
+pub const TASK_TIMEOUT: Duration = Duration::from_secs(600);
+
+/// What is left to do once a task has finished, run with the app and the task's result
+pub type OnFinished =
+    Box<dyn FnOnce(&mut App, Result<(), SurfBoredError>) -> Result<(), SurfBoredError> + Send>;
+
+/// A network operation on a copy of the client, giving the client back once it is done
+pub type Operation = Box<
+    dyn FnOnce(
+            X0xBoredClient,
+        )
+            -> Pin<Box<dyn Future<Output = Result<X0xBoredClient, SurfBoredError>> + Send>>
+        + Send,
+>;
+
+/// Everything the app waits on between one draw and the next
+pub enum AppEvent {
+    /// A key press, mouse event, paste or resize from the terminal
+    Terminal(Event),
+    /// Time to redraw, for the waiting animation and download progress
+    Tick,
+    /// A task has finished, with the client it worked on if it succeeded
+    TaskFinished {
+        id: usize,
+        client: Result<Box<X0xBoredClient>, SurfBoredError>,
+        on_finished: OnFinished,
+    },
+    /// The client has connected to x0x, or failed to
+    Connected(Result<Box<X0xBoredClient>, BoredError>),
+    /// The connection to the daemon has been checked
+    ConnectionChecked(ConnectionCheck),
+}
+
+/// What checking the connection to the daemon found
+#[derive(Debug, PartialEq)]
+pub enum ConnectionCheck {
+    Healthy,
+    /// The daemon had gone but has been connected to again
+    Reconnected,
+    /// The daemon has gone and couldn't be connected to again
+    Lost(BoredError),
+}
+
+/// A network operation asked for before the client had connected, run once it has
+pub struct QueuedTask {
+    message: String,
+    operation: Operation,
+    on_finished: OnFinished,
+}
+
+/// The task being waited on, shown as a pop up until it finishes
+pub struct RunningTask {
+    id: usize,
+    message: String,
+    started: Instant,
+    handle: JoinHandle<()>,
+}
+
+impl RunningTask {
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn get_elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+}
+
+/// Network operations run as background tasks so the ui keeps drawing and taking keys while
+/// they wait on x0x. Each task works on a copy of the client which takes the place of the
+/// app's client when it is done, so only one runs at a time.
+pub struct Tasks {
+    running: Option<RunningTask>,
+    /// Waiting for the client to connect, like a running task a later one takes its place
+    queued: Option<QueuedTask>,
+    next_id: usize,
+    sender: UnboundedSender<AppEvent>,
+    receiver: UnboundedReceiver<AppEvent>,
+}
+
+impl Tasks {
+    pub fn new() -> Tasks {
+        let (sender, receiver) = unbounded_channel();
+        Tasks {
+            running: None,
+            queued: None,
+            next_id: 0,
+            sender,
+            receiver,
+        }
+    }
+
+    pub fn get_running(&self) -> Option<&RunningTask> {
+        self.running.as_ref()
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.running.is_some()
+    }
+
+    /// Run an operation on a copy of the client in the background, a task already running is
+    /// cancelled as its copy of the client would be replaced anyway
+    pub fn spawn(
+        &mut self,
+        message: &str,
+        operation: impl Future<Output = Result<X0xBoredClient, SurfBoredError>> + Send + 'static,
+        on_finished: OnFinished,
+    ) {
+        self.cancel();
+        let id = self.next_id;
+        self.next_id += 1;
+        let sender = self.sender.clone();
+        let handle = tokio::spawn(async move {
+            let client = operation.await.map(Box::new);
+            let _ = sender.send(AppEvent::TaskFinished {
+                id,
+                client,
+                on_finished,
+            });
+        });
+        self.running = Some(RunningTask {
+            id,
+            message: message.to_string(),
+            started: Instant::now(),
+            handle,
+        });
+    }
+
+    /// Keep an operation to run once the client has connected, in place of any kept before
+    pub fn queue(&mut self, message: &str, operation: Operation, on_finished: OnFinished) {
+        self.queued = Some(QueuedTask {
+            message: message.to_string(),
+            operation,
+            on_finished,
+        });
+    }
+
+    /// Run the operation kept while connecting on the connected client
+    pub fn spawn_queued(&mut self, client: X0xBoredClient) {
+        if let Some(queued) = self.queued.take() {
+            self.spawn(
+                &queued.message,
+                (queued.operation)(client),
+                queued.on_finished,
+            );
+        }
+    }
+
+    /// Forget the operation kept while connecting, as the client won't connect
+    pub fn clear_queued(&mut self) {
+        self.queued = None;
+    }
+
+    /// Connect to the x0x daemon in the background, keeping the cache in the data directory
+    /// given or the standard one, the ui carries on while it is waited on
+    pub fn connect(&self, data_dir: Option<PathBuf>, daemon: X0xDaemon) {
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            let client = connect(data_dir, &daemon).await.map(Box::new);
+            let _ = sender.send(AppEvent::Connected(client));
+        });
+    }
+
+    /// Check the daemon is still there in the background, reconnecting if it has gone. Clones
+    /// of the client share the connection so the app's client is reconnected too.
+    pub fn check_connection(&self, client: X0xBoredClient) {
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            let check = match client.health_check().await {
+                Ok(()) => ConnectionCheck::Healthy,
+                Err(_) => match client.reconnect().await {
+                    Ok(()) => ConnectionCheck::Reconnected,
+                    Err(e) => ConnectionCheck::Lost(e),
+                },
+            };
+            let _ = sender.send(AppEvent::ConnectionChecked(check));
+        });
+    }
+
+    /// Stop the running task, the app is left as it was before the task started
+    pub fn cancel(&mut self) {
+        if let Some(running) = self.running.take() {
+            running.handle.abort();
+        }
+    }
+
+    /// Wait for the running task to finish, tasks that were cancelled after they finished are
+    /// ignored
+    pub async fn recv(&mut self) -> AppEvent {
+        loop {
+            let Some(event) = self.receiver.recv().await else {
+                // the sender is kept so this can't happen, but don't spin if it does
+                return std::future::pending().await;
+            };
+            if let AppEvent::TaskFinished { id, .. } = event
+                && self.running.as_ref().is_none_or(|running| running.id != id)
+            {
+                continue;
+            }
+            if let AppEvent::TaskFinished { .. } = event {
+                self.running = None;
+            }
+            return event;
+        }
+    }
+}
+
+/// Connect to the x0x daemon, keeping the cache in the data directory given or the standard one
+pub async fn connect(
+    data_dir: Option<PathBuf>,
+    daemon: &X0xDaemon,
+) -> Result<X0xBoredClient, BoredError> {
+    let data_dir = data_dir
+        .or_else(bored::x0x_client::get_we_are_bored_data_dir)
+        .unwrap_or_else(|| PathBuf::from("."));
+    X0xBoredClient::init_with_daemon(data_dir, daemon).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[tokio::test]
+    async fn test_tasks() {
+        let mut tasks = Tasks::new();
+        assert!(!tasks.is_running());
+        tasks.spawn(
+            "Never finishing",
+            std::future::pending(),
+            Box::new(|_, result| result),
+        );
+        assert_eq!(
+            tasks.get_running().map(RunningTask::get_message),
+            Some("Never finishing")
+        );
+        // the second replaces the first, whose finish is never seen
+        tasks.spawn(
+            "Failing",
+            async { Err(SurfBoredError::StillWaiting) },
+            Box::new(|_, result| result),
+        );
+        let AppEvent::TaskFinished { client, .. } = tasks.recv().await else {
+            panic!("expected the task to finish");
+        };
+        assert!(client.is_err());
+        assert!(!tasks.is_running());
+        tasks.spawn(
+            "Cancelled",
+            std::future::pending(),
+            Box::new(|_, result| result),
+        );
+        tasks.cancel();
+        assert!(!tasks.is_running());
+    }
+
+    #[tokio::test]
+    async fn test_queued_task() {
+        let mut tasks = Tasks::new();
+        tasks.queue(
+            "Replaced",
+            Box::new(|_| Box::pin(async { Err(SurfBoredError::StillWaiting) })),
+            Box::new(|_, result| result),
+        );
+        tasks.queue(
+            "Queued",
+            Box::new(|client| Box::pin(async { Ok(client) })),
+            Box::new(|_, result| result),
+        );
+        assert!(!tasks.is_running());
+        tasks.spawn_queued(X0xBoredClient::offline(
+            Path::new("."),
+            &X0xDaemon::Standard,
+        ));
+        assert_eq!(
+            tasks.get_running().map(RunningTask::get_message),
+            Some("Queued")
+        );
+        let AppEvent::TaskFinished { client, .. } = tasks.recv().await else {
+            panic!("expected the task to finish");
+        };
+        assert!(client.is_ok());
+        tasks.queue(
+            "Never run",
+            Box::new(|client| Box::pin(async { Ok(client) })),
+            Box::new(|_, result| result),
+        );
+        tasks.clear_queued();
+        tasks.spawn_queued(X0xBoredClient::offline(
+            Path::new("."),
+            &X0xDaemon::Standard,
+        ));
+        assert!(!tasks.is_running());
+    }
+}
@@ -15,9 +15,137 @@ You should have received a copy of the GNU Affero General Public License
 along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
+use crate::app::SurfBoredError;
+use bored::Palette;
+use bored::url::URL;
 use ratatui::style::{Color, Style, Stylize};
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// The 16 ansi colours with the red, green and blue xterm shows them as
+const ANSI_COLOURS: [(Color, (u8, u8, u8)); 16] = [
+    (Color::Black, (0, 0, 0)),
+    (Color::Red, (205, 0, 0)),
+    (Color::Green, (0, 205, 0)),
+    (Color::Yellow, (205, 205, 0)),
+    (Color::Blue, (0, 0, 238)),
+    (Color::Magenta, (205, 0, 205)),
+    (Color::Cyan, (0, 205, 205)),
+    (Color::Gray, (229, 229, 229)),
+    (Color::DarkGray, (127, 127, 127)),
+    (Color::LightRed, (255, 0, 0)),
+    (Color::LightGreen, (0, 255, 0)),
+    (Color::LightYellow, (255, 255, 0)),
+    (Color::LightBlue, (92, 92, 255)),
+    (Color::LightMagenta, (255, 0, 255)),
+    (Color::LightCyan, (0, 255, 255)),
+    (Color::White, (255, 255, 255)),
+];
+
+/// Levels of each channel in the 6×6×6 colour cube of 256 colour terminals
+const CUBE_LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+
+/// Red, green and blue of a colour, none for the terminal's own default colours
+fn to_rgb(colour: Color) -> Option<(u8, u8, u8)> {
+    match colour {
+        Color::Rgb(red, green, blue) => Some((red, green, blue)),
+        Color::Indexed(index) if index < 16 => Some(ANSI_COLOURS[index as usize].1),
+        Color::Indexed(index) if index < 232 => {
+            let cube = index - 16;
+            let level = |i: u8| CUBE_LEVELS[i as usize];
+            Some((level(cube / 36), level(cube / 6 % 6), level(cube % 6)))
+        }
+        Color::Indexed(index) => {
+            let grey = 8 + 10 * (index - 232);
+            Some((grey, grey, grey))
+        }
+        Color::Reset => None,
+        colour => ANSI_COLOURS
+            .iter()
+            .find(|(ansi, _)| *ansi == colour)
+            .map(|(_, rgb)| *rgb),
+    }
+}
+
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+    let channel = |a: u8, b: u8| (a as i32 - b as i32).pow(2) as u32;
+    channel(a.0, b.0) + channel(a.1, b.1) + channel(a.2, b.2)
+}
+
+/// How many colours the terminal can show, themes are brought down to what it can show
+#[derive(Clone, Copy, Debug, PartialEq, Deserialize)]
+pub enum ColourDepth {
+    #[serde(rename = "16")]
+    Ansi16,
+    #[serde(rename = "256")]
+    Indexed256,
+    #[serde(rename = "truecolour")]
+    TrueColour,
+}
+
+impl ColourDepth {
+    /// Work out the colours the terminal can show from the COLORTERM and TERM variables
+    pub fn detect() -> ColourDepth {
+        ColourDepth::from_env(
+            std::env::var("COLORTERM").ok().as_deref(),
+            std::env::var("TERM").ok().as_deref(),
+        )
+    }
+
+    fn from_env(colorterm: Option<&str>, term: Option<&str>) -> ColourDepth {
+        if matches!(colorterm, Some("truecolor") | Some("24bit")) {
+            return ColourDepth::TrueColour;
+        }
+        match term {
+            Some(term) if term.ends_with("-direct") => ColourDepth::TrueColour,
+            Some(term) if term.contains("256color") => ColourDepth::Indexed256,
+            Some(_) => ColourDepth::Ansi16,
+            // windows terminals don't set TERM and show every colour
+            None if cfg!(windows) => ColourDepth::TrueColour,
+            None => ColourDepth::Ansi16,
+        }
+    }
+
+    /// The nearest colour the terminal can show
+    pub fn reduce(self, colour: Color) -> Color {
+        let Some(rgb) = to_rgb(colour) else {
+            return colour;
+        };
+        match (self, colour) {
+            (ColourDepth::TrueColour, _) => colour,
+            (ColourDepth::Indexed256, Color::Rgb(..)) => {
+                let nearest_level = |channel: u8| {
+                    (0..CUBE_LEVELS.len())
+                        .min_by_key(|i| (CUBE_LEVELS[*i] as i32 - channel as i32).abs())
+                        .unwrap_or(0) as u8
+                };
+                let cube = 16
+                    + 36 * nearest_level(rgb.0)
+                    + 6 * nearest_level(rgb.1)
+                    + nearest_level(rgb.2);
+                let average = (rgb.0 as u16 + rgb.1 as u16 + rgb.2 as u16) / 3;
+                let grey = 232 + (average.saturating_sub(3) / 10).min(23) as u8;
+                [cube, grey]
+                    .into_iter()
+                    .min_by_key(|index| {
+                        distance(rgb, to_rgb(Color::Indexed(*index)).unwrap_or_default())
+                    })
+                    .map_or(colour, Color::Indexed)
+            }
+            (ColourDepth::Indexed256, _) => colour,
+            (ColourDepth::Ansi16, Color::Rgb(..) | Color::Indexed(_)) => ANSI_COLOURS
+                .iter()
+                .min_by_key(|(_, ansi)| distance(rgb, *ansi))
+                .map_or(colour, |(ansi, _)| *ansi),
+            (ColourDepth::Ansi16, _) => colour,
+        }
+    }
+}
+
 /// Represent colours in theme used by app
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct Theme {
     name: String,
     text_fg: Color,
@@ -25,6 +153,8 @@ pub struct Theme {
     dimmed_text_fg: Color,
     header_bg: Color,
     hyperlink_style: Style,
+    /// Colour of hyperlinks that leave the bored network, the same as other hyperlinks if none
+    web_hyperlink_fg: Option<Color>,
 }
 
 impl Theme {
@@ -36,9 +166,85 @@ impl Theme {
             dimmed_text_fg: Color::Rgb(205, 152, 211),
             header_bg: Color::Rgb(109, 228, 175), // bright green header_bg: Color::Rgb(149, 232, 196), // pale green
             hyperlink_style: Style::new().underlined(),
+            web_hyperlink_fg: Some(Color::Rgb(109, 228, 175)),
+        }
+    }
+
+    pub fn light() -> Theme {
+        Theme {
+            name: "Light".to_string(),
+            text_fg: Color::Rgb(40, 40, 48),
+            text_bg: Color::Rgb(250, 248, 240),
+            dimmed_text_fg: Color::Rgb(150, 148, 140),
+            header_bg: Color::Rgb(92, 140, 214),
+            hyperlink_style: Style::new().underlined(),
+            web_hyperlink_fg: Some(Color::Rgb(176, 88, 20)),
+        }
+    }
+
+    pub fn high_contrast() -> Theme {
+        Theme {
+            name: "High contrast".to_string(),
+            text_fg: Color::White,
+            text_bg: Color::Black,
+            dimmed_text_fg: Color::Gray,
+            header_bg: Color::Yellow,
+            hyperlink_style: Style::new().fg(Color::Yellow).underlined().bold(),
+            web_hyperlink_fg: Some(Color::Cyan),
+        }
+    }
+
+    /// Black on white with every colour well apart
+    pub fn high_contrast_light() -> Theme {
+        Theme {
+            name: "High contrast light".to_string(),
+            text_fg: Color::Black,
+            text_bg: Color::White,
+            dimmed_text_fg: Color::DarkGray,
+            header_bg: Color::Blue,
+            hyperlink_style: Style::new().fg(Color::Blue).underlined().bold(),
+            web_hyperlink_fg: Some(Color::Magenta),
+        }
+    }
+
+    /// Colours from the Okabe-Ito palette which can be told apart with any kind of colour
+    /// blindness, hyperlinks are underlined as well so they don't rely on colour alone
+    pub fn colour_blind_safe() -> Theme {
+        Theme {
+            name: "Colour blind safe".to_string(),
+            text_fg: Color::Rgb(240, 240, 240),
+            text_bg: Color::Rgb(20, 20, 20),
+            dimmed_text_fg: Color::Rgb(150, 150, 150),
+            header_bg: Color::Rgb(86, 180, 233),
+            hyperlink_style: Style::new().fg(Color::Rgb(230, 159, 0)).underlined(),
+            web_hyperlink_fg: Some(Color::Rgb(86, 180, 233)),
+        }
+    }
+
+    pub fn monochrome() -> Theme {
+        Theme {
+            name: "Monochrome".to_string(),
+            text_fg: Color::White,
+            text_bg: Color::Black,
+            dimmed_text_fg: Color::DarkGray,
+            header_bg: Color::White,
+            hyperlink_style: Style::new().underlined(),
+            web_hyperlink_fg: None,
         }
     }
 
+    /// Themes that come with surf-bored, the first is used unless another is picked
+    pub fn built_in() -> Vec<Theme> {
+        vec![
+            Theme::surf_bored_synth_wave(),
+            Theme::light(),
+            Theme::high_contrast(),
+            Theme::high_contrast_light(),
+            Theme::colour_blind_safe(),
+            Theme::monochrome(),
+        ]
+    }
+
     /// to use for tests so should not be amended
     pub fn default() -> Theme {
         let style = Style::default();
@@ -49,7 +255,77 @@ impl Theme {
             dimmed_text_fg: style.fg.unwrap_or_default(),
             header_bg: style.bg.unwrap_or_default(),
             hyperlink_style: Style::new().underlined(),
+            web_hyperlink_fg: None,
+        }
+    }
+
+    /// The theme in the colours a bored would like to be shown in, those it doesn't set are
+    /// kept from the theme
+    pub fn with_palette(&self, palette: &Palette) -> Theme {
+        let rgb = |(red, green, blue)| Color::Rgb(red, green, blue);
+        let mut theme = self.clone();
+        if let Some(text) = palette.get_text() {
+            theme.text_fg = rgb(text);
+            theme.dimmed_text_fg = rgb(text);
+        }
+        if let Some(background) = palette.get_background() {
+            theme.text_bg = rgb(background);
+        }
+        // covered notices fade halfway into the background
+        if let (Some(text), Some(background)) = (palette.get_text(), palette.get_background()) {
+            let fade = |t: u8, b: u8| ((t as u16 + b as u16) / 2) as u8;
+            theme.dimmed_text_fg = Color::Rgb(
+                fade(text.0, background.0),
+                fade(text.1, background.1),
+                fade(text.2, background.2),
+            );
+        }
+        if let Some(header) = palette.get_header() {
+            theme.header_bg = rgb(header);
+        }
+        if let Some(hyperlink) = palette.get_hyperlink() {
+            theme.hyperlink_style = theme.hyperlink_style.fg(rgb(hyperlink));
+        }
+        theme
+    }
+
+    /// The theme in the nearest colours the terminal can show. If the text or header would end
+    /// up the same colour as the background they are swapped for ones that stand out.
+    pub fn for_colour_depth(&self, colour_depth: ColourDepth) -> Theme {
+        let reduce = |colour: Color| colour_depth.reduce(colour);
+        let mut theme = Theme {
+            name: self.name.clone(),
+            text_fg: reduce(self.text_fg),
+            text_bg: reduce(self.text_bg),
+            dimmed_text_fg: reduce(self.dimmed_text_fg),
+            header_bg: reduce(self.header_bg),
+            hyperlink_style: Style {
+                fg: self.hyperlink_style.fg.map(reduce),
+                bg: self.hyperlink_style.bg.map(reduce),
+                ..self.hyperlink_style
+            },
+            web_hyperlink_fg: self.web_hyperlink_fg.map(reduce),
+        };
+        let contrasting = match to_rgb(theme.text_bg) {
+            Some((red, green, blue)) if red as u16 + green as u16 + blue as u16 > 384 => {
+                Color::Black
+            }
+            _ => Color::White,
+        };
+        if theme.text_fg == theme.text_bg && theme.text_bg != Color::Reset {
+            theme.text_fg = contrasting;
+        }
+        if theme.dimmed_text_fg == theme.text_bg && theme.text_bg != Color::Reset {
+            theme.dimmed_text_fg = theme.text_fg;
+        }
+        if theme.header_bg == theme.text_bg && theme.text_bg != Color::Reset {
+            theme.header_bg = theme.text_fg;
         }
+        theme
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
     }
 
     pub fn header_style(&self) -> Style {
@@ -71,4 +347,370 @@ impl Theme {
     pub fn hyperlink_style(&self) -> Style {
         self.hyperlink_style
     }
+
+    /// Style of a hyperlink by where it goes, so links that leave the bored network stand out
+    /// from those that stay on it. Web links are in their own colour and italics, app commands
+    /// are bold rather than underlined.
+    pub fn get_hyperlink_style(&self, link: &str) -> Style {
+        match URL::from_string(link.to_string()) {
+            Ok(URL::ClearNet(_)) => match self.web_hyperlink_fg {
+                Some(web_hyperlink_fg) => self.hyperlink_style.fg(web_hyperlink_fg).italic(),
+                None => self.hyperlink_style.italic(),
+            },
+            Ok(URL::BoredApp(_)) => self.hyperlink_style.not_underlined().bold(),
+            _ => self.hyperlink_style,
+        }
+    }
+}
+
+/// A theme as written in the themes file, colours can be names such as "light blue", indexes
+/// of the 256 terminal colours such as "208" or hex such as "#cd98d3"
+#[derive(Debug, Deserialize, PartialEq)]
+struct ThemeConfig {
+    name: String,
+    text_fg: String,
+    text_bg: String,
+    /// Text of notices covered by others, the same as text_fg if not given
+    dimmed_text_fg: Option<String>,
+    header_bg: String,
+    hyperlink_fg: Option<String>,
+    /// Hyperlinks that leave the bored network, the same colour as other hyperlinks if not given
+    web_hyperlink_fg: Option<String>,
+    #[serde(default = "underline_hyperlinks")]
+    hyperlink_underlined: bool,
+    #[serde(default)]
+    hyperlink_bold: bool,
+    #[serde(default)]
+    hyperlink_italic: bool,
+    /// Swap the colours of hyperlinks so they stand out without relying on colour
+    #[serde(default)]
+    hyperlink_reversed: bool,
+}
+
+fn underline_hyperlinks() -> bool {
+    true
+}
+
+fn use_bored_colours() -> bool {
+    true
+}
+
+fn parse_colour(colour: &str) -> Result<Color, SurfBoredError> {
+    Color::from_str(colour).map_err(|_| SurfBoredError::UnknownColour(colour.to_string()))
+}
+
+impl ThemeConfig {
+    fn to_theme(&self) -> Result<Theme, SurfBoredError> {
+        let text_fg = parse_colour(&self.text_fg)?;
+        let mut hyperlink_style = Style::new();
+        if let Some(hyperlink_fg) = &self.hyperlink_fg {
+            hyperlink_style = hyperlink_style.fg(parse_colour(hyperlink_fg)?);
+        }
+        if self.hyperlink_underlined {
+            hyperlink_style = hyperlink_style.underlined();
+        }
+        if self.hyperlink_bold {
+            hyperlink_style = hyperlink_style.bold();
+        }
+        if self.hyperlink_italic {
+            hyperlink_style = hyperlink_style.italic();
+        }
+        if self.hyperlink_reversed {
+            hyperlink_style = hyperlink_style.reversed();
+        }
+        Ok(Theme {
+            name: self.name.clone(),
+            text_fg,
+            text_bg: parse_colour(&self.text_bg)?,
+            dimmed_text_fg: match &self.dimmed_text_fg {
+                Some(dimmed_text_fg) => parse_colour(dimmed_text_fg)?,
+                None => text_fg,
+            },
+            header_bg: parse_colour(&self.header_bg)?,
+            hyperlink_style,
+            web_hyperlink_fg: self
+                .web_hyperlink_fg
+                .as_deref()
+                .map(parse_colour)
+                .transpose()?,
+        })
+    }
+}
+
+/// The user's themes file, which sets the default theme and can add themes of their own
+#[derive(Debug, Deserialize, PartialEq)]
+pub struct ThemeFile {
+    /// Name of the theme to start with
+    pub default: Option<String>,
+    /// Whether to show boreds in the colours they ask for rather than the theme's
+    #[serde(default = "use_bored_colours")]
+    pub bored_colours: bool,
+    /// Colours the terminal can show, worked out from the environment if not given
+    pub colour_depth: Option<ColourDepth>,
+    #[serde(default)]
+    themes: Vec<ThemeConfig>,
+}
+
+impl Default for ThemeFile {
+    fn default() -> Self {
+        ThemeFile {
+            default: None,
+            bored_colours: use_bored_colours(),
+            colour_depth: None,
+            themes: vec![],
+        }
+    }
+}
+
+impl ThemeFile {
+    /// The themes file at the path, an empty one if there is no file yet
+    pub fn load_file(path: &Path) -> Result<ThemeFile, SurfBoredError> {
+        if !path.exists() {
+            return Ok(ThemeFile::default());
+        }
+        let Ok(theme_string) = fs::read_to_string(path) else {
+            return Err(SurfBoredError::ThemeFileReadError);
+        };
+        toml::from_str(&theme_string)
+            .map_err(|e| SurfBoredError::ThemeFileError(e.message().to_string()))
+    }
+
+    /// The built in themes followed by those in the file, one in the file with the same name
+    /// as a built in theme replaces it
+    pub fn get_themes(&self) -> Result<Vec<Theme>, SurfBoredError> {
+        let mut themes = Theme::built_in();
+        for theme_config in &self.themes {
+            let theme = theme_config.to_theme()?;
+            match themes.iter().position(|t| t.name == theme.name) {
+                Some(index) => themes[index] = theme,
+                None => themes.push(theme),
+            }
+        }
+        Ok(themes)
+    }
+
+    /// Set the default theme in the file at the path, keeping the themes already in it
+    pub fn save_default(path: &Path, name: &str) -> Result<(), SurfBoredError> {
+        Self::save_value(path, "default", toml::Value::String(name.to_string()))
+    }
+
+    /// Set whether boreds are shown in their own colours in the file at the path
+    pub fn save_bored_colours(path: &Path, bored_colours: bool) -> Result<(), SurfBoredError> {
+        Self::save_value(path, "bored_colours", toml::Value::Boolean(bored_colours))
+    }
+
+    fn save_value(path: &Path, key: &str, value: toml::Value) -> Result<(), SurfBoredError> {
+        let mut table = match fs::read_to_string(path) {
+            Ok(theme_string) => toml::from_str::<toml::Table>(&theme_string)
+                .map_err(|e| SurfBoredError::ThemeFileError(e.message().to_string()))?,
+            Err(_) => toml::Table::new(),
+        };
+        table.insert(key.to_string(), value);
+        let Ok(theme_string) = toml::to_string(&table) else {
+            return Err(SurfBoredError::ThemeFileWriteError);
+        };
+        let Ok(()) = fs::write(path, theme_string) else {
+            return Err(SurfBoredError::ThemeFileWriteError);
+        };
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::Modifier;
+
+    #[test]
+    fn test_theme_file() -> Result<(), SurfBoredError> {
+        let path =
+            std::env::temp_dir().join(format!("surf-bored-themes-{}.toml", std::process::id()));
+        let theme_file = ThemeFile::load_file(&path)?;
+        assert_eq!(theme_file, ThemeFile::default());
+        assert_eq!(theme_file.get_themes()?, Theme::built_in());
+        fs::write(
+            &path,
+            r##"
+            default = "Ocean"
+            colour_depth = "256"
+
+            [[themes]]
+            name = "Ocean"
+            text_fg = "#e0f0ff"
+            text_bg = "#001a33"
+            header_bg = "38"
+            hyperlink_fg = "light cyan"
+            hyperlink_bold = true
+
+            [[themes]]
+            name = "Light"
+            text_fg = "black"
+            text_bg = "white"
+            dimmed_text_fg = "gray"
+            header_bg = "blue"
+            hyperlink_underlined = false
+            hyperlink_reversed = true
+            "##,
+        )?;
+        let theme_file = ThemeFile::load_file(&path)?;
+        assert_eq!(theme_file.default, Some("Ocean".to_string()));
+        assert!(theme_file.bored_colours);
+        assert_eq!(theme_file.colour_depth, Some(ColourDepth::Indexed256));
+        let themes = theme_file.get_themes()?;
+        assert_eq!(themes.len(), Theme::built_in().len() + 1);
+        let ocean = &themes[themes.len() - 1];
+        assert_eq!(ocean.get_name(), "Ocean");
+        assert_eq!(ocean.text_fg, Color::Rgb(224, 240, 255));
+        assert_eq!(ocean.dimmed_text_fg, ocean.text_fg);
+        assert_eq!(ocean.header_bg, Color::Indexed(38));
+        assert_eq!(
+            ocean.hyperlink_style(),
+            Style::new().fg(Color::LightCyan).underlined().bold()
+        );
+        // replaces the built in theme
+        assert_eq!(themes[1].get_name(), "Light");
+        assert_eq!(themes[1].text_bg, Color::White);
+        assert_eq!(themes[1].hyperlink_style(), Style::new().reversed());
+
+        ThemeFile::save_default(&path, "Light")?;
+        ThemeFile::save_bored_colours(&path, false)?;
+        let theme_file = ThemeFile::load_file(&path)?;
+        assert_eq!(theme_file.default, Some("Light".to_string()));
+        assert!(!theme_file.bored_colours);
+        assert_eq!(theme_file.get_themes()?, themes);
+
+        fs::write(
+            &path,
+            "[[themes]]\nname = \"Bad\"\ntext_fg = \"not a colour\"\ntext_bg = \"black\"\nheader_bg = \"red\"",
+        )?;
+        assert_eq!(
+            ThemeFile::load_file(&path)?.get_themes(),
+            Err(SurfBoredError::UnknownColour("not a colour".to_string()))
+        );
+        fs::write(&path, "themes = 3")?;
+        assert!(ThemeFile::load_file(&path).is_err());
+        let _ = fs::remove_file(path);
+        Ok(())
+    }
+    #[test]
+    fn test_colour_depth() {
+        assert_eq!(
+            ColourDepth::from_env(Some("truecolor"), Some("xterm-256color")),
+            ColourDepth::TrueColour
+        );
+        assert_eq!(
+            ColourDepth::from_env(None, Some("xterm-256color")),
+            ColourDepth::Indexed256
+        );
+        assert_eq!(
+            ColourDepth::from_env(None, Some("xterm-direct")),
+            ColourDepth::TrueColour
+        );
+        assert_eq!(
+            ColourDepth::from_env(None, Some("linux")),
+            ColourDepth::Ansi16
+        );
+
+        let colour = Color::Rgb(205, 152, 211);
+        assert_eq!(ColourDepth::TrueColour.reduce(colour), colour);
+        assert_eq!(ColourDepth::Indexed256.reduce(colour), Color::Indexed(176));
+        assert_eq!(ColourDepth::Ansi16.reduce(colour), Color::Gray);
+        assert_eq!(
+            ColourDepth::Indexed256.reduce(Color::Rgb(100, 100, 100)),
+            Color::Indexed(241)
+        );
+        assert_eq!(
+            ColourDepth::Ansi16.reduce(Color::Indexed(196)),
+            Color::LightRed
+        );
+        assert_eq!(ColourDepth::Ansi16.reduce(Color::Reset), Color::Reset);
+        assert_eq!(ColourDepth::Ansi16.reduce(Color::Yellow), Color::Yellow);
+
+        // every built in theme must stay readable however few colours the terminal has
+        for colour_depth in [ColourDepth::Indexed256, ColourDepth::Ansi16] {
+            for theme in Theme::built_in() {
+                let reduced = theme.for_colour_depth(colour_depth);
+                assert_ne!(reduced.text_fg, reduced.text_bg, "{}", theme.get_name());
+                assert_ne!(
+                    reduced.dimmed_text_fg,
+                    reduced.text_bg,
+                    "{}",
+                    theme.get_name()
+                );
+                assert_ne!(reduced.header_bg, reduced.text_bg, "{}", theme.get_name());
+                assert!(
+                    reduced
+                        .hyperlink_style()
+                        .add_modifier
+                        .intersects(Modifier::UNDERLINED | Modifier::BOLD | Modifier::REVERSED),
+                    "{}",
+                    theme.get_name()
+                );
+            }
+        }
+        let theme = Theme {
+            name: "Murky".to_string(),
+            text_fg: Color::Rgb(20, 20, 30),
+            text_bg: Color::Rgb(10, 10, 10),
+            dimmed_text_fg: Color::Rgb(15, 15, 15),
+            header_bg: Color::Rgb(30, 20, 20),
+            hyperlink_style: Style::new().fg(Color::Rgb(240, 0, 0)).underlined(),
+            web_hyperlink_fg: Some(Color::Rgb(0, 0, 240)),
+        };
+        let reduced = theme.for_colour_depth(ColourDepth::Ansi16);
+        assert_eq!(reduced.text_bg, Color::Black);
+        assert_eq!(reduced.text_fg, Color::White);
+        assert_eq!(reduced.dimmed_text_fg, Color::White);
+        assert_eq!(reduced.header_bg, Color::White);
+        assert_eq!(reduced.hyperlink_style().fg, Some(Color::LightRed));
+        assert_eq!(reduced.web_hyperlink_fg, Some(Color::Blue));
+    }
+
+    #[test]
+    fn test_hyperlink_styles() {
+        let theme = Theme::high_contrast();
+        assert_eq!(
+            theme.get_hyperlink_style("bored://games"),
+            theme.hyperlink_style()
+        );
+        assert_eq!(
+            theme.get_hyperlink_style("bored+ro://games"),
+            theme.hyperlink_style()
+        );
+        assert_eq!(
+            theme.get_hyperlink_style("https://autonomi.com"),
+            Style::new().fg(Color::Cyan).underlined().bold().italic()
+        );
+        assert_eq!(
+            theme.get_hyperlink_style("app://about"),
+            Style::new().fg(Color::Yellow).not_underlined().bold()
+        );
+        // without a colour of their own web links are still told apart
+        assert_eq!(
+            Theme::monochrome().get_hyperlink_style("http://autonomi.com"),
+            Style::new().underlined().italic()
+        );
+    }
+
+    #[test]
+    fn test_with_palette() -> Result<(), bored::BoredError> {
+        let theme = Theme::surf_bored_synth_wave();
+        assert_eq!(theme.with_palette(&Palette::default()), theme);
+        let palette = Palette::create(Some("#ffffff"), Some("#000000"), None, Some("#ff0000"))?;
+        let coloured = theme.with_palette(&palette);
+        assert_eq!(coloured.get_name(), theme.get_name());
+        assert_eq!(coloured.text_fg, Color::Rgb(255, 255, 255));
+        assert_eq!(coloured.text_bg, Color::Rgb(0, 0, 0));
+        assert_eq!(coloured.dimmed_text_fg, Color::Rgb(127, 127, 127));
+        assert_eq!(coloured.header_bg, theme.header_bg);
+        assert_eq!(
+            coloured.hyperlink_style(),
+            Style::new().fg(Color::Rgb(255, 0, 0)).underlined()
+        );
+        let palette = Palette::create(None, None, Some("#6de4af"), None)?;
+        let coloured = theme.with_palette(&palette);
+        assert_eq!(coloured.text_style(), theme.text_style());
+        assert_eq!(coloured.header_bg, Color::Rgb(109, 228, 175));
+        Ok(())
+    }
 }
@@ -0,0 +1,141 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// How long each toast is shown for
+pub const TOAST_TIMEOUT: Duration = Duration::from_secs(4);
+/// Most toasts waiting to be shown, the oldest are dropped as they would be out of date by the
+/// time they were shown
+const MAX_TOASTS: usize = 4;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ToastKind {
+    Info,
+    Success,
+    Warning,
+}
+
+impl ToastKind {
+    pub fn get_title(&self) -> &'static str {
+        match self {
+            ToastKind::Info => "Info",
+            ToastKind::Success => "Done",
+            ToastKind::Warning => "Warning",
+        }
+    }
+}
+
+/// A short message shown in the corner for a while, for things that don't need the user to
+/// stop what they are doing
+#[derive(Debug)]
+pub struct Toast {
+    kind: ToastKind,
+    message: String,
+    /// When the toast was first shown, none while it waits behind others
+    shown: Option<Instant>,
+}
+
+impl Toast {
+    pub fn get_kind(&self) -> ToastKind {
+        self.kind
+    }
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+}
+
+/// Toasts shown one after another, each for TOAST_TIMEOUT
+#[derive(Debug, Default)]
+pub struct Toasts {
+    queue: VecDeque<Toast>,
+}
+
+impl Toasts {
+    pub fn push(&mut self, kind: ToastKind, message: &str) {
+        if self.queue.len() >= MAX_TOASTS {
+            // keep the one being shown so it isn't cut short
+            self.queue.remove(1);
+        }
+        self.queue.push_back(Toast {
+            kind,
+            message: message.to_string(),
+            shown: None,
+        });
+    }
+
+    /// The toast to show now
+    pub fn get_current(&self) -> Option<&Toast> {
+        self.queue.front()
+    }
+
+    /// Drop the toast being shown once its time is up, starting the time of the next one
+    pub fn update(&mut self) {
+        self.update_at(Instant::now());
+    }
+
+    fn update_at(&mut self, now: Instant) {
+        if let Some(shown) = self.queue.front().and_then(|toast| toast.shown)
+            && now.duration_since(shown) >= TOAST_TIMEOUT
+        {
+            self.queue.pop_front();
+        }
+        if let Some(toast) = self.queue.front_mut()
+            && toast.shown.is_none()
+        {
+            toast.shown = Some(now);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_toasts() {
+        let mut toasts = Toasts::default();
+        assert!(toasts.get_current().is_none());
+        toasts.push(ToastKind::Success, "Copied");
+        toasts.push(ToastKind::Info, "Refreshed");
+        let start = Instant::now();
+        toasts.update_at(start);
+        assert_eq!(toasts.get_current().map(Toast::get_message), Some("Copied"));
+        toasts.update_at(start + TOAST_TIMEOUT / 2);
+        assert_eq!(toasts.get_current().map(Toast::get_message), Some("Copied"));
+        // the next one gets its full time from when it is shown
+        let next = start + TOAST_TIMEOUT;
+        toasts.update_at(next);
+        assert_eq!(
+            toasts.get_current().map(Toast::get_kind),
+            Some(ToastKind::Info)
+        );
+        toasts.update_at(next + TOAST_TIMEOUT / 2);
+        assert!(toasts.get_current().is_some());
+        toasts.update_at(next + TOAST_TIMEOUT);
+        assert!(toasts.get_current().is_none());
+        // too many and the oldest waiting are dropped, not the one being shown
+        for message in ["1", "2", "3", "4", "5"] {
+            toasts.push(ToastKind::Warning, message);
+        }
+        toasts.update_at(start);
+        let messages: Vec<&str> = toasts.queue.iter().map(Toast::get_message).collect();
+        assert_eq!(messages, vec!["1", "3", "4", "5"]);
+    }
+}
@@ -15,27 +15,42 @@ You should have received a copy of the GNU Affero General Public License
 along with this program.  If not, see <https://www.gnu.org/licenses/>.
 */
 
-use bored::Coordinate;
-use bored::notice::{Notice, NoticeHyperlinkMap, get_display, get_hyperlinks};
+use bored::{Bored, Coordinate, Coverage, MAX_BORED_SIZE};
+use bored::notice::{Notice, NoticeHyperlinkMap, NoticeKind, get_display, get_hyperlinks};
+use bored::templates::TEMPLATES;
+use bored::x0x_client::{LedgerOperation, PostingEstimate, expected_hashes};
+use chrono::Local;
 use ratatui::buffer::Buffer;
 use ratatui::style::Stylize;
-use ratatui::widgets::{BorderType, Row, Table, TableState, Widget};
+use ratatui::widgets::{
+    BorderType, Row, Scrollbar, ScrollbarOrientation, ScrollbarState, Table, TableState, Widget,
+};
 use ratatui::{
-    Frame, Terminal,
-    backend::Backend,
+    Frame,
     layout::{Alignment, Constraint, Direction, Layout, Margin, Rect},
-    style::Style,
-    text::{Span, Text},
+    style::{Color, Style},
+    text::{Line, Span, Text},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
 use std::cmp::min;
 use std::time::Duration;
-use tokio::time::sleep;
 
-use crate::app::{App, CreateMode, DraftMode, HyperlinkMode, SurfBoredError, View};
-use crate::display_bored::BoredViewPort;
-use crate::display_bored::{character_wrap, style_notice_hyperlinks};
-use crate::theme::Theme;
+use crate::app::{
+    App, CreateMode, DRAFT_GRID_SPACING, DirectoryFileAction, DraftMode, EventField, HyperlinkMode,
+    KeyMode, ListingField, TRUSTED_SCHEMES_VAR, View,
+};
+use crate::directory::fuzzy_match;
+use crate::display_bored::{BoredViewPort, MiniMap, Overview};
+use crate::display_bored::{
+    add_event_titles, character_wrap, get_linear_lines, get_percent_showing, get_recency_order,
+    label_notice_hyperlinks, style_notice_hyperlinks,
+};
+use crate::downloads::{DownloadStatus, format_size};
+use crate::error_log::format_age;
+use crate::help;
+use crate::qr::get_qr_lines;
+use crate::theme::{ColourDepth, Theme};
+use crate::toasts::{Toast, ToastKind};
 
 pub fn ui(frame: &mut Frame, app: &mut App) {
     let area = frame.area();
@@ -52,37 +67,81 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
         ])
         .split(area);
     let bored = app.get_current_bored();
+    app.apply_palette(bored.as_ref().map(|bored| bored.get_palette()));
     if let Some(ref bored) = bored {
         bored_url = if let Some(client) = app.client.as_ref() {
             if let Ok(bored_address) = client.get_bored_address() {
-                bored_address.to_string()
+                bored_address.to_derived_name().to_string()
             } else {
                 String::new()
             }
         } else {
             String::new()
         };
-        bored_name = bored.get_name().to_owned() + "\n";
-        let mut bored_view_port = BoredViewPort::create(
-            &bored,
-            Coordinate {
-                x: ui_chunks[1].width,
-                y: ui_chunks[1].height,
-            },
-            app.selected_notice,
-        );
+        let new_notices = app.get_new_notices(bored);
+        bored_name = match new_notices.len() {
+            0 => bored.get_name().to_owned(),
+            1 => format!("{} (1 new notice)", bored.get_name()),
+            count => format!("{} ({count} new notices)", bored.get_name()),
+        };
+        if let Some(moved_to) = bored.get_moved_to() {
+            bored_name.push_str(&format!(" (says it has moved to {moved_to})"));
+        }
+        bored_name.push('\n');
+        let view_dimensions = Coordinate {
+            x: ui_chunks[1].width,
+            y: ui_chunks[1].height,
+        };
+        // keep the view port between frames so the bored is only rendered again when it changes
+        let mut bored_view_port = match app.bored_view_port.take() {
+            Some(mut bored_view_port) => {
+                bored_view_port.update(bored, view_dimensions, app.selected_notice);
+                bored_view_port
+            }
+            None => BoredViewPort::create(bored.clone(), view_dimensions, app.selected_notice),
+        };
+        bored_view_port.raise_notice(app.get_peeked_notice());
+        bored_view_port.mark_dead_links(app.get_dead_links());
         if let View::NoticeView {
             hyperlinks_index: _,
         } = app.current_view
         {
+        } else if app.linear_mode {
+            let lines = get_linear_lines(bored, app.selected_notice, &app.theme, &app.petnames)
+                .unwrap_or_default();
+            let linear = Paragraph::new(lines)
+                .style(app.theme.text_style())
+                .wrap(Wrap { trim: false });
+            frame.render_widget(linear, ui_chunks[1]);
+            // screen readers follow the cursor so keep it at the start of the selected notice
+            frame.set_cursor_position((ui_chunks[1].x, ui_chunks[1].y));
         } else {
-            if let Some(view_top_left) = app.bored_view_port.as_ref().map(|s| s.get_view_top_left())
-            {
-                bored_view_port.move_view(view_top_left);
-            }
             let mut bored_view_buffer = Buffer::empty(ui_chunks[1]);
             bored_view_port.render_view(&mut bored_view_buffer, app.theme.clone());
+            bored_view_port.shade_notices(
+                &mut bored_view_buffer,
+                &new_notices,
+                Style::new().bold().italic(),
+            );
+            for (i, hit) in app.get_search_hits().iter().enumerate() {
+                let style = if Some(i) == app.search_hit {
+                    app.theme.inverted_text_style().underlined().bold()
+                } else {
+                    app.theme.inverted_text_style()
+                };
+                bored_view_port.highlight(&mut bored_view_buffer, &hit.coordinates, style);
+            }
             frame.buffer_mut().merge(&bored_view_buffer);
+            render_scrollbars(
+                frame,
+                ui_chunks[1],
+                bored.get_dimensions(),
+                bored_view_port.get_view_top_left(),
+                app.theme.dimmed_text_style(),
+            );
+            if app.minimap_visible {
+                render_minimap(frame, ui_chunks[1], bored, &bored_view_port, app);
+            }
         }
         app.bored_view_port = Some(bored_view_port);
     } else {
@@ -171,16 +230,37 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
                     name_block = name_block.clone().style(app.theme.inverted_text_style())
                 }
                 CreateMode::URLName => {
-                    status_text =
-                        "Type url name, press (enter) to create board or (esc) to leave. Leave blank to have random url"
-                            .to_string();
+                    status_text = match &app.url_name_check {
+                        Some(check) if check.taken && check.url_name == app.url_name_input.trim() => {
+                            "That url name is taken, press (up) or (down) for a free one, (enter) to use it anyway or (esc) to leave".to_string()
+                        }
+                        _ => "Type url name, press (enter) to check it is free and create board or (esc) to leave. Leave blank to have random url".to_string(),
+                    };
                     url_name_block = url_name_block
                         .clone()
                         .style(app.theme.inverted_text_style())
                 }
             };
             let name_text = Paragraph::new(app.name_input.clone()).block(name_block);
-            let url_name_text = Paragraph::new(app.url_name_input.clone()).block(url_name_block);
+            let mut url_name_lines = vec![Line::from(app.url_name_input.clone())];
+            if let Some(check) = app.url_name_check.as_ref().filter(|check| check.taken) {
+                url_name_lines.push(Line::from(""));
+                url_name_lines.push(Line::styled(
+                    if check.free.is_empty() {
+                        format!("bored://{} is taken", check.url_name)
+                    } else {
+                        format!(
+                            "bored://{} is taken, free names close to it: {}",
+                            check.url_name,
+                            check.free.join(", ")
+                        )
+                    },
+                    app.theme.dimmed_text_style(),
+                ));
+            }
+            let url_name_text = Paragraph::new(url_name_lines)
+                .block(url_name_block)
+                .wrap(Wrap { trim: false });
             frame.render_widget(name_text, pop_up_chunks[0]);
             frame.render_widget(url_name_text, pop_up_chunks[1]);
         }
@@ -188,7 +268,7 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
             if let Some(draft) = app.get_draft() {
                 match draft_mode {
                     DraftMode::Content => {
-                        status_text = "Type to enter message, (ctrl + h) to insert hyperlink, (ctrl + p) to position notice or (esc) to leave".to_string();
+                        status_text = "Type to enter message, (ctrl + h) to insert hyperlink, (ctrl + p) to position notice, (ctrl + g) to post it in the next free space, (ctrl + r) to resize it, (ctrl + e) to write it in your editor, (ctrl + t) to start from a template, (ctrl + w) to make it an event, (ctrl + o) to embed the notice picked with (m) or (esc) to leave".to_string();
                         let display = draft.get_display().unwrap();
                         let display_text = display.get_display_text();
                         let display_text = character_wrap(display_text, draft.get_text_width());
@@ -201,6 +281,7 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
                             .borders(Borders::ALL)
                             .border_type(BorderType::Thick)
                             .style(app.theme.text_style());
+                        let draft_block = add_event_titles(draft_block, &draft);
                         let draft_text = Paragraph::new(display_text).block(draft_block);
                         let mut draft_buffer = Buffer::empty(draft_rect);
                         draft_text.render(draft_rect, &mut draft_buffer);
@@ -211,7 +292,7 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
                                 x: draft_rect.x,
                                 y: draft_rect.y,
                             },
-                            app.theme.hyperlink_style(),
+                            &app.theme,
                         );
                         frame.buffer_mut().merge(&draft_buffer);
                     }
@@ -267,8 +348,93 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
                         frame.render_widget(link_note, pop_up_chunks[1]);
                         frame.render_widget(link_url, pop_up_chunks[2]);
                     }
-                    DraftMode::Position => {
-                        status_text = "Use (the arrow keys) to position the notice and (enter) to place or (esc) to edit text".to_string();
+                    DraftMode::Event(field) => {
+                        status_text = "Type the event's details, (tab) to move to the next one, (enter) to save them or (esc) to leave them as they were. Leaving the title blank makes the notice plain text".to_string();
+                        let pop_up_rect = area.inner(Margin::new(area.width / 8, area.height / 5));
+                        Clear.render(pop_up_rect, frame.buffer_mut());
+                        let pop_up_block = Block::default()
+                            .title("Make the notice an event")
+                            .borders(Borders::ALL)
+                            .border_type(BorderType::Thick)
+                            .style(app.theme.text_style());
+                        frame.render_widget(pop_up_block, pop_up_rect);
+                        let pop_up_chunks = Layout::default()
+                            .direction(Direction::Vertical)
+                            .margin(1)
+                            .constraints([Constraint::Ratio(1, 4); 4])
+                            .split(pop_up_rect);
+                        let fields = [
+                            (EventField::Title, "Title", &app.event_title_input),
+                            (
+                                EventField::Start,
+                                "Starts, as 2026-06-12 19:00 in your time zone",
+                                &app.event_start_input,
+                            ),
+                            (
+                                EventField::End,
+                                "Ends, optional, a time alone is on the day it starts",
+                                &app.event_end_input,
+                            ),
+                            (
+                                EventField::Location,
+                                "Where, optional",
+                                &app.event_location_input,
+                            ),
+                        ];
+                        for ((event_field, title, input), chunk) in
+                            fields.into_iter().zip(pop_up_chunks.iter())
+                        {
+                            let style = if event_field == *field {
+                                app.theme.inverted_text_style()
+                            } else {
+                                app.theme.text_style()
+                            };
+                            let block = Block::default().title(title).style(style);
+                            let paragraph = Paragraph::new(input.clone())
+                                .block(block)
+                                .wrap(Wrap { trim: false });
+                            frame.render_widget(paragraph, *chunk);
+                        }
+                    }
+                    DraftMode::Position | DraftMode::Resize | DraftMode::Confirm(_) => {
+                        status_text = match draft_mode {
+                            DraftMode::Resize => "Use (shift + the arrow keys) to grow or shrink the notice and (enter) or (esc) to go back to editing text".to_string(),
+                            DraftMode::Confirm(_) => "Press (y) to post the notice or (n) to carry on positioning it".to_string(),
+                            _ => {
+                                let top_left = draft.get_top_left();
+                                format!(
+                                    "Top left: {}, {}. Use (the arrow keys) or drag with the mouse to position the notice, (g) to turn snapping to the grid {} and (enter) to place or (esc) to edit text",
+                                    top_left.x,
+                                    top_left.y,
+                                    if app.grid_snap { "off" } else { "on" },
+                                )
+                            }
+                        };
+                        if draft_mode == &DraftMode::Position
+                            && let Some(bored_view_port) = &app.bored_view_port
+                        {
+                            bored_view_port.render_grid(
+                                frame.buffer_mut(),
+                                DRAFT_GRID_SPACING,
+                                app.theme.dimmed_text_style(),
+                            );
+                            let covered = app.get_covered_by_draft();
+                            bored_view_port.shade_covered(
+                                frame.buffer_mut(),
+                                &covered,
+                                app.theme.dimmed_text_style(),
+                                app.theme.inverted_text_style().crossed_out(),
+                            );
+                            let hidden = covered
+                                .iter()
+                                .filter(|(_, coverage)| *coverage == Coverage::Full)
+                                .count();
+                            if hidden > 0 {
+                                status_text = format!(
+                                    "Placing the notice here would hide {hidden} notice(s) entirely, (y) places it anyway. {status_text}"
+                                );
+                            }
+                        }
                         let display = draft.get_display().unwrap();
                         let display_text = display.get_display_text();
                         let display_text = character_wrap(display_text, draft.get_text_width());
@@ -281,6 +447,7 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
                             .borders(Borders::ALL)
                             .border_type(BorderType::Thick)
                             .style(app.theme.text_style());
+                        let draft_block = add_event_titles(draft_block, &draft);
                         let draft_text = Paragraph::new(display_text).block(draft_block);
                         let mut draft_buffer = Buffer::empty(draft_rect);
                         draft_text.render(draft_rect, &mut draft_buffer);
@@ -291,9 +458,12 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
                                 x: draft_rect.x,
                                 y: draft_rect.y,
                             },
-                            app.theme.hyperlink_style(),
+                            &app.theme,
                         );
                         frame.buffer_mut().merge(&draft_buffer);
+                        if let DraftMode::Confirm(estimate) = draft_mode {
+                            render_posting_confirmation(frame, area, estimate, app);
+                        }
                     }
                 }
             }
@@ -305,25 +475,110 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
                     "g   Goto bored",
                     "d   Open directory of boreds",
                     "a   About Surf Bored",
+                    "?   Help",
                     "q   Quit",
                 ]
             } else {
-                status_text = "Use (the arrow keys) to select a notice in that direction, (tab) to cycle selection, (enter) to view notice (n) to create a new notice, (s) to save to directory or (space) to view menu.".to_string();
+                status_text = "Use (the arrow keys) to select a notice in that direction, (tab) to cycle selection, (enter) to view notice (n) to create a new notice, (p) to create one in the next free space, (/) to search, (s) to save to directory, (b) to save to directory under a name of your choosing or (space) to view menu. (ctrl + arrow keys), (page up/down) and (home/end) move the view, (backspace) or (alt + left/right) go back and forward through boreds. Click to select a notice or follow a hyperlink, scroll or drag empty space to move about.".to_string();
+                if app.linear_mode {
+                    status_text = "Linear view. Use (up) and (down) to move between notices, (home) and (end) for the first and last, (enter) to view the selected notice and follow its links, (n) to create a new notice, (v) to show the bored's layout or (space) to view menu.".to_string();
+                }
+                if let Some(search) = &app.search {
+                    let hit_count = app.get_search_hits().len();
+                    let hit = app.search_hit.map_or(0, |i| i + 1);
+                    status_text = format!(
+                        "Match {hit} of {hit_count} for \"{search}\", press (n) for the next match, (N) for the previous, (/) to change the search or (esc) to stop searching"
+                    );
+                }
+                if let Some(Ok(stats)) = bored.as_ref().map(|b| b.stats()) {
+                    status_text.push_str(&format!(
+                        "\n{} notices, {:.0}% full",
+                        stats.notice_count, stats.fill_percentage
+                    ));
+                }
+                if let Some(hyperlink) = &app.hovered_hyperlink {
+                    status_text
+                        .push_str(&format!("\n{}", describe_hyperlink(&hyperlink.get_link())));
+                    if let Some(preview) = app.get_link_preview(&hyperlink.get_link()) {
+                        status_text.push_str(&format!("\n{preview}"));
+                    }
+                }
+                if app.keymap == crate::keymap::Keymap::Vim {
+                    status_text.push_str("\nVim keys: (h/j/k/l) select, (gg/G) top and bottom, (i) new notice, (:) command");
+                }
                 vec![
                     "r   Refresh bored",
                     "n   New notice",
+                    "p   New notice in next free space",
                     "s   Save board to directory",
+                    "b   Save board to directory as...",
+                    "/   Search bored",
                     "c   Create bored",
                     "g   Goto bored",
+                    "y   Copy address of bored",
+                    "Q   QR code of address",
                     "d   Open directory of boreds",
+                    "l   List downloads",
+                    "f   Files attached to notices",
+                    "m   Show or hide map",
+                    "z   Zoomed out overview",
+                    "x   Peek under notices",
+                    "t   Themes",
+                    "e   Drafts",
+                    "k   Reader key",
+                    "E   Error log",
+                    "L   Ledger",
+                    "w   List of notices",
+                    "C   Check links",
+                    "I   Index of linked boreds",
+                    "W   Graph of linked boreds",
+                    "v   Linear view for screen readers",
                     "a   About",
+                    "?   Help",
                     "q   Quit",
                 ]
             }
         }
         View::NoticeView { hyperlinks_index } => {
             if let Some(notice) = app.get_selected_notice() {
-                status_text = "Press (tab) to cycle through hyperlinks, (enter) to activate selected hyperlink and (esc) to leave".to_string();
+                status_text = "Press (tab) to cycle through hyperlinks, (enter) to activate selected hyperlink, (1-9) to activate a numbered hyperlink, (y) to copy the hyperlink or the notice text, (m) to pick it to embed in another notice and (esc) to leave".to_string();
+                if let Some(attachment) = notice.get_attachment() {
+                    status_text.push_str(&format!(
+                        "\nAttached: {} ({}), press (d) to download",
+                        attachment.get_filename(),
+                        format_size(attachment.get_size())
+                    ));
+                }
+                if let (NoticeKind::Event { title, .. }, Some(when)) =
+                    (notice.get_kind(), notice.get_kind().describe_event(&Local))
+                {
+                    status_text.push_str(&format!(
+                        "\nEvent: {title}, {when}, press (c) to save the events on this bored to add to a calendar"
+                    ));
+                }
+                if let Some(author) = app.petnames.describe_author(&notice) {
+                    status_text.push_str(&format!(
+                        "\nPosted by {author}, press (p) to give them a name only you see"
+                    ));
+                }
+                let views = app.get_notice_views(&notice);
+                if views > 0 {
+                    status_text.push_str(&format!(
+                        "\nOpened by {views} reader{} that count views",
+                        if views == 1 { "" } else { "s" }
+                    ));
+                }
+                if let Some(hyperlink) = app.get_selected_hyperlink() {
+                    status_text
+                        .push_str(&format!("\n{}", describe_hyperlink(&hyperlink.get_link())));
+                    if let Some(preview) = app.get_link_preview(&hyperlink.get_link()) {
+                        status_text.push_str(&format!("\n{preview}"));
+                    }
+                    if app.get_dead_links().contains(&hyperlink.get_link()) {
+                        status_text
+                            .push_str("\nThis link went nowhere when the links were checked");
+                    }
+                }
                 let pop_up_rect = area.inner(Margin::new(
                     safe_subtract_u16(area.width, notice.get_dimensions().x) / 2,
                     safe_subtract_u16(area.height, notice.get_dimensions().y) / 2,
@@ -342,6 +597,7 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
                     .borders(Borders::ALL)
                     .border_type(border_type)
                     .style(app.theme.inverted_text_style());
+                let pop_up_block = add_event_titles(pop_up_block, &notice);
                 let pop_up_text =
                     character_wrap(display.get_display_text(), notice.get_text_width());
                 let pop_up_paragraph =
@@ -355,7 +611,7 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
                         x: pop_up_rect.x,
                         y: pop_up_rect.y,
                     },
-                    app.theme.hyperlink_style(),
+                    &app.theme,
                 );
                 // Highlight selected hyperlink
                 if let Ok(notice_hyperlink_map) = NoticeHyperlinkMap::create(&notice) {
@@ -371,42 +627,1083 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
                         }
                     }
                 }
+                label_notice_hyperlinks(
+                    &notice,
+                    &mut pop_up_buffer,
+                    Coordinate {
+                        x: pop_up_rect.x,
+                        y: pop_up_rect.y,
+                    },
+                    app.theme.text_style().bold(),
+                );
                 frame.buffer_mut().merge(&pop_up_buffer);
             }
         }
+        View::HelpView { scroll, from } => {
+            let pop_up_rect = area.inner(Margin::new(area.width / 10, area.height / 10));
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            let pop_up_block = Block::default()
+                .title("Help")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .style(app.theme.text_style());
+            let help_area = pop_up_block.inner(pop_up_rect);
+            frame.render_widget(pop_up_block, pop_up_rect);
+            let help_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(6), Constraint::Fill(1)])
+                .split(help_area);
+            let concepts = Paragraph::new(help::CONCEPTS)
+                .style(app.theme.text_style())
+                .wrap(Wrap { trim: true });
+            frame.render_widget(concepts, help_chunks[0]);
+            let mut rows = vec![];
+            for section in help::get_help_sections(from) {
+                rows.push(Row::new(vec![
+                    Span::from(section.title).bold().underlined(),
+                ]));
+                for binding in section.bindings {
+                    rows.push(Row::new(vec![binding.keys, binding.action]));
+                }
+                rows.push(Row::new(vec![""]));
+            }
+            let table = Table::new(rows, [Constraint::Length(24), Constraint::Fill(1)])
+                .style(app.theme.text_style());
+            let mut table_state = TableState::default().with_offset(*scroll as usize);
+            frame.render_stateful_widget(table, help_chunks[1], &mut table_state);
+            status_text =
+                "Press (up/down) or (page up/down) to scroll and (esc), (?) or (F1) to close the help"
+                    .to_string();
+        }
+        View::SearchView => {
+            let pop_up_rect = area.inner(Margin::new(
+                area.width / 4,
+                safe_subtract_u16(area.height, 3) / 2,
+            ));
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            let pop_up_block = Block::default()
+                .title("Search the board")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .style(app.theme.text_style());
+            let search_text = Paragraph::new(app.search_input.clone())
+                .style(app.theme.inverted_text_style())
+                .block(pop_up_block);
+            frame.render_widget(search_text, pop_up_rect);
+            status_text =
+                "Type the text to search notices for, press (enter) to search or (esc) to leave"
+                    .to_string();
+        }
+        View::CommandView => {
+            let pop_up_rect = area.inner(Margin::new(
+                area.width / 4,
+                safe_subtract_u16(area.height, 3) / 2,
+            ));
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            let pop_up_block = Block::default()
+                .title("Command")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .style(app.theme.text_style());
+            let command_text = Paragraph::new(format!(":{}", app.command_input))
+                .style(app.theme.inverted_text_style())
+                .block(pop_up_block);
+            frame.render_widget(command_text, pop_up_rect);
+            status_text = "Type a command such as (e bored://address), (dir), (w) or (q), press (enter) to run it or (esc) to leave".to_string();
+        }
+        View::BookmarkView => {
+            let pop_up_rect = area.inner(Margin::new(
+                area.width / 4,
+                safe_subtract_u16(area.height, 3) / 2,
+            ));
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            let pop_up_block = Block::default()
+                .title("Add board to directory as")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .style(app.theme.text_style());
+            let name_text = Paragraph::new(app.bookmark_name_input.clone())
+                .style(app.theme.inverted_text_style())
+                .block(pop_up_block);
+            frame.render_widget(name_text, pop_up_rect);
+            status_text =
+                "Type to name the board in your directory, press (enter) to add it or (esc) to leave"
+                    .to_string();
+        }
+        View::PetnameView => {
+            let pop_up_rect = area.inner(Margin::new(
+                area.width / 4,
+                safe_subtract_u16(area.height, 3) / 2,
+            ));
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            let pop_up_block = Block::default()
+                .title("Show the author as")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .style(app.theme.text_style());
+            let name_text = Paragraph::new(app.petname_input.clone())
+                .style(app.theme.inverted_text_style())
+                .block(pop_up_block);
+            frame.render_widget(name_text, pop_up_rect);
+            status_text = "Type a name to show the author by, leave it blank to show their agent id, press (enter) to save it or (esc) to leave".to_string();
+        }
+        View::DirectoryFileView(action) => {
+            let pop_up_rect = area.inner(Margin::new(
+                area.width / 4,
+                safe_subtract_u16(area.height, 3) / 2,
+            ));
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            let title = match action {
+                DirectoryFileAction::Import => "Import boreds from file",
+                DirectoryFileAction::Export => "Export directory to file",
+            };
+            let pop_up_block = Block::default()
+                .title(title)
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .style(app.theme.text_style());
+            let path_text = Paragraph::new(app.directory_file_input.clone())
+                .style(app.theme.inverted_text_style())
+                .block(pop_up_block);
+            frame.render_widget(path_text, pop_up_rect);
+            status_text =
+                "Type the path of a .html bookmarks or .json file, press (enter) to confirm or (esc) to leave"
+                    .to_string();
+        }
         View::GoToView => {
-            status_text = "Type to enter URL or use terminal emulator paste, (enter) to go to address (esc) to leave".to_string();
+            status_text = "Type to enter URL or use terminal emulator paste, (up/down) to pick a suggestion, (tab) to complete it, (enter) to go to address (esc) to leave".to_string();
+            let suggestions = app.get_goto_suggestions();
+            if !suggestions.is_empty() {
+                let rows: Vec<Row> = suggestions
+                    .iter()
+                    .map(|suggestion| {
+                        Row::new(vec![
+                            highlight_matches(&suggestion.name, &app.goto_input),
+                            highlight_matches(&suggestion.address, &app.goto_input),
+                        ])
+                        .style(app.theme.text_style())
+                    })
+                    .collect();
+                let suggestions_rect = Rect::new(
+                    area.x,
+                    ui_chunks[1].y,
+                    min(area.width, 100),
+                    min(rows.len() as u16 + 2, ui_chunks[1].height),
+                );
+                let suggestions_block = Block::default()
+                    .title("Suggestions")
+                    .style(app.theme.text_style())
+                    .borders(Borders::ALL);
+                let table = Table::new(rows, [Constraint::Fill(1), Constraint::Fill(2)])
+                    .row_highlight_style(app.theme.inverted_text_style())
+                    .block(suggestions_block);
+                let mut table_state = TableState::default().with_selected(app.goto_selected);
+                Clear.render(suggestions_rect, frame.buffer_mut());
+                frame.render_stateful_widget(table, suggestions_rect, &mut table_state);
+            }
         }
-        View::DirectoryView(directory_index) => {
-            let mut table_state = TableState::default().with_selected(*directory_index);
-            let header = ["Bored name", "Home"]
+        View::DirectoryView(directory_row) => {
+            let mut table_state = TableState::default().with_selected(*directory_row);
+            let header = ["Bored name", "Tags", "Home", "Watched"]
                 .into_iter()
                 .map(Span::from)
                 .collect::<Row>()
                 .style(app.theme.text_style())
                 .bold()
                 .height(1);
-            let directory_table = app.directory.as_table();
+            let search = app.directory_search.as_deref().unwrap_or_default();
+            let directory_table = app
+                .directory
+                .as_table(app.directory_tag_filter.as_deref(), search);
             let rows: Vec<Row> = directory_table
                 .iter()
-                .map(|r| Row::new(vec![r[0].clone(), r[1].clone()]).style(app.theme.text_style()))
+                .map(|[name, tags, home, watched]| {
+                    Row::new(vec![
+                        highlight_matches(name, search),
+                        highlight_matches(tags, search),
+                        Line::from(home.clone()),
+                        Line::from(watched.clone()),
+                    ])
+                    .style(app.theme.text_style())
+                })
+                .collect();
+            let title = match &app.directory_tag_filter {
+                Some(tag) => format!("Directory of boreds tagged {tag}"),
+                None => "Directory of boreds".to_string(),
+            };
+            let pop_up_rect = area.inner(Margin::new(area.width / 8, area.height / 4));
+            let mut pop_up_block = Block::default()
+                .title(title)
+                .style(app.theme.text_style())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick);
+            if let Some(search) = &app.directory_search {
+                pop_up_block = pop_up_block.title_bottom(format!("Search: {search}_"));
+            }
+            let table = Table::new(
+                rows,
+                [
+                    Constraint::Fill(2),
+                    Constraint::Fill(1),
+                    Constraint::Length(6),
+                    Constraint::Length(9),
+                ],
+            )
+            .header(header)
+            .row_highlight_style(app.theme.inverted_text_style())
+            .block(pop_up_block);
+            let note = app
+                .get_directory_index(*directory_row)
+                .ok()
+                .and_then(|i| app.directory.get_bored_address(i).ok())
+                .and_then(|listing| listing.note)
+                .map_or(String::new(), |note| format!("{note} | "));
+            status_text = match app.directory_search {
+                Some(_) => format!(
+                    "{note}Type to search names, addresses and tags, press up and down to select, (enter) to confirm selection and (esc) to close the search"
+                ),
+                None => format!(
+                    "{note}Press up and down to select, (enter) to confirm selection, (/) to search, (ctrl + h) to set as home bored, (e) to edit, (w) to watch for new notices, (t) to filter by tag, (i) to import, (x) to export, (shift + up/down) to move, (delete) to remove and (esc) to cancel"
+                ),
+            };
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            frame.render_stateful_widget(table, pop_up_rect, &mut table_state);
+        }
+        View::ListingView { field, .. } => {
+            let pop_up_rect = area.inner(Margin::new(area.width / 8, area.height / 5));
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            let pop_up_block = Block::default()
+                .title("Edit directory listing")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .style(app.theme.text_style());
+            frame.render_widget(pop_up_block, pop_up_rect);
+            let pop_up_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .margin(1)
+                .constraints([
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(50),
+                ])
+                .split(pop_up_rect);
+            let fields = [
+                (ListingField::Name, "Name", &app.listing_name_input),
+                (
+                    ListingField::Tags,
+                    "Tags, separated by commas",
+                    &app.listing_tags_input,
+                ),
+                (ListingField::Note, "Note", &app.listing_note_input),
+            ];
+            for ((listing_field, title, input), chunk) in
+                fields.into_iter().zip(pop_up_chunks.iter())
+            {
+                let style = if listing_field == *field {
+                    app.theme.inverted_text_style()
+                } else {
+                    app.theme.text_style()
+                };
+                let block = Block::default().title(title).style(style);
+                let paragraph = Paragraph::new(input.clone())
+                    .block(block)
+                    .wrap(Wrap { trim: false });
+                frame.render_widget(paragraph, *chunk);
+            }
+            status_text = "Type to edit, (tab) to move to the next field, (enter) to save or (esc) to cancel".to_string();
+        }
+        View::OverviewView => {
+            if let Some(ref bored) = bored {
+                let area = ui_chunks[1];
+                let mut overview = Overview::create(
+                    bored,
+                    app.selected_notice,
+                    app.theme.clone(),
+                    Coordinate {
+                        x: area.width,
+                        y: area.height,
+                    },
+                );
+                let dimensions = overview.get_dimensions();
+                let overview_rect = Rect::new(
+                    area.x + safe_subtract_u16(area.width, dimensions.x) / 2,
+                    area.y + safe_subtract_u16(area.height, dimensions.y) / 2,
+                    min(dimensions.x, area.width),
+                    min(dimensions.y, area.height),
+                );
+                Clear.render(area, frame.buffer_mut());
+                frame.render_widget(Block::default().style(app.theme.text_style()), area);
+                frame.render_widget(&overview, overview_rect);
+                overview.set_screen_area(overview_rect);
+                status_text = format!(
+                    "Overview, each cell is {0}×{0} of the bored. Use (the arrow keys) or (tab) to select a notice or click one, (enter) to go to the selected notice and (esc) to leave",
+                    overview.get_scale()
+                );
+                app.overview = Some(overview);
+            }
+        }
+        View::DownloadsView(download_index) => {
+            let mut table_state = TableState::default().with_selected(*download_index);
+            let header = ["File", "Size", "Status"]
+                .into_iter()
+                .map(Span::from)
+                .collect::<Row>()
+                .style(app.theme.text_style())
+                .bold()
+                .height(1);
+            let rows: Vec<Row> = app
+                .downloads
+                .get_downloads()
+                .iter()
+                .map(|download| {
+                    let status = match download.get_status() {
+                        DownloadStatus::Queued => "Queued".to_string(),
+                        DownloadStatus::Downloading => format!("{}%", download.get_percentage()),
+                        DownloadStatus::Done(_) => "Done".to_string(),
+                        DownloadStatus::Failed(e) => format!("Failed: {e}"),
+                    };
+                    let attachment = download.get_attachment();
+                    Row::new(vec![
+                        attachment.get_filename().to_string(),
+                        format_size(attachment.get_size()),
+                        status,
+                    ])
+                    .style(app.theme.text_style())
+                })
                 .collect();
             let pop_up_rect = area.inner(Margin::new(area.width / 8, area.height / 4));
             let pop_up_block = Block::default()
-                .title("Directory of boreds")
+                .title(format!("Downloads to {}", app.downloads.get_dir().display()))
                 .style(app.theme.text_style())
                 .borders(Borders::ALL)
                 .border_type(BorderType::Thick);
-            let table = Table::new(rows, [Constraint::Fill(1), Constraint::Length(6)])
+            let widths = [
+                Constraint::Fill(1),
+                Constraint::Length(10),
+                Constraint::Fill(1),
+            ];
+            let table = Table::new(rows, widths)
                 .header(header)
                 .row_highlight_style(app.theme.inverted_text_style())
                 .block(pop_up_block);
-            status_text =
-                "Press up and down to select, (enter) to confirm selection, (ctrl + h) to set as home bored and (esc) to cancel"
-                    .to_string();
+            status_text = if app.downloads.has_downloads() {
+                "Press up and down to select, (enter) to open a finished download, (c) to clear finished downloads and (esc) to leave"
+                    .to_string()
+            } else {
+                "No downloads yet, press (d) while viewing a notice with an attachment to download it and (esc) to leave"
+                    .to_string()
+            };
             Clear.render(pop_up_rect, frame.buffer_mut());
             frame.render_stateful_widget(table, pop_up_rect, &mut table_state);
         }
+        View::ErrorLogView(error_index) => {
+            let errors = app.error_log.get_errors();
+            let mut table_state = TableState::default().with_selected(*error_index);
+            let header = ["When", "Error"]
+                .into_iter()
+                .map(Span::from)
+                .collect::<Row>()
+                .style(app.theme.text_style())
+                .bold()
+                .height(1);
+            let rows: Vec<Row> = errors
+                .iter()
+                .map(|logged| {
+                    Row::new(vec![
+                        format_age(logged.get_age()),
+                        logged.get_error().to_string(),
+                    ])
+                    .style(app.theme.text_style())
+                })
+                .collect();
+            let pop_up_rect = area.inner(Margin::new(area.width / 8, area.height / 4));
+            let pop_up_block = Block::default()
+                .title("Error log")
+                .style(app.theme.text_style())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick);
+            let pop_up_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Fill(1), Constraint::Length(4)])
+                .split(pop_up_block.inner(pop_up_rect));
+            let widths = [Constraint::Length(8), Constraint::Fill(1)];
+            let table = Table::new(rows, widths)
+                .header(header)
+                .row_highlight_style(app.theme.inverted_text_style());
+            // the table cuts long errors short so the selected one is shown in full below it
+            let selected_error = errors
+                .get(*error_index)
+                .map(|logged| logged.get_error().to_string())
+                .unwrap_or_default();
+            let selected_text = Paragraph::new(selected_error)
+                .style(app.theme.dimmed_text_style())
+                .wrap(Wrap { trim: false });
+            status_text = if errors.is_empty() {
+                "No errors so far, press (esc) to leave".to_string()
+            } else {
+                "Press up and down to select, (c) to clear the log and (esc) to leave".to_string()
+            };
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            frame.render_widget(pop_up_block, pop_up_rect);
+            frame.render_stateful_widget(table, pop_up_chunks[0], &mut table_state);
+            frame.render_widget(selected_text, pop_up_chunks[1]);
+        }
+        View::LedgerView(entry_index) => {
+            let mut table_state = TableState::default().with_selected(*entry_index);
+            let header = ["When", "What", "Work", "Size", "Address"]
+                .into_iter()
+                .map(Span::from)
+                .collect::<Row>()
+                .style(app.theme.text_style())
+                .bold()
+                .height(1);
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default();
+            let rows: Vec<Row> = app
+                .ledger
+                .iter()
+                .map(|entry| {
+                    let what = match entry.operation {
+                        LedgerOperation::CreatedBored => "Created bored",
+                        LedgerOperation::ChangedSettings => "Changed settings",
+                        LedgerOperation::PostedNotice => "Posted notice",
+                        LedgerOperation::SharedFile => "Shared file",
+                    };
+                    let work = match entry.pow_difficulty {
+                        0 => "none".to_string(),
+                        difficulty => difficulty.to_string(),
+                    };
+                    Row::new(vec![
+                        format_age(now.saturating_sub(Duration::from_millis(entry.at.max(0) as u64))),
+                        what.to_string(),
+                        work,
+                        format_size(entry.size),
+                        entry.address.clone(),
+                    ])
+                    .style(app.theme.text_style())
+                })
+                .collect();
+            let pop_up_rect = area.inner(Margin::new(area.width / 8, area.height / 4));
+            let pop_up_block = Block::default()
+                .title("Ledger")
+                .style(app.theme.text_style())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick);
+            let pop_up_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Fill(1), Constraint::Length(2)])
+                .split(pop_up_block.inner(pop_up_rect));
+            let widths = [
+                Constraint::Length(8),
+                Constraint::Length(16),
+                Constraint::Length(5),
+                Constraint::Length(10),
+                Constraint::Fill(1),
+            ];
+            let table = Table::new(rows, widths)
+                .header(header)
+                .row_highlight_style(app.theme.inverted_text_style());
+            // addresses are long so the selected one is shown in full below the table
+            let selected_address = app
+                .ledger
+                .get(*entry_index)
+                .map(|entry| entry.address.clone())
+                .unwrap_or_default();
+            let selected_text = Paragraph::new(selected_address)
+                .style(app.theme.dimmed_text_style())
+                .wrap(Wrap { trim: false });
+            status_text = if app.ledger.is_empty() {
+                "Nothing published from here yet, press (esc) to leave".to_string()
+            } else {
+                "Work is the proof of work difficulty each took. Press up and down to select and (esc) to leave".to_string()
+            };
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            frame.render_widget(pop_up_block, pop_up_rect);
+            frame.render_stateful_widget(table, pop_up_chunks[0], &mut table_state);
+            frame.render_widget(selected_text, pop_up_chunks[1]);
+        }
+        View::NoticeListView(row) => {
+            let mut table_state = TableState::default().with_selected(*row);
+            let header = ["#", "When", "By", "First line", "Links", "Showing"]
+                .into_iter()
+                .map(Span::from)
+                .collect::<Row>()
+                .style(app.theme.text_style())
+                .bold()
+                .height(1);
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default();
+            let mut rows: Vec<Row> = vec![];
+            if let Some(bored) = &bored {
+                let new_notices = app.get_new_notices(bored);
+                let percent_showing = get_percent_showing(bored);
+                for notice_index in get_recency_order(bored) {
+                    let notice = &bored.notices()[notice_index];
+                    let when = notice
+                        .get_timestamp()
+                        .map(|at| {
+                            format_age(now.saturating_sub(Duration::from_millis(at.max(0) as u64)))
+                        })
+                        .unwrap_or_default();
+                    let first_line = notice
+                        .get_display()
+                        .map(|display| display.get_display_text())
+                        .unwrap_or_default()
+                        .lines()
+                        .find(|line| !line.trim().is_empty())
+                        .unwrap_or_default()
+                        .to_string();
+                    let links = get_hyperlinks(notice.get_content()).map_or(0, |links| links.len());
+                    let style = if new_notices.contains(&notice_index) {
+                        app.theme.text_style().bold()
+                    } else {
+                        app.theme.text_style()
+                    };
+                    rows.push(
+                        Row::new(vec![
+                            (notice_index + 1).to_string(),
+                            when,
+                            app.petnames.describe_author(notice).unwrap_or_default(),
+                            first_line,
+                            links.to_string(),
+                            format!("{}%", percent_showing[notice_index]),
+                        ])
+                        .style(style),
+                    );
+                }
+            }
+            let pop_up_rect = area.inner(Margin::new(area.width / 8, area.height / 4));
+            let pop_up_block = Block::default()
+                .title("Notices")
+                .style(app.theme.text_style())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick);
+            let widths = [
+                Constraint::Length(4),
+                Constraint::Length(8),
+                Constraint::Length(12),
+                Constraint::Fill(1),
+                Constraint::Length(5),
+                Constraint::Length(7),
+            ];
+            status_text = if rows.is_empty() {
+                "There are no notices on this bored, press (esc) to leave".to_string()
+            } else {
+                "Newest first, new notices in bold. Press (enter) to show the notice on the bored and (esc) to leave".to_string()
+            };
+            let table = Table::new(rows, widths)
+                .header(header)
+                .row_highlight_style(app.theme.inverted_text_style());
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            frame.render_widget(pop_up_block.clone(), pop_up_rect);
+            frame.render_stateful_widget(table, pop_up_block.inner(pop_up_rect), &mut table_state);
+        }
+        View::IndexView(row) => {
+            let mut table_state = TableState::default().with_selected(*row);
+            let header = ["Bored", "Found", "Match"]
+                .into_iter()
+                .map(Span::from)
+                .collect::<Row>()
+                .style(app.theme.text_style())
+                .bold()
+                .height(1);
+            let hits = app.index.get().search(&app.index_search);
+            let rows: Vec<Row> = hits
+                .iter()
+                .map(|hit| {
+                    let found = match hit.entry.depth {
+                        0 => "seed".to_string(),
+                        1 => "1 link".to_string(),
+                        depth => format!("{depth} links"),
+                    };
+                    let matched = match hit.snippet {
+                        Some(snippet) => snippet.to_string(),
+                        None => hit.entry.address.to_derived_name().to_string(),
+                    };
+                    Row::new(vec![hit.entry.name.clone(), found, matched])
+                        .style(app.theme.text_style())
+                })
+                .collect();
+            let title = if app.index.is_crawling() {
+                "Index, crawling...".to_string()
+            } else {
+                format!("Index of {} boreds", app.index.get().get_entries().len())
+            };
+            let pop_up_rect = area.inner(Margin::new(area.width / 8, area.height / 4));
+            let pop_up_block = Block::default()
+                .title(title)
+                .title_bottom(format!("Search: {}_", app.index_search))
+                .style(app.theme.text_style())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick);
+            let widths = [
+                Constraint::Fill(1),
+                Constraint::Length(8),
+                Constraint::Fill(2),
+            ];
+            status_text = if app.index.get().get_entries().is_empty() {
+                "Nothing has been crawled yet, boreds in the directory and those they link to are added once crawled. Press (ctrl + r) to crawl again and (esc) to leave".to_string()
+            } else {
+                "Type to search the names and notices of the boreds found by following links from the directory, press up and down to select, (enter) to go to the bored, (ctrl + r) to crawl again and (esc) to leave".to_string()
+            };
+            let table = Table::new(rows, widths)
+                .header(header)
+                .row_highlight_style(app.theme.inverted_text_style());
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            frame.render_widget(pop_up_block.clone(), pop_up_rect);
+            frame.render_stateful_widget(table, pop_up_block.inner(pop_up_rect), &mut table_state);
+        }
+        View::GraphView(node) => {
+            let title = if app.index.is_crawling() {
+                "Graph, crawling...".to_string()
+            } else {
+                format!("Graph of {} boreds", app.graph.get_nodes().len())
+            };
+            let pop_up_rect = area.inner(Margin::new(area.width / 16, area.height / 8));
+            let pop_up_block = Block::default()
+                .title(title)
+                .style(app.theme.text_style())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick);
+            let inner = pop_up_block.inner(pop_up_rect);
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            frame.render_widget(pop_up_block, pop_up_rect);
+            let nodes = app.graph.get_nodes();
+            status_text = match nodes.get(*node) {
+                Some(selected) => {
+                    let links_from = app.graph.get_links_from(*node).len();
+                    let links_to = app.graph.get_links_to(*node).len();
+                    format!(
+                        "{} at {}, links to {links_from} of the boreds here and is linked to from {links_to}.\nPress (the arrow keys) to select a bored, (enter) to go to it, (ctrl + r) to crawl again and (esc) to leave",
+                        selected.name,
+                        selected.address.to_derived_name()
+                    )
+                }
+                None => "Nothing has been crawled yet, boreds in the directory and those they link to are shown once crawled. Press (ctrl + r) to crawl again and (esc) to leave".to_string(),
+            };
+            if let Some(selected) = nodes.get(*node) {
+                let size = app.graph.get_size();
+                let label_width = selected.label.chars().count() as u16;
+                // keep the selected bored in the middle of the view where there is room
+                let offset = Coordinate {
+                    x: (selected.top_left.x + label_width / 2)
+                        .saturating_sub(inner.width / 2)
+                        .min(size.x.saturating_sub(inner.width)),
+                    y: selected
+                        .top_left
+                        .y
+                        .saturating_sub(inner.height / 2)
+                        .min(size.y.saturating_sub(inner.height)),
+                };
+                let neighbours: Vec<usize> = app
+                    .graph
+                    .get_links_from(*node)
+                    .into_iter()
+                    .chain(app.graph.get_links_to(*node))
+                    .collect();
+                let buffer = frame.buffer_mut();
+                for (y, line) in app.graph.render().iter().enumerate() {
+                    let Some(screen_y) = (y as u16).checked_sub(offset.y) else {
+                        continue;
+                    };
+                    if screen_y >= inner.height {
+                        break;
+                    }
+                    for (x, char) in line.iter().enumerate() {
+                        let Some(screen_x) = (x as u16).checked_sub(offset.x) else {
+                            continue;
+                        };
+                        if screen_x >= inner.width {
+                            break;
+                        }
+                        if let Some(cell) =
+                            buffer.cell_mut((inner.x + screen_x, inner.y + screen_y))
+                        {
+                            cell.set_char(*char).set_style(app.theme.text_style());
+                        }
+                    }
+                }
+                for (index, graph_node) in nodes.iter().enumerate() {
+                    let style = if index == *node {
+                        app.theme.inverted_text_style()
+                    } else if neighbours.contains(&index) {
+                        app.theme.text_style().bold()
+                    } else {
+                        continue;
+                    };
+                    let width = graph_node.label.chars().count() as u16;
+                    for x in graph_node.top_left.x..graph_node.top_left.x + width {
+                        if let (Some(screen_x), Some(screen_y)) = (
+                            x.checked_sub(offset.x).filter(|x| *x < inner.width),
+                            graph_node
+                                .top_left
+                                .y
+                                .checked_sub(offset.y)
+                                .filter(|y| *y < inner.height),
+                        ) && let Some(cell) =
+                            buffer.cell_mut((inner.x + screen_x, inner.y + screen_y))
+                        {
+                            cell.set_style(style);
+                        }
+                    }
+                }
+            }
+        }
+        View::DraftsView(draft_index) => {
+            let mut table_state = TableState::default().with_selected(*draft_index);
+            let header = ["Bored", "Text", "Size"]
+                .into_iter()
+                .map(Span::from)
+                .collect::<Row>()
+                .style(app.theme.text_style())
+                .bold()
+                .height(1);
+            let rows: Vec<Row> = app
+                .drafts
+                .get_drafts()
+                .iter()
+                .map(|draft| {
+                    Row::new(vec![
+                        draft.link.clone(),
+                        draft.get_summary().to_string(),
+                        format!("{}×{}", draft.dimensions.x, draft.dimensions.y),
+                    ])
+                    .style(app.theme.text_style())
+                })
+                .collect();
+            let pop_up_rect = area.inner(Margin::new(area.width / 8, area.height / 4));
+            let pop_up_block = Block::default()
+                .title("Drafts")
+                .style(app.theme.text_style())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick);
+            let widths = [
+                Constraint::Fill(1),
+                Constraint::Fill(2),
+                Constraint::Length(7),
+            ];
+            let table = Table::new(rows, widths)
+                .header(header)
+                .row_highlight_style(app.theme.inverted_text_style())
+                .block(pop_up_block);
+            status_text = if app.drafts.get_drafts().is_empty() {
+                "No drafts, notices are kept here as they are written until they are placed, press (esc) to leave"
+                    .to_string()
+            } else {
+                "Press up and down to select, (enter) to carry on writing the draft, (delete) to discard it and (esc) to leave"
+                    .to_string()
+            };
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            frame.render_stateful_widget(table, pop_up_rect, &mut table_state);
+        }
+        View::TemplateView(template_index) => {
+            let mut table_state = TableState::default().with_selected(*template_index);
+            let header = ["Template", "Size", "For"]
+                .into_iter()
+                .map(Span::from)
+                .collect::<Row>()
+                .style(app.theme.text_style())
+                .bold()
+                .height(1);
+            let rows: Vec<Row> = TEMPLATES
+                .iter()
+                .map(|template| {
+                    Row::new(vec![
+                        template.name.to_string(),
+                        format!("{}×{}", template.dimensions.x, template.dimensions.y),
+                        template.description.to_string(),
+                    ])
+                    .style(app.theme.text_style())
+                })
+                .collect();
+            let pop_up_rect = area.inner(Margin::new(area.width / 8, area.height / 8));
+            let pop_up_block = Block::default()
+                .title("Templates")
+                .style(app.theme.text_style())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick);
+            let pop_up_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([
+                    Constraint::Length(TEMPLATES.len() as u16 + 2),
+                    Constraint::Fill(1),
+                ])
+                .split(pop_up_block.inner(pop_up_rect));
+            let widths = [
+                Constraint::Length(10),
+                Constraint::Length(6),
+                Constraint::Fill(1),
+            ];
+            let table = Table::new(rows, widths)
+                .header(header)
+                .row_highlight_style(app.theme.inverted_text_style());
+            // the selected template's text as it will show on the notice
+            let preview = TEMPLATES
+                .get(*template_index)
+                .and_then(|template| template.create_notice().ok())
+                .and_then(|notice| notice.get_display().ok())
+                .map(|display| display.get_display_text())
+                .unwrap_or_default();
+            let preview = Paragraph::new(preview).style(app.theme.dimmed_text_style());
+            status_text = "Press up and down to select, (enter) to start the notice from the template, replacing what has been written, and (esc) to go back to the notice"
+                .to_string();
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            frame.render_widget(pop_up_block, pop_up_rect);
+            frame.render_stateful_widget(table, pop_up_chunks[0], &mut table_state);
+            frame.render_widget(preview, pop_up_chunks[1]);
+        }
+        View::OpenView { target, scheme, .. } => {
+            let pop_up_rect = area.inner(Margin::new(
+                area.width / 6,
+                safe_subtract_u16(area.height, 8) / 2,
+            ));
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            let pop_up_block = Block::default()
+                .title("Open outside Surf Bored?")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .style(app.theme.text_style());
+            let lines = vec![
+                Line::from(target.clone()).bold(),
+                Line::from(""),
+                Line::from(app.openers.get_opener(target, scheme).describe(target)),
+            ];
+            let open_paragraph = Paragraph::new(lines)
+                .block(pop_up_block)
+                .wrap(Wrap { trim: false });
+            frame.render_widget(open_paragraph, pop_up_rect);
+            status_text = format!(
+                "Press (y) to open it or (n) not to. Set {} to schemes such as https, or file for downloads, to open them without asking. What they are opened with can be changed in {}",
+                TRUSTED_SCHEMES_VAR,
+                app.get_openers_path().display()
+            );
+        }
+        View::QrView(address) => {
+            // scanners want dark modules on light whatever the theme
+            let qr_style = Style::default().fg(Color::Black).bg(Color::White);
+            let mut lines = vec![Line::from(address.clone()), Line::from("")];
+            match get_qr_lines(address) {
+                Ok(qr_lines) => lines.extend(
+                    qr_lines
+                        .into_iter()
+                        .map(|line| Line::styled(line, qr_style)),
+                ),
+                Err(e) => lines.push(Line::from(e.to_string())),
+            }
+            let width = lines
+                .iter()
+                .map(|line| line.width() as u16)
+                .max()
+                .unwrap_or_default()
+                + 2;
+            let height = lines.len() as u16 + 2;
+            status_text = if width > area.width || height > area.height {
+                "Make the terminal bigger to see the whole QR code, press (y) to copy the address or (esc) to leave".to_string()
+            } else {
+                "Scan the code to open the bored elsewhere, press (y) to copy the address or (esc) to leave".to_string()
+            };
+            let pop_up_rect = Rect::new(
+                area.x + safe_subtract_u16(area.width, width) / 2,
+                area.y + safe_subtract_u16(area.height, height) / 2,
+                min(width, area.width),
+                min(height, area.height),
+            );
+            let pop_up_block = Block::default()
+                .title("QR code")
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .style(app.theme.text_style());
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            let qr_paragraph = Paragraph::new(lines)
+                .block(pop_up_block)
+                .alignment(Alignment::Center);
+            frame.render_widget(qr_paragraph, pop_up_rect);
+        }
+        View::KeyView(key_mode) => {
+            let pop_up_rect = area.inner(Margin::new(area.width / 8, area.height / 4));
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            let pop_up_block = Block::default()
+                .title("Reader key")
+                .title_bottom(app.get_reader_key_path().display().to_string())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick)
+                .style(app.theme.text_style());
+            let key_text = match &app.reader_key {
+                Some(reader_key) => format!(
+                    "Your public key, give it to whoever encrypts boreds for you:\n\n{}\n\nThe secret half is kept in the file below and only you can read it. Boreds encrypted for you can't be read without it, so keep a copy somewhere safe.",
+                    reader_key.public_key()
+                ),
+                None => "You have no reader key. Make one, or import the secret key you use on another device, to be able to read boreds encrypted for you.".to_string(),
+            };
+            let mut lines = vec![Line::from(key_text)];
+            match key_mode {
+                KeyMode::Show => {
+                    status_text = if app.reader_key.is_some() {
+                        "Press (y) to copy your public key, (w) to see its seed phrase, (delete) to clear the key or (esc) to leave".to_string()
+                    } else {
+                        "Press (n) to make a new key, (i) to import one or (esc) to leave"
+                            .to_string()
+                    };
+                }
+                KeyMode::Import => {
+                    status_text = "Type or paste the secret key or its seed phrase, press (enter) to import it or (esc) to go back".to_string();
+                    lines.push(Line::from(""));
+                    // the secret is never shown, only how much of it has been typed
+                    lines.push(Line::styled(
+                        format!("Secret key: {}", "*".repeat(app.key_input.chars().count())),
+                        app.theme.inverted_text_style(),
+                    ));
+                }
+                KeyMode::SeedPhrase => {
+                    status_text = "Press (esc) when you have written it down".to_string();
+                    if let Some(reader_key) = &app.reader_key {
+                        lines.push(Line::from(""));
+                        lines.push(Line::from(
+                            "Your seed phrase, write it down and keep it somewhere safe. Anyone with it can read boreds encrypted for you:",
+                        ));
+                        lines.push(Line::from(""));
+                        lines.push(Line::styled(
+                            reader_key.to_seed_phrase(),
+                            app.theme.inverted_text_style(),
+                        ));
+                    }
+                }
+                KeyMode::Clear => {
+                    status_text = "Press (y) to clear the key or (n) to keep it".to_string();
+                    lines.push(Line::from(""));
+                    lines.push(Line::styled(
+                        "Clear the key? Boreds encrypted for it can't be read again unless it is imported.",
+                        app.theme.inverted_text_style(),
+                    ));
+                }
+            }
+            let key_paragraph = Paragraph::new(lines)
+                .block(pop_up_block)
+                .wrap(Wrap { trim: false });
+            frame.render_widget(key_paragraph, pop_up_rect);
+        }
+        View::ThemeView(theme_index) => {
+            let mut table_state = TableState::default().with_selected(*theme_index);
+            // each theme is shown in its own colours so it can be seen before it is picked
+            let rows: Vec<Row> = app
+                .themes
+                .iter()
+                .map(|theme| {
+                    let theme = theme.for_colour_depth(app.colour_depth);
+                    Row::new(vec![
+                        Line::from(theme.get_name().to_string()).style(theme.text_style()),
+                        Line::from(vec![
+                            Span::styled(" Header ", theme.header_style()),
+                            Span::styled(" text ", theme.text_style()),
+                            Span::styled("dimmed", theme.dimmed_text_style()),
+                            Span::styled(" ", theme.text_style()),
+                            Span::styled("link", theme.text_style().patch(theme.hyperlink_style())),
+                            Span::styled(" ", theme.text_style()),
+                            Span::styled(
+                                "web link",
+                                theme
+                                    .text_style()
+                                    .patch(theme.get_hyperlink_style("https://")),
+                            ),
+                            Span::styled(" ", theme.text_style()),
+                        ]),
+                    ])
+                })
+                .collect();
+            let pop_up_rect = area.inner(Margin::new(area.width / 4, area.height / 4));
+            let bored_colours = if app.bored_colours { "on" } else { "off" };
+            let colour_depth = match app.colour_depth {
+                ColourDepth::Ansi16 => "16",
+                ColourDepth::Indexed256 => "256",
+                ColourDepth::TrueColour => "all",
+            };
+            let pop_up_block = Block::default()
+                .title("Themes")
+                .title_bottom(format!(
+                    "Boreds' own colours: {bored_colours}, terminal colours: {colour_depth}"
+                ))
+                .style(app.theme.text_style())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick);
+            let widths = [Constraint::Fill(1), Constraint::Length(26)];
+            let table = Table::new(rows, widths)
+                .highlight_symbol("> ")
+                .block(pop_up_block);
+            status_text = format!(
+                "Using {}. Press up and down to select, (enter) to use the selected theme from now on, (b) to turn boreds' own colours {} and (esc) to leave. Themes can be added, and the colours the terminal can show set, in {}",
+                app.picked_theme.get_name(),
+                if app.bored_colours { "off" } else { "on" },
+                app.get_themes_path().display()
+            );
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            frame.render_stateful_widget(table, pop_up_rect, &mut table_state);
+        }
+        View::AttachmentsView { index, selected } => {
+            let mut table_state = TableState::default().with_selected(*index);
+            let header = ["", "File", "Size"]
+                .into_iter()
+                .map(Span::from)
+                .collect::<Row>()
+                .style(app.theme.text_style())
+                .bold()
+                .height(1);
+            let attachments = app.get_current_attachments();
+            let rows: Vec<Row> = attachments
+                .iter()
+                .map(|attachment| {
+                    let picked = if selected.iter().any(|a| a == attachment.get_address()) {
+                        "[x]"
+                    } else {
+                        "[ ]"
+                    };
+                    Row::new(vec![
+                        picked.to_string(),
+                        attachment.get_filename().to_string(),
+                        format_size(attachment.get_size()),
+                    ])
+                    .style(app.theme.text_style())
+                })
+                .collect();
+            let pop_up_rect = area.inner(Margin::new(area.width / 8, area.height / 4));
+            let pop_up_block = Block::default()
+                .title("Files attached to notices")
+                .style(app.theme.text_style())
+                .borders(Borders::ALL)
+                .border_type(BorderType::Thick);
+            let widths = [
+                Constraint::Length(3),
+                Constraint::Fill(1),
+                Constraint::Length(10),
+            ];
+            let table = Table::new(rows, widths)
+                .header(header)
+                .row_highlight_style(app.theme.inverted_text_style())
+                .block(pop_up_block);
+            status_text = if attachments.is_empty() {
+                "No notices on this bored have files attached, press (esc) to leave".to_string()
+            } else {
+                "Press up and down to select, (space) to pick a file, (a) to pick all, (enter) to download the picked files or the selected one and (esc) to leave"
+                    .to_string()
+            };
+            Clear.render(pop_up_rect, frame.buffer_mut());
+            frame.render_stateful_widget(table, pop_up_rect, &mut table_state);
+        }
+    }
+    if let (View::BoredView | View::DraftView(_), Some(bored), Some(bored_view_port)) =
+        (&app.current_view, &bored, &app.bored_view_port)
+    {
+        let view_top_left = bored_view_port.get_view_top_left();
+        let dimensions = bored.get_dimensions();
+        status_text.push_str(&format!(
+            "\nViewing {},{} / {}×{}",
+            view_top_left.x, view_top_left.y, dimensions.x, dimensions.y
+        ));
+    }
+    if app.tasks.is_running() {
+        // only these keys are taken while waiting
+        status_text = "Press (esc) to stop waiting or (ctrl + c) to quit".to_string();
     }
     // setup status area
     let status_block = Block::default()
@@ -434,6 +1731,155 @@ pub fn ui(frame: &mut Frame, app: &mut App) {
         Clear.render(menu_rect, frame.buffer_mut());
         frame.render_widget(menu, menu_rect);
     }
+    if let Some(toast) = app.toasts.get_current() {
+        render_toast(frame, ui_chunks[1], toast, &app.theme);
+    }
+    if let Some(running) = app.tasks.get_running() {
+        render_wait_pop_up(
+            frame,
+            running.get_message(),
+            running.get_elapsed(),
+            &app.theme,
+        );
+    }
+}
+
+/// The text with the characters the search fuzzy matches underlined
+fn highlight_matches(text: &str, search: &str) -> Line<'static> {
+    let positions = fuzzy_match(search, text).unwrap_or_default();
+    text.chars()
+        .enumerate()
+        .map(|(i, c)| {
+            if positions.contains(&i) {
+                Span::styled(c.to_string(), Style::new().underlined().bold())
+            } else {
+                Span::raw(c.to_string())
+            }
+        })
+        .collect()
+}
+
+/// Map of the whole bored in the top right corner of the view
+fn render_minimap(
+    frame: &mut Frame,
+    area: Rect,
+    bored: &Bored,
+    bored_view_port: &BoredViewPort,
+    app: &App,
+) {
+    // leave room for the border and the scrollbar
+    let max_dimensions = Coordinate {
+        x: safe_subtract_u16(area.width / 3, 2),
+        y: safe_subtract_u16(area.height / 2, 2),
+    };
+    if max_dimensions.x == 0 || max_dimensions.y == 0 {
+        return;
+    }
+    let minimap = MiniMap::create(
+        bored,
+        bored_view_port.get_view(),
+        app.selected_notice,
+        app.theme.clone(),
+        max_dimensions,
+    );
+    let dimensions = minimap.get_dimensions();
+    let minimap_rect = Rect::new(
+        area.right() - dimensions.x - 3,
+        area.y,
+        dimensions.x + 2,
+        dimensions.y + 2,
+    );
+    let minimap_block = Block::default()
+        .title("Map")
+        .borders(Borders::ALL)
+        .style(app.theme.dimmed_text_style());
+    let minimap_area = minimap_block.inner(minimap_rect);
+    Clear.render(minimap_rect, frame.buffer_mut());
+    // block first as it would restyle the map
+    frame.render_widget(minimap_block, minimap_rect);
+    frame.render_widget(minimap, minimap_area);
+}
+
+/// Scrollbars along the right and bottom of the view showing where it is on the bored, only
+/// for the directions the bored doesn't fit in
+fn render_scrollbars(
+    frame: &mut Frame,
+    area: Rect,
+    bored_dimensions: Coordinate,
+    view_top_left: Coordinate,
+    style: Style,
+) {
+    if bored_dimensions.y > area.height {
+        // one more than the furthest the view can move so the thumb reaches the end
+        let mut state = ScrollbarState::new((bored_dimensions.y - area.height) as usize + 1)
+            .position(view_top_left.y as usize)
+            .viewport_content_length(area.height as usize);
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight).style(style);
+        frame.render_stateful_widget(scrollbar, area, &mut state);
+    }
+    if bored_dimensions.x > area.width {
+        let mut state = ScrollbarState::new((bored_dimensions.x - area.width) as usize + 1)
+            .position(view_top_left.x as usize)
+            .viewport_content_length(area.width as usize);
+        let scrollbar = Scrollbar::new(ScrollbarOrientation::HorizontalBottom).style(style);
+        frame.render_stateful_widget(scrollbar, area, &mut state);
+    }
+}
+
+/// Pop up asking whether to post the draft, saying what posting it will take
+fn render_posting_confirmation(
+    frame: &mut Frame,
+    area: Rect,
+    estimate: &PostingEstimate,
+    app: &App,
+) {
+    let pop_up_rect = area.inner(Margin::new(area.width / 4, area.height / 4));
+    Clear.render(pop_up_rect, frame.buffer_mut());
+    let pop_up_block = Block::default()
+        .title("Post the notice?")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Thick)
+        .style(app.theme.text_style());
+    frame.render_widget(pop_up_block, pop_up_rect);
+    let pop_up_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .margin(1)
+        .constraints([Constraint::Percentage(100), Constraint::Min(1)])
+        .split(pop_up_rect);
+    let mut lines = vec![if estimate.pow_difficulty == 0 {
+        "Posting on this bored is free and needs no proof of work.".to_string()
+    } else {
+        format!(
+            "Posting on this bored is free but needs proof of work at difficulty {}, which can take a while.",
+            estimate.pow_difficulty
+        )
+    }];
+    if let Some(per_session) = app.work_limit.per_session
+        && estimate.pow_difficulty > 0
+    {
+        let work = expected_hashes(estimate.pow_difficulty);
+        lines.push(format!(
+            "It takes about {:.1} million hashes, this session has {:.1} million left of its limit.",
+            work as f64 / 1e6,
+            per_session.saturating_sub(app.get_work_done()) as f64 / 1e6
+        ));
+    }
+    lines.push(format!(
+        "The bored will be {} KB of the most {} KB a bored can be.",
+        estimate.bored_size.div_ceil(1024),
+        MAX_BORED_SIZE / 1024
+    ));
+    if estimate.pruned_notices > 0 {
+        lines.push(format!(
+            "{} notice(s) the new one hides entirely will be removed.",
+            estimate.pruned_notices
+        ));
+    }
+    lines.push("Once posted the notice can't be taken back.".to_string());
+    let pop_up_text = Paragraph::new(lines.join("\n\n")).wrap(Wrap { trim: false });
+    frame.render_widget(pop_up_text, pop_up_chunks[0]);
+    let navigation_text = Paragraph::new("(y) Yes    (n) No").alignment(Alignment::Center);
+    frame.render_widget(navigation_text, pop_up_chunks[1]);
 }
 
 fn get_draft_postion_on_viewport(
@@ -450,85 +1896,91 @@ fn get_draft_postion_on_viewport(
     Rect::new(x, y, draft.get_dimensions().x, draft.get_dimensions().y)
 }
 
+/// Where a hyperlink goes and what kind of link it is, so the user knows before following it
+fn describe_hyperlink(link: &str) -> String {
+    match bored::url::URL::from_string(link.to_string()) {
+        Ok(url) => format!("Link ({}): {link}", url.get_scheme()),
+        Err(_) => format!("Link (unknown): {link}"),
+    }
+}
+
 pub fn safe_subtract_u16(a: u16, b: u16) -> u16 {
     if (a as i32 - b as i32) < 0 { 0 } else { a - b }
 }
 
-pub async fn wait_pop_up<B: Backend>(
-    terminal: &mut Terminal<B>,
-    previous_buffer: Buffer,
-    future: impl Future<Output = Result<(), SurfBoredError>>,
-    message: &str,
-    theme: Theme,
-) -> Result<(), SurfBoredError> {
-    let mut count = 0;
-    let animate = async {
-        let mut antimation = Antimation::new();
-        while count < 1200 {
-            let result = terminal.draw(|frame| {
-                frame.buffer_mut().merge(&previous_buffer);
-                let area = frame.area();
-                let pop_up_rect = area.inner(Margin::new(area.width / 4, area.height / 4));
-                Clear.render(pop_up_rect, frame.buffer_mut());
-                let pop_up_block = Block::default()
-                    .title("Working...")
-                    .borders(Borders::ALL)
-                    .border_type(BorderType::Thick)
-                    .style(theme.header_style());
-                let ant_frame = antimation.next_frame();
-                let pop_up_text = Paragraph::new(Text::styled(
-                    format!("{message}\n {ant_frame}"),
-                    Style::default(),
-                ))
-                .wrap(Wrap { trim: false })
-                .block(pop_up_block);
-                frame.render_widget(pop_up_text, pop_up_rect);
-            });
-            count += 1;
-            sleep(Duration::from_millis(500)).await;
-            match result {
-                Err(_) => return Err::<(), SurfBoredError>(SurfBoredError::CannotRenderWait),
-                _ => (),
-            }
-        }
-        Err(SurfBoredError::StillWaiting)
+/// A toast in the top right corner of the area, as narrow as its message allows
+fn render_toast(frame: &mut Frame, area: Rect, toast: &Toast, theme: &Theme) {
+    let length = toast.get_message().chars().count() as u16;
+    let width = min(length + 4, area.width / 2).max(12);
+    let text_width = width.saturating_sub(2).max(1);
+    let height = length / text_width + 3;
+    let toast_rect = Rect::new(
+        area.x + safe_subtract_u16(area.width, width + 1),
+        area.y + 1,
+        min(width, area.width),
+        min(height, area.height),
+    );
+    let style = match toast.get_kind() {
+        ToastKind::Warning => theme.inverted_text_style(),
+        ToastKind::Info | ToastKind::Success => theme.header_style(),
     };
-    tokio::select! {
-        e = animate => { e }
-        f = future => { f }
-    }?;
-    Ok(())
+    let toast_block = Block::default()
+        .title(toast.get_kind().get_title())
+        .borders(Borders::ALL)
+        .style(style);
+    let toast_text = Paragraph::new(toast.get_message())
+        .wrap(Wrap { trim: true })
+        .block(toast_block);
+    Clear.render(toast_rect, frame.buffer_mut());
+    frame.render_widget(toast_text, toast_rect);
 }
 
-pub struct Antimation {
-    count: usize,
+/// Pop up shown while waiting on x0x, with an ant that keeps moving so it is clear the app
+/// hasn't frozen
+fn render_wait_pop_up(frame: &mut Frame, message: &str, elapsed: Duration, theme: &Theme) {
+    let area = frame.area();
+    let pop_up_rect = area.inner(Margin::new(area.width / 4, area.height / 4));
+    Clear.render(pop_up_rect, frame.buffer_mut());
+    let pop_up_block = Block::default()
+        .title("Working...")
+        .borders(Borders::ALL)
+        .border_type(BorderType::Thick)
+        .style(theme.header_style());
+    let ant_frame = get_antimation_frame(elapsed);
+    let pop_up_text = Paragraph::new(Text::styled(
+        format!("{message}\n {ant_frame}"),
+        Style::default(),
+    ))
+    .wrap(Wrap { trim: false })
+    .block(pop_up_block);
+    frame.render_widget(pop_up_text, pop_up_rect);
 }
-impl Antimation {
-    fn new() -> Antimation {
-        Antimation { count: 0 }
-    }
 
-    fn next_frame(&mut self) -> String {
-        let frame = if self.count == 0 {
-            "o o    \n  \\\\\n  (\"\")\n  >||<\n   /\\".to_string()
-        } else if self.count == 2 {
-            "   o o\n    //\n  (\"\")\n  >||<\n   /\\".to_string()
-        } else {
-            "  oo  \n   ||  \n  (\'\')\n  >||<  \n   /\\  ".to_string()
-        };
-        if self.count >= 3 {
-            self.count = 0
-        } else {
-            self.count += 1;
-        }
-        frame
+/// The ant changes every half a second
+fn get_antimation_frame(elapsed: Duration) -> &'static str {
+    match (elapsed.as_millis() / 500) % 4 {
+        0 => "o o    \n  \\\\\n  (\"\")\n  >||<\n   /\\",
+        2 => "   o o\n    //\n  (\"\")\n  >||<\n   /\\",
+        _ => "  oo  \n   ||  \n  ('')\n  >||<  \n   /\\  ",
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use bored::Bored;
+
+    #[test]
+    fn test_describe_hyperlink() {
+        assert_eq!(
+            describe_hyperlink("https://autonomi.com"),
+            "Link (https): https://autonomi.com"
+        );
+        assert_eq!(
+            describe_hyperlink("bored://games"),
+            "Link (bored): bored://games"
+        );
+        assert_eq!(describe_hyperlink(""), "Link (unknown): ");
+    }
 
     #[test]
     fn test_safe_subtract_u16() {
@@ -543,15 +1995,40 @@ mod tests {
         let draft = Notice::create(Coordinate { x: 30, y: 10 });
         let draft_postion_on_viewport = get_draft_postion_on_viewport(&draft, &None, 4);
         assert_eq!(draft_postion_on_viewport, Rect::new(0, 4, 30, 10));
-        let mut bored_view_port = BoredViewPort::create(&bored, Coordinate { x: 40, y: 15 }, None);
+        let mut bored_view_port =
+            BoredViewPort::create(bored.clone(), Coordinate { x: 40, y: 15 }, None);
         bored_view_port.move_view(Coordinate { x: 80, y: 5 });
         let draft_postion_on_viewport =
             get_draft_postion_on_viewport(&draft, &Some(bored_view_port), 4);
         assert_eq!(draft_postion_on_viewport, Rect::new(0, 4, 30, 10));
-        let mut bored_view_port = BoredViewPort::create(&bored, Coordinate { x: 40, y: 15 }, None);
+        let mut bored_view_port =
+            BoredViewPort::create(bored.clone(), Coordinate { x: 40, y: 15 }, None);
         bored_view_port.move_view(Coordinate { x: 10, y: 5 });
         let draft_postion_on_viewport =
             get_draft_postion_on_viewport(&draft, &Some(bored_view_port), 4);
         assert_eq!(draft_postion_on_viewport, Rect::new(0, 4, 30, 10));
     }
+
+    #[test]
+    fn test_render_scrollbars() -> Result<(), Box<dyn std::error::Error>> {
+        let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(20, 5))?;
+        let completed = terminal.draw(|frame| {
+            let area = frame.area();
+            // only wider than the view so only the horizontal scrollbar is shown
+            render_scrollbars(
+                frame,
+                area,
+                Coordinate { x: 40, y: 5 },
+                Coordinate { x: 20, y: 0 },
+                Style::default(),
+            );
+        })?;
+        let buffer = completed.buffer;
+        assert!((0..4).all(|y| buffer[(19, y)].symbol() == " "));
+        assert!((0..20).all(|x| buffer[(x, 4)].symbol() != " "));
+        // scrolled to the end so the thumb is against the right end
+        assert_eq!(buffer[(18, 4)].symbol(), buffer[(10, 4)].symbol());
+        assert_ne!(buffer[(18, 4)].symbol(), buffer[(1, 4)].symbol());
+        Ok(())
+    }
 }
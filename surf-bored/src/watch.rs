@@ -0,0 +1,161 @@
+/*
+Copyright (C) 2025 We are bored
+
+This program is free software: you can redistribute it and/or modify
+it under the terms of the GNU Affero General Public License as
+published by the Free Software Foundation, either version 3 of the
+License, or (at your option) any later version.
+
+This program is distributed in the hope that it will be useful,
+but WITHOUT ANY WARRANTY; without even the implied warranty of
+MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+GNU Affero General Public License for more details.
+
+You should have received a copy of the GNU Affero General Public License
+along with this program.  If not, see <https://www.gnu.org/licenses/>.
+*/
+
+use crate::directory::Listing;
+use bored::url::BoredAddress;
+use bored::x0x_client::X0xBoredClient;
+use bored::{Bored, BoredError};
+use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc::{UnboundedReceiver, UnboundedSender, unbounded_channel};
+
+/// How often the watched boreds are checked for new notices
+pub const WATCH_INTERVAL: Duration = Duration::from_secs(5 * 60);
+
+/// Each watched bored's name and address with what was fetched for it
+type Polled = Vec<(String, BoredAddress, Result<Bored, BoredError>)>;
+
+/// Checks the boreds watched in the directory in the background, whichever bored is being
+/// shown, and says which have new notices
+pub struct Watcher {
+    /// Ids of the notices on each watched bored when it was last checked, by address
+    seen: HashMap<String, HashSet<String>>,
+    last_poll: Option<Instant>,
+    polling: bool,
+    sender: UnboundedSender<Polled>,
+    receiver: UnboundedReceiver<Polled>,
+}
+
+impl Watcher {
+    pub fn new() -> Watcher {
+        let (sender, receiver) = unbounded_channel();
+        Watcher {
+            seen: HashMap::new(),
+            last_poll: None,
+            polling: false,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Fetch the watched boreds on a copy of the client if they are due a check, it doesn't
+    /// change the bored the client is on
+    pub fn poll(&mut self, client: &X0xBoredClient, watched: Vec<Listing>) {
+        if self.polling
+            || watched.is_empty()
+            || self
+                .last_poll
+                .is_some_and(|last_poll| last_poll.elapsed() < WATCH_INTERVAL)
+        {
+            return;
+        }
+        let (names, addresses): (Vec<String>, Vec<BoredAddress>) = watched
+            .into_iter()
+            .filter_map(|listing| {
+                let address = BoredAddress::from_string(&listing.bored_address).ok()?;
+                Some((listing.name, address))
+            })
+            .unzip();
+        self.last_poll = Some(Instant::now());
+        self.polling = true;
+        let client = client.clone();
+        let sender = self.sender.clone();
+        tokio::spawn(async move {
+            let boreds = client.fetch_boreds(&addresses).await;
+            let polled = names
+                .into_iter()
+                .zip(addresses)
+                .zip(boreds)
+                .map(|((name, address), bored)| (name, address, bored))
+                .collect();
+            let _ = sender.send(polled);
+        });
+    }
+
+    /// Names of the watched boreds with new notices since they were last checked and how many,
+    /// leaving out the bored at the current address as the user can see it already
+    pub fn update(&mut self, current_address: Option<&BoredAddress>) -> Vec<(String, usize)> {
+        let mut new_notices = vec![];
+        while let Ok(polled) = self.receiver.try_recv() {
+            self.polling = false;
+            for (name, address, bored) in polled {
+                let Ok(bored) = bored else {
+                    continue;
+                };
+                if let Some(count) = self.record(&address, &bored)
+                    && current_address != Some(&address)
+                {
+                    new_notices.push((name, count));
+                }
+            }
+        }
+        new_notices
+    }
+
+    /// Remember the notices on a bored, returning how many weren't there last time, the
+    /// first time a bored is seen sets what is already there
+    fn record(&mut self, address: &BoredAddress, bored: &Bored) -> Option<usize> {
+        let notice_ids: HashSet<String> = bored
+            .notices()
+            .iter()
+            .map(|notice| notice.get_notice_id().to_string())
+            .collect();
+        let previous = self.seen.insert(address.to_string(), notice_ids.clone())?;
+        let count = notice_ids.difference(&previous).count();
+        (count > 0).then_some(count)
+    }
+}
+
+/// Tell the desktop about new notices, done on its own thread as it can block and failing
+/// to show it is no reason to bother the user
+pub fn notify_desktop(message: &str) {
+    let message = message.to_string();
+    tokio::task::spawn_blocking(move || {
+        let _ = notify_rust::Notification::new()
+            .summary("Surf Bored")
+            .body(&message)
+            .show();
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bored::Coordinate;
+    use bored::notice::Notice;
+
+    #[test]
+    fn test_record() -> Result<(), BoredError> {
+        let address = BoredAddress::from_string("bored://games")?;
+        let mut bored = Bored::create("Games", Coordinate { x: 40, y: 20 });
+        let mut notice = Notice::create(Coordinate { x: 10, y: 5 });
+        notice.set_notice_id("notice:1:first".to_string());
+        bored.add(notice, Coordinate { x: 0, y: 0 })?;
+        let mut watcher = Watcher::new();
+        // what is there the first time isn't new
+        assert_eq!(watcher.record(&address, &bored), None);
+        assert_eq!(watcher.record(&address, &bored), None);
+        for (id, x) in [("notice:2:second", 10), ("notice:3:third", 20)] {
+            let mut notice = Notice::create(Coordinate { x: 10, y: 5 });
+            notice.set_notice_id(id.to_string());
+            bored.add(notice, Coordinate { x, y: 10 })?;
+        }
+        assert_eq!(watcher.record(&address, &bored), Some(2));
+        assert_eq!(watcher.record(&address, &bored), None);
+        Ok(())
+    }
+}